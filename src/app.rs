@@ -27,16 +27,25 @@
 //!     });
 //! ```
 
-use crate::css::{StyleContext, StyleManager};
-use crate::event::{Event, WindowEvent};
+use crate::clipboard::{Clipboard, SystemClipboard};
+use crate::css::{Animations, StyleContext, StyleManager, Transitions};
+use crate::dispatch::EventDispatcher;
+use crate::event::{Event, TimerEvent, WindowEvent, WindowId};
+use crate::focus::FocusManager;
 use crate::geometry::{Rect, Size};
 use crate::layout::Constraints;
 use crate::platform::{Platform, PlatformEvent, PlatformError, Window, WindowBuilder};
-use crate::render::Renderer;
+use crate::render::{ImageCache, Renderer};
 use crate::theme::{Theme, ThemeData};
-use crate::widget::{EventContext, LayoutContext, PaintContext, Widget};
+use crate::widget::{build_element_tree, EventContext, LayoutContext, PaintContext, Widget};
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::event_loop::{ActiveEventLoop, ControlFlow};
+
+/// How often to check whether images decoding in the background are ready.
+const IMAGE_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Application builder and runner.
 pub struct App {
@@ -165,6 +174,9 @@ impl App {
     }
 
     /// Run the application with the given root widget builder.
+    ///
+    /// The root widget fills the main window. Widgets can open more windows
+    /// with [`EventContext::open_window`]; closing the main window quits.
     pub fn run<F, W>(self, builder: F) -> Result<(), AppError>
     where
         F: FnOnce() -> W + 'static,
@@ -181,7 +193,7 @@ impl App {
         let style_manager = self.style_manager.map(Arc::new);
 
         // State that will be initialized on resume
-        let mut state: Option<AppState<W>> = None;
+        let mut state: Option<AppState> = None;
         let mut builder_opt: Option<F> = Some(builder);
 
         platform.run(move |event_loop, event| {
@@ -208,8 +220,6 @@ impl App {
                         }
                     };
 
-                    let renderer = Renderer::new(&window);
-
                     // Build root widget
                     let root = if let Some(b) = builder_opt.take() {
                         b()
@@ -217,38 +227,37 @@ impl App {
                         panic!("Builder already consumed");
                     };
 
+                    let clipboard: Arc<dyn Clipboard> = Arc::new(SystemClipboard::new());
+                    let main = AppWindow::new(next_window_id(), None, window, Box::new(root), clipboard.clone());
                     state = Some(AppState {
-                        window,
-                        renderer,
-                        root: Box::new(root),
+                        main: main.id,
+                        windows: vec![main],
+                        clipboard,
                         theme_data,
                         style_manager: style_manager.clone(),
-                        event_ctx: EventContext::new(),
-                        needs_layout: true,
-                        needs_paint: true,
                     });
-
-                    // Request initial redraw
-                    if let Some(s) = &state {
-                        s.window.request_redraw();
-                    }
                 }
                 PlatformEvent::Window { window_id, event } => {
                     if let Some(s) = &mut state {
-                        if s.window.id() != window_id {
+                        let Some(index) = s.windows.iter().position(|w| w.window.id() == window_id) else {
                             return;
-                        }
+                        };
 
                         match &event {
                             Event::Window(WindowEvent::CloseRequested) => {
-                                event_loop.exit();
+                                // Let widgets see the request, then close the window
+                                let id = s.windows[index].id;
+                                s.dispatch(event_loop, index, &event);
+                                s.close_window(id);
+                                if s.is_closed(s.main) {
+                                    event_loop.exit();
+                                }
+                                return;
                             }
                             Event::Window(WindowEvent::Resized { width, height }) => {
-                                let size = Size::new(*width as f32, *height as f32);
-                                s.renderer.resize(size);
-                                s.needs_layout = true;
-                                s.needs_paint = true;
-                                s.window.request_redraw();
+                                let w = &mut s.windows[index];
+                                w.renderer.resize(Size::new(*width as f32, *height as f32));
+                                w.invalidate();
                             }
                             Event::Window(WindowEvent::ThemeChanged { dark }) => {
                                 s.theme_data = if *dark {
@@ -256,116 +265,74 @@ impl App {
                                 } else {
                                     ThemeData::light()
                                 };
-                                s.needs_paint = true;
-                                s.window.request_redraw();
+                                // `prefers-color-scheme` rules may affect layout
+                                for w in &mut s.windows {
+                                    w.invalidate();
+                                }
                             }
                             Event::Mouse(mouse) => {
-                                s.event_ctx.mouse_position = mouse.position;
+                                s.windows[index].event_ctx.mouse_position = mouse.position;
                             }
                             _ => {}
                         }
 
                         // Dispatch event to widgets
-                        s.root.handle_event(&event, &mut s.event_ctx);
-
-                        // Handle theme change requests
-                        use crate::widget::ThemeRequest;
-                        match s.event_ctx.theme_request {
-                            ThemeRequest::Light => {
-                                s.theme_data = ThemeData::light();
-                                s.event_ctx.is_dark_theme = false;
-                                s.needs_paint = true;
-                            }
-                            ThemeRequest::Dark => {
-                                s.theme_data = ThemeData::dark();
-                                s.event_ctx.is_dark_theme = true;
-                                s.needs_paint = true;
-                            }
-                            ThemeRequest::Toggle => {
-                                if s.event_ctx.is_dark_theme {
-                                    s.theme_data = ThemeData::light();
-                                    s.event_ctx.is_dark_theme = false;
-                                } else {
-                                    s.theme_data = ThemeData::dark();
-                                    s.event_ctx.is_dark_theme = true;
-                                }
-                                s.needs_paint = true;
-                            }
-                            ThemeRequest::None => {}
-                        }
-                        s.event_ctx.theme_request = ThemeRequest::None;
-
-                        if s.event_ctx.should_redraw {
-                            s.event_ctx.should_redraw = false;
-                            s.needs_paint = true;
-                            s.window.request_redraw();
-                        }
+                        s.dispatch(event_loop, index, &event);
                     }
                 }
                 PlatformEvent::RedrawRequested { window_id } => {
                     if let Some(s) = &mut state {
-                        if s.window.id() != window_id {
-                            return;
+                        if let Some(w) = s.windows.iter_mut().find(|w| w.window.id() == window_id) {
+                            w.redraw(&s.theme_data, s.style_manager.as_ref());
                         }
-
-                        // Layout if needed
-                        if s.needs_layout {
-                            s.needs_layout = false;
-                            let size = s.window.size();
-                            let style_ctx = if let Some(sm) = &s.style_manager {
-                                StyleContext::with_styles(&s.theme_data, sm.clone())
-                                    .with_viewport(size.width, size.height)
-                            } else {
-                                StyleContext::new(&s.theme_data)
-                                    .with_viewport(size.width, size.height)
+                    }
+                }
+                PlatformEvent::AboutToWait => {
+                    if let Some(s) = &mut state {
+                        // Deliver due timers and animation frames; handlers
+                        // may open or close windows, so look each one up by id
+                        let ids: Vec<WindowId> = s.windows.iter().map(|w| w.id).collect();
+                        for id in ids {
+                            let Some(index) = s.windows.iter().position(|w| w.id == id) else {
+                                continue;
                             };
-                            let layout_ctx = LayoutContext::new(&style_ctx);
-                            let constraints = Constraints::tight(size);
-                            s.root.layout(constraints, &layout_ctx);
-                            s.root.set_bounds(Rect::from_origin_size(
-                                crate::geometry::Point::ZERO,
-                                size,
-                            ));
+                            for timer_event in s.windows[index].event_ctx.timers.take_due(Instant::now()) {
+                                let Some(index) = s.windows.iter().position(|w| w.id == id) else {
+                                    break;
+                                };
+                                s.dispatch(event_loop, index, &Event::Timer(timer_event));
+                            }
+                        }
+                        if s.is_closed(s.main) {
+                            event_loop.exit();
+                            return;
                         }
 
-                        // Paint if needed (or force paint for initial frames on X11)
-                        let force_paint = s.renderer.needs_initial_frames();
-                        if s.needs_paint || force_paint {
-                            s.needs_paint = false;
-                            let size = s.window.size();
-                            let style_ctx = if let Some(sm) = &s.style_manager {
-                                StyleContext::with_styles(&s.theme_data, sm.clone())
-                                    .with_viewport(size.width, size.height)
-                            } else {
-                                StyleContext::new(&s.theme_data)
-                                    .with_viewport(size.width, size.height)
-                            };
-                            let paint_ctx = PaintContext::new(&style_ctx);
-
-                            s.renderer.begin_frame(s.theme_data.colors.background);
-
-                            let mut painter = s.renderer.painter();
-                            let root_rect = Rect::from_origin_size(
-                                crate::geometry::Point::ZERO,
-                                size,
-                            );
-                            s.root.paint(&mut painter, root_rect, &paint_ctx);
-
-                            let commands = painter.finish();
-                            s.renderer.draw(&commands);
-
-                            s.renderer.end_frame();
-                            
-                            // Request more redraws for initial X11 timing workaround
-                            if s.renderer.needs_initial_frames() {
-                                s.window.request_redraw();
+                        // Images decoded in the background may be on screen
+                        let images = ImageCache::global();
+                        if images.take_finished() {
+                            for w in &mut s.windows {
+                                w.invalidate();
                             }
                         }
+
+                        // Sleep until the next deadline, checking on images
+                        // still decoding now and then
+                        let now = Instant::now();
+                        let image_check = images.is_loading().then(|| now + IMAGE_POLL_INTERVAL);
+                        let deadline = s
+                            .windows
+                            .iter()
+                            .filter_map(|w| w.event_ctx.timers.next_deadline(now))
+                            .chain(image_check)
+                            .min();
+                        let control_flow = match deadline {
+                            Some(deadline) => ControlFlow::WaitUntil(deadline),
+                            None => ControlFlow::Wait,
+                        };
+                        event_loop.set_control_flow(control_flow);
                     }
                 }
-                PlatformEvent::AboutToWait => {
-                    // Nothing to do
-                }
             }
         }).map_err(AppError::Platform)
     }
@@ -377,16 +344,369 @@ impl Default for App {
     }
 }
 
+/// Global window ID counter.
+static WINDOW_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Generate a new unique window ID.
+pub(crate) fn next_window_id() -> WindowId {
+    WINDOW_ID_COUNTER.fetch_add(1, Ordering::SeqCst)
+}
+
+/// A window opened at runtime with [`EventContext::open_window`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let palette = ctx.open_window(
+///     WindowOptions::new(Column::new().child(label!("Tools")))
+///         .title("Tools")
+///         .size(240.0, 480.0)
+///         .parent(ctx.window),
+/// );
+/// ```
+pub struct WindowOptions {
+    title: String,
+    size: Size,
+    resizable: bool,
+    decorations: bool,
+    parent: Option<WindowId>,
+    root: Box<dyn Widget>,
+}
+
+impl WindowOptions {
+    /// Create options for a window showing `root`.
+    pub fn new(root: impl Widget + 'static) -> Self {
+        Self {
+            title: "OpenKit".to_string(),
+            size: Size::new(400.0, 300.0),
+            resizable: true,
+            decorations: true,
+            parent: None,
+            root: Box::new(root),
+        }
+    }
+
+    /// Set the window title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the initial window size.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Size::new(width, height);
+        self
+    }
+
+    /// Set whether the window is resizable.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set whether the platform draws a title bar and border.
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Make the window transient for `parent`.
+    ///
+    /// The window is closed along with its parent, and on platforms that
+    /// support it stays above the parent.
+    pub fn parent(mut self, parent: WindowId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+}
+
+/// Window change requested while handling an event.
+pub enum WindowRequest {
+    /// Open a window with the given id
+    Open(WindowId, WindowOptions),
+    /// Close a window and the windows transient for it
+    Close(WindowId),
+}
+
 /// Internal application state.
-struct AppState<W: Widget> {
-    window: Window,
-    renderer: Renderer,
-    root: Box<W>,
+struct AppState {
+    /// The window created at startup; closing it quits
+    main: WindowId,
+    windows: Vec<AppWindow>,
+    clipboard: Arc<dyn Clipboard>,
     theme_data: ThemeData,
     style_manager: Option<Arc<StyleManager>>,
+}
+
+impl AppState {
+    /// Dispatch an event to one window and apply the resulting requests.
+    fn dispatch(&mut self, event_loop: &ActiveEventLoop, index: usize, event: &Event) {
+        let window = &mut self.windows[index];
+        window.dispatch(event);
+
+        // Handle theme change requests for every window
+        use crate::widget::ThemeRequest;
+        let dark = match std::mem::replace(&mut window.event_ctx.theme_request, ThemeRequest::None) {
+            ThemeRequest::Light => Some(false),
+            ThemeRequest::Dark => Some(true),
+            ThemeRequest::Toggle => Some(!window.event_ctx.is_dark_theme),
+            ThemeRequest::None => None,
+        };
+        if let Some(dark) = dark {
+            self.theme_data = if dark {
+                ThemeData::dark()
+            } else {
+                ThemeData::light()
+            };
+            for w in &mut self.windows {
+                w.event_ctx.is_dark_theme = dark;
+                w.invalidate();
+            }
+        }
+
+        // Open and close windows
+        let requests = std::mem::take(&mut self.windows[index].event_ctx.window_requests);
+        for request in requests {
+            match request {
+                WindowRequest::Open(id, options) => self.open_window(event_loop, id, options),
+                WindowRequest::Close(id) => self.close_window(id),
+            }
+        }
+        if self.is_closed(self.main) {
+            event_loop.exit();
+        }
+    }
+
+    /// Create a window requested by a widget.
+    fn open_window(&mut self, event_loop: &ActiveEventLoop, id: WindowId, options: WindowOptions) {
+        let parent = options
+            .parent
+            .and_then(|parent| self.windows.iter().find(|w| w.id == parent));
+        let mut builder = WindowBuilder::new()
+            .title(&options.title)
+            .size(options.size.width, options.size.height)
+            .resizable(options.resizable)
+            .decorations(options.decorations);
+        if let Some(parent) = parent {
+            builder = builder.parent(&parent.window);
+        }
+
+        match builder.build(event_loop) {
+            Ok(window) => {
+                let parent = parent.map(|parent| parent.id);
+                let is_dark_theme = self.windows.first().is_some_and(|w| w.event_ctx.is_dark_theme);
+                let mut window = AppWindow::new(id, parent, window, options.root, self.clipboard.clone());
+                window.event_ctx.is_dark_theme = is_dark_theme;
+                self.windows.push(window);
+            }
+            Err(e) => log::warn!("Failed to open window: {}", e),
+        }
+    }
+
+    /// Close a window along with the windows transient for it.
+    fn close_window(&mut self, id: WindowId) {
+        let mut closing = vec![id];
+        let mut i = 0;
+        while i < closing.len() {
+            let parent = closing[i];
+            closing.extend(
+                self.windows
+                    .iter()
+                    .filter(|w| w.parent == Some(parent))
+                    .map(|w| w.id),
+            );
+            i += 1;
+        }
+        self.windows.retain(|w| !closing.contains(&w.id));
+    }
+
+    fn is_closed(&self, id: WindowId) -> bool {
+        !self.windows.iter().any(|w| w.id == id)
+    }
+}
+
+/// A window and the widget tree it shows.
+struct AppWindow {
+    id: WindowId,
+    parent: Option<WindowId>,
+    window: Window,
+    renderer: Renderer,
+    root: Box<dyn Widget>,
     event_ctx: EventContext,
+    focus: FocusManager,
+    events: EventDispatcher,
+    transitions: Arc<Transitions>,
+    animations: Arc<Animations>,
     needs_layout: bool,
     needs_paint: bool,
+    /// Caret reported to the input method, which is enabled while set
+    ime_cursor_area: Option<Rect>,
+}
+
+impl AppWindow {
+    fn new(
+        id: WindowId,
+        parent: Option<WindowId>,
+        window: Window,
+        root: Box<dyn Widget>,
+        clipboard: Arc<dyn Clipboard>,
+    ) -> Self {
+        let renderer = Renderer::new(&window);
+        let mut event_ctx = EventContext::new();
+        event_ctx.window = id;
+        event_ctx.clipboard = clipboard;
+
+        // Request initial redraw, and a first frame so animating widgets can
+        // start
+        event_ctx.timers.request_frame();
+        window.request_redraw();
+
+        Self {
+            id,
+            parent,
+            window,
+            renderer,
+            root,
+            event_ctx,
+            focus: FocusManager::new(),
+            events: EventDispatcher::new(),
+            transitions: Arc::new(Transitions::new()),
+            animations: Arc::new(Animations::new()),
+            needs_layout: true,
+            needs_paint: true,
+            ime_cursor_area: None,
+        }
+    }
+
+    /// Lay out and paint everything again.
+    fn invalidate(&mut self) {
+        self.needs_layout = true;
+        self.needs_paint = true;
+        self.window.request_redraw();
+    }
+
+    fn style_context<'a>(&self, theme_data: &'a ThemeData, style_manager: Option<&Arc<StyleManager>>) -> StyleContext<'a> {
+        let size = self.window.size();
+        if let Some(sm) = style_manager {
+            StyleContext::with_styles(theme_data, sm.clone())
+                .with_viewport(size.width, size.height)
+        } else {
+            StyleContext::new(theme_data)
+                .with_viewport(size.width, size.height)
+        }
+        .with_element_tree(Arc::new(build_element_tree(self.root.as_ref())))
+        .with_transitions(self.transitions.clone())
+        .with_animations(self.animations.clone())
+        .with_focus_visible(self.focus.focus_visible())
+    }
+
+    /// Lay out and paint the window as needed.
+    fn redraw(&mut self, theme_data: &ThemeData, style_manager: Option<&Arc<StyleManager>>) {
+        // Sample transitions and animations at this frame's time
+        let force_paint = self.renderer.needs_initial_frames();
+        if self.needs_layout || self.needs_paint || force_paint {
+            let now = Instant::now();
+            self.transitions.begin_frame(now);
+            self.animations.begin_frame(now);
+        }
+
+        // Layout if needed
+        if self.needs_layout {
+            self.needs_layout = false;
+            let size = self.window.size();
+            let style_ctx = self.style_context(theme_data, style_manager);
+            let layout_ctx = LayoutContext::new(&style_ctx);
+            let constraints = Constraints::tight(size);
+            self.root.layout(constraints, &layout_ctx);
+            self.root.set_bounds(Rect::from_origin_size(
+                crate::geometry::Point::ZERO,
+                size,
+            ));
+        }
+
+        // Paint if needed (or force paint for initial frames on X11)
+        if self.needs_paint || force_paint {
+            self.needs_paint = false;
+            let size = self.window.size();
+            let style_ctx = self.style_context(theme_data, style_manager);
+            let paint_ctx = PaintContext::new(&style_ctx);
+
+            self.renderer.begin_frame(theme_data.colors.background);
+
+            let mut painter = self.renderer.painter();
+            let root_rect = Rect::from_origin_size(
+                crate::geometry::Point::ZERO,
+                size,
+            );
+            paint_ctx.paint_child(self.root.as_ref(), &mut painter, root_rect);
+
+            let commands = painter.finish();
+            self.update_ime(paint_ctx.ime_cursor_area.get());
+            self.events.set_hit_tree(paint_ctx.into_hit_tree());
+            self.renderer.draw(&commands);
+
+            self.renderer.end_frame();
+
+            // Request more redraws for initial X11 timing workaround
+            if self.renderer.needs_initial_frames() {
+                self.window.request_redraw();
+            }
+
+            // Keep painting while transitions or animations run
+            if self.transitions.is_running() || self.animations.is_running() {
+                self.event_ctx.request_animation_frame();
+            }
+        }
+    }
+
+    /// Enable the input method while a focused text widget shows a caret,
+    /// and keep its candidate window next to that caret.
+    fn update_ime(&mut self, area: Option<Rect>) {
+        if area == self.ime_cursor_area {
+            return;
+        }
+        if area.is_some() != self.ime_cursor_area.is_some() {
+            self.window.set_ime_allowed(area.is_some());
+        }
+        if let Some(area) = area {
+            self.window.set_ime_cursor_area(area);
+        }
+        self.ime_cursor_area = area;
+    }
+
+    /// Dispatch an event to the widget tree and apply the resulting requests.
+    fn dispatch(&mut self, event: &Event) {
+        let result = self.events.dispatch(self.root.as_mut(), event, &mut self.event_ctx);
+
+        // Move keyboard focus and notify the widgets that gained or lost it
+        let focus_visible = self.focus.focus_visible();
+        let focus_events = self.focus.update(self.root.as_ref(), event, result, &mut self.event_ctx);
+        for focus_event in focus_events {
+            self.root.handle_event(&Event::Focus(focus_event), &mut self.event_ctx);
+            self.event_ctx.should_redraw = true;
+        }
+        if self.focus.focus_visible() != focus_visible {
+            self.event_ctx.should_redraw = true;
+        }
+
+        // Advance CSS transitions and animations on animation frames
+        if matches!(event, Event::Timer(TimerEvent::Frame { .. }))
+            && (self.transitions.is_running() || self.animations.is_running())
+        {
+            self.needs_layout |= self.transitions.needs_layout() || self.animations.needs_layout();
+            self.event_ctx.should_redraw = true;
+        }
+
+        if self.event_ctx.should_layout {
+            self.event_ctx.should_layout = false;
+            self.needs_layout = true;
+        }
+        if self.event_ctx.should_redraw {
+            self.event_ctx.should_redraw = false;
+            self.needs_paint = true;
+            self.window.request_redraw();
+        }
+    }
 }
 
 /// Application errors.
@@ -0,0 +1,347 @@
+//! Clipboard access for text widgets.
+//!
+//! Widgets reach the clipboard through [`EventContext::clipboard_text`] and
+//! [`EventContext::set_clipboard_text`]. Applications get a
+//! [`SystemClipboard`] shared by all their windows; an `EventContext` created
+//! on its own, as in tests, uses a [`MemoryClipboard`].
+//!
+//! [`EventContext::clipboard_text`]: crate::widget::EventContext::clipboard_text
+//! [`EventContext::set_clipboard_text`]: crate::widget::EventContext::set_clipboard_text
+
+use crate::event::{Key, KeyEvent, KeyEventKind};
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Longest wait for a clipboard helper to hand over the clipboard's text.
+const PASTE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A text clipboard.
+pub trait Clipboard: Send + Sync {
+    /// The text on the clipboard, if it holds any.
+    fn read_text(&self) -> Option<String>;
+
+    /// Replace the clipboard's contents with `text`.
+    fn write_text(&self, text: &str);
+}
+
+/// A clipboard private to the process.
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    text: Mutex<Option<String>>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn read_text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+
+    fn write_text(&self, text: &str) {
+        *self.text.lock().unwrap() = Some(text.to_string());
+    }
+}
+
+/// Commands copying to and pasting from one clipboard helper.
+#[derive(Debug)]
+struct ClipboardTool {
+    copy: &'static [&'static str],
+    paste: &'static [&'static str],
+}
+
+const WAYLAND_TOOLS: &[ClipboardTool] = &[ClipboardTool {
+    copy: &["wl-copy"],
+    paste: &["wl-paste", "--no-newline"],
+}];
+
+const X11_TOOLS: &[ClipboardTool] = &[
+    ClipboardTool {
+        copy: &["xclip", "-selection", "clipboard", "-in"],
+        paste: &["xclip", "-selection", "clipboard", "-out"],
+    },
+    ClipboardTool {
+        copy: &["xsel", "--clipboard", "--input"],
+        paste: &["xsel", "--clipboard", "--output"],
+    },
+];
+
+impl ClipboardTool {
+    fn copy(&self, text: &str) -> bool {
+        let Ok(mut child) = Command::new(self.copy[0])
+            .args(&self.copy[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        // The helper forks to keep serving the selection, so this returns
+        // as soon as it has read the text
+        let exited = child.wait().is_ok_and(|status| status.success());
+        written && exited
+    }
+
+    /// The clipboard's text, or `None` if the helper failed or gave up.
+    /// Errors if the helper couldn't be started, e.g. isn't installed.
+    fn paste(&self) -> io::Result<Option<String>> {
+        let mut child = Command::new(self.paste[0])
+            .args(&self.paste[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let Some(mut stdout) = child.stdout.take() else {
+            return Ok(None);
+        };
+
+        // Read off the UI thread, so a clipboard owner that never answers
+        // can't freeze the application
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut text = Vec::new();
+            let read = stdout.read_to_end(&mut text).is_ok();
+            let _ = sender.send(read.then_some(text));
+        });
+        let Ok(text) = receiver.recv_timeout(PASTE_TIMEOUT) else {
+            log::warn!("Clipboard helper {} did not answer in time", self.paste[0]);
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        };
+        let success = child.wait().is_ok_and(|status| status.success());
+        Ok(text
+            .filter(|_| success)
+            .map(|text| String::from_utf8_lossy(&text).into_owned()))
+    }
+}
+
+/// The desktop clipboard on X11 and Wayland.
+///
+/// Talks to the display server through the standard helpers: `wl-copy` and
+/// `wl-paste` on Wayland, `xclip` or `xsel` on X11. Without a display or an
+/// installed helper it keeps the text in the process, like a
+/// [`MemoryClipboard`].
+#[derive(Debug)]
+pub struct SystemClipboard {
+    tools: &'static [ClipboardTool],
+    local: MemoryClipboard,
+}
+
+impl SystemClipboard {
+    /// Use the clipboard of the display server the application runs on.
+    pub fn new() -> Self {
+        let tools = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            WAYLAND_TOOLS
+        } else if std::env::var_os("DISPLAY").is_some() {
+            X11_TOOLS
+        } else {
+            &[]
+        };
+        Self {
+            tools,
+            local: MemoryClipboard::new(),
+        }
+    }
+}
+
+impl Default for SystemClipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn read_text(&self) -> Option<String> {
+        // Ask the first helper that is installed. If it fails, the display
+        // server's clipboard is empty or unreadable, and the text copied in
+        // this process may be stale, so it isn't used instead.
+        match self.tools.iter().find_map(|tool| tool.paste().ok()) {
+            Some(text) => text,
+            None => self.local.read_text(),
+        }
+    }
+
+    fn write_text(&self, text: &str) {
+        self.local.write_text(text);
+        if !self.tools.is_empty() && !self.tools.iter().any(|tool| tool.copy(text)) {
+            log::warn!("No clipboard helper available, copied text stays in the application");
+        }
+    }
+}
+
+/// A clipboard shortcut pressed in a text-editing widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    /// Ctrl+C: copy the selection
+    Copy,
+    /// Ctrl+X: copy the selection and delete it
+    Cut,
+    /// Ctrl+V: replace the selection with the clipboard's text
+    Paste,
+    /// Ctrl+A: select all text
+    SelectAll,
+}
+
+impl EditCommand {
+    /// The command `key` triggers, if any.
+    ///
+    /// Uses Cmd instead of Ctrl on macOS.
+    pub fn from_key(key: &KeyEvent) -> Option<Self> {
+        if key.kind != KeyEventKind::Down || !key.modifiers.command() || key.modifiers.alt {
+            return None;
+        }
+        match key.key {
+            Key::C => Some(EditCommand::Copy),
+            Key::X => Some(EditCommand::Cut),
+            Key::V => Some(EditCommand::Paste),
+            Key::A => Some(EditCommand::SelectAll),
+            _ => None,
+        }
+    }
+}
+
+/// Join the lines of pasted text for a single-line field.
+pub fn single_line(text: &str) -> String {
+    text.trim_end_matches(['\r', '\n']).lines().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Event, FocusEvent, Modifiers};
+    use crate::widget::password_field::PasswordField;
+    use crate::widget::textfield::TextField;
+    use crate::widget::{EventContext, Widget};
+
+    fn shortcut(key: Key) -> Event {
+        let mut modifiers = Modifiers::empty();
+        if cfg!(target_os = "macos") {
+            modifiers.super_key = true;
+        } else {
+            modifiers.control = true;
+        }
+        Event::Key(KeyEvent {
+            kind: KeyEventKind::Down,
+            key,
+            physical_key: None,
+            text: None,
+            modifiers,
+            is_repeat: false,
+        })
+    }
+
+    #[test]
+    fn test_edit_command() {
+        let Event::Key(copy) = shortcut(Key::C) else {
+            unreachable!()
+        };
+        assert_eq!(EditCommand::from_key(&copy), Some(EditCommand::Copy));
+
+        let plain = KeyEvent {
+            modifiers: Modifiers::empty(),
+            ..copy.clone()
+        };
+        assert_eq!(EditCommand::from_key(&plain), None);
+
+        let released = KeyEvent {
+            kind: KeyEventKind::Up,
+            ..copy
+        };
+        assert_eq!(EditCommand::from_key(&released), None);
+    }
+
+    #[test]
+    fn test_copy_paste() {
+        let mut ctx = EventContext::new();
+        let focus = |widget: &dyn Widget| Event::Focus(FocusEvent::FocusIn { widget_id: widget.id() });
+
+        let mut source = TextField::new().value("hello");
+        source.handle_event(&focus(&source), &mut ctx);
+        source.handle_event(&shortcut(Key::A), &mut ctx);
+        source.handle_event(&shortcut(Key::X), &mut ctx);
+        assert_eq!(ctx.clipboard_text().as_deref(), Some("hello"));
+        assert_eq!(source.get_value(), "");
+
+        let mut target = TextField::new().value("say ");
+        target.handle_event(&focus(&target), &mut ctx);
+        target.handle_event(&shortcut(Key::V), &mut ctx);
+        assert_eq!(target.get_value(), "say hello");
+
+        // Passwords can be pasted in but never copied out
+        let mut password = PasswordField::new();
+        password.handle_event(&focus(&password), &mut ctx);
+        password.handle_event(&shortcut(Key::V), &mut ctx);
+        assert_eq!(password.value(), "hello");
+        ctx.set_clipboard_text("other");
+        password.handle_event(&shortcut(Key::A), &mut ctx);
+        password.handle_event(&shortcut(Key::C), &mut ctx);
+        password.handle_event(&shortcut(Key::X), &mut ctx);
+        assert_eq!(ctx.clipboard_text().as_deref(), Some("other"));
+        assert_eq!(password.value(), "hello");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_paste_helper_timeout() {
+        let echo = ClipboardTool {
+            copy: &["true"],
+            paste: &["echo", "pasted"],
+        };
+        assert_eq!(echo.paste().unwrap().as_deref(), Some("pasted\n"));
+
+        // A helper that never answers is given up on
+        let stuck = ClipboardTool {
+            copy: &["true"],
+            paste: &["sleep", "10"],
+        };
+        let start = std::time::Instant::now();
+        assert_eq!(stuck.paste().unwrap(), None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_system_clipboard_helpers() {
+        const MISSING: ClipboardTool = ClipboardTool {
+            copy: &["openkit-missing-helper"],
+            paste: &["openkit-missing-helper"],
+        };
+        const FAILING: ClipboardTool = ClipboardTool {
+            copy: &["false"],
+            paste: &["false"],
+        };
+        const ECHO: ClipboardTool = ClipboardTool {
+            copy: &["true"],
+            paste: &["echo", "pasted"],
+        };
+        let clipboard = |tools| {
+            let clipboard = SystemClipboard {
+                tools,
+                local: MemoryClipboard::new(),
+            };
+            clipboard.local.write_text("local");
+            clipboard
+        };
+
+        // Helpers that aren't installed are skipped
+        assert_eq!(clipboard(&[MISSING, ECHO]).read_text().as_deref(), Some("pasted\n"));
+        // The first installed helper answers, even when it fails
+        assert_eq!(clipboard(&[FAILING, ECHO]).read_text(), None);
+        // Without a helper, the text stays in the process
+        assert_eq!(clipboard(&[MISSING]).read_text().as_deref(), Some("local"));
+        assert_eq!(clipboard(&[]).read_text().as_deref(), Some("local"));
+    }
+}
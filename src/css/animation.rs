@@ -0,0 +1,460 @@
+//! `@keyframes` animations.
+//!
+//! A widget whose style names a `@keyframes` rule in `animation-name` is
+//! animated by [`Animations`]: each frame the keyframes are sampled at the
+//! animation's progress and the interpolated values replace the cascaded
+//! ones, so painting picks them up through `ComputedStyle`.
+//!
+//! ```css
+//! @keyframes pulse {
+//!     from { opacity: 1; }
+//!     50% { opacity: 0.4; transform: scale(0.97); }
+//!     to { opacity: 1; }
+//! }
+//! .skeleton { animation: pulse 1.5s ease-in-out infinite; }
+//! ```
+//!
+//! Keyframes animate the same properties as transitions (see
+//! [`AnimatableProperty`]). A keyframe may set its own
+//! `animation-timing-function`, used up to the next keyframe.
+
+use crate::css::properties::{AnimationDirection, AnimationPlayState, AnimationSpec};
+use crate::css::{AnimatableProperty, AnimatedValue, ComputedStyle, CssValue, StyleContext, StyleProperty, TimingFunction};
+use crate::event::WidgetId;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// A `@keyframes` rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes {
+    pub name: String,
+    /// Keyframes sorted by offset
+    pub frames: Vec<Keyframe>,
+}
+
+impl Keyframes {
+    pub fn new(name: impl Into<String>, mut frames: Vec<Keyframe>) -> Self {
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        Self {
+            name: name.into(),
+            frames,
+        }
+    }
+
+    /// Apply the keyframes sampled at `progress` (0.0 to 1.0) to `style`.
+    ///
+    /// `base` is the widget's style without the animation; properties not
+    /// set by the first or last keyframe animate from or to its values.
+    /// Returns whether any animated property affects layout.
+    pub fn sample(
+        &self,
+        progress: f32,
+        timing: &TimingFunction,
+        base: &ComputedStyle,
+        style: &mut ComputedStyle,
+        ctx: &StyleContext,
+    ) -> bool {
+        // Each keyframe's values, resolved against the base style
+        let frame_styles: Vec<ComputedStyle> = self
+            .frames
+            .iter()
+            .map(|frame| {
+                let mut frame_style = ComputedStyle {
+                    specified: HashSet::new(),
+                    ..base.clone()
+                };
+                for (property, value) in &frame.declarations {
+                    frame_style.apply(property, value, ctx);
+                }
+                frame_style.resolve_current_color();
+                frame_style
+            })
+            .collect();
+
+        let mut affects_layout = false;
+        for property in AnimatableProperty::ALL {
+            // (offset, value, timing) for each keyframe setting the property
+            let mut stops: Vec<(f32, AnimatedValue, &TimingFunction)> = Vec::new();
+            for (frame, frame_style) in self.frames.iter().zip(&frame_styles) {
+                if let Some(value) = property.get(frame_style) {
+                    let frame_timing = frame.timing.as_ref().unwrap_or(timing);
+                    // A later keyframe at the same offset wins
+                    if stops.last().is_some_and(|(offset, ..)| *offset == frame.offset) {
+                        stops.pop();
+                    }
+                    stops.push((frame.offset, value, frame_timing));
+                }
+            }
+            if stops.is_empty() {
+                continue;
+            }
+
+            let Some(underlying) = property.value(base) else {
+                continue;
+            };
+            if stops[0].0 > 0.0 {
+                stops.insert(0, (0.0, underlying, timing));
+            }
+            if stops[stops.len() - 1].0 < 1.0 {
+                stops.push((1.0, underlying, timing));
+            }
+
+            let next = stops
+                .iter()
+                .position(|(offset, ..)| *offset > progress)
+                .unwrap_or(stops.len() - 1)
+                .max(1);
+            let (from_offset, from, frame_timing) = stops[next - 1];
+            let (to_offset, to, _) = stops[next];
+            let t = if to_offset > from_offset {
+                ((progress - from_offset) / (to_offset - from_offset)).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+
+            property.set(style, from.lerp(&to, frame_timing.evaluate(t)));
+            affects_layout |= property.affects_layout();
+        }
+        affects_layout
+    }
+}
+
+/// One keyframe block, e.g. `50% { opacity: 0.5 }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    /// Position in the animation (0.0 to 1.0)
+    pub offset: f32,
+    pub declarations: HashMap<StyleProperty, CssValue>,
+    /// Timing function from the keyframe's `animation-timing-function`
+    pub timing: Option<TimingFunction>,
+}
+
+impl Keyframe {
+    pub fn new(offset: f32, mut declarations: HashMap<StyleProperty, CssValue>) -> Self {
+        let timing = declarations
+            .remove(&StyleProperty::AnimationTimingFunction)
+            .and_then(|value| TimingFunction::from_value(&value));
+        // Animation properties are ignored inside keyframes
+        declarations.retain(|property, _| !is_animation_property(property));
+        Self {
+            offset,
+            declarations,
+            timing,
+        }
+    }
+}
+
+fn is_animation_property(property: &StyleProperty) -> bool {
+    matches!(
+        property,
+        StyleProperty::Animation
+            | StyleProperty::AnimationName
+            | StyleProperty::AnimationDuration
+            | StyleProperty::AnimationTimingFunction
+            | StyleProperty::AnimationDelay
+            | StyleProperty::AnimationIterationCount
+            | StyleProperty::AnimationDirection
+            | StyleProperty::AnimationFillMode
+            | StyleProperty::AnimationPlayState
+    )
+}
+
+impl AnimationSpec {
+    /// Keyframe progress after `elapsed` seconds (delay included), or `None`
+    /// if the animation has no effect at that time.
+    pub fn progress(&self, elapsed: f32) -> Option<f32> {
+        let elapsed = elapsed - self.delay;
+        let duration = self.duration.as_secs_f32();
+        let count = self.iteration_count;
+
+        let (iteration, progress) = if elapsed < 0.0 {
+            if !self.fill_mode.fills_backwards() {
+                return None;
+            }
+            (0.0, 0.0)
+        } else if duration <= 0.0 || elapsed >= duration * count {
+            if !self.fill_mode.fills_forwards() {
+                return None;
+            }
+            // Infinitely many zero-length iterations end played forwards,
+            // whichever way they alternate
+            if count.is_infinite() {
+                return Some(if self.direction == AnimationDirection::Reverse { 0.0 } else { 1.0 });
+            }
+            // End of the last (possibly partial) iteration
+            if count > 0.0 && count.fract() == 0.0 {
+                (count - 1.0, 1.0)
+            } else {
+                (count.floor(), count.fract())
+            }
+        } else {
+            let t = elapsed / duration;
+            (t.floor(), t.fract())
+        };
+
+        let odd = iteration % 2.0 == 1.0;
+        Some(match self.direction {
+            AnimationDirection::Normal => progress,
+            AnimationDirection::Reverse => 1.0 - progress,
+            AnimationDirection::Alternate if odd => 1.0 - progress,
+            AnimationDirection::Alternate => progress,
+            AnimationDirection::AlternateReverse if odd => progress,
+            AnimationDirection::AlternateReverse => 1.0 - progress,
+        })
+    }
+
+    /// Whether the animation still changes after `elapsed` seconds.
+    pub fn is_active(&self, elapsed: f32) -> bool {
+        elapsed < self.delay + self.duration.as_secs_f32() * self.iteration_count
+    }
+}
+
+/// Timeline of one animation on a widget.
+#[derive(Debug)]
+struct AnimationClock {
+    start: Instant,
+    paused_at: Option<Instant>,
+    active: bool,
+    affects_layout: bool,
+}
+
+/// Animation state of one widget.
+#[derive(Debug, Default)]
+struct WidgetAnimations {
+    clocks: HashMap<String, AnimationClock>,
+    /// Whether the widget was styled since the last frame began
+    seen: bool,
+}
+
+#[derive(Debug)]
+struct AnimationState {
+    now: Instant,
+    widgets: HashMap<WidgetId, WidgetAnimations>,
+}
+
+/// Running `@keyframes` animations for a widget tree.
+///
+/// Shared with every `StyleContext` of a window (see
+/// `StyleContext::with_animations`) and advanced once per frame. An
+/// animation starts when its name first appears in a widget's style and
+/// restarts if the name is removed and added again.
+#[derive(Debug)]
+pub struct Animations {
+    state: Mutex<AnimationState>,
+}
+
+impl Animations {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(AnimationState {
+                now: Instant::now(),
+                widgets: HashMap::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, AnimationState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a frame at `now`.
+    ///
+    /// All styles computed until the next frame sample animations at `now`.
+    /// Widgets that weren't styled during the previous frame are forgotten.
+    pub fn begin_frame(&self, now: Instant) {
+        let mut state = self.lock();
+        state.now = now;
+        state.widgets.retain(|_, widget| std::mem::take(&mut widget.seen));
+    }
+
+    /// Whether any animation is playing or waiting for its delay.
+    pub fn is_running(&self) -> bool {
+        self.lock()
+            .widgets
+            .values()
+            .any(|widget| widget.clocks.values().any(|clock| clock.active))
+    }
+
+    /// Whether a playing animation changes layout (width or height).
+    pub fn needs_layout(&self) -> bool {
+        self.lock().widgets.values().any(|widget| {
+            widget
+                .clocks
+                .values()
+                .any(|clock| clock.active && clock.affects_layout)
+        })
+    }
+
+    /// Apply the widget's animations to `style`.
+    pub fn apply(
+        &self,
+        widget_id: WidgetId,
+        style: &mut ComputedStyle,
+        keyframes: &HashMap<String, Keyframes>,
+        ctx: &StyleContext,
+    ) {
+        let specs = style.animations();
+        let mut state = self.lock();
+        let now = state.now;
+        if specs.is_empty() && !state.widgets.contains_key(&widget_id) {
+            return;
+        }
+
+        let widget = state.widgets.entry(widget_id).or_default();
+        widget.seen = true;
+        widget.clocks.retain(|name, _| specs.iter().any(|spec| spec.name == *name));
+
+        let base = style.clone();
+        for spec in specs {
+            let Some(keyframes) = keyframes.get(&spec.name) else {
+                continue;
+            };
+            let clock = widget.clocks.entry(spec.name.clone()).or_insert(AnimationClock {
+                start: now,
+                paused_at: None,
+                active: true,
+                affects_layout: false,
+            });
+
+            match spec.play_state {
+                AnimationPlayState::Paused => {
+                    clock.paused_at.get_or_insert(now);
+                }
+                AnimationPlayState::Running => {
+                    if let Some(paused_at) = clock.paused_at.take() {
+                        clock.start += now.saturating_duration_since(paused_at);
+                    }
+                }
+            }
+
+            let elapsed = clock
+                .paused_at
+                .unwrap_or(now)
+                .saturating_duration_since(clock.start)
+                .as_secs_f32();
+            clock.active = clock.paused_at.is_none() && spec.is_active(elapsed);
+            if let Some(progress) = spec.progress(elapsed) {
+                clock.affects_layout = keyframes.sample(progress, &spec.timing, &base, style, ctx);
+            }
+        }
+    }
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::properties::AnimationFillMode;
+    use crate::css::{StyleManager, WidgetState};
+    use crate::theme::ThemeData;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn spec(direction: AnimationDirection, fill_mode: AnimationFillMode, count: f32) -> AnimationSpec {
+        AnimationSpec {
+            name: "a".to_string(),
+            duration: Duration::from_secs(1),
+            delay: 0.5,
+            timing: TimingFunction::Linear,
+            iteration_count: count,
+            direction,
+            fill_mode,
+            play_state: AnimationPlayState::Running,
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let normal = spec(AnimationDirection::Normal, AnimationFillMode::None, 2.0);
+        assert_eq!(normal.progress(0.0), None);
+        assert_eq!(normal.progress(0.75), Some(0.25));
+        assert_eq!(normal.progress(1.75), Some(0.25));
+        assert_eq!(normal.progress(3.0), None);
+        assert!(normal.is_active(2.0) && !normal.is_active(2.5));
+
+        let alternate = spec(AnimationDirection::Alternate, AnimationFillMode::Both, 2.0);
+        assert_eq!(alternate.progress(0.0), Some(0.0));
+        assert_eq!(alternate.progress(1.75), Some(0.75));
+        // Fills forwards with the end of the reversed second iteration
+        assert_eq!(alternate.progress(5.0), Some(0.0));
+
+        let infinite = spec(AnimationDirection::Reverse, AnimationFillMode::None, f32::INFINITY);
+        assert_eq!(infinite.progress(100.75), Some(0.75));
+
+        // `animation: a 0s infinite forwards` ends at once
+        let instant = |direction| AnimationSpec {
+            duration: Duration::ZERO,
+            ..spec(direction, AnimationFillMode::Forwards, f32::INFINITY)
+        };
+        assert_eq!(instant(AnimationDirection::Normal).progress(1.0), Some(1.0));
+        assert_eq!(instant(AnimationDirection::Reverse).progress(1.0), Some(0.0));
+        assert_eq!(instant(AnimationDirection::AlternateReverse).progress(1.0), Some(1.0));
+    }
+
+    #[test]
+    fn test_keyframe_animation() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                "@keyframes fade {
+                     from { opacity: 0; }
+                     50% { opacity: 1; animation-timing-function: steps(2); }
+                     to { opacity: 0.5; }
+                 }
+                 .toast { background-color: #000000; animation: fade 1s linear; }",
+            )
+            .unwrap();
+        let animations = Arc::new(Animations::new());
+        let ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_animations(animations.clone());
+        let classes = ["toast".to_string()];
+        let style = || ctx.compute_style_for(3, "label", None, &classes, &WidgetState::default());
+
+        let start = Instant::now();
+        animations.begin_frame(start);
+        assert_eq!(style().opacity, 0.0);
+        assert!(animations.is_running());
+
+        animations.begin_frame(start + Duration::from_millis(250));
+        let quarter = style();
+        assert!((quarter.opacity - 0.5).abs() < 0.01);
+        assert!(quarter.is_set(&StyleProperty::Opacity));
+
+        // The 50% keyframe steps to the last value half-way to it
+        animations.begin_frame(start + Duration::from_millis(800));
+        assert!((style().opacity - 0.75).abs() < 0.01);
+
+        // Without fill-mode the animation stops affecting the style
+        animations.begin_frame(start + Duration::from_millis(1200));
+        assert_eq!(style().opacity, 1.0);
+        assert!(!animations.is_running());
+    }
+
+    #[test]
+    fn test_huge_animation_duration() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                "@keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+                 .toast { animation: fade 1e39s linear; }",
+            )
+            .unwrap();
+        let animations = Arc::new(Animations::new());
+        let ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_animations(animations.clone());
+        let classes = ["toast".to_string()];
+
+        // An infinite duration is clamped instead of panicking
+        animations.begin_frame(Instant::now());
+        let style = ctx.compute_style_for(4, "label", None, &classes, &WidgetState::default());
+        assert_eq!(style.opacity, 0.0);
+        assert!(animations.is_running());
+    }
+}
@@ -0,0 +1,159 @@
+//! `@media` query types and evaluation.
+
+use crate::css::{Length, LengthUnit, StyleContext};
+
+/// A comma-separated list of media queries; matches if any query matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaQueryList {
+    pub queries: Vec<MediaQuery>,
+}
+
+impl MediaQueryList {
+    pub fn new(queries: Vec<MediaQuery>) -> Self {
+        Self { queries }
+    }
+
+    /// Evaluate against the current viewport and theme.
+    pub fn matches(&self, ctx: &StyleContext) -> bool {
+        self.queries.iter().any(|query| query.matches(ctx))
+    }
+}
+
+/// A single media query, e.g. `screen and (max-width: 600px)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaQuery {
+    pub negated: bool,
+    pub media_type: MediaType,
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    /// Evaluate against the current viewport and theme.
+    pub fn matches(&self, ctx: &StyleContext) -> bool {
+        let matches = self.media_type != MediaType::Print
+            && self.features.iter().all(|feature| feature.matches(ctx));
+        matches != self.negated
+    }
+}
+
+/// Media types. Widgets always render to a screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    All,
+    Screen,
+    Print,
+}
+
+impl MediaType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "all" => Some(MediaType::All),
+            "screen" => Some(MediaType::Screen),
+            "print" => Some(MediaType::Print),
+            _ => None,
+        }
+    }
+}
+
+/// A media feature test, e.g. `(min-width: 600px)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaFeature {
+    Width(Length),
+    MinWidth(Length),
+    MaxWidth(Length),
+    Height(Length),
+    MinHeight(Length),
+    MaxHeight(Length),
+    Orientation(Orientation),
+    PrefersColorScheme(ColorScheme),
+    /// A feature this engine doesn't support; never matches.
+    Unknown(String),
+}
+
+impl MediaFeature {
+    /// Evaluate against the current viewport and theme.
+    pub fn matches(&self, ctx: &StyleContext) -> bool {
+        let px = |length: &Length| media_length_px(length, ctx);
+        let (width, height) = (ctx.viewport_width, ctx.viewport_height);
+
+        match self {
+            MediaFeature::Width(l) => width == px(l),
+            MediaFeature::MinWidth(l) => width >= px(l),
+            MediaFeature::MaxWidth(l) => width <= px(l),
+            MediaFeature::Height(l) => height == px(l),
+            MediaFeature::MinHeight(l) => height >= px(l),
+            MediaFeature::MaxHeight(l) => height <= px(l),
+            MediaFeature::Orientation(Orientation::Portrait) => height >= width,
+            MediaFeature::Orientation(Orientation::Landscape) => width > height,
+            MediaFeature::PrefersColorScheme(ColorScheme::Dark) => ctx.theme.is_dark,
+            MediaFeature::PrefersColorScheme(ColorScheme::Light) => !ctx.theme.is_dark,
+            MediaFeature::Unknown(_) => false,
+        }
+    }
+}
+
+/// Value of the `orientation` media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+/// Value of the `prefers-color-scheme` media feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+/// Convert a length in a media query to pixels.
+///
+/// Relative units use the initial font size rather than any element's.
+fn media_length_px(length: &Length, ctx: &StyleContext) -> f32 {
+    match length.unit {
+        LengthUnit::Em | LengthUnit::Rem => length.value * ctx.theme.typography.base_size,
+        _ => ctx.to_pixels(length),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ThemeData;
+
+    fn query(features: Vec<MediaFeature>) -> MediaQuery {
+        MediaQuery {
+            negated: false,
+            media_type: MediaType::All,
+            features,
+        }
+    }
+
+    #[test]
+    fn test_viewport_features() {
+        let theme = ThemeData::light();
+        let narrow = StyleContext::new(&theme).with_viewport(500.0, 800.0);
+        let wide = StyleContext::new(&theme).with_viewport(1200.0, 800.0);
+
+        let small = query(vec![MediaFeature::MaxWidth(Length::px(600.0))]);
+        assert!(small.matches(&narrow));
+        assert!(!small.matches(&wide));
+
+        let portrait = query(vec![MediaFeature::Orientation(Orientation::Portrait)]);
+        assert!(portrait.matches(&narrow));
+        assert!(!portrait.matches(&wide));
+
+        let not_small = MediaQuery { negated: true, ..small };
+        assert!(not_small.matches(&wide));
+    }
+
+    #[test]
+    fn test_color_scheme() {
+        let light = ThemeData::light();
+        let dark = ThemeData::dark();
+        let dark_query = query(vec![MediaFeature::PrefersColorScheme(ColorScheme::Dark)]);
+
+        assert!(!dark_query.matches(&StyleContext::new(&light)));
+        assert!(dark_query.matches(&StyleContext::new(&dark)));
+    }
+}
@@ -19,19 +19,33 @@
 //!     .run(|| view!());
 //! ```
 
+mod animation;
 mod loader;
+mod media;
 mod parser;
 pub mod properties;
 mod selector;
+mod shorthand;
 mod stylesheet;
+mod transition;
 mod value;
+mod vars;
 
+pub use animation::{Animations, Keyframe, Keyframes};
 pub use loader::{StyleManager, StyleBuilder, CssLoadError};
+pub use media::{ColorScheme, MediaFeature, MediaQuery, MediaQueryList, MediaType, Orientation};
 pub use parser::CssParser;
-pub use properties::{ComputedStyle, StyleProperty, AlignItems, FlexDirection, JustifyContent};
-pub use selector::{Selector, SelectorPart, PseudoClass, Specificity};
+pub use properties::{
+    ComputedStyle, StyleProperty, AlignItems, FlexDirection, JustifyContent, Transform, TransitionTiming,
+    AnimationDirection, AnimationFillMode, AnimationPlayState, AnimationSpec,
+};
+pub use selector::{
+    Combinator, ElementInfo, ElementNode, ElementTree, MatchTarget, PseudoClass, Selector,
+    SelectorPart, Specificity,
+};
 pub use stylesheet::{StyleSheet, StyleRule, StyleSheetBuilder, RuleBuilder};
-pub use value::{CssValue, Length, LengthUnit};
+pub use transition::{AnimatableProperty, AnimatedValue, Transitions};
+pub use value::{CalcExpr, CssValue, Length, LengthUnit, StepPosition, TimingFunction};
 
 use crate::theme::ThemeData;
 use std::collections::HashMap;
@@ -43,6 +57,7 @@ use std::sync::Arc;
 /// - Theme data for design tokens
 /// - Style manager for custom stylesheets
 /// - Viewport information for responsive units
+/// - The element tree for combinator selectors
 #[derive(Debug, Clone)]
 pub struct StyleContext<'a> {
     pub theme: &'a ThemeData,
@@ -50,6 +65,18 @@ pub struct StyleContext<'a> {
     pub parent_font_size: f32,
     pub viewport_width: f32,
     pub viewport_height: f32,
+    /// Size of the containing block, used as the basis for percentages.
+    pub containing_width: f32,
+    pub containing_height: f32,
+    pub element_tree: Option<Arc<ElementTree>>,
+    /// Running transitions, applied to styles computed for widgets
+    pub transitions: Option<Arc<Transitions>>,
+    /// Running `@keyframes` animations, applied after transitions
+    pub animations: Option<Arc<Animations>>,
+    /// Whether focus was moved with the keyboard, for `:focus-visible`
+    pub focus_visible: bool,
+    /// Combined stylesheet from the style manager, built once per context
+    stylesheet: Arc<StyleSheet>,
 }
 
 impl<'a> StyleContext<'a> {
@@ -60,6 +87,13 @@ impl<'a> StyleContext<'a> {
             parent_font_size: theme.typography.base_size,
             viewport_width: 1920.0,
             viewport_height: 1080.0,
+            containing_width: 1920.0,
+            containing_height: 1080.0,
+            element_tree: None,
+            transitions: None,
+            animations: None,
+            focus_visible: false,
+            stylesheet: Arc::new(StyleSheet::default()),
         }
     }
 
@@ -67,17 +101,59 @@ impl<'a> StyleContext<'a> {
     pub fn with_styles(theme: &'a ThemeData, styles: Arc<StyleManager>) -> Self {
         Self {
             theme,
+            stylesheet: Arc::new(styles.combined_stylesheet()),
             style_manager: Some(styles),
             parent_font_size: theme.typography.base_size,
             viewport_width: 1920.0,
             viewport_height: 1080.0,
+            containing_width: 1920.0,
+            containing_height: 1080.0,
+            element_tree: None,
+            transitions: None,
+            animations: None,
+            focus_visible: false,
         }
     }
 
     /// Set the viewport size for responsive units.
+    ///
+    /// Also makes the viewport the containing block for percentages.
     pub fn with_viewport(mut self, width: f32, height: f32) -> Self {
         self.viewport_width = width;
         self.viewport_height = height;
+        self.containing_width = width;
+        self.containing_height = height;
+        self
+    }
+
+    /// Set the containing block size used to resolve percentages.
+    pub fn with_containing_block(mut self, width: f32, height: f32) -> Self {
+        self.containing_width = width;
+        self.containing_height = height;
+        self
+    }
+
+    /// Attach the element tree used to match combinator selectors.
+    pub fn with_element_tree(mut self, tree: Arc<ElementTree>) -> Self {
+        self.element_tree = Some(tree);
+        self
+    }
+
+    /// Attach transition state, so styles computed for widgets animate.
+    pub fn with_transitions(mut self, transitions: Arc<Transitions>) -> Self {
+        self.transitions = Some(transitions);
+        self
+    }
+
+    /// Set whether the focused widget matches `:focus-visible`.
+    pub fn with_focus_visible(mut self, focus_visible: bool) -> Self {
+        self.focus_visible = focus_visible;
+        self
+    }
+
+    /// Attach animation state, so `animation-name` plays `@keyframes` rules.
+    pub fn with_animations(mut self, animations: Arc<Animations>) -> Self {
+        self.animations = Some(animations);
         self
     }
 
@@ -97,11 +173,7 @@ impl<'a> StyleContext<'a> {
 
     /// Get the combined stylesheet (custom + default).
     pub fn combined_stylesheet(&self) -> StyleSheet {
-        if let Some(sm) = &self.style_manager {
-            sm.combined_stylesheet()
-        } else {
-            StyleSheet::default()
-        }
+        (*self.stylesheet).clone()
     }
 
     /// Compute styles for a widget.
@@ -112,8 +184,69 @@ impl<'a> StyleContext<'a> {
         classes: &[String],
         state: &WidgetState,
     ) -> ComputedStyle {
-        let stylesheet = self.combined_stylesheet();
-        stylesheet.compute_style(widget_type, widget_id, classes, state, self)
+        let state = self.focus_state(state);
+        self.stylesheet.compute_style(widget_type, widget_id, classes, &state, self)
+    }
+
+    /// Mark a focused widget's focus as visible if it came from the keyboard.
+    fn focus_state(&self, state: &WidgetState) -> WidgetState {
+        WidgetState {
+            focus_visible: state.focus_visible || (state.focused && self.focus_visible),
+            ..*state
+        }
+    }
+
+    /// Compute styles for a widget, matching combinators against its
+    /// position in the element tree.
+    ///
+    /// Structural state (`:first-child` etc.) is taken from the tree.
+    /// Falls back to [`StyleContext::compute_style`] when the widget is
+    /// not in the tree. Running transitions and animations are applied to
+    /// the result.
+    pub fn compute_style_for(
+        &self,
+        widget_key: u64,
+        widget_type: &str,
+        widget_id: Option<&str>,
+        classes: &[String],
+        state: &WidgetState,
+    ) -> ComputedStyle {
+        let state = &self.focus_state(state);
+        let found = self
+            .element_tree
+            .as_deref()
+            .and_then(|tree| tree.find(widget_key).map(|node| (tree, node)));
+
+        let mut style = match found {
+            Some((tree, node)) => {
+                let structural = tree.node(node).element.state;
+                let state = WidgetState {
+                    first_child: structural.first_child,
+                    last_child: structural.last_child,
+                    nth_child: structural.nth_child,
+                    ..*state
+                };
+                let target = MatchTarget::new(widget_type, widget_id, classes, &state).in_tree(tree, node);
+                self.stylesheet.compute_style_for(&target, self)
+            }
+            None => self.compute_style(widget_type, widget_id, classes, state),
+        };
+
+        if let Some(transitions) = &self.transitions {
+            transitions.apply(widget_key, &mut style);
+        }
+        if let Some(animations) = &self.animations {
+            animations.apply(widget_key, &mut style, &self.stylesheet.keyframes, self);
+        }
+        style
+    }
+
+    /// Convert a length to pixels, resolving percentages against `basis`.
+    pub fn to_pixels_with_basis(&self, length: &Length, basis: f32) -> f32 {
+        match length.unit {
+            LengthUnit::Percent => length.value / 100.0 * basis,
+            _ => self.to_pixels(length),
+        }
     }
 
     /// Convert a length to pixels.
@@ -137,6 +270,8 @@ pub struct WidgetState {
     pub hovered: bool,
     pub pressed: bool,
     pub focused: bool,
+    /// Focused, with focus moved or used by the keyboard
+    pub focus_visible: bool,
     pub disabled: bool,
     pub checked: bool,
     pub first_child: bool,
@@ -150,13 +285,14 @@ impl WidgetState {
             PseudoClass::Hover => self.hovered,
             PseudoClass::Active => self.pressed,
             PseudoClass::Focus => self.focused,
-            PseudoClass::FocusVisible => self.focused, // Simplified
+            PseudoClass::FocusVisible => self.focused && self.focus_visible,
             PseudoClass::Disabled => self.disabled,
             PseudoClass::Enabled => !self.disabled,
             PseudoClass::Checked => self.checked,
             PseudoClass::FirstChild => self.first_child,
             PseudoClass::LastChild => self.last_child,
             PseudoClass::NthChild(n) => self.nth_child == *n,
+            PseudoClass::Root => false, // Needs the element tree
             PseudoClass::Not(_) => true, // TODO: Implement
         }
     }
@@ -197,6 +333,10 @@ impl ClassList {
         self.classes.contains(&class.to_string())
     }
 
+    pub fn as_slice(&self) -> &[String] {
+        &self.classes
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &String> {
         self.classes.iter()
     }
@@ -1,8 +1,14 @@
 //! CSS parser using the cssparser crate.
 
-use cssparser::{Parser, ParserInput, Token, ParseError};
+use cssparser::{Delimiter, Parser, ParserInput, Token, ParseError};
+use crate::css::media::{
+    ColorScheme, MediaFeature, MediaQuery, MediaQueryList, MediaType, Orientation,
+};
+use crate::css::animation::{Keyframe, Keyframes};
+use crate::css::selector::Combinator;
+use crate::css::shorthand;
 use crate::css::{
-    CssValue, Length, LengthUnit, Selector, SelectorPart, PseudoClass,
+    CalcExpr, CssValue, Length, LengthUnit, Selector, SelectorPart, PseudoClass,
     StyleProperty, StyleRule, StyleSheet,
 };
 use crate::geometry::Color;
@@ -16,8 +22,21 @@ impl CssParser {
     pub fn parse_stylesheet(css: &str) -> Result<StyleSheet, CssParseError> {
         let mut input = ParserInput::new(css);
         let mut parser = Parser::new(&mut input);
-        let mut rules = Vec::new();
+        let mut sheet = StyleSheet::empty();
+
+        Self::parse_rule_list(&mut parser, &[], &mut sheet);
+
+        Ok(sheet)
+    }
 
+    /// Parse rules and at-rules until the input is exhausted.
+    ///
+    /// `media` holds the queries of any enclosing `@media` blocks.
+    fn parse_rule_list<'i>(
+        parser: &mut Parser<'i, '_>,
+        media: &[MediaQueryList],
+        sheet: &mut StyleSheet,
+    ) {
         while !parser.is_exhausted() {
             // Skip whitespace and comments
             let _ = parser.try_parse::<_, _, ParseError<'_, ()>>(|p| {
@@ -29,43 +48,288 @@ impl CssParser {
                 break;
             }
 
-            match Self::parse_rule(&mut parser) {
-                Ok(rule) => rules.push(rule),
+            let at_keyword = parser.try_parse(|p| match p.next()?.clone() {
+                Token::AtKeyword(name) => Ok(name.to_string()),
+                token => Err(p.new_unexpected_token_error::<()>(token)),
+            });
+            if let Ok(name) = at_keyword {
+                Self::parse_at_rule(&name, parser, media, sheet);
+                continue;
+            }
+
+            match Self::parse_rule(parser) {
+                Ok(parsed) => {
+                    sheet.rules.extend(parsed.into_iter().map(|rule| rule.with_media(media.to_vec())));
+                }
                 Err(_) => {
                     // Skip to next rule on error
                     let _ = parser.next();
                 }
             }
         }
+    }
 
-        Ok(StyleSheet::new(rules))
+    /// Parse an at-rule after its `@name` token. Unsupported at-rules are skipped.
+    fn parse_at_rule<'i>(
+        name: &str,
+        parser: &mut Parser<'i, '_>,
+        media: &[MediaQueryList],
+        sheet: &mut StyleSheet,
+    ) {
+        if name.eq_ignore_ascii_case("media") {
+            let queries = parser.parse_until_before(
+                Delimiter::CurlyBracketBlock | Delimiter::Semicolon,
+                |p| Ok::<_, ParseError<'i, ()>>(Self::parse_media_query_list(p)),
+            );
+            if let (Ok(queries), Ok(())) = (queries, parser.expect_curly_bracket_block()) {
+                let mut media = media.to_vec();
+                media.push(queries);
+                let _ = parser.parse_nested_block(|p| {
+                    Self::parse_rule_list(p, &media, sheet);
+                    Ok::<_, ParseError<'i, ()>>(())
+                });
+                return;
+            }
+        } else if name.eq_ignore_ascii_case("keyframes") || name.eq_ignore_ascii_case("-webkit-keyframes") {
+            let keyframes_name = parser.parse_until_before(
+                Delimiter::CurlyBracketBlock | Delimiter::Semicolon,
+                |p| match p.next()?.clone() {
+                    Token::Ident(name) | Token::QuotedString(name) => Ok(name.to_string()),
+                    token => Err(p.new_unexpected_token_error::<()>(token)),
+                },
+            );
+            if let (Ok(keyframes_name), Ok(())) = (keyframes_name, parser.expect_curly_bracket_block()) {
+                let frames = parser.parse_nested_block(|p| {
+                    Ok::<_, ParseError<'i, ()>>(Self::parse_keyframe_list(p))
+                });
+                if let Ok(frames) = frames {
+                    sheet.add_keyframes(Keyframes::new(keyframes_name, frames));
+                }
+                return;
+            }
+        }
+
+        // Skip the prelude and the block or terminating semicolon
+        let _ = parser.parse_until_after(Delimiter::CurlyBracketBlock | Delimiter::Semicolon, |p| {
+            while p.next().is_ok() {}
+            Ok::<_, ParseError<'i, ()>>(())
+        });
+    }
+
+    /// Parse the keyframe blocks inside `@keyframes name { ... }`.
+    ///
+    /// Blocks with an invalid selector are skipped.
+    fn parse_keyframe_list<'i>(parser: &mut Parser<'i, '_>) -> Vec<Keyframe> {
+        let mut frames = Vec::new();
+        while !parser.is_exhausted() {
+            parser.skip_whitespace();
+            if parser.is_exhausted() {
+                break;
+            }
+
+            let offsets = parser.parse_until_before(Delimiter::CurlyBracketBlock, |p| {
+                let offsets = p.parse_comma_separated(|p| match p.next()?.clone() {
+                    Token::Ident(k) if k.eq_ignore_ascii_case("from") => Ok(0.0),
+                    Token::Ident(k) if k.eq_ignore_ascii_case("to") => Ok(1.0),
+                    Token::Percentage { unit_value, .. } if (0.0..=1.0).contains(&unit_value) => Ok(unit_value),
+                    token => Err(p.new_unexpected_token_error::<()>(token)),
+                })?;
+                Ok::<_, ParseError<'i, ()>>(offsets)
+            });
+            if parser.expect_curly_bracket_block().is_err() {
+                break;
+            }
+            let declarations = parser.parse_nested_block(Self::parse_declarations);
+
+            if let (Ok(offsets), Ok(declarations)) = (offsets, declarations) {
+                for offset in offsets {
+                    frames.push(Keyframe::new(offset, declarations.clone()));
+                }
+            }
+        }
+        frames
+    }
+
+    /// Parse a comma-separated media query list.
+    ///
+    /// Invalid queries never match, as in browsers.
+    fn parse_media_query_list<'i>(parser: &mut Parser<'i, '_>) -> MediaQueryList {
+        let mut queries = Vec::new();
+
+        loop {
+            let query = parser.parse_until_before(Delimiter::Comma, Self::parse_media_query);
+            queries.push(query.unwrap_or_else(|_| MediaQuery {
+                negated: false,
+                media_type: MediaType::All,
+                features: vec![MediaFeature::Unknown("invalid".to_string())],
+            }));
+
+            if parser.try_parse(|p| p.expect_comma()).is_err() {
+                break;
+            }
+        }
+
+        MediaQueryList::new(queries)
+    }
+
+    /// Parse `[not | only] <type> [and (<feature>)]*` or `(<feature>) [and (<feature>)]*`.
+    fn parse_media_query<'i>(parser: &mut Parser<'i, '_>) -> Result<MediaQuery, ParseError<'i, ()>> {
+        let mut negated = false;
+        let mut media_type = MediaType::All;
+        let mut features = Vec::new();
+        let mut expect_type = true;
+
+        while let Ok(token) = parser.next() {
+            match token.clone() {
+                Token::Ident(ref id) if expect_type && id.eq_ignore_ascii_case("not") => negated = true,
+                Token::Ident(ref id) if expect_type && id.eq_ignore_ascii_case("only") => {}
+                Token::Ident(ref id) if !expect_type && id.eq_ignore_ascii_case("and") => {}
+                Token::Ident(ref id) if expect_type => {
+                    media_type = MediaType::from_name(id).ok_or_else(|| parser.new_custom_error(()))?;
+                    expect_type = false;
+                }
+                Token::ParenthesisBlock => {
+                    features.push(parser.parse_nested_block(Self::parse_media_feature)?);
+                    expect_type = false;
+                }
+                token => return Err(parser.new_unexpected_token_error(token)),
+            }
+        }
+
+        Ok(MediaQuery {
+            negated,
+            media_type,
+            features,
+        })
+    }
+
+    /// Parse the inside of a `(<name>: <value>)` media feature.
+    fn parse_media_feature<'i>(parser: &mut Parser<'i, '_>) -> Result<MediaFeature, ParseError<'i, ()>> {
+        let name = parser.expect_ident()?.to_ascii_lowercase();
+        let value = match parser.try_parse(|p| p.expect_colon()) {
+            Ok(()) => Self::parse_value(parser).ok(),
+            Err(_) => None,
+        };
+
+        let length = || value.as_ref().and_then(CssValue::as_length);
+        let keyword = || match &value {
+            Some(CssValue::Keyword(k)) => Some(k.to_ascii_lowercase()),
+            _ => None,
+        };
+
+        let feature = match name.as_str() {
+            "width" => length().map(MediaFeature::Width),
+            "min-width" => length().map(MediaFeature::MinWidth),
+            "max-width" => length().map(MediaFeature::MaxWidth),
+            "height" => length().map(MediaFeature::Height),
+            "min-height" => length().map(MediaFeature::MinHeight),
+            "max-height" => length().map(MediaFeature::MaxHeight),
+            "orientation" => match keyword().as_deref() {
+                Some("portrait") => Some(MediaFeature::Orientation(Orientation::Portrait)),
+                Some("landscape") => Some(MediaFeature::Orientation(Orientation::Landscape)),
+                _ => None,
+            },
+            "prefers-color-scheme" => match keyword().as_deref() {
+                Some("light") => Some(MediaFeature::PrefersColorScheme(ColorScheme::Light)),
+                Some("dark") => Some(MediaFeature::PrefersColorScheme(ColorScheme::Dark)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // Skip anything left over, such as range syntax
+        while parser.next().is_ok() {}
+
+        Ok(feature.unwrap_or(MediaFeature::Unknown(name)))
     }
 
     /// Parse a single CSS rule.
-    fn parse_rule<'i>(parser: &mut Parser<'i, '_>) -> Result<StyleRule, CssParseError> {
-        // Parse selector
-        let selector = Self::parse_selector(parser)?;
+    ///
+    /// A selector list (`h1, h2 { ... }`) produces one rule per selector.
+    fn parse_rule<'i>(parser: &mut Parser<'i, '_>) -> Result<Vec<StyleRule>, CssParseError> {
+        // Parse selector list
+        let mut selectors = Vec::new();
+        let mut invalid = false;
+        loop {
+            let selector = parser
+                .parse_until_before(Delimiter::Comma | Delimiter::CurlyBracketBlock, |p| {
+                    Ok::<_, ParseError<'i, ()>>(Self::parse_selector(p))
+                })
+                .map_err(|_| CssParseError::InvalidSelector)?;
+
+            match selector {
+                Ok(selector) => selectors.push(selector),
+                Err(_) => invalid = true,
+            }
+
+            if parser.try_parse(|p| p.expect_comma()).is_err() {
+                break;
+            }
+        }
 
         // Parse declaration block
+        parser
+            .expect_curly_bracket_block()
+            .map_err(|_| CssParseError::InvalidSelector)?;
         let declarations = parser.parse_nested_block(|p| {
             Self::parse_declarations(p)
         }).map_err(|_| CssParseError::InvalidDeclaration)?;
 
-        Ok(StyleRule::new(selector, declarations))
+        // An invalid selector invalidates the whole rule
+        if invalid || selectors.is_empty() {
+            return Err(CssParseError::InvalidSelector);
+        }
+
+        Ok(selectors
+            .into_iter()
+            .map(|selector| StyleRule::new(selector, declarations.clone()))
+            .collect())
     }
 
     /// Parse a CSS selector.
     fn parse_selector<'i>(parser: &mut Parser<'i, '_>) -> Result<Selector, CssParseError> {
         let mut parts = Vec::new();
+        // Combinator seen since the last compound selector; whitespace only
+        // counts as a descendant combinator if another compound follows.
+        let mut pending: Option<Combinator> = None;
 
-        loop {
-            parser.skip_whitespace();
+        parser.skip_whitespace();
 
-            let token = match parser.next() {
+        loop {
+            let token = match parser.next_including_whitespace() {
                 Ok(t) => t.clone(),
                 Err(_) => break,
             };
 
+            let combinator = match token {
+                Token::WhiteSpace(_) => Some(Combinator::Descendant),
+                Token::Delim('>') => Some(Combinator::Child),
+                Token::Delim('+') => Some(Combinator::NextSibling),
+                Token::Delim('~') => Some(Combinator::SubsequentSibling),
+                _ => None,
+            };
+            if let Some(combinator) = combinator {
+                if parts.is_empty() {
+                    return Err(CssParseError::InvalidSelector);
+                }
+                // An explicit combinator overrides surrounding whitespace
+                pending = match (pending, combinator) {
+                    (Some(explicit), Combinator::Descendant) => Some(explicit),
+                    (Some(Combinator::Descendant) | None, c) => Some(c),
+                    (Some(_), _) => return Err(CssParseError::InvalidSelector),
+                };
+                continue;
+            }
+
+            if matches!(token, Token::CurlyBracketBlock) {
+                // Start of declaration block, we're done with selector
+                break;
+            }
+
+            if let Some(combinator) = pending.take() {
+                parts.push(SelectorPart::Combinator(combinator));
+            }
+
             match token {
                 Token::Ident(name) => {
                     parts.push(SelectorPart::Type(name.to_string()));
@@ -75,7 +339,7 @@ impl CssParser {
                 }
                 Token::Delim('.') => {
                     // Class selector
-                    if let Ok(Token::Ident(name)) = parser.next() {
+                    if let Ok(Token::Ident(name)) = parser.next_including_whitespace() {
                         parts.push(SelectorPart::Class(name.to_string()));
                     }
                 }
@@ -84,16 +348,12 @@ impl CssParser {
                 }
                 Token::Colon => {
                     // Pseudo-class
-                    if let Ok(Token::Ident(name)) = parser.next() {
+                    if let Ok(Token::Ident(name)) = parser.next_including_whitespace() {
                         if let Some(pseudo) = PseudoClass::from_name(name) {
                             parts.push(SelectorPart::PseudoClass(pseudo));
                         }
                     }
                 }
-                Token::CurlyBracketBlock => {
-                    // Start of declaration block, we're done with selector
-                    break;
-                }
                 _ => {
                     // Unknown token, might be start of block
                     break;
@@ -101,7 +361,8 @@ impl CssParser {
             }
         }
 
-        if parts.is_empty() {
+        // A combinator must be followed by a compound selector
+        if parts.is_empty() || matches!(pending, Some(c) if c != Combinator::Descendant) {
             return Err(CssParseError::InvalidSelector);
         }
 
@@ -134,11 +395,20 @@ impl CssParser {
                 continue;
             }
 
-            // Parse value
+            // Parse value up to the end of the declaration
             parser.skip_whitespace();
-            if let Ok(value) = Self::parse_value(parser) {
+            let value = parser.parse_until_before(Delimiter::Semicolon, |p| {
+                Ok::<_, ParseError<'i, ()>>(Self::parse_value(p))
+            });
+            if let Ok(Ok(value)) = value {
                 let property = StyleProperty::from_name(&property_name);
-                declarations.insert(property, value);
+                // Shorthands containing var() are expanded after substitution
+                match shorthand::expand(&property, &value) {
+                    Some(longhands) if !value.contains_var() => declarations.extend(longhands),
+                    _ => {
+                        declarations.insert(property, value);
+                    }
+                }
             }
 
             // Skip semicolon
@@ -152,7 +422,56 @@ impl CssParser {
     }
 
     /// Parse a CSS value.
+    ///
+    /// Space-separated components become a [`CssValue::List`] and
+    /// comma-separated groups a [`CssValue::CommaList`]; a single component
+    /// is returned as-is.
     fn parse_value<'i>(parser: &mut Parser<'i, '_>) -> Result<CssValue, CssParseError> {
+        let mut groups = Vec::new();
+        let mut current = Vec::new();
+
+        loop {
+            parser.skip_whitespace();
+            if parser.is_exhausted() {
+                break;
+            }
+
+            if parser.try_parse(|p| p.expect_comma()).is_ok() {
+                groups.push(Self::value_group(std::mem::take(&mut current))?);
+                continue;
+            }
+
+            // `!important` is accepted but not given priority
+            let important = parser.try_parse(|p| {
+                p.expect_delim('!')?;
+                p.expect_ident_matching("important")
+            });
+            if important.is_ok() {
+                continue;
+            }
+
+            current.push(Self::parse_component(parser)?);
+        }
+        groups.push(Self::value_group(current)?);
+
+        if groups.len() == 1 {
+            Ok(groups.remove(0))
+        } else {
+            Ok(CssValue::CommaList(groups))
+        }
+    }
+
+    /// Collapse a space-separated group of components into a value.
+    fn value_group(mut components: Vec<CssValue>) -> Result<CssValue, CssParseError> {
+        match components.len() {
+            0 => Err(CssParseError::InvalidValue),
+            1 => Ok(components.remove(0)),
+            _ => Ok(CssValue::List(components)),
+        }
+    }
+
+    /// Parse a single component value.
+    fn parse_component<'i>(parser: &mut Parser<'i, '_>) -> Result<CssValue, CssParseError> {
         let token = parser.next().map_err(|_| CssParseError::InvalidValue)?;
 
         match token {
@@ -183,6 +502,10 @@ impl CssParser {
             Token::Dimension { value, unit, .. } => {
                 if let Some(length_unit) = LengthUnit::parse(unit) {
                     Ok(CssValue::Length(Length::new(*value, length_unit)))
+                } else if unit.eq_ignore_ascii_case("ms") {
+                    Ok(CssValue::Time(*value))
+                } else if unit.eq_ignore_ascii_case("s") {
+                    Ok(CssValue::Time(*value * 1000.0))
                 } else {
                     Err(CssParseError::InvalidValue)
                 }
@@ -190,6 +513,13 @@ impl CssParser {
             Token::QuotedString(s) => {
                 Ok(CssValue::String(s.to_string()))
             }
+            Token::UnquotedUrl(url) => {
+                Ok(CssValue::Url(url.to_string()))
+            }
+            Token::Delim('/') => {
+                // Separator used by `font` and `border-radius`
+                Ok(CssValue::Keyword("/".to_string()))
+            }
             Token::Function(name) => {
                 let name = name.to_string();
                 parser.parse_nested_block(|p| {
@@ -209,8 +539,137 @@ impl CssParser {
             "rgb" | "rgba" => Self::parse_rgb_function(parser),
             "hsl" | "hsla" => Self::parse_hsl_function(parser),
             "var" => Self::parse_var_function(parser),
-            "calc" => Ok(CssValue::Keyword("calc(...)".to_string())), // Simplified
-            _ => Ok(CssValue::Keyword(format!("{}(...)", name))),
+            "calc" | "min" | "max" | "clamp" => {
+                Self::parse_math_function(name, parser).map(CssValue::Calc)
+            }
+            "url" => match parser.expect_string() {
+                Ok(url) => Ok(CssValue::Url(url.to_string())),
+                Err(_) => Ok(CssValue::Keyword("url()".to_string())),
+            },
+            _ => {
+                let args = match Self::parse_value(parser) {
+                    Ok(CssValue::CommaList(args)) => args,
+                    Ok(arg) => vec![arg],
+                    Err(_) => Vec::new(),
+                };
+                Ok(CssValue::Function(name.to_string(), args))
+            }
+        }
+    }
+
+    /// Parse the contents of calc(), min(), max() or clamp().
+    fn parse_math_function<'i>(
+        name: &str,
+        parser: &mut Parser<'i, '_>,
+    ) -> Result<CalcExpr, ParseError<'i, ()>> {
+        let expr = match name {
+            "calc" => Self::parse_calc_sum(parser)?,
+            "min" | "max" => {
+                let args = parser.parse_comma_separated(Self::parse_calc_sum)?;
+                if name == "min" {
+                    CalcExpr::Min(args)
+                } else {
+                    CalcExpr::Max(args)
+                }
+            }
+            _ => {
+                let mut args = parser.parse_comma_separated(Self::parse_calc_sum)?;
+                if args.len() != 3 {
+                    return Err(parser.new_custom_error(()));
+                }
+                let max = args.pop().unwrap();
+                let value = args.pop().unwrap();
+                let min = args.pop().unwrap();
+                CalcExpr::Clamp(Box::new(min), Box::new(value), Box::new(max))
+            }
+        };
+        parser.expect_exhausted()?;
+        Ok(expr)
+    }
+
+    /// Parse `<product> [('+' | '-') <product>]*`.
+    fn parse_calc_sum<'i>(parser: &mut Parser<'i, '_>) -> Result<CalcExpr, ParseError<'i, ()>> {
+        let mut expr = Self::parse_calc_product(parser)?;
+
+        loop {
+            let op = parser.try_parse(|p| match p.next()?.clone() {
+                Token::Delim(op @ ('+' | '-')) => Ok(op),
+                token => Err(p.new_unexpected_token_error::<()>(token)),
+            });
+            let Ok(op) = op else { break };
+
+            let rhs = Box::new(Self::parse_calc_product(parser)?);
+            expr = if op == '+' {
+                CalcExpr::Add(Box::new(expr), rhs)
+            } else {
+                CalcExpr::Sub(Box::new(expr), rhs)
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse `<term> [('*' | '/') <term>]*`.
+    fn parse_calc_product<'i>(parser: &mut Parser<'i, '_>) -> Result<CalcExpr, ParseError<'i, ()>> {
+        let mut expr = Self::parse_calc_term(parser)?;
+
+        loop {
+            let op = parser.try_parse(|p| match p.next()?.clone() {
+                Token::Delim(op @ ('*' | '/')) => Ok(op),
+                token => Err(p.new_unexpected_token_error::<()>(token)),
+            });
+            let Ok(op) = op else { break };
+
+            let rhs = Box::new(Self::parse_calc_term(parser)?);
+            expr = if op == '*' {
+                CalcExpr::Mul(Box::new(expr), rhs)
+            } else {
+                CalcExpr::Div(Box::new(expr), rhs)
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse a number, length, percentage, parenthesized sum or nested function.
+    fn parse_calc_term<'i>(parser: &mut Parser<'i, '_>) -> Result<CalcExpr, ParseError<'i, ()>> {
+        let token = parser.next()?.clone();
+
+        match token {
+            Token::Number { value, .. } => Ok(CalcExpr::Number(value)),
+            Token::Percentage { unit_value, .. } => {
+                Ok(CalcExpr::Value(Length::percent(unit_value * 100.0)))
+            }
+            Token::Dimension { value, ref unit, .. } => match LengthUnit::parse(unit) {
+                Some(unit) => Ok(CalcExpr::Value(Length::new(value, unit))),
+                None => Err(parser.new_unexpected_token_error(token.clone())),
+            },
+            Token::ParenthesisBlock => parser.parse_nested_block(|p| {
+                let expr = Self::parse_calc_sum(p)?;
+                p.expect_exhausted()?;
+                Ok(expr)
+            }),
+            Token::Function(ref name) => {
+                let name = name.to_string();
+                parser.parse_nested_block(|p| match name.as_str() {
+                    "calc" | "min" | "max" | "clamp" => Self::parse_math_function(&name, p),
+                    "var" => match Self::parse_var_function(p)? {
+                        CssValue::Var(name, fallback) => {
+                            let fallback = match fallback {
+                                Some(fallback) => Some(Box::new(
+                                    CalcExpr::from_value(&fallback)
+                                        .ok_or_else(|| p.new_custom_error(()))?,
+                                )),
+                                None => None,
+                            };
+                            Ok(CalcExpr::Var(name, fallback))
+                        }
+                        _ => Err(p.new_custom_error(())),
+                    },
+                    _ => Err(p.new_custom_error(())),
+                })
+            }
+            _ => Err(parser.new_unexpected_token_error(token)),
         }
     }
 
@@ -309,6 +768,13 @@ impl CssParser {
         Ok(CssValue::Var(var_name, fallback))
     }
 
+    /// Parse a standalone property value, such as a variable's value.
+    pub fn parse_value_str(value: &str) -> Result<CssValue, CssParseError> {
+        let mut input = ParserInput::new(value);
+        let mut parser = Parser::new(&mut input);
+        Self::parse_value(&mut parser)
+    }
+
     /// Parse inline style string.
     pub fn parse_inline_style(style: &str) -> HashMap<StyleProperty, CssValue> {
         let declarations = HashMap::new();
@@ -391,6 +857,189 @@ mod tests {
         assert_eq!(stylesheet.rules.len(), 1);
     }
 
+    #[test]
+    fn test_parse_combinators() {
+        let css = ".sidebar button { color: red; }\n.toolbar > .icon-button { color: blue; }\nlabel + button { }";
+        let stylesheet = CssParser::parse_stylesheet(css).unwrap();
+
+        assert_eq!(
+            stylesheet.rules[0].selector.parts,
+            vec![
+                SelectorPart::Class("sidebar".into()),
+                SelectorPart::Combinator(Combinator::Descendant),
+                SelectorPart::Type("button".into()),
+            ]
+        );
+        assert_eq!(
+            stylesheet.rules[1].selector.parts,
+            vec![
+                SelectorPart::Class("toolbar".into()),
+                SelectorPart::Combinator(Combinator::Child),
+                SelectorPart::Class("icon-button".into()),
+            ]
+        );
+        assert_eq!(
+            stylesheet.rules[2].selector.parts[1],
+            SelectorPart::Combinator(Combinator::NextSibling)
+        );
+    }
+
+    #[test]
+    fn test_parse_selector_list() {
+        let css = "h1, .title > label,h2 { color: red; }";
+        let stylesheet = CssParser::parse_stylesheet(css).unwrap();
+
+        assert_eq!(stylesheet.rules.len(), 3);
+        assert_eq!(stylesheet.rules[0].selector.parts, vec![SelectorPart::Type("h1".into())]);
+        assert_eq!(stylesheet.rules[1].selector.parts.len(), 3);
+        assert_eq!(stylesheet.rules[2].selector.parts, vec![SelectorPart::Type("h2".into())]);
+        assert!(stylesheet.rules.iter().all(|r| r.declarations.contains_key(&StyleProperty::Color)));
+    }
+
+    #[test]
+    fn test_parse_multi_token_values() {
+        let declarations = CssParser::parse_inline_style(
+            "font-family: Inter, sans-serif; box-shadow: 0 1px 2px rgba(0, 0, 0, 0.5);",
+        );
+
+        assert_eq!(
+            declarations[&StyleProperty::FontFamily],
+            CssValue::CommaList(vec![
+                CssValue::Keyword("Inter".into()),
+                CssValue::Keyword("sans-serif".into()),
+            ])
+        );
+        match &declarations[&StyleProperty::BoxShadow] {
+            CssValue::List(values) => {
+                assert_eq!(values.len(), 4);
+                assert_eq!(values[2], CssValue::Length(Length::px(2.0)));
+                assert!(matches!(values[3], CssValue::Color(_)));
+            }
+            other => panic!("unexpected box-shadow value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_expand_shorthands() {
+        let declarations = CssParser::parse_inline_style(
+            "padding: 8px 16px; border: 1px solid red; flex: 1; gap: 4px 8px; \
+             font: italic 14px/1.5 Inter, sans-serif; transition: opacity 150ms ease-out 50ms;",
+        );
+        let px = |v: f32| CssValue::Length(Length::px(v));
+
+        assert!(!declarations.contains_key(&StyleProperty::Padding));
+        assert_eq!(declarations[&StyleProperty::PaddingTop], px(8.0));
+        assert_eq!(declarations[&StyleProperty::PaddingLeft], px(16.0));
+
+        assert_eq!(declarations[&StyleProperty::BorderWidth], px(1.0));
+        assert_eq!(declarations[&StyleProperty::BorderStyle], CssValue::Keyword("solid".into()));
+        assert!(matches!(declarations[&StyleProperty::BorderColor], CssValue::Color(_)));
+
+        assert_eq!(declarations[&StyleProperty::FlexGrow], CssValue::Number(1.0));
+        assert_eq!(declarations[&StyleProperty::FlexBasis], CssValue::Percentage(0.0));
+
+        assert_eq!(declarations[&StyleProperty::RowGap], px(4.0));
+        assert_eq!(declarations[&StyleProperty::ColumnGap], px(8.0));
+
+        assert_eq!(declarations[&StyleProperty::FontStyle], CssValue::Keyword("italic".into()));
+        assert_eq!(declarations[&StyleProperty::FontSize], px(14.0));
+        assert_eq!(declarations[&StyleProperty::LineHeight], CssValue::Number(1.5));
+
+        assert_eq!(declarations[&StyleProperty::TransitionProperty], CssValue::Keyword("opacity".into()));
+        assert_eq!(declarations[&StyleProperty::TransitionDuration], CssValue::Time(150.0));
+        assert_eq!(declarations[&StyleProperty::TransitionDelay], CssValue::Time(50.0));
+    }
+
+    #[test]
+    fn test_parse_calc() {
+        let declarations = CssParser::parse_inline_style(
+            "width: calc(100% - 240px); height: clamp(10px, calc((50% + 2rem) / 2), var(--max, 300px)); min-width: min(1px, 2px, 3px);",
+        );
+
+        assert_eq!(
+            declarations[&StyleProperty::Width],
+            CssValue::Calc(CalcExpr::Sub(
+                Box::new(CalcExpr::Value(Length::percent(100.0))),
+                Box::new(CalcExpr::Value(Length::px(240.0))),
+            ))
+        );
+        match &declarations[&StyleProperty::Height] {
+            CssValue::Calc(CalcExpr::Clamp(_, value, max)) => {
+                assert!(matches!(**value, CalcExpr::Div(..)));
+                assert!(matches!(**max, CalcExpr::Var(ref name, Some(_)) if name == "--max"));
+            }
+            other => panic!("unexpected clamp value: {:?}", other),
+        }
+        assert!(matches!(
+            &declarations[&StyleProperty::MinWidth],
+            CssValue::Calc(CalcExpr::Min(args)) if args.len() == 3
+        ));
+
+        // Operators require surrounding whitespace
+        let declarations = CssParser::parse_inline_style("width: calc(100% -240px);");
+        assert!(!declarations.contains_key(&StyleProperty::Width));
+    }
+
+    #[test]
+    fn test_parse_media_rules() {
+        let css = r#"
+            .sidebar { width: 240px; }
+            @media screen and (max-width: 600px), (orientation: portrait) {
+                .sidebar { width: 100%; }
+                @media (prefers-color-scheme: dark) {
+                    .sidebar { color: white; }
+                }
+            }
+            @font-face { font-family: Inter; }
+            @import "other.css";
+            .footer { height: 20px; }
+        "#;
+        let stylesheet = CssParser::parse_stylesheet(css).unwrap();
+
+        assert_eq!(stylesheet.rules.len(), 4);
+        assert!(stylesheet.rules[0].media.is_empty());
+        assert_eq!(stylesheet.rules[1].media.len(), 1);
+        assert_eq!(stylesheet.rules[2].media.len(), 2);
+        assert!(stylesheet.rules[3].media.is_empty());
+
+        let queries = &stylesheet.rules[1].media[0].queries;
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].media_type, MediaType::Screen);
+        assert_eq!(queries[0].features, vec![MediaFeature::MaxWidth(Length::px(600.0))]);
+        assert_eq!(queries[1].features, vec![MediaFeature::Orientation(Orientation::Portrait)]);
+        assert_eq!(
+            stylesheet.rules[2].media[1].queries[0].features,
+            vec![MediaFeature::PrefersColorScheme(ColorScheme::Dark)]
+        );
+    }
+
+    #[test]
+    fn test_parse_keyframes() {
+        let css = r#"
+            @keyframes slide-in {
+                to { transform: translateX(0); }
+                from, 20% { opacity: 0; animation-timing-function: ease-in; }
+            }
+            .toast { animation: slide-in 300ms ease-out 2 alternate both; }
+        "#;
+        let stylesheet = CssParser::parse_stylesheet(css).unwrap();
+        assert_eq!(stylesheet.rules.len(), 1);
+
+        let keyframes = &stylesheet.keyframes["slide-in"];
+        let offsets: Vec<f32> = keyframes.frames.iter().map(|frame| frame.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.2, 1.0]);
+        assert_eq!(keyframes.frames[0].timing, Some(crate::css::TimingFunction::EaseIn));
+        assert!(!keyframes.frames[0].declarations.contains_key(&StyleProperty::AnimationTimingFunction));
+        assert!(keyframes.frames[2].declarations.contains_key(&StyleProperty::Transform));
+
+        let declarations = &stylesheet.rules[0].declarations;
+        assert_eq!(declarations[&StyleProperty::AnimationName], CssValue::Keyword("slide-in".into()));
+        assert_eq!(declarations[&StyleProperty::AnimationDuration], CssValue::Time(300.0));
+        assert_eq!(declarations[&StyleProperty::AnimationIterationCount], CssValue::Number(2.0));
+        assert_eq!(declarations[&StyleProperty::AnimationDirection], CssValue::Keyword("alternate".into()));
+        assert_eq!(declarations[&StyleProperty::AnimationFillMode], CssValue::Keyword("both".into()));
+    }
+
     #[test]
     fn test_parse_inline_style() {
         let style = "color: blue; font-size: 16px;";
@@ -1,7 +1,11 @@
 //! CSS property definitions and computed styles.
 
-use crate::css::{CssValue, StyleContext};
+use crate::css::vars::VarResolver;
+use crate::css::{shorthand, CssValue, StyleContext, TimingFunction};
+use std::collections::{HashMap, HashSet};
 use crate::geometry::{BorderRadius, Color, EdgeInsets};
+use crate::render::SpanStyle;
+use crate::theme::BoxShadow;
 
 /// A style property name.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,6 +25,7 @@ pub enum StyleProperty {
     MaxHeight,
 
     // Flexbox
+    Flex,
     FlexDirection,
     FlexWrap,
     JustifyContent,
@@ -46,6 +51,7 @@ pub enum StyleProperty {
     PaddingLeft,
 
     // Background
+    Background,
     BackgroundColor,
     BackgroundImage,
     BackgroundSize,
@@ -53,6 +59,7 @@ pub enum StyleProperty {
     BackgroundRepeat,
 
     // Border
+    Border,
     BorderWidth,
     BorderTopWidth,
     BorderRightWidth,
@@ -72,6 +79,7 @@ pub enum StyleProperty {
 
     // Typography
     Color,
+    Font,
     FontFamily,
     FontSize,
     FontWeight,
@@ -101,6 +109,17 @@ pub enum StyleProperty {
     TransitionTimingFunction,
     TransitionDelay,
 
+    // Animations
+    Animation,
+    AnimationName,
+    AnimationDuration,
+    AnimationTimingFunction,
+    AnimationDelay,
+    AnimationIterationCount,
+    AnimationDirection,
+    AnimationFillMode,
+    AnimationPlayState,
+
     // Transform
     Transform,
     TransformOrigin,
@@ -135,6 +154,7 @@ impl StyleProperty {
             "max-width" => StyleProperty::MaxWidth,
             "max-height" => StyleProperty::MaxHeight,
 
+            "flex" => StyleProperty::Flex,
             "flex-direction" => StyleProperty::FlexDirection,
             "flex-wrap" => StyleProperty::FlexWrap,
             "justify-content" => StyleProperty::JustifyContent,
@@ -158,12 +178,14 @@ impl StyleProperty {
             "padding-bottom" => StyleProperty::PaddingBottom,
             "padding-left" => StyleProperty::PaddingLeft,
 
-            "background-color" | "background" => StyleProperty::BackgroundColor,
+            "background" => StyleProperty::Background,
+            "background-color" => StyleProperty::BackgroundColor,
             "background-image" => StyleProperty::BackgroundImage,
             "background-size" => StyleProperty::BackgroundSize,
             "background-position" => StyleProperty::BackgroundPosition,
             "background-repeat" => StyleProperty::BackgroundRepeat,
 
+            "border" => StyleProperty::Border,
             "border-width" => StyleProperty::BorderWidth,
             "border-top-width" => StyleProperty::BorderTopWidth,
             "border-right-width" => StyleProperty::BorderRightWidth,
@@ -182,6 +204,7 @@ impl StyleProperty {
             "border-bottom-left-radius" => StyleProperty::BorderBottomLeftRadius,
 
             "color" => StyleProperty::Color,
+            "font" => StyleProperty::Font,
             "font-family" => StyleProperty::FontFamily,
             "font-size" => StyleProperty::FontSize,
             "font-weight" => StyleProperty::FontWeight,
@@ -209,6 +232,16 @@ impl StyleProperty {
             "transition-timing-function" => StyleProperty::TransitionTimingFunction,
             "transition-delay" => StyleProperty::TransitionDelay,
 
+            "animation" => StyleProperty::Animation,
+            "animation-name" => StyleProperty::AnimationName,
+            "animation-duration" => StyleProperty::AnimationDuration,
+            "animation-timing-function" => StyleProperty::AnimationTimingFunction,
+            "animation-delay" => StyleProperty::AnimationDelay,
+            "animation-iteration-count" => StyleProperty::AnimationIterationCount,
+            "animation-direction" => StyleProperty::AnimationDirection,
+            "animation-fill-mode" => StyleProperty::AnimationFillMode,
+            "animation-play-state" => StyleProperty::AnimationPlayState,
+
             "transform" => StyleProperty::Transform,
             "transform-origin" => StyleProperty::TransformOrigin,
 
@@ -225,6 +258,28 @@ impl StyleProperty {
         }
     }
 
+    /// Check if this is a custom property (`--name`).
+    pub fn is_custom_property(&self) -> bool {
+        matches!(self, StyleProperty::Custom(name) if name.starts_with("--"))
+    }
+
+    /// Check if this property is a shorthand for several longhands.
+    pub fn is_shorthand(&self) -> bool {
+        matches!(
+            self,
+            StyleProperty::Margin
+                | StyleProperty::Padding
+                | StyleProperty::Border
+                | StyleProperty::BorderRadius
+                | StyleProperty::Flex
+                | StyleProperty::Gap
+                | StyleProperty::Background
+                | StyleProperty::Font
+                | StyleProperty::Transition
+                | StyleProperty::Animation
+        )
+    }
+
     /// Check if this property is inherited by default.
     pub fn is_inherited(&self) -> bool {
         matches!(
@@ -285,6 +340,7 @@ pub struct ComputedStyle {
     // Border
     pub border_width: EdgeInsets,
     pub border_color: Color,
+    pub border_style: BorderStyle,
     pub border_radius: BorderRadius,
 
     // Typography
@@ -292,12 +348,36 @@ pub struct ComputedStyle {
     pub font_family: String,
     pub font_size: f32,
     pub font_weight: u16,
+    pub font_style: FontStyle,
     pub line_height: f32,
     pub text_align: TextAlign,
 
     // Effects
     pub opacity: f32,
     pub cursor: Cursor,
+    pub transform: Transform,
+    /// `box-shadow` layers, the first painted on top
+    pub box_shadow: Vec<BoxShadow>,
+    /// Indices of `box_shadow` layers in `currentcolor`, which take the final
+    /// `color` once all declarations are applied
+    pub(crate) current_color_shadows: Vec<usize>,
+
+    // Transitions, one entry per comma-separated item (times in milliseconds)
+    pub transition_property: Vec<String>,
+    pub transition_duration: Vec<f32>,
+    pub transition_timing_function: Vec<TimingFunction>,
+    pub transition_delay: Vec<f32>,
+
+    // Animations, one entry per comma-separated item (times in milliseconds)
+    pub animation_name: Vec<String>,
+    pub animation_duration: Vec<f32>,
+    pub animation_timing_function: Vec<TimingFunction>,
+    pub animation_delay: Vec<f32>,
+    /// Iterations per animation; `f32::INFINITY` for `infinite`
+    pub animation_iteration_count: Vec<f32>,
+    pub animation_direction: Vec<AnimationDirection>,
+    pub animation_fill_mode: Vec<AnimationFillMode>,
+    pub animation_play_state: Vec<AnimationPlayState>,
 
     // Outline
     pub outline_width: f32,
@@ -312,6 +392,12 @@ pub struct ComputedStyle {
     pub z_index: i32,
     pub visibility: Visibility,
     pub pointer_events: PointerEvents,
+
+    // Custom properties (`--name`), with var() references already substituted
+    pub custom_properties: HashMap<String, CssValue>,
+
+    /// Longhand properties set by a matching declaration
+    pub specified: HashSet<StyleProperty>,
 }
 
 impl Default for ComputedStyle {
@@ -349,17 +435,36 @@ impl Default for ComputedStyle {
 
             border_width: EdgeInsets::ZERO,
             border_color: Color::TRANSPARENT,
+            border_style: BorderStyle::None,
             border_radius: BorderRadius::ZERO,
 
             color: Color::BLACK,
             font_family: "Inter".to_string(),
             font_size: 16.0,
             font_weight: 400,
+            font_style: FontStyle::Normal,
             line_height: 1.5,
             text_align: TextAlign::Left,
 
             opacity: 1.0,
             cursor: Cursor::Default,
+            transform: Transform::IDENTITY,
+            box_shadow: Vec::new(),
+            current_color_shadows: Vec::new(),
+
+            transition_property: vec!["all".to_string()],
+            transition_duration: vec![0.0],
+            transition_timing_function: vec![TimingFunction::Ease],
+            transition_delay: vec![0.0],
+
+            animation_name: vec!["none".to_string()],
+            animation_duration: vec![0.0],
+            animation_timing_function: vec![TimingFunction::Ease],
+            animation_delay: vec![0.0],
+            animation_iteration_count: vec![1.0],
+            animation_direction: vec![AnimationDirection::Normal],
+            animation_fill_mode: vec![AnimationFillMode::None],
+            animation_play_state: vec![AnimationPlayState::Running],
 
             outline_width: 0.0,
             outline_color: Color::TRANSPARENT,
@@ -371,56 +476,374 @@ impl Default for ComputedStyle {
             z_index: 0,
             visibility: Visibility::Visible,
             pointer_events: PointerEvents::Auto,
+
+            custom_properties: HashMap::new(),
+
+            specified: HashSet::new(),
         }
     }
 }
 
 impl ComputedStyle {
+    /// Check whether a longhand property was set by a matching declaration.
+    pub fn is_set(&self, property: &StyleProperty) -> bool {
+        self.specified.contains(property)
+    }
+
+    /// The font family and weight rules set, for drawing text in.
+    ///
+    /// Properties no rule sets are left unset, so text keeps the default font.
+    pub fn font(&self) -> SpanStyle {
+        SpanStyle {
+            font_family: self
+                .is_set(&StyleProperty::FontFamily)
+                .then(|| self.font_family.clone()),
+            weight: self.is_set(&StyleProperty::FontWeight).then_some(self.font_weight),
+            ..Default::default()
+        }
+    }
+
+    /// Get the background color, or `fallback` if no rule sets it.
+    pub fn background_or(&self, fallback: Color) -> Color {
+        if self.is_set(&StyleProperty::BackgroundColor) {
+            self.background_color
+        } else {
+            fallback
+        }
+    }
+
+    /// Get the text color, or `fallback` if no rule sets it.
+    pub fn color_or(&self, fallback: Color) -> Color {
+        if self.is_set(&StyleProperty::Color) {
+            self.color
+        } else {
+            fallback
+        }
+    }
+
+    /// Get the border color, or `fallback` if no rule sets it.
+    pub fn border_color_or(&self, fallback: Color) -> Color {
+        if self.is_set(&StyleProperty::BorderColor) {
+            self.border_color
+        } else {
+            fallback
+        }
+    }
+
+    /// Get the border width, or `fallback` if no rule sets it.
+    ///
+    /// A `border-style` of `none` or `hidden` gives zero width.
+    pub fn border_width_or(&self, fallback: f32) -> f32 {
+        if self.is_set(&StyleProperty::BorderStyle)
+            && matches!(self.border_style, BorderStyle::None | BorderStyle::Hidden)
+        {
+            0.0
+        } else if self.is_set(&StyleProperty::BorderWidth) {
+            self.border_width.top
+        } else {
+            fallback
+        }
+    }
+
+    /// Get the border radius, taking unset corners from `fallback`.
+    pub fn border_radius_or(&self, fallback: BorderRadius) -> BorderRadius {
+        let corner = |property, value, fallback| if self.is_set(&property) { value } else { fallback };
+        BorderRadius {
+            top_left: corner(StyleProperty::BorderTopLeftRadius, self.border_radius.top_left, fallback.top_left),
+            top_right: corner(StyleProperty::BorderTopRightRadius, self.border_radius.top_right, fallback.top_right),
+            bottom_right: corner(
+                StyleProperty::BorderBottomRightRadius,
+                self.border_radius.bottom_right,
+                fallback.bottom_right,
+            ),
+            bottom_left: corner(
+                StyleProperty::BorderBottomLeftRadius,
+                self.border_radius.bottom_left,
+                fallback.bottom_left,
+            ),
+        }
+    }
+
+    /// Get the padding, taking unset sides from `fallback`.
+    pub fn padding_or(&self, fallback: EdgeInsets) -> EdgeInsets {
+        let side = |property, value, fallback| if self.is_set(&property) { value } else { fallback };
+        EdgeInsets {
+            top: side(StyleProperty::PaddingTop, self.padding.top, fallback.top),
+            right: side(StyleProperty::PaddingRight, self.padding.right, fallback.right),
+            bottom: side(StyleProperty::PaddingBottom, self.padding.bottom, fallback.bottom),
+            left: side(StyleProperty::PaddingLeft, self.padding.left, fallback.left),
+        }
+    }
+
+    /// Get the box shadows, or `fallback` if no rule sets them.
+    pub fn box_shadow_or(&self, fallback: &[BoxShadow]) -> Vec<BoxShadow> {
+        if self.is_set(&StyleProperty::BoxShadow) {
+            self.box_shadow.clone()
+        } else {
+            fallback.to_vec()
+        }
+    }
+
+    /// Get the font size, or `fallback` if no rule sets it.
+    pub fn font_size_or(&self, fallback: f32) -> f32 {
+        if self.is_set(&StyleProperty::FontSize) {
+            self.font_size
+        } else {
+            fallback
+        }
+    }
+
+    /// Get the transition timing for a property, if it transitions.
+    ///
+    /// `names` are the property names that select it in `transition-property`
+    /// (e.g. `border-color` and `border`). The last matching entry wins.
+    pub fn transition_for(&self, names: &[&str]) -> Option<TransitionTiming> {
+        let index = self
+            .transition_property
+            .iter()
+            .rposition(|p| p == "all" || names.contains(&p.as_str()))?;
+        let at = |len: usize| index % len.max(1);
+        let duration = self.transition_duration.get(at(self.transition_duration.len())).copied()?;
+        let delay = self.transition_delay.get(at(self.transition_delay.len())).copied().unwrap_or(0.0);
+        let timing = self
+            .transition_timing_function
+            .get(at(self.transition_timing_function.len()))
+            .cloned()
+            .unwrap_or_default();
+
+        (duration > 0.0).then(|| TransitionTiming {
+            duration: css_time(duration),
+            delay: css_time(delay),
+            timing,
+        })
+    }
+
+    /// Get the animations applied by `animation-name`, with their timing.
+    ///
+    /// The other `animation-*` lists are repeated to match the names.
+    pub fn animations(&self) -> Vec<AnimationSpec> {
+        fn nth<T: Clone + Default>(values: &[T], index: usize) -> T {
+            match values.len() {
+                0 => T::default(),
+                len => values[index % len].clone(),
+            }
+        }
+
+        self.animation_name
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| name.as_str() != "none")
+            .map(|(i, name)| AnimationSpec {
+                name: name.clone(),
+                duration: css_time(nth(&self.animation_duration, i)),
+                delay: nth(&self.animation_delay, i) / 1000.0,
+                timing: nth(&self.animation_timing_function, i),
+                iteration_count: nth(&self.animation_iteration_count, i),
+                direction: nth(&self.animation_direction, i),
+                fill_mode: nth(&self.animation_fill_mode, i),
+                play_state: nth(&self.animation_play_state, i),
+            })
+            .collect()
+    }
+
     /// Apply a property value.
+    ///
+    /// `var()` references are substituted from this style's custom properties,
+    /// then the `StyleContext` variables; the declaration is ignored if a
+    /// reference can't be resolved.
     pub fn apply(&mut self, property: &StyleProperty, value: &CssValue, ctx: &StyleContext) {
+        if value.contains_var() {
+            let declared = HashMap::new();
+            let substituted = VarResolver::new(&declared, &self.custom_properties, ctx).substitute(value);
+            if let Some(value) = substituted {
+                self.apply(property, &value, ctx);
+            }
+            return;
+        }
+
+        if property.is_shorthand() {
+            if *property == StyleProperty::Gap {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.gap = px;
+                }
+            }
+            for (longhand, value) in shorthand::expand(property, value).unwrap_or_default() {
+                self.apply(&longhand, &value, ctx);
+            }
+            return;
+        }
+
+        if self.apply_longhand(property, value, ctx) && !property.is_custom_property() {
+            self.specified.insert(property.clone());
+        }
+    }
+
+    /// Resolve values that depend on the final `color`, after the cascade
+    /// or a keyframe's declarations have been applied.
+    pub(crate) fn resolve_current_color(&mut self) {
+        for &index in &self.current_color_shadows {
+            if let Some(shadow) = self.box_shadow.get_mut(index) {
+                shadow.color = self.color;
+            }
+        }
+    }
+
+    /// Apply a longhand property value, returning whether it was valid.
+    fn apply_longhand(&mut self, property: &StyleProperty, value: &CssValue, ctx: &StyleContext) -> bool {
         match property {
             StyleProperty::Display => {
                 if let CssValue::Keyword(k) = value {
                     self.display = Display::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Position => {
                 if let CssValue::Keyword(k) = value {
                     self.position = Position::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Width => {
-                if let Some(len) = value.as_length() {
-                    self.width = Some(ctx.to_pixels(&len));
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.width = Some(px);
+                    true
                 } else if value.is_auto() {
                     self.width = None;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Height => {
-                if let Some(len) = value.as_length() {
-                    self.height = Some(ctx.to_pixels(&len));
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.height = Some(px);
+                    true
                 } else if value.is_auto() {
                     self.height = None;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MinWidth => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.min_width = Some(px);
+                    true
+                } else if value.is_auto() {
+                    self.min_width = None;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MinHeight => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.min_height = Some(px);
+                    true
+                } else if value.is_auto() {
+                    self.min_height = None;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MaxWidth => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.max_width = Some(px);
+                    true
+                } else if value.is_none() {
+                    self.max_width = None;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MaxHeight => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.max_height = Some(px);
+                    true
+                } else if value.is_none() {
+                    self.max_height = None;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::BackgroundColor => {
                 if let Some(color) = value.as_color() {
                     self.background_color = color;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Color => {
                 if let Some(color) = value.as_color() {
                     self.color = color;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::FontSize => {
-                if let Some(len) = value.as_length() {
-                    self.font_size = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.font_size = px;
+                    true
+                } else {
+                    false
                 }
             }
+            StyleProperty::FontFamily => {
+                let first = match value {
+                    CssValue::CommaList(families) => families.first(),
+                    family => Some(family),
+                };
+                if let Some(family) = first.and_then(font_family_name) {
+                    self.font_family = family;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::FontStyle => {
+                if let CssValue::Keyword(k) = value {
+                    self.font_style = FontStyle::from_keyword(k);
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::LineHeight => match value {
+                CssValue::Number(n) => {
+                    self.line_height = *n;
+                    true
+                }
+                CssValue::Percentage(p) => {
+                    self.line_height = p / 100.0;
+                    true
+                }
+                CssValue::Length(_) | CssValue::Calc(_) if self.font_size > 0.0 => {
+                    if let Some(px) = self.length_px(property, value, ctx) {
+                        self.line_height = px / self.font_size;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                CssValue::Keyword(k) if k == "normal" => {
+                    self.line_height = ComputedStyle::default().line_height;
+                    true
+                }
+                _ => false,
+            },
             StyleProperty::FontWeight => {
                 if let CssValue::Number(n) = value {
                     self.font_weight = *n as u16;
+                    true
                 } else if let CssValue::Keyword(k) = value {
                     self.font_weight = match k.as_str() {
                         "normal" => 400,
@@ -429,120 +852,451 @@ impl ComputedStyle {
                         "bolder" => 700,
                         _ => 400,
                     };
+                    true
+                } else {
+                    false
                 }
             }
-            StyleProperty::Padding => {
-                if let CssValue::List(values) = value {
-                    self.padding = parse_edge_insets(values, ctx);
-                } else if let Some(len) = value.as_length() {
-                    let px = ctx.to_pixels(&len);
-                    self.padding = EdgeInsets::all(px);
+            StyleProperty::MarginTop => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.margin.top = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MarginRight => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.margin.right = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MarginBottom => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.margin.bottom = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::MarginLeft => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.margin.left = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::PaddingTop => {
-                if let Some(len) = value.as_length() {
-                    self.padding.top = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.padding.top = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::PaddingRight => {
-                if let Some(len) = value.as_length() {
-                    self.padding.right = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.padding.right = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::PaddingBottom => {
-                if let Some(len) = value.as_length() {
-                    self.padding.bottom = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.padding.bottom = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::PaddingLeft => {
-                if let Some(len) = value.as_length() {
-                    self.padding.left = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.padding.left = px;
+                    true
+                } else {
+                    false
                 }
             }
-            StyleProperty::Margin => {
-                if let CssValue::List(values) = value {
-                    self.margin = parse_edge_insets(values, ctx);
-                } else if let Some(len) = value.as_length() {
-                    let px = ctx.to_pixels(&len);
-                    self.margin = EdgeInsets::all(px);
+            StyleProperty::BorderTopLeftRadius => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.border_radius.top_left = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::BorderTopRightRadius => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.border_radius.top_right = px;
+                    true
+                } else {
+                    false
                 }
             }
-            StyleProperty::BorderRadius => {
-                if let Some(len) = value.as_length() {
-                    let px = ctx.to_pixels(&len);
-                    self.border_radius = BorderRadius::all(px);
+            StyleProperty::BorderBottomRightRadius => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.border_radius.bottom_right = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::BorderBottomLeftRadius => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.border_radius.bottom_left = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::BorderColor => {
                 if let Some(color) = value.as_color() {
                     self.border_color = color;
+                    true
+                } else if matches!(value, CssValue::Keyword(k) if k.eq_ignore_ascii_case("currentcolor")) {
+                    self.border_color = self.color;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::BorderWidth => {
-                if let Some(len) = value.as_length() {
-                    let px = ctx.to_pixels(&len);
+                if let CssValue::List(values) = value {
+                    self.border_width = parse_edge_insets(values, ctx);
+                    true
+                } else if let Some(px) = border_width_keyword(value) {
+                    self.border_width = EdgeInsets::all(px);
+                    true
+                } else if let Some(px) = self.length_px(property, value, ctx) {
                     self.border_width = EdgeInsets::all(px);
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::BorderStyle => {
+                if let CssValue::Keyword(k) = value {
+                    self.border_style = BorderStyle::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Opacity => {
                 if let CssValue::Number(n) = value {
                     self.opacity = n.clamp(0.0, 1.0);
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::Transform => {
+                if let Some(transform) = parse_transform(value, ctx) {
+                    self.transform = transform;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::BoxShadow => {
+                if value.is_none() {
+                    self.box_shadow.clear();
+                    self.current_color_shadows.clear();
+                    return true;
+                }
+                let shadows: Option<Vec<(BoxShadow, bool)>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| parse_box_shadow(v, self.color, ctx))
+                    .collect();
+                if let Some(shadows) = shadows {
+                    self.current_color_shadows = (0..shadows.len()).filter(|&i| shadows[i].1).collect();
+                    self.box_shadow = shadows.into_iter().map(|(shadow, _)| shadow).collect();
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::TransitionProperty => {
+                let names: Option<Vec<String>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| match v {
+                        CssValue::Keyword(k) => Some(k.to_ascii_lowercase()),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(names) = names {
+                    self.transition_property = names;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::TransitionDuration | StyleProperty::TransitionDelay => {
+                let times: Option<Vec<f32>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| match v {
+                        CssValue::Time(ms) => Some(*ms),
+                        _ => None,
+                    })
+                    .collect();
+                match (property, times) {
+                    (StyleProperty::TransitionDuration, Some(times)) => self.transition_duration = times,
+                    (_, Some(times)) => self.transition_delay = times,
+                    _ => return false,
+                }
+                true
+            }
+            StyleProperty::AnimationName => {
+                let names: Option<Vec<String>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| match v {
+                        CssValue::Keyword(k) | CssValue::String(k) => Some(k.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(names) = names {
+                    self.animation_name = names;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::AnimationDuration | StyleProperty::AnimationDelay => {
+                let times: Option<Vec<f32>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| match v {
+                        CssValue::Time(ms) => Some(*ms),
+                        _ => None,
+                    })
+                    .collect();
+                match (property, times) {
+                    (StyleProperty::AnimationDuration, Some(times)) => self.animation_duration = times,
+                    (_, Some(times)) => self.animation_delay = times,
+                    _ => return false,
+                }
+                true
+            }
+            StyleProperty::AnimationTimingFunction => {
+                let timings: Option<Vec<TimingFunction>> = comma_separated(value)
+                    .into_iter()
+                    .map(TimingFunction::from_value)
+                    .collect();
+                if let Some(timings) = timings {
+                    self.animation_timing_function = timings;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::AnimationIterationCount => {
+                let counts: Option<Vec<f32>> = comma_separated(value)
+                    .into_iter()
+                    .map(|v| match v {
+                        CssValue::Number(n) if *n >= 0.0 => Some(*n),
+                        CssValue::Keyword(k) if k.eq_ignore_ascii_case("infinite") => Some(f32::INFINITY),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(counts) = counts {
+                    self.animation_iteration_count = counts;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::AnimationDirection => {
+                if let Some(directions) = keyword_list(value, AnimationDirection::from_keyword) {
+                    self.animation_direction = directions;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::AnimationFillMode => {
+                if let Some(modes) = keyword_list(value, AnimationFillMode::from_keyword) {
+                    self.animation_fill_mode = modes;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::AnimationPlayState => {
+                if let Some(states) = keyword_list(value, AnimationPlayState::from_keyword) {
+                    self.animation_play_state = states;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::TransitionTimingFunction => {
+                let timings: Option<Vec<TimingFunction>> = comma_separated(value)
+                    .into_iter()
+                    .map(TimingFunction::from_value)
+                    .collect();
+                if let Some(timings) = timings {
+                    self.transition_timing_function = timings;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::RowGap => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.row_gap = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::ColumnGap => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.column_gap = px;
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::FlexGrow => {
+                if let CssValue::Number(n) = value {
+                    self.flex_grow = n.max(0.0);
+                    true
+                } else {
+                    false
                 }
             }
-            StyleProperty::Gap => {
-                if let Some(len) = value.as_length() {
-                    self.gap = ctx.to_pixels(&len);
-                    self.row_gap = self.gap;
-                    self.column_gap = self.gap;
+            StyleProperty::FlexShrink => {
+                if let CssValue::Number(n) = value {
+                    self.flex_shrink = n.max(0.0);
+                    true
+                } else {
+                    false
+                }
+            }
+            StyleProperty::FlexBasis => {
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.flex_basis = Some(px);
+                    true
+                } else if value.is_auto() {
+                    self.flex_basis = None;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::FlexDirection => {
                 if let CssValue::Keyword(k) = value {
                     self.flex_direction = FlexDirection::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::JustifyContent => {
                 if let CssValue::Keyword(k) = value {
                     self.justify_content = JustifyContent::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::AlignItems => {
                 if let CssValue::Keyword(k) = value {
                     self.align_items = AlignItems::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::Cursor => {
                 if let CssValue::Keyword(k) = value {
                     self.cursor = Cursor::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::TextAlign => {
                 if let CssValue::Keyword(k) = value {
                     self.text_align = TextAlign::from_keyword(k);
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::OutlineWidth => {
-                if let Some(len) = value.as_length() {
-                    self.outline_width = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.outline_width = px;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::OutlineColor => {
                 if let Some(color) = value.as_color() {
                     self.outline_color = color;
+                    true
+                } else {
+                    false
                 }
             }
             StyleProperty::OutlineOffset => {
-                if let Some(len) = value.as_length() {
-                    self.outline_offset = ctx.to_pixels(&len);
+                if let Some(px) = self.length_px(property, value, ctx) {
+                    self.outline_offset = px;
+                    true
+                } else {
+                    false
                 }
             }
-            _ => {
-                // Other properties not yet implemented
+            StyleProperty::Custom(name) if property.is_custom_property() => {
+                self.custom_properties.insert(name.clone(), value.clone());
+                true
             }
+            // Other properties not yet implemented
+            _ => false,
+        }
+    }
+}
+
+impl ComputedStyle {
+    /// Resolve a length, percentage or `calc()` value for `property` to pixels.
+    fn length_px(&self, property: &StyleProperty, value: &CssValue, ctx: &StyleContext) -> Option<f32> {
+        let basis = self.percentage_basis(property, ctx);
+        match value {
+            CssValue::Calc(expr) => expr.resolve(ctx, basis),
+            value => value.as_length().map(|len| ctx.to_pixels_with_basis(&len, basis)),
+        }
+    }
+
+    /// The length that percentages are relative to for `property`.
+    fn percentage_basis(&self, property: &StyleProperty, ctx: &StyleContext) -> f32 {
+        match property {
+            StyleProperty::Height
+            | StyleProperty::MinHeight
+            | StyleProperty::MaxHeight
+            | StyleProperty::Top
+            | StyleProperty::Bottom
+            | StyleProperty::RowGap => ctx.containing_height,
+            StyleProperty::FontSize => ctx.parent_font_size,
+            StyleProperty::LineHeight => self.font_size,
+            // Margins and padding resolve against the containing block's width
+            _ => ctx.containing_width,
         }
     }
 }
 
+/// Longest time a transition or animation may take, so huge CSS times can't
+/// overflow `Duration` or `Instant` arithmetic.
+const MAX_CSS_TIME: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// A CSS time in milliseconds as a duration; negative times are zero.
+fn css_time(ms: f32) -> std::time::Duration {
+    std::time::Duration::try_from_secs_f32(ms.max(0.0) / 1000.0).map_or(MAX_CSS_TIME, |time| time.min(MAX_CSS_TIME))
+}
+
 /// Parse edge insets from a list of CSS values.
 fn parse_edge_insets(values: &[CssValue], ctx: &StyleContext) -> EdgeInsets {
     let pixels: Vec<f32> = values
@@ -559,6 +1313,159 @@ fn parse_edge_insets(values: &[CssValue], ctx: &StyleContext) -> EdgeInsets {
     }
 }
 
+/// Pixel width for the `thin`, `medium` and `thick` border keywords.
+fn border_width_keyword(value: &CssValue) -> Option<f32> {
+    match value {
+        CssValue::Keyword(k) => match k.as_str() {
+            "thin" => Some(1.0),
+            "medium" => Some(3.0),
+            "thick" => Some(5.0),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Split a comma-separated list into its items.
+fn comma_separated(value: &CssValue) -> Vec<&CssValue> {
+    match value {
+        CssValue::CommaList(items) => items.iter().collect(),
+        value => vec![value],
+    }
+}
+
+/// Parse a comma-separated list of keywords; any unknown keyword invalidates it.
+fn keyword_list<T>(value: &CssValue, from_keyword: fn(&str) -> Option<T>) -> Option<Vec<T>> {
+    comma_separated(value)
+        .into_iter()
+        .map(|v| match v {
+            CssValue::Keyword(k) => from_keyword(&k.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse one `box-shadow` layer: two to four lengths for the offsets, blur
+/// and spread, with an optional color and `inset` in any order.
+///
+/// Layers without a color, or in `currentcolor`, take `current_color` and
+/// are flagged, so the final `color` can replace it after the cascade.
+fn parse_box_shadow(value: &CssValue, current_color: Color, ctx: &StyleContext) -> Option<(BoxShadow, bool)> {
+    let values = match value {
+        CssValue::List(values) => values.as_slice(),
+        value => std::slice::from_ref(value),
+    };
+
+    let mut lengths = Vec::new();
+    // Set once a color is given, to `None` for `currentcolor`
+    let mut color: Option<Option<Color>> = None;
+    let mut inset = false;
+    for value in values {
+        match value {
+            CssValue::Keyword(k) if k.eq_ignore_ascii_case("inset") && !inset => inset = true,
+            CssValue::Keyword(k) if k.eq_ignore_ascii_case("currentcolor") && color.is_none() => {
+                color = Some(None);
+            }
+            CssValue::Percentage(_) => return None,
+            value => {
+                if let Some(length) = value.as_length() {
+                    lengths.push(ctx.to_pixels(&length));
+                } else if let (Some(c), None) = (value.as_color(), color) {
+                    color = Some(Some(c));
+                } else {
+                    return None;
+                }
+            }
+        }
+    }
+
+    let (offset_x, offset_y, blur, spread) = match lengths[..] {
+        [x, y] => (x, y, 0.0, 0.0),
+        [x, y, blur] => (x, y, blur, 0.0),
+        [x, y, blur, spread] => (x, y, blur, spread),
+        _ => return None,
+    };
+    if blur < 0.0 {
+        return None;
+    }
+    let color = color.flatten();
+    let shadow = if inset {
+        BoxShadow::inset(offset_x, offset_y, blur, spread, color.unwrap_or(current_color))
+    } else {
+        BoxShadow::new(offset_x, offset_y, blur, spread, color.unwrap_or(current_color))
+    };
+    Some((shadow, color.is_none()))
+}
+
+/// Parse a `transform` value: `none` or a list of translate/scale functions.
+///
+/// Rotation and skew can't be painted and make the whole value invalid.
+fn parse_transform(value: &CssValue, ctx: &StyleContext) -> Option<Transform> {
+    let functions = match value {
+        CssValue::Keyword(k) if k.eq_ignore_ascii_case("none") => return Some(Transform::IDENTITY),
+        CssValue::List(functions) => functions.as_slice(),
+        value => std::slice::from_ref(value),
+    };
+
+    let mut transform = Transform::IDENTITY;
+    for function in functions {
+        let CssValue::Function(name, args) = function else {
+            return None;
+        };
+        let px = |arg: Option<&CssValue>| -> Option<f32> {
+            match arg? {
+                CssValue::Number(n) if *n == 0.0 => Some(0.0),
+                CssValue::Calc(expr) => expr.resolve(ctx, 0.0),
+                value => value.as_length().map(|len| ctx.to_pixels(&len)),
+            }
+        };
+        let number = |arg: Option<&CssValue>| match arg? {
+            CssValue::Number(n) => Some(*n),
+            CssValue::Percentage(p) => Some(*p / 100.0),
+            _ => None,
+        };
+
+        // Each function applies after the previous ones: scales also scale
+        // later translations.
+        let (tx, ty, sx, sy) = match name.to_ascii_lowercase().as_str() {
+            "translate" => {
+                let x = px(args.first())?;
+                let y = if args.len() > 1 { px(args.get(1))? } else { 0.0 };
+                (x, y, 1.0, 1.0)
+            }
+            "translatex" => (px(args.first())?, 0.0, 1.0, 1.0),
+            "translatey" => (0.0, px(args.first())?, 1.0, 1.0),
+            "scale" => {
+                let x = number(args.first())?;
+                let y = if args.len() > 1 { number(args.get(1))? } else { x };
+                (0.0, 0.0, x, y)
+            }
+            "scalex" => (0.0, 0.0, number(args.first())?, 1.0),
+            "scaley" => (0.0, 0.0, 1.0, number(args.first())?),
+            _ => return None,
+        };
+        transform = transform.then(Transform {
+            translate_x: tx,
+            translate_y: ty,
+            scale_x: sx,
+            scale_y: sy,
+        });
+    }
+    Some(transform)
+}
+
+/// Get a font family name from a keyword, string or multi-word list.
+fn font_family_name(value: &CssValue) -> Option<String> {
+    match value {
+        CssValue::Keyword(name) | CssValue::String(name) => Some(name.clone()),
+        CssValue::List(words) => {
+            let words: Option<Vec<String>> = words.iter().map(font_family_name).collect();
+            words.map(|w| w.join(" "))
+        }
+        _ => None,
+    }
+}
+
 // Enums for style values
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -699,6 +1606,56 @@ pub enum AlignSelf {
     Baseline,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    #[default]
+    None,
+    Hidden,
+    Dotted,
+    Dashed,
+    Solid,
+    Double,
+    Groove,
+    Ridge,
+    Inset,
+    Outset,
+}
+
+impl BorderStyle {
+    pub fn from_keyword(s: &str) -> Self {
+        match s {
+            "hidden" => BorderStyle::Hidden,
+            "dotted" => BorderStyle::Dotted,
+            "dashed" => BorderStyle::Dashed,
+            "solid" => BorderStyle::Solid,
+            "double" => BorderStyle::Double,
+            "groove" => BorderStyle::Groove,
+            "ridge" => BorderStyle::Ridge,
+            "inset" => BorderStyle::Inset,
+            "outset" => BorderStyle::Outset,
+            _ => BorderStyle::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl FontStyle {
+    pub fn from_keyword(s: &str) -> Self {
+        match s {
+            "italic" => FontStyle::Italic,
+            "oblique" => FontStyle::Oblique,
+            _ => FontStyle::Normal,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TextAlign {
     #[default]
@@ -785,3 +1742,147 @@ impl Cursor {
         }
     }
 }
+
+/// A 2D transform: scale about the widget's center, then translate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translate_x: f32,
+    pub translate_y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        translate_x: 0.0,
+        translate_y: 0.0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+    };
+
+    pub fn is_identity(&self) -> bool {
+        *self == Transform::IDENTITY
+    }
+
+    /// Compose with a transform applied inside this one.
+    pub fn then(&self, inner: Transform) -> Transform {
+        Transform {
+            translate_x: self.translate_x + inner.translate_x * self.scale_x,
+            translate_y: self.translate_y + inner.translate_y * self.scale_y,
+            scale_x: self.scale_x * inner.scale_x,
+            scale_y: self.scale_y * inner.scale_y,
+        }
+    }
+
+    /// Interpolate towards `other` (`t` from 0.0 to 1.0).
+    pub fn lerp(&self, other: Transform, t: f32) -> Transform {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Transform {
+            translate_x: lerp(self.translate_x, other.translate_x),
+            translate_y: lerp(self.translate_y, other.translate_y),
+            scale_x: lerp(self.scale_x, other.scale_x),
+            scale_y: lerp(self.scale_y, other.scale_y),
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+/// Timing of a property's transition, from the `transition-*` properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionTiming {
+    pub duration: std::time::Duration,
+    pub delay: std::time::Duration,
+    pub timing: TimingFunction,
+}
+
+/// Value of `animation-direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationDirection {
+    #[default]
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+impl AnimationDirection {
+    pub fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "normal" => Some(AnimationDirection::Normal),
+            "reverse" => Some(AnimationDirection::Reverse),
+            "alternate" => Some(AnimationDirection::Alternate),
+            "alternate-reverse" => Some(AnimationDirection::AlternateReverse),
+            _ => None,
+        }
+    }
+}
+
+/// Value of `animation-fill-mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationFillMode {
+    #[default]
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+impl AnimationFillMode {
+    pub fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(AnimationFillMode::None),
+            "forwards" => Some(AnimationFillMode::Forwards),
+            "backwards" => Some(AnimationFillMode::Backwards),
+            "both" => Some(AnimationFillMode::Both),
+            _ => None,
+        }
+    }
+
+    /// Whether the first keyframe applies during the delay.
+    pub fn fills_backwards(&self) -> bool {
+        matches!(self, AnimationFillMode::Backwards | AnimationFillMode::Both)
+    }
+
+    /// Whether the last keyframe applies after the animation ends.
+    pub fn fills_forwards(&self) -> bool {
+        matches!(self, AnimationFillMode::Forwards | AnimationFillMode::Both)
+    }
+}
+
+/// Value of `animation-play-state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationPlayState {
+    #[default]
+    Running,
+    Paused,
+}
+
+impl AnimationPlayState {
+    pub fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "running" => Some(AnimationPlayState::Running),
+            "paused" => Some(AnimationPlayState::Paused),
+            _ => None,
+        }
+    }
+}
+
+/// One animation from the `animation-*` properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationSpec {
+    /// Name of the `@keyframes` rule
+    pub name: String,
+    pub duration: std::time::Duration,
+    /// Delay in seconds; negative delays start part-way through
+    pub delay: f32,
+    pub timing: TimingFunction,
+    pub iteration_count: f32,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+    pub play_state: AnimationPlayState,
+}
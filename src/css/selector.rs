@@ -1,6 +1,7 @@
 //! CSS selector types and matching.
 
 use crate::css::WidgetState;
+use std::collections::HashMap;
 
 /// A CSS selector.
 #[derive(Debug, Clone, PartialEq)]
@@ -59,6 +60,9 @@ impl Selector {
     }
 
     /// Check if this selector matches a widget.
+    ///
+    /// Selectors with combinators never match here because the widget's
+    /// ancestors and siblings are unknown; use [`Selector::matches_target`].
     pub fn matches(
         &self,
         widget_type: &str,
@@ -66,42 +70,269 @@ impl Selector {
         classes: &[String],
         state: &WidgetState,
     ) -> bool {
-        for part in &self.parts {
-            match part {
-                SelectorPart::Universal => continue,
-                SelectorPart::Type(name) => {
-                    if name != widget_type {
-                        return false;
-                    }
+        self.matches_target(&MatchTarget::new(widget_type, widget_id, classes, state))
+    }
+
+    /// Check if this selector matches a widget, including combinators.
+    pub fn matches_target(&self, target: &MatchTarget) -> bool {
+        let compounds = self.compounds();
+        let Some((subject, combinator)) = compounds.last() else {
+            return false;
+        };
+
+        let is_root = target
+            .location
+            .is_some_and(|(tree, node)| tree.node(node).parent.is_none());
+        if !compound_matches(
+            subject,
+            target.widget_type,
+            target.widget_id,
+            target.classes,
+            target.state,
+            is_root,
+        ) {
+            return false;
+        }
+
+        match (combinator, target.location) {
+            (None, _) => true,
+            (Some(combinator), Some((tree, node))) => {
+                match_relative(&compounds[..compounds.len() - 1], *combinator, tree, node)
+            }
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Split the selector into compound selectors, each paired with the
+    /// combinator that links it to the compound on its left.
+    fn compounds(&self) -> Vec<(&[SelectorPart], Option<Combinator>)> {
+        let mut compounds = Vec::new();
+        let mut start = 0;
+        let mut combinator = None;
+
+        for (i, part) in self.parts.iter().enumerate() {
+            if let SelectorPart::Combinator(c) = part {
+                compounds.push((&self.parts[start..i], combinator));
+                combinator = Some(*c);
+                start = i + 1;
+            }
+        }
+        compounds.push((&self.parts[start..], combinator));
+        compounds
+    }
+}
+
+/// Match the remaining compounds (right to left) against the elements
+/// related to `node` through `combinator`.
+fn match_relative(
+    compounds: &[(&[SelectorPart], Option<Combinator>)],
+    combinator: Combinator,
+    tree: &ElementTree,
+    node: usize,
+) -> bool {
+    let Some(((parts, next), rest)) = compounds.split_last() else {
+        return true;
+    };
+
+    let matches_at = |candidate: usize| {
+        let node = tree.node(candidate);
+        let element = &node.element;
+        if !compound_matches(
+            parts,
+            &element.widget_type,
+            element.element_id.as_deref(),
+            &element.classes,
+            &element.state,
+            node.parent.is_none(),
+        ) {
+            return false;
+        }
+        match next {
+            Some(next) => match_relative(rest, *next, tree, candidate),
+            None => true,
+        }
+    };
+
+    match combinator {
+        Combinator::Child => tree.node(node).parent.is_some_and(matches_at),
+        Combinator::Descendant => {
+            let mut current = tree.node(node).parent;
+            while let Some(ancestor) = current {
+                if matches_at(ancestor) {
+                    return true;
                 }
-                SelectorPart::Class(name) => {
-                    if !classes.contains(name) {
-                        return false;
-                    }
+                current = tree.node(ancestor).parent;
+            }
+            false
+        }
+        Combinator::NextSibling => tree.node(node).prev_sibling.is_some_and(matches_at),
+        Combinator::SubsequentSibling => {
+            let mut current = tree.node(node).prev_sibling;
+            while let Some(sibling) = current {
+                if matches_at(sibling) {
+                    return true;
                 }
-                SelectorPart::Id(name) => {
-                    if widget_id != Some(name.as_str()) {
-                        return false;
-                    }
+                current = tree.node(sibling).prev_sibling;
+            }
+            false
+        }
+    }
+}
+
+/// Check whether a compound selector (no combinators) matches a single element.
+fn compound_matches(
+    parts: &[SelectorPart],
+    widget_type: &str,
+    widget_id: Option<&str>,
+    classes: &[String],
+    state: &WidgetState,
+    is_root: bool,
+) -> bool {
+    for part in parts {
+        match part {
+            SelectorPart::Universal => continue,
+            SelectorPart::Type(name) => {
+                if name != widget_type {
+                    return false;
                 }
-                SelectorPart::PseudoClass(pseudo) => {
-                    if !state.matches(pseudo) {
-                        return false;
-                    }
+            }
+            SelectorPart::Class(name) => {
+                if !classes.contains(name) {
+                    return false;
                 }
-                SelectorPart::Attribute { name: _, op: _, value: _ } => {
-                    // TODO: Implement attribute matching
+            }
+            SelectorPart::Id(name) => {
+                if widget_id != Some(name.as_str()) {
                     return false;
                 }
-                SelectorPart::PseudoElement(_) => {
-                    // Pseudo-elements don't affect matching
+            }
+            SelectorPart::PseudoClass(PseudoClass::Root) => {
+                if !is_root {
+                    return false;
                 }
-                SelectorPart::Combinator(_) => {
-                    // TODO: Implement combinator matching
+            }
+            SelectorPart::PseudoClass(pseudo) => {
+                if !state.matches(pseudo) {
+                    return false;
                 }
             }
+            SelectorPart::Attribute { name: _, op: _, value: _ } => {
+                // TODO: Implement attribute matching
+                return false;
+            }
+            SelectorPart::PseudoElement(_) => {
+                // Pseudo-elements don't affect matching
+            }
+            SelectorPart::Combinator(_) => {
+                // Compounds never contain combinators
+            }
         }
-        true
+    }
+    true
+}
+
+/// The widget being matched against a selector.
+#[derive(Debug, Clone, Copy)]
+pub struct MatchTarget<'a> {
+    pub widget_type: &'a str,
+    pub widget_id: Option<&'a str>,
+    pub classes: &'a [String],
+    pub state: &'a WidgetState,
+    /// The element tree and this widget's node in it, needed for combinators.
+    pub location: Option<(&'a ElementTree, usize)>,
+}
+
+impl<'a> MatchTarget<'a> {
+    pub fn new(
+        widget_type: &'a str,
+        widget_id: Option<&'a str>,
+        classes: &'a [String],
+        state: &'a WidgetState,
+    ) -> Self {
+        Self {
+            widget_type,
+            widget_id,
+            classes,
+            state,
+            location: None,
+        }
+    }
+
+    /// Place the target in an element tree.
+    pub fn in_tree(mut self, tree: &'a ElementTree, node: usize) -> Self {
+        self.location = Some((tree, node));
+        self
+    }
+}
+
+/// Selector-relevant data for a single widget.
+#[derive(Debug, Clone)]
+pub struct ElementInfo {
+    pub widget_type: String,
+    pub element_id: Option<String>,
+    pub classes: Vec<String>,
+    pub state: WidgetState,
+}
+
+/// A node in an [`ElementTree`].
+#[derive(Debug, Clone)]
+pub struct ElementNode {
+    pub element: ElementInfo,
+    pub parent: Option<usize>,
+    pub prev_sibling: Option<usize>,
+}
+
+/// A snapshot of the widget tree used to match combinators.
+///
+/// Nodes are stored flat with parent and previous-sibling links, keyed by
+/// widget ID.
+#[derive(Debug, Clone, Default)]
+pub struct ElementTree {
+    nodes: Vec<ElementNode>,
+    index: HashMap<u64, usize>,
+}
+
+impl ElementTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node and return its index.
+    pub fn push(
+        &mut self,
+        widget_id: u64,
+        element: ElementInfo,
+        parent: Option<usize>,
+        prev_sibling: Option<usize>,
+    ) -> usize {
+        let node = self.nodes.len();
+        self.nodes.push(ElementNode {
+            element,
+            parent,
+            prev_sibling,
+        });
+        self.index.insert(widget_id, node);
+        node
+    }
+
+    /// Get a node by index.
+    pub fn node(&self, node: usize) -> &ElementNode {
+        &self.nodes[node]
+    }
+
+    /// Find the node index for a widget ID.
+    pub fn find(&self, widget_id: u64) -> Option<usize> {
+        self.index.get(&widget_id).copied()
+    }
+
+    /// Number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
     }
 }
 
@@ -162,6 +393,7 @@ pub enum PseudoClass {
     FirstChild,
     LastChild,
     NthChild(usize),
+    Root,
     Not(Box<Selector>),
 }
 
@@ -177,6 +409,7 @@ impl PseudoClass {
             "checked" => Some(PseudoClass::Checked),
             "first-child" => Some(PseudoClass::FirstChild),
             "last-child" => Some(PseudoClass::LastChild),
+            "root" => Some(PseudoClass::Root),
             _ => None,
         }
     }
@@ -242,6 +475,63 @@ mod tests {
         assert!(selector.matches("button", None, &["btn".to_string()], &state));
     }
 
+    fn element(widget_type: &str, classes: &[&str]) -> ElementInfo {
+        ElementInfo {
+            widget_type: widget_type.to_string(),
+            element_id: None,
+            classes: classes.iter().map(|c| c.to_string()).collect(),
+            state: WidgetState::default(),
+        }
+    }
+
+    #[test]
+    fn test_combinator_matching() {
+        // column.sidebar > [label, button.icon-button]
+        let mut tree = ElementTree::new();
+        let root = tree.push(1, element("column", &["sidebar"]), None, None);
+        let label = tree.push(2, element("label", &[]), Some(root), None);
+        let button = tree.push(3, element("button", &["icon-button"]), Some(root), Some(label));
+
+        let classes = vec!["icon-button".to_string()];
+        let state = WidgetState::default();
+        let target = MatchTarget::new("button", None, &classes, &state).in_tree(&tree, button);
+
+        let descendant = Selector::new(vec![
+            SelectorPart::Class("sidebar".into()),
+            SelectorPart::Combinator(Combinator::Descendant),
+            SelectorPart::Type("button".into()),
+        ]);
+        let child = Selector::new(vec![
+            SelectorPart::Type("column".into()),
+            SelectorPart::Combinator(Combinator::Child),
+            SelectorPart::Class("icon-button".into()),
+        ]);
+        let adjacent = Selector::new(vec![
+            SelectorPart::Type("label".into()),
+            SelectorPart::Combinator(Combinator::NextSibling),
+            SelectorPart::Type("button".into()),
+        ]);
+        let sibling = Selector::new(vec![
+            SelectorPart::Type("button".into()),
+            SelectorPart::Combinator(Combinator::SubsequentSibling),
+            SelectorPart::Type("button".into()),
+        ]);
+        let wrong_ancestor = Selector::new(vec![
+            SelectorPart::Class("toolbar".into()),
+            SelectorPart::Combinator(Combinator::Descendant),
+            SelectorPart::Type("button".into()),
+        ]);
+
+        assert!(descendant.matches_target(&target));
+        assert!(child.matches_target(&target));
+        assert!(adjacent.matches_target(&target));
+        assert!(!sibling.matches_target(&target));
+        assert!(!wrong_ancestor.matches_target(&target));
+
+        // Without tree context combinators cannot match
+        assert!(!descendant.matches("button", None, &classes, &state));
+    }
+
     #[test]
     fn test_specificity_ordering() {
         let id = Specificity::new(1, 0, 0);
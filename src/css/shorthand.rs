@@ -0,0 +1,429 @@
+//! Expansion of shorthand properties into their longhands.
+
+use crate::css::{CssValue, StyleProperty};
+
+/// Expand a shorthand declaration into longhand declarations.
+///
+/// Returns `None` if `property` is not a shorthand or the value is invalid
+/// for it. Longhands missing from the value are reset to their initial value.
+pub(crate) fn expand(
+    property: &StyleProperty,
+    value: &CssValue,
+) -> Option<Vec<(StyleProperty, CssValue)>> {
+    match property {
+        StyleProperty::Margin => expand_box(
+            value,
+            [
+                StyleProperty::MarginTop,
+                StyleProperty::MarginRight,
+                StyleProperty::MarginBottom,
+                StyleProperty::MarginLeft,
+            ],
+        ),
+        StyleProperty::Padding => expand_box(
+            value,
+            [
+                StyleProperty::PaddingTop,
+                StyleProperty::PaddingRight,
+                StyleProperty::PaddingBottom,
+                StyleProperty::PaddingLeft,
+            ],
+        ),
+        StyleProperty::BorderRadius => expand_border_radius(value),
+        StyleProperty::Border => expand_border(value),
+        StyleProperty::Flex => expand_flex(value),
+        StyleProperty::Gap => expand_gap(value),
+        StyleProperty::Background => expand_background(value),
+        StyleProperty::Font => expand_font(value),
+        StyleProperty::Transition => expand_transition(value),
+        StyleProperty::Animation => expand_animation(value),
+        _ => None,
+    }
+}
+
+/// Get the space-separated components of a value.
+fn components(value: &CssValue) -> &[CssValue] {
+    match value {
+        CssValue::List(values) => values,
+        value => std::slice::from_ref(value),
+    }
+}
+
+/// Get the comma-separated groups of a value.
+fn groups(value: &CssValue) -> &[CssValue] {
+    match value {
+        CssValue::CommaList(values) => values,
+        value => std::slice::from_ref(value),
+    }
+}
+
+fn keyword(value: &str) -> CssValue {
+    CssValue::Keyword(value.to_string())
+}
+
+fn is_slash(value: &CssValue) -> bool {
+    matches!(value, CssValue::Keyword(k) if k == "/")
+}
+
+fn is_length(value: &CssValue) -> bool {
+    value.as_length().is_some() || matches!(value, CssValue::Calc(_))
+}
+
+/// Expand a 1-4 value box shorthand (top, right, bottom, left).
+fn expand_box(value: &CssValue, sides: [StyleProperty; 4]) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let values = components(value);
+    let [top, right, bottom, left] = match values {
+        [all] => [all, all, all, all],
+        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+        [top, right, bottom, left] => [top, right, bottom, left],
+        _ => return None,
+    };
+
+    let [p_top, p_right, p_bottom, p_left] = sides;
+    Some(vec![
+        (p_top, top.clone()),
+        (p_right, right.clone()),
+        (p_bottom, bottom.clone()),
+        (p_left, left.clone()),
+    ])
+}
+
+/// Expand `border-radius`, ignoring elliptical radii after `/`.
+fn expand_border_radius(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let values = components(value);
+    let horizontal: Vec<&CssValue> = values.iter().take_while(|v| !is_slash(v)).collect();
+
+    let [top_left, top_right, bottom_right, bottom_left] = match horizontal.as_slice() {
+        [all] => [*all, *all, *all, *all],
+        [a, b] => [*a, *b, *a, *b],
+        [a, b, c] => [*a, *b, *c, *b],
+        [a, b, c, d] => [*a, *b, *c, *d],
+        _ => return None,
+    };
+
+    Some(vec![
+        (StyleProperty::BorderTopLeftRadius, top_left.clone()),
+        (StyleProperty::BorderTopRightRadius, top_right.clone()),
+        (StyleProperty::BorderBottomRightRadius, bottom_right.clone()),
+        (StyleProperty::BorderBottomLeftRadius, bottom_left.clone()),
+    ])
+}
+
+const BORDER_STYLES: &[&str] = &[
+    "none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge", "inset", "outset",
+];
+
+/// Expand `border: <width> || <style> || <color>`.
+fn expand_border(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let mut width = None;
+    let mut style = None;
+    let mut color = None;
+
+    for component in components(value) {
+        match component {
+            CssValue::Keyword(k) if matches!(k.as_str(), "thin" | "medium" | "thick") && width.is_none() => {
+                width = Some(component.clone());
+            }
+            CssValue::Keyword(k) if BORDER_STYLES.contains(&k.as_str()) && style.is_none() => {
+                style = Some(component.clone());
+            }
+            CssValue::Keyword(k) if k.eq_ignore_ascii_case("currentcolor") && color.is_none() => {
+                color = Some(component.clone());
+            }
+            _ if is_length(component) && width.is_none() => width = Some(component.clone()),
+            _ if component.as_color().is_some() && color.is_none() => color = Some(component.clone()),
+            _ => return None,
+        }
+    }
+
+    Some(vec![
+        (StyleProperty::BorderWidth, width.unwrap_or_else(|| keyword("medium"))),
+        (StyleProperty::BorderStyle, style.unwrap_or_else(|| keyword("none"))),
+        (StyleProperty::BorderColor, color.unwrap_or_else(|| keyword("currentcolor"))),
+    ])
+}
+
+/// Expand `flex: none | auto | <grow> [<shrink>] [<basis>]`.
+fn expand_flex(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let (grow, shrink, basis) = match components(value) {
+        [CssValue::Keyword(k)] if k == "none" => (0.0, 0.0, keyword("auto")),
+        [CssValue::Keyword(k)] if k == "auto" => (1.0, 1.0, keyword("auto")),
+        [CssValue::Keyword(k)] if k == "initial" => (0.0, 1.0, keyword("auto")),
+        [CssValue::Number(grow)] => (*grow, 1.0, CssValue::Percentage(0.0)),
+        [basis] if is_length(basis) || basis.is_auto() => (1.0, 1.0, basis.clone()),
+        [CssValue::Number(grow), CssValue::Number(shrink)] => {
+            (*grow, *shrink, CssValue::Percentage(0.0))
+        }
+        [CssValue::Number(grow), basis] if is_length(basis) || basis.is_auto() => {
+            (*grow, 1.0, basis.clone())
+        }
+        [CssValue::Number(grow), CssValue::Number(shrink), basis]
+            if is_length(basis) || basis.is_auto() =>
+        {
+            (*grow, *shrink, basis.clone())
+        }
+        _ => return None,
+    };
+
+    Some(vec![
+        (StyleProperty::FlexGrow, CssValue::Number(grow)),
+        (StyleProperty::FlexShrink, CssValue::Number(shrink)),
+        (StyleProperty::FlexBasis, basis),
+    ])
+}
+
+/// Expand `gap: <row-gap> [<column-gap>]`.
+fn expand_gap(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let (row, column) = match components(value) {
+        [both] => (both, both),
+        [row, column] => (row, column),
+        _ => return None,
+    };
+
+    Some(vec![
+        (StyleProperty::RowGap, row.clone()),
+        (StyleProperty::ColumnGap, column.clone()),
+    ])
+}
+
+const BACKGROUND_REPEATS: &[&str] = &["repeat", "repeat-x", "repeat-y", "no-repeat", "space", "round"];
+
+/// Expand `background`. Only the final layer is used for multi-layer values.
+fn expand_background(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let layer = groups(value).last()?;
+
+    let mut color = None;
+    let mut image = None;
+    let mut repeat = Vec::new();
+    let mut position = Vec::new();
+    let mut size = Vec::new();
+    let mut after_slash = false;
+
+    for component in components(layer) {
+        match component {
+            _ if is_slash(component) => after_slash = true,
+            CssValue::Url(_) | CssValue::Function(..) => image = Some(component.clone()),
+            CssValue::Keyword(k) if k == "none" => image = Some(component.clone()),
+            CssValue::Keyword(k) if BACKGROUND_REPEATS.contains(&k.as_str()) => {
+                repeat.push(component.clone());
+            }
+            _ if after_slash => size.push(component.clone()),
+            CssValue::Keyword(k)
+                if matches!(k.as_str(), "left" | "right" | "top" | "bottom" | "center") =>
+            {
+                position.push(component.clone());
+            }
+            _ if is_length(component) => position.push(component.clone()),
+            _ if component.as_color().is_some() => color = Some(component.clone()),
+            _ => return None,
+        }
+    }
+
+    let collect = |mut values: Vec<CssValue>, initial: CssValue| match values.len() {
+        0 => initial,
+        1 => values.remove(0),
+        _ => CssValue::List(values),
+    };
+
+    Some(vec![
+        (StyleProperty::BackgroundColor, color.unwrap_or_else(|| keyword("transparent"))),
+        (StyleProperty::BackgroundImage, image.unwrap_or_else(|| keyword("none"))),
+        (StyleProperty::BackgroundRepeat, collect(repeat, keyword("repeat"))),
+        (
+            StyleProperty::BackgroundPosition,
+            collect(position, CssValue::List(vec![CssValue::Percentage(0.0), CssValue::Percentage(0.0)])),
+        ),
+        (StyleProperty::BackgroundSize, collect(size, keyword("auto"))),
+    ])
+}
+
+/// Expand `font: [<style> || <weight>] <size>[/<line-height>] <family>#`.
+fn expand_font(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let all_groups = groups(value);
+    let (first, other_families) = all_groups.split_first()?;
+    let parts = components(first);
+
+    let mut style = None;
+    let mut weight = None;
+    let mut index = 0;
+
+    // Optional style and weight before the size
+    while let Some(component) = parts.get(index) {
+        match component {
+            CssValue::Keyword(k) if matches!(k.as_str(), "italic" | "oblique") => {
+                style = Some(component.clone());
+            }
+            CssValue::Keyword(k) if matches!(k.as_str(), "bold" | "bolder" | "lighter") => {
+                weight = Some(component.clone());
+            }
+            CssValue::Number(_) => weight = Some(component.clone()),
+            CssValue::Keyword(k) if matches!(k.as_str(), "normal" | "small-caps") => {}
+            _ => break,
+        }
+        index += 1;
+    }
+
+    // Required size
+    let size = parts.get(index).filter(|v| is_length(v))?.clone();
+    index += 1;
+
+    let mut line_height = None;
+    if parts.get(index).is_some_and(is_slash) {
+        line_height = Some(parts.get(index + 1)?.clone());
+        index += 2;
+    }
+
+    // Required family list; the first family may span several identifiers
+    let first_family = match &parts[index..] {
+        [] => return None,
+        [family] => family.clone(),
+        words => CssValue::List(words.to_vec()),
+    };
+    let family = if other_families.is_empty() {
+        first_family
+    } else {
+        let mut families = vec![first_family];
+        families.extend(other_families.iter().cloned());
+        CssValue::CommaList(families)
+    };
+
+    Some(vec![
+        (StyleProperty::FontStyle, style.unwrap_or_else(|| keyword("normal"))),
+        (StyleProperty::FontWeight, weight.unwrap_or_else(|| keyword("normal"))),
+        (StyleProperty::FontSize, size),
+        (StyleProperty::LineHeight, line_height.unwrap_or_else(|| keyword("normal"))),
+        (StyleProperty::FontFamily, family),
+    ])
+}
+
+const TIMING_KEYWORDS: &[&str] = &[
+    "linear", "ease", "ease-in", "ease-out", "ease-in-out", "step-start", "step-end",
+];
+
+/// Expand `transition: [<property> || <duration> || <timing> || <delay>]#`.
+fn expand_transition(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    let mut properties = Vec::new();
+    let mut durations = Vec::new();
+    let mut timings = Vec::new();
+    let mut delays = Vec::new();
+
+    for group in groups(value) {
+        let mut property = None;
+        let mut duration = None;
+        let mut timing = None;
+        let mut delay = None;
+
+        for component in components(group) {
+            match component {
+                CssValue::Time(_) if duration.is_none() => duration = Some(component.clone()),
+                CssValue::Time(_) if delay.is_none() => delay = Some(component.clone()),
+                CssValue::Keyword(k) if TIMING_KEYWORDS.contains(&k.as_str()) && timing.is_none() => {
+                    timing = Some(component.clone());
+                }
+                CssValue::Function(name, _)
+                    if matches!(name.as_str(), "cubic-bezier" | "steps") && timing.is_none() =>
+                {
+                    timing = Some(component.clone());
+                }
+                CssValue::Keyword(_) if property.is_none() => property = Some(component.clone()),
+                _ => return None,
+            }
+        }
+
+        properties.push(property.unwrap_or_else(|| keyword("all")));
+        durations.push(duration.unwrap_or(CssValue::Time(0.0)));
+        timings.push(timing.unwrap_or_else(|| keyword("ease")));
+        delays.push(delay.unwrap_or(CssValue::Time(0.0)));
+    }
+
+    let collect = |mut values: Vec<CssValue>| {
+        if values.len() == 1 {
+            values.remove(0)
+        } else {
+            CssValue::CommaList(values)
+        }
+    };
+
+    Some(vec![
+        (StyleProperty::TransitionProperty, collect(properties)),
+        (StyleProperty::TransitionDuration, collect(durations)),
+        (StyleProperty::TransitionTimingFunction, collect(timings)),
+        (StyleProperty::TransitionDelay, collect(delays)),
+    ])
+}
+
+/// Expand `animation: [<duration> || <timing> || <delay> || <count> ||
+/// <direction> || <fill-mode> || <play-state> || <name>]#`.
+fn expand_animation(value: &CssValue) -> Option<Vec<(StyleProperty, CssValue)>> {
+    const DIRECTIONS: &[&str] = &["normal", "reverse", "alternate", "alternate-reverse"];
+    const FILL_MODES: &[&str] = &["none", "forwards", "backwards", "both"];
+    const PLAY_STATES: &[&str] = &["running", "paused"];
+
+    let mut lists: [Vec<CssValue>; 8] = Default::default();
+    for group in groups(value) {
+        let [mut name, mut duration, mut timing, mut delay, mut count, mut direction, mut fill, mut play] =
+            [None, None, None, None, None, None, None, None];
+
+        for component in components(group) {
+            let is = |keywords: &[&str]| {
+                matches!(component, CssValue::Keyword(k) if keywords.contains(&k.to_ascii_lowercase().as_str()))
+            };
+            match component {
+                CssValue::Time(_) if duration.is_none() => duration = Some(component.clone()),
+                CssValue::Time(_) if delay.is_none() => delay = Some(component.clone()),
+                CssValue::Number(_) if count.is_none() => count = Some(component.clone()),
+                CssValue::Keyword(k) if k.eq_ignore_ascii_case("infinite") && count.is_none() => {
+                    count = Some(component.clone());
+                }
+                CssValue::Keyword(k) if TIMING_KEYWORDS.contains(&k.as_str()) && timing.is_none() => {
+                    timing = Some(component.clone());
+                }
+                CssValue::Function(name, _)
+                    if matches!(name.as_str(), "cubic-bezier" | "steps") && timing.is_none() =>
+                {
+                    timing = Some(component.clone());
+                }
+                _ if is(DIRECTIONS) && direction.is_none() => direction = Some(component.clone()),
+                _ if is(FILL_MODES) && fill.is_none() => fill = Some(component.clone()),
+                _ if is(PLAY_STATES) && play.is_none() => play = Some(component.clone()),
+                CssValue::Keyword(_) | CssValue::String(_) if name.is_none() => name = Some(component.clone()),
+                _ => return None,
+            }
+        }
+
+        let values = [
+            name.unwrap_or_else(|| keyword("none")),
+            duration.unwrap_or(CssValue::Time(0.0)),
+            timing.unwrap_or_else(|| keyword("ease")),
+            delay.unwrap_or(CssValue::Time(0.0)),
+            count.unwrap_or(CssValue::Number(1.0)),
+            direction.unwrap_or_else(|| keyword("normal")),
+            fill.unwrap_or_else(|| keyword("none")),
+            play.unwrap_or_else(|| keyword("running")),
+        ];
+        for (list, value) in lists.iter_mut().zip(values) {
+            list.push(value);
+        }
+    }
+
+    let collect = |mut values: Vec<CssValue>| {
+        if values.len() == 1 {
+            values.remove(0)
+        } else {
+            CssValue::CommaList(values)
+        }
+    };
+    let [names, durations, timings, delays, counts, directions, fills, plays] = lists;
+
+    Some(vec![
+        (StyleProperty::AnimationName, collect(names)),
+        (StyleProperty::AnimationDuration, collect(durations)),
+        (StyleProperty::AnimationTimingFunction, collect(timings)),
+        (StyleProperty::AnimationDelay, collect(delays)),
+        (StyleProperty::AnimationIterationCount, collect(counts)),
+        (StyleProperty::AnimationDirection, collect(directions)),
+        (StyleProperty::AnimationFillMode, collect(fills)),
+        (StyleProperty::AnimationPlayState, collect(plays)),
+    ])
+}
@@ -3,23 +3,33 @@
 use crate::css::{
     CssValue, ComputedStyle, Selector, StyleContext, StyleProperty, WidgetState,
 };
-use crate::css::selector::Specificity;
+use crate::css::animation::Keyframes;
+use crate::css::media::MediaQueryList;
+use crate::css::selector::{ElementTree, MatchTarget, Specificity};
+use crate::css::vars;
 use std::collections::HashMap;
 
 /// A CSS stylesheet containing rules.
 #[derive(Debug, Clone, Default)]
 pub struct StyleSheet {
     pub rules: Vec<StyleRule>,
+    /// `@keyframes` rules by name
+    pub keyframes: HashMap<String, Keyframes>,
 }
 
 impl StyleSheet {
     pub fn new(rules: Vec<StyleRule>) -> Self {
-        Self { rules }
+        Self { rules, keyframes: HashMap::new() }
     }
 
     /// Create an empty stylesheet.
     pub fn empty() -> Self {
-        Self { rules: Vec::new() }
+        Self::default()
+    }
+
+    /// Add a `@keyframes` rule, replacing any with the same name.
+    pub fn add_keyframes(&mut self, keyframes: Keyframes) {
+        self.keyframes.insert(keyframes.name.clone(), keyframes);
     }
 
     /// Add a rule to the stylesheet.
@@ -34,11 +44,19 @@ impl StyleSheet {
         widget_id: Option<&str>,
         classes: &[String],
         state: &WidgetState,
+    ) -> Vec<(&'a StyleRule, Specificity)> {
+        self.find_matching_rules_for(&MatchTarget::new(widget_type, widget_id, classes, state))
+    }
+
+    /// Find all matching rules for a widget, including combinator selectors.
+    pub fn find_matching_rules_for<'a>(
+        &'a self,
+        target: &MatchTarget,
     ) -> Vec<(&'a StyleRule, Specificity)> {
         self.rules
             .iter()
             .filter_map(|rule| {
-                if rule.selector.matches(widget_type, widget_id, classes, state) {
+                if rule.selector.matches_target(target) {
                     Some((rule, rule.selector.specificity))
                 } else {
                     None
@@ -56,27 +74,100 @@ impl StyleSheet {
         state: &WidgetState,
         ctx: &StyleContext,
     ) -> ComputedStyle {
+        self.compute_style_for(&MatchTarget::new(widget_type, widget_id, classes, state), ctx)
+    }
+
+    /// Compute styles for a widget placed in an element tree.
+    ///
+    /// Custom properties are inherited from the widget's ancestors when the
+    /// target has a location in the tree.
+    pub fn compute_style_for(&self, target: &MatchTarget, ctx: &StyleContext) -> ComputedStyle {
+        let inherited = match target.location {
+            Some((tree, node)) => self.inherited_custom_properties(tree, node, ctx),
+            None => HashMap::new(),
+        };
+
         let mut style = ComputedStyle::default();
+        let matches = self.cascade(target, ctx);
 
-        // Find all matching rules
-        let mut matches = self.find_matching_rules(widget_type, widget_id, classes, state);
+        // Custom properties cascade first so var() sees their final values
+        style.custom_properties =
+            vars::compute_custom_properties(&Self::declared_custom_properties(&matches), &inherited, ctx);
+
+        // Apply rules in order; within a rule, shorthands left unexpanded
+        // (because they contain var()) go first so longhands override them,
+        // and `color` leads the longhands so `currentcolor` sees it
+        for (rule, _) in matches {
+            let (shorthands, mut longhands): (Vec<_>, Vec<_>) = rule
+                .declarations
+                .iter()
+                .filter(|(property, _)| !property.is_custom_property())
+                .partition(|(property, _)| property.is_shorthand());
+            longhands.sort_by_key(|(property, _)| **property != StyleProperty::Color);
+            for (property, value) in shorthands.into_iter().chain(longhands) {
+                style.apply(property, value, ctx);
+            }
+        }
+        style.resolve_current_color();
+
+        style
+    }
+
+    /// Find the rules that apply to a widget, in ascending cascade order.
+    fn cascade<'a>(&'a self, target: &MatchTarget, ctx: &StyleContext) -> Vec<(&'a StyleRule, Specificity)> {
+        // Find all matching rules whose media queries apply
+        let mut matches = self.find_matching_rules_for(target);
+        matches.retain(|(rule, _)| rule.media_matches(ctx));
 
         // Sort by specificity (ascending so higher specificity overrides)
         matches.sort_by(|a, b| a.1.cmp(&b.1));
+        matches
+    }
 
-        // Apply rules in order
+    /// Collect the winning `--name` declarations from cascade-ordered rules.
+    fn declared_custom_properties(matches: &[(&StyleRule, Specificity)]) -> HashMap<String, CssValue> {
+        let mut declared = HashMap::new();
         for (rule, _) in matches {
             for (property, value) in &rule.declarations {
-                style.apply(property, value, ctx);
+                if let StyleProperty::Custom(name) = property {
+                    if property.is_custom_property() {
+                        declared.insert(name.clone(), value.clone());
+                    }
+                }
             }
         }
+        declared
+    }
 
-        style
+    /// Compute the custom properties that `node` inherits from its parent.
+    fn inherited_custom_properties(
+        &self,
+        tree: &ElementTree,
+        node: usize,
+        ctx: &StyleContext,
+    ) -> HashMap<String, CssValue> {
+        let Some(parent) = tree.node(node).parent else {
+            return HashMap::new();
+        };
+
+        let inherited = self.inherited_custom_properties(tree, parent, ctx);
+        let element = &tree.node(parent).element;
+        let target = MatchTarget::new(
+            &element.widget_type,
+            element.element_id.as_deref(),
+            &element.classes,
+            &element.state,
+        )
+        .in_tree(tree, parent);
+
+        let declared = Self::declared_custom_properties(&self.cascade(&target, ctx));
+        vars::compute_custom_properties(&declared, &inherited, ctx)
     }
 
     /// Merge another stylesheet into this one.
     pub fn merge(&mut self, other: StyleSheet) {
         self.rules.extend(other.rules);
+        self.keyframes.extend(other.keyframes);
     }
 }
 
@@ -85,6 +176,8 @@ impl StyleSheet {
 pub struct StyleRule {
     pub selector: Selector,
     pub declarations: HashMap<StyleProperty, CssValue>,
+    /// Queries of the enclosing `@media` blocks; all must match.
+    pub media: Vec<MediaQueryList>,
 }
 
 impl StyleRule {
@@ -92,9 +185,21 @@ impl StyleRule {
         Self {
             selector,
             declarations,
+            media: Vec::new(),
         }
     }
 
+    /// Make the rule conditional on media queries.
+    pub fn with_media(mut self, media: Vec<MediaQueryList>) -> Self {
+        self.media = media;
+        self
+    }
+
+    /// Check if the rule's media queries match the current viewport and theme.
+    pub fn media_matches(&self, ctx: &StyleContext) -> bool {
+        self.media.iter().all(|queries| queries.matches(ctx))
+    }
+
     /// Create a rule from selector and a builder closure.
     pub fn build<F>(selector: Selector, builder: F) -> Self
     where
@@ -230,7 +335,7 @@ impl RuleBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::geometry::Color;
+    use crate::geometry::{BorderRadius, Color, EdgeInsets};
     use crate::theme::ThemeData;
 
     #[test]
@@ -291,4 +396,199 @@ mod tests {
 
         assert_eq!(style.background_color, Color::BLUE);
     }
+
+    #[test]
+    fn test_compute_style_shorthands() {
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".card { padding: 4px 8px; padding-top: 2px; border-radius: 2px 6px; border: 1px solid red; }",
+        )
+        .unwrap();
+
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let state = WidgetState::default();
+        let style = stylesheet.compute_style("card", None, &["card".to_string()], &state, &ctx);
+
+        assert_eq!(style.padding.top, 2.0);
+        assert_eq!(style.padding.right, 8.0);
+        assert_eq!(style.padding.bottom, 4.0);
+        assert_eq!(style.border_radius.top_left, 2.0);
+        assert_eq!(style.border_radius.top_right, 6.0);
+        assert_eq!(style.border_width.left, 1.0);
+        assert_eq!(style.border_color, Color::from_rgb8(255, 0, 0));
+    }
+
+    #[test]
+    fn test_compute_style_calc() {
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".sidebar { width: calc(100% - 240px); height: max(50%, 100px); padding-left: calc(1rem + 10%); }",
+        )
+        .unwrap();
+
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme).with_viewport(1000.0, 150.0);
+        let state = WidgetState::default();
+        let style = stylesheet.compute_style("column", None, &["sidebar".to_string()], &state, &ctx);
+
+        assert_eq!(style.width, Some(760.0));
+        assert_eq!(style.height, Some(100.0));
+        assert_eq!(style.padding.left, theme.typography.base_size + 100.0);
+    }
+
+    #[test]
+    fn test_compute_style_box_shadow() {
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".card { color: blue; box-shadow: 0 4px 6px -1px rgba(0, 0, 0, 0.1), inset 1px 2px red, 0 0 3px; }
+             .flat { box-shadow: none; }
+             .bad { box-shadow: 1px; }",
+        )
+        .unwrap();
+
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let state = WidgetState::default();
+        let style = stylesheet.compute_style("card", None, &["card".to_string()], &state, &ctx);
+
+        assert_eq!(style.box_shadow.len(), 3);
+        let first = &style.box_shadow[0];
+        assert_eq!((first.offset_x, first.offset_y, first.blur_radius, first.spread_radius), (0.0, 4.0, 6.0, -1.0));
+        assert!(!first.inset);
+        let second = &style.box_shadow[1];
+        assert!(second.inset);
+        assert_eq!((second.offset_x, second.offset_y, second.blur_radius), (1.0, 2.0, 0.0));
+        assert_eq!(second.color, Color::from_rgb8(255, 0, 0));
+        // Without a color the shadow takes the text color
+        assert_eq!(style.box_shadow[2].color, style.color);
+
+        // Even when a later rule sets the text color
+        let recolored = crate::css::CssParser::parse_stylesheet(
+            ".card { box-shadow: 0 0 3px, 1px 1px currentcolor, 1px 1px red; } .card.warning { color: orange; }",
+        )
+        .unwrap()
+        .compute_style("card", None, &["card".to_string(), "warning".to_string()], &state, &ctx);
+        let colors: Vec<Color> = recolored.box_shadow.iter().map(|shadow| shadow.color).collect();
+        assert_eq!(recolored.color, Color::from_rgb8(255, 165, 0));
+        assert_eq!(colors, vec![recolored.color, recolored.color, Color::from_rgb8(255, 0, 0)]);
+
+        let flat = stylesheet.compute_style("card", None, &["card".to_string(), "flat".to_string()], &state, &ctx);
+        assert!(flat.box_shadow.is_empty());
+        assert!(flat.box_shadow_or(std::slice::from_ref(&theme.shadows.md)).is_empty());
+
+        // An invalid value keeps the earlier one
+        let bad = stylesheet.compute_style("card", None, &["card".to_string(), "bad".to_string()], &state, &ctx);
+        assert_eq!(bad.box_shadow.len(), 3);
+    }
+
+    #[test]
+    fn test_compute_style_media() {
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".sidebar { width: 240px; }
+             @media (max-width: 600px) { .sidebar { width: 100%; } }
+             @media (prefers-color-scheme: dark) { .sidebar { color: white; } }",
+        )
+        .unwrap();
+
+        let state = WidgetState::default();
+        let classes = ["sidebar".to_string()];
+
+        let light = ThemeData::light();
+        let wide = StyleContext::new(&light).with_viewport(1024.0, 768.0);
+        let style = stylesheet.compute_style("column", None, &classes, &state, &wide);
+        assert_eq!(style.width, Some(240.0));
+        assert_eq!(style.color, Color::BLACK);
+
+        let dark = ThemeData::dark();
+        let narrow = StyleContext::new(&dark).with_viewport(480.0, 800.0);
+        let style = stylesheet.compute_style("column", None, &classes, &state, &narrow);
+        assert_eq!(style.width, Some(480.0));
+        assert_eq!(style.color, Color::WHITE);
+    }
+
+    #[test]
+    fn test_compute_style_vars() {
+        use crate::css::{ElementInfo, ElementTree, MatchTarget};
+
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ":root { --accent: #ff0000; --space: 4px; }
+             .card { --pad: var(--space) calc(var(--space) * 2); padding: var(--pad); }
+             .card label { color: var(--accent); margin: var(--missing, 3px); }
+             .loop { --a: var(--b); --b: var(--a); color: var(--a, blue); }",
+        )
+        .unwrap();
+
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let element = |widget_type: &str, class: Option<&str>| ElementInfo {
+            widget_type: widget_type.to_string(),
+            element_id: None,
+            classes: class.map(|c| vec![c.to_string()]).unwrap_or_default(),
+            state: WidgetState::default(),
+        };
+
+        // column > column.card > label
+        let mut tree = ElementTree::new();
+        let root = tree.push(1, element("column", None), None, None);
+        let card = tree.push(2, element("column", Some("card")), Some(root), None);
+        let label = tree.push(3, element("label", None), Some(card), None);
+
+        let state = WidgetState::default();
+        let card_classes = vec!["card".to_string()];
+        let target = MatchTarget::new("column", None, &card_classes, &state).in_tree(&tree, card);
+        let style = stylesheet.compute_style_for(&target, &ctx);
+        assert_eq!(style.padding.top, 4.0);
+        assert_eq!(style.padding.right, 8.0);
+
+        // Custom properties inherit from the root through the card
+        let target = MatchTarget::new("label", None, &[], &state).in_tree(&tree, label);
+        let style = stylesheet.compute_style_for(&target, &ctx);
+        assert_eq!(style.color, Color::from_rgb8(255, 0, 0));
+        assert_eq!(style.margin.left, 3.0);
+        assert!(style.custom_properties.contains_key("--pad"));
+
+        // Cyclic variables are invalid, so the fallback applies
+        let style = stylesheet.compute_style("column", None, &["loop".to_string()], &state, &ctx);
+        assert_eq!(style.color, Color::from_rgb8(0, 0, 255));
+    }
+
+    #[test]
+    fn test_unset_properties_fall_back() {
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".field { padding-left: 20px; border-top-left-radius: 2px; color: red; }",
+        )
+        .unwrap();
+
+        let state = WidgetState::default();
+        let style = stylesheet.compute_style("textfield", None, &["field".to_string()], &state, &ctx);
+        assert_eq!(style.color_or(Color::BLUE), Color::from_rgb8(255, 0, 0));
+        assert_eq!(style.background_or(Color::BLUE), Color::BLUE);
+        assert_eq!(style.font_size_or(14.0), 14.0);
+        assert_eq!(style.padding_or(EdgeInsets::all(8.0)), EdgeInsets::new(8.0, 8.0, 8.0, 20.0));
+        assert_eq!(
+            style.border_radius_or(BorderRadius::all(6.0)),
+            BorderRadius::new(2.0, 6.0, 6.0, 6.0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_fall_back() {
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let stylesheet = crate::css::CssParser::parse_stylesheet(
+            ".field { background-color: 12px; box-shadow: 1px; font-size: red; border-top-left-radius: blue; }",
+        )
+        .unwrap();
+
+        // Invalid declarations leave the properties unset, so the fallbacks apply
+        let state = WidgetState::default();
+        let style = stylesheet.compute_style("textfield", None, &["field".to_string()], &state, &ctx);
+        assert!(!style.is_set(&StyleProperty::BackgroundColor));
+        assert_eq!(style.background_or(Color::BLUE), Color::BLUE);
+        let shadows = style.box_shadow_or(std::slice::from_ref(&theme.shadows.md));
+        assert_eq!(shadows.len(), 1);
+        assert_eq!(shadows[0].offset_y, theme.shadows.md.offset_y);
+        assert_eq!(style.font_size_or(14.0), 14.0);
+        assert_eq!(style.border_radius_or(BorderRadius::all(6.0)), BorderRadius::all(6.0));
+    }
 }
@@ -0,0 +1,421 @@
+//! CSS transitions.
+//!
+//! [`Transitions`] remembers the computed values of each widget's animatable
+//! properties. When a new cascade produces a different value (typically after
+//! a `WidgetState` change such as hover or focus) and the widget's style
+//! declares a transition for that property, the value is animated from what
+//! was on screen to the new value over the declared duration, delay and
+//! timing function.
+//!
+//! ```css
+//! .button {
+//!     background-color: #3b82f6;
+//!     transition: background-color 150ms ease-out, transform 100ms;
+//! }
+//! .button:hover { background-color: #2563eb; transform: scale(1.05); }
+//! ```
+//!
+//! Only properties set by a rule in both the old and new style transition;
+//! widgets paint unset properties from theme colors, which have no value to
+//! interpolate from.
+
+use crate::css::properties::Transform;
+use crate::css::{ComputedStyle, StyleProperty, TimingFunction};
+use crate::event::WidgetId;
+use crate::geometry::{BorderRadius, Color};
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Properties that can be transitioned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimatableProperty {
+    BackgroundColor,
+    Color,
+    BorderColor,
+    Opacity,
+    Width,
+    Height,
+    BorderRadius,
+    Transform,
+}
+
+impl AnimatableProperty {
+    pub const ALL: [AnimatableProperty; 8] = [
+        AnimatableProperty::BackgroundColor,
+        AnimatableProperty::Color,
+        AnimatableProperty::BorderColor,
+        AnimatableProperty::Opacity,
+        AnimatableProperty::Width,
+        AnimatableProperty::Height,
+        AnimatableProperty::BorderRadius,
+        AnimatableProperty::Transform,
+    ];
+
+    /// Names selecting this property in `transition-property`.
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            AnimatableProperty::BackgroundColor => &["background-color", "background"],
+            AnimatableProperty::Color => &["color"],
+            AnimatableProperty::BorderColor => &["border-color", "border"],
+            AnimatableProperty::Opacity => &["opacity"],
+            AnimatableProperty::Width => &["width"],
+            AnimatableProperty::Height => &["height"],
+            AnimatableProperty::BorderRadius => &["border-radius"],
+            AnimatableProperty::Transform => &["transform"],
+        }
+    }
+
+    /// Whether changing this property affects layout.
+    pub fn affects_layout(&self) -> bool {
+        matches!(self, AnimatableProperty::Width | AnimatableProperty::Height)
+    }
+
+    /// Read the property from a style, if a rule sets it to an interpolable value.
+    pub fn get(&self, style: &ComputedStyle) -> Option<AnimatedValue> {
+        self.longhands()
+            .iter()
+            .all(|property| style.is_set(property))
+            .then(|| self.value(style))
+            .flatten()
+    }
+
+    /// Read the property's value whether or not a rule sets it.
+    ///
+    /// `None` for values that can't be interpolated, such as `width: auto`.
+    pub fn value(&self, style: &ComputedStyle) -> Option<AnimatedValue> {
+        match self {
+            AnimatableProperty::BackgroundColor => Some(AnimatedValue::Color(style.background_color)),
+            AnimatableProperty::Color => Some(AnimatedValue::Color(style.color)),
+            AnimatableProperty::BorderColor => Some(AnimatedValue::Color(style.border_color)),
+            AnimatableProperty::Opacity => Some(AnimatedValue::Number(style.opacity)),
+            AnimatableProperty::Width => style.width.map(AnimatedValue::Number),
+            AnimatableProperty::Height => style.height.map(AnimatedValue::Number),
+            AnimatableProperty::BorderRadius => Some(AnimatedValue::Radius(style.border_radius)),
+            AnimatableProperty::Transform => Some(AnimatedValue::Transform(style.transform)),
+        }
+    }
+
+    /// Write an interpolated value into a style, marking it as set.
+    pub fn set(&self, style: &mut ComputedStyle, value: AnimatedValue) {
+        match (self, value) {
+            (AnimatableProperty::BackgroundColor, AnimatedValue::Color(c)) => style.background_color = c,
+            (AnimatableProperty::Color, AnimatedValue::Color(c)) => style.color = c,
+            (AnimatableProperty::BorderColor, AnimatedValue::Color(c)) => style.border_color = c,
+            (AnimatableProperty::Opacity, AnimatedValue::Number(n)) => style.opacity = n.clamp(0.0, 1.0),
+            (AnimatableProperty::Width, AnimatedValue::Number(n)) => style.width = Some(n.max(0.0)),
+            (AnimatableProperty::Height, AnimatedValue::Number(n)) => style.height = Some(n.max(0.0)),
+            (AnimatableProperty::BorderRadius, AnimatedValue::Radius(r)) => style.border_radius = r,
+            (AnimatableProperty::Transform, AnimatedValue::Transform(t)) => style.transform = t,
+            _ => return,
+        }
+        style.specified.extend(self.longhands().iter().cloned());
+    }
+
+    /// The longhand properties holding this value.
+    fn longhands(&self) -> &'static [StyleProperty] {
+        match self {
+            AnimatableProperty::BackgroundColor => &[StyleProperty::BackgroundColor],
+            AnimatableProperty::Color => &[StyleProperty::Color],
+            AnimatableProperty::BorderColor => &[StyleProperty::BorderColor],
+            AnimatableProperty::Opacity => &[StyleProperty::Opacity],
+            AnimatableProperty::Width => &[StyleProperty::Width],
+            AnimatableProperty::Height => &[StyleProperty::Height],
+            AnimatableProperty::BorderRadius => &[
+                StyleProperty::BorderTopLeftRadius,
+                StyleProperty::BorderTopRightRadius,
+                StyleProperty::BorderBottomRightRadius,
+                StyleProperty::BorderBottomLeftRadius,
+            ],
+            AnimatableProperty::Transform => &[StyleProperty::Transform],
+        }
+    }
+}
+
+/// A value of an animatable property.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimatedValue {
+    Number(f32),
+    Color(Color),
+    Radius(BorderRadius),
+    Transform(Transform),
+}
+
+impl AnimatedValue {
+    /// Interpolate towards `to`. `t` may leave 0..1 for overshooting curves.
+    pub fn lerp(&self, to: &AnimatedValue, t: f32) -> AnimatedValue {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        match (self, to) {
+            (AnimatedValue::Number(a), AnimatedValue::Number(b)) => AnimatedValue::Number(lerp(*a, *b)),
+            (AnimatedValue::Color(a), AnimatedValue::Color(b)) => {
+                let c = a.lerp(*b, t);
+                AnimatedValue::Color(Color::rgba(
+                    c.r.clamp(0.0, 1.0),
+                    c.g.clamp(0.0, 1.0),
+                    c.b.clamp(0.0, 1.0),
+                    c.a.clamp(0.0, 1.0),
+                ))
+            }
+            (AnimatedValue::Radius(a), AnimatedValue::Radius(b)) => AnimatedValue::Radius(BorderRadius {
+                top_left: lerp(a.top_left, b.top_left).max(0.0),
+                top_right: lerp(a.top_right, b.top_right).max(0.0),
+                bottom_right: lerp(a.bottom_right, b.bottom_right).max(0.0),
+                bottom_left: lerp(a.bottom_left, b.bottom_left).max(0.0),
+            }),
+            (AnimatedValue::Transform(a), AnimatedValue::Transform(b)) => AnimatedValue::Transform(a.lerp(*b, t)),
+            // Mismatched values jump at the midpoint
+            _ if t < 0.5 => *self,
+            _ => *to,
+        }
+    }
+}
+
+/// A transition in progress.
+#[derive(Debug, Clone)]
+struct RunningTransition {
+    property: AnimatableProperty,
+    from: AnimatedValue,
+    to: AnimatedValue,
+    start: Instant,
+    duration: Duration,
+    timing: TimingFunction,
+}
+
+impl RunningTransition {
+    fn sample(&self, now: Instant) -> AnimatedValue {
+        let elapsed = now.saturating_duration_since(self.start);
+        let progress = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        self.from.lerp(&self.to, self.timing.evaluate(progress))
+    }
+
+    fn finished(&self, now: Instant) -> bool {
+        now >= self.start + self.duration
+    }
+}
+
+/// Transition state of one widget.
+#[derive(Debug, Default)]
+struct WidgetTransitions {
+    /// Values from the most recent cascade, before transitions are applied
+    targets: HashMap<AnimatableProperty, AnimatedValue>,
+    running: Vec<RunningTransition>,
+    /// Whether the widget was styled since the last frame began
+    seen: bool,
+}
+
+#[derive(Debug)]
+struct TransitionState {
+    now: Instant,
+    widgets: HashMap<WidgetId, WidgetTransitions>,
+}
+
+/// Running CSS transitions for a widget tree.
+///
+/// Shared with every `StyleContext` of a window (see
+/// `StyleContext::with_transitions`) and advanced once per frame.
+#[derive(Debug)]
+pub struct Transitions {
+    state: Mutex<TransitionState>,
+}
+
+impl Transitions {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(TransitionState {
+                now: Instant::now(),
+                widgets: HashMap::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TransitionState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start a frame at `now`.
+    ///
+    /// All styles computed until the next frame sample transitions at `now`.
+    /// Widgets that weren't styled during the previous frame are forgotten.
+    pub fn begin_frame(&self, now: Instant) {
+        let mut state = self.lock();
+        state.now = now;
+        state.widgets.retain(|_, widget| std::mem::take(&mut widget.seen));
+    }
+
+    /// Whether any transition is running or waiting for its delay.
+    pub fn is_running(&self) -> bool {
+        let state = self.lock();
+        let now = state.now;
+        state
+            .widgets
+            .values()
+            .any(|widget| widget.running.iter().any(|t| !t.finished(now)))
+    }
+
+    /// Whether a running transition changes layout (width or height).
+    pub fn needs_layout(&self) -> bool {
+        let state = self.lock();
+        let now = state.now;
+        state.widgets.values().any(|widget| {
+            widget
+                .running
+                .iter()
+                .any(|t| t.property.affects_layout() && !t.finished(now))
+        })
+    }
+
+    /// Start transitions for changed values and apply running ones to `style`.
+    pub fn apply(&self, widget_id: WidgetId, style: &mut ComputedStyle) {
+        let mut state = self.lock();
+        let now = state.now;
+        let widget = state.widgets.entry(widget_id).or_default();
+        widget.seen = true;
+
+        for property in AnimatableProperty::ALL {
+            let target = property.get(style);
+            let previous = widget.targets.get(&property).copied();
+            if target == previous {
+                continue;
+            }
+
+            // Start from what is on screen, which may be mid-transition
+            let current = widget
+                .running
+                .iter()
+                .find(|t| t.property == property)
+                .map(|t| t.sample(now))
+                .or(previous);
+            widget.running.retain(|t| t.property != property);
+
+            if let (Some(from), Some(to), Some(timing)) =
+                (current, target, style.transition_for(property.names()))
+            {
+                widget.running.push(RunningTransition {
+                    property,
+                    from,
+                    to,
+                    start: now + timing.delay,
+                    duration: timing.duration,
+                    timing: timing.timing,
+                });
+            }
+
+            match target {
+                Some(value) => widget.targets.insert(property, value),
+                None => widget.targets.remove(&property),
+            };
+        }
+
+        widget.running.retain(|t| !t.finished(now));
+        for transition in &widget.running {
+            transition.property.set(style, transition.sample(now));
+        }
+    }
+}
+
+impl Default for Transitions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{StyleContext, StyleManager, WidgetState};
+    use crate::theme::ThemeData;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_hover_transition() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                ".btn { background-color: #000000; transition: background-color 100ms linear; }
+                 .btn:hover { background-color: #ffffff; }",
+            )
+            .unwrap();
+        let transitions = Arc::new(Transitions::new());
+        let ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_transitions(transitions.clone());
+        let classes = ["btn".to_string()];
+        let hovered = WidgetState { hovered: true, ..Default::default() };
+        let style = |state: &WidgetState| ctx.compute_style_for(1, "button", None, &classes, state);
+
+        let start = Instant::now();
+        transitions.begin_frame(start);
+        assert_eq!(style(&WidgetState::default()).background_color, Color::BLACK);
+
+        // Hover starts a transition instead of jumping
+        let hover = style(&hovered);
+        assert_eq!(hover.background_color, Color::BLACK);
+        assert!(transitions.is_running());
+
+        transitions.begin_frame(start + Duration::from_millis(50));
+        let mid = style(&hovered).background_color;
+        assert!((mid.r - 0.5).abs() < 0.01);
+
+        // Un-hovering mid-way reverses from the current value
+        transitions.begin_frame(start + Duration::from_millis(60));
+        let reversed = style(&WidgetState::default()).background_color;
+        assert!((reversed.r - 0.6).abs() < 0.01);
+
+        transitions.begin_frame(start + Duration::from_millis(200));
+        assert_eq!(style(&WidgetState::default()).background_color, Color::BLACK);
+        assert!(!transitions.is_running());
+    }
+
+    #[test]
+    fn test_delay_and_untransitioned_properties() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                ".box { opacity: 1; color: #000000; transition: opacity 100ms linear 100ms; }
+                 .box:focus { opacity: 0; color: #ffffff; }",
+            )
+            .unwrap();
+        let transitions = Arc::new(Transitions::new());
+        let ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_transitions(transitions.clone());
+        let classes = ["box".to_string()];
+        let focused = WidgetState { focused: true, ..Default::default() };
+        let style = |state: &WidgetState| ctx.compute_style_for(7, "box", None, &classes, state);
+
+        let start = Instant::now();
+        transitions.begin_frame(start);
+        style(&WidgetState::default());
+        let changed = style(&focused);
+        assert_eq!(changed.color, Color::WHITE);
+        assert_eq!(changed.opacity, 1.0);
+
+        transitions.begin_frame(start + Duration::from_millis(150));
+        assert!((style(&focused).opacity - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_huge_transition_times() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                ".box { opacity: 1; transition: opacity 1e39s linear 1e39s; }
+                 .box:focus { opacity: 0; }",
+            )
+            .unwrap();
+        let transitions = Arc::new(Transitions::new());
+        let ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_transitions(transitions.clone());
+        let classes = ["box".to_string()];
+        let focused = WidgetState { focused: true, ..Default::default() };
+        let style = |state: &WidgetState| ctx.compute_style_for(9, "box", None, &classes, state);
+
+        // Infinite times are clamped instead of panicking
+        let start = Instant::now();
+        transitions.begin_frame(start);
+        style(&WidgetState::default());
+        assert_eq!(style(&focused).opacity, 1.0);
+        transitions.begin_frame(start + Duration::from_secs(60));
+        assert_eq!(style(&focused).opacity, 1.0);
+        assert!(transitions.is_running());
+    }
+}
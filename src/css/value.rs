@@ -1,5 +1,6 @@
 //! CSS value types.
 
+use crate::css::{CssParser, StyleContext};
 use crate::geometry::Color;
 
 /// A CSS value.
@@ -23,8 +24,15 @@ pub enum CssValue {
     Var(String, Option<Box<CssValue>>),
     /// calc() expression
     Calc(CalcExpr),
+    /// Time value in milliseconds
+    Time(f32),
+    /// Function call other than the built-in ones (e.g., `cubic-bezier()`),
+    /// with one entry per comma-separated argument
+    Function(String, Vec<CssValue>),
     /// Multiple values (e.g., for margin shorthand)
     List(Vec<CssValue>),
+    /// Comma-separated values (e.g., for font-family)
+    CommaList(Vec<CssValue>),
 }
 
 impl CssValue {
@@ -56,6 +64,27 @@ impl CssValue {
         }
     }
 
+    /// Try to get as a time in milliseconds.
+    pub fn as_time(&self) -> Option<f32> {
+        match self {
+            CssValue::Time(ms) => Some(*ms),
+            CssValue::Number(n) if *n == 0.0 => Some(0.0),
+            _ => None,
+        }
+    }
+
+    /// Check if this value contains a `var()` reference anywhere.
+    pub fn contains_var(&self) -> bool {
+        match self {
+            CssValue::Var(..) => true,
+            CssValue::Calc(expr) => expr.contains_var(),
+            CssValue::Function(_, values) | CssValue::List(values) | CssValue::CommaList(values) => {
+                values.iter().any(CssValue::contains_var)
+            }
+            _ => false,
+        }
+    }
+
     /// Check if this is "auto".
     pub fn is_auto(&self) -> bool {
         matches!(self, CssValue::Keyword(k) if k == "auto")
@@ -153,37 +182,135 @@ impl LengthUnit {
 }
 
 /// A calc() expression.
+///
+/// Also used for `min()`, `max()` and `clamp()`. Leaves are lengths,
+/// percentages or plain numbers; `var()` references are resolved when the
+/// expression is evaluated.
 #[derive(Debug, Clone, PartialEq)]
 pub enum CalcExpr {
     Value(Length),
+    Number(f32),
+    Var(String, Option<Box<CalcExpr>>),
     Add(Box<CalcExpr>, Box<CalcExpr>),
     Sub(Box<CalcExpr>, Box<CalcExpr>),
-    Mul(Box<CalcExpr>, f32),
-    Div(Box<CalcExpr>, f32),
+    Mul(Box<CalcExpr>, Box<CalcExpr>),
+    Div(Box<CalcExpr>, Box<CalcExpr>),
+    Min(Vec<CalcExpr>),
+    Max(Vec<CalcExpr>),
+    Clamp(Box<CalcExpr>, Box<CalcExpr>, Box<CalcExpr>),
 }
 
 impl CalcExpr {
     /// Evaluate the calc expression to a length in pixels.
+    ///
+    /// Variables evaluate to their fallback (or zero) and viewport units are
+    /// left unscaled; use [`CalcExpr::resolve`] when a `StyleContext` is available.
     pub fn evaluate(&self, base_px: f32, rem_px: f32, em_px: f32) -> f32 {
+        let length = |length: &Length| match length.unit {
+            LengthUnit::Px => length.value,
+            LengthUnit::Rem => length.value * rem_px,
+            LengthUnit::Em => length.value * em_px,
+            LengthUnit::Percent => length.value / 100.0 * base_px,
+            LengthUnit::Vw | LengthUnit::Vh | LengthUnit::Vmin | LengthUnit::Vmax => {
+                // Viewport units need window size context
+                length.value
+            }
+        };
+        let var = |_: &str, fallback: Option<&CalcExpr>| {
+            Some(fallback.map_or(0.0, |f| f.evaluate(base_px, rem_px, em_px)))
+        };
+        self.eval_with(&length, &var).unwrap_or(0.0)
+    }
+
+    /// Resolve the expression to pixels, with percentages relative to `basis`.
+    ///
+    /// Returns `None` if a variable is undefined without a fallback, or on
+    /// division by zero.
+    pub fn resolve(&self, ctx: &StyleContext, basis: f32) -> Option<f32> {
+        self.resolve_depth(ctx, basis, 0)
+    }
+
+    fn resolve_depth(&self, ctx: &StyleContext, basis: f32, depth: usize) -> Option<f32> {
+        // Guards against variables that reference themselves
+        const MAX_VAR_DEPTH: usize = 16;
+
+        let length = |length: &Length| ctx.to_pixels_with_basis(length, basis);
+        let var = |name: &str, fallback: Option<&CalcExpr>| {
+            let resolved = ctx
+                .resolve_var(name)
+                .filter(|_| depth < MAX_VAR_DEPTH)
+                .and_then(|value| CssParser::parse_value_str(&value).ok())
+                .and_then(|value| CalcExpr::from_value(&value))
+                .and_then(|expr| expr.resolve_depth(ctx, basis, depth + 1));
+            match resolved {
+                Some(px) => Some(px),
+                None => fallback?.resolve_depth(ctx, basis, depth + 1),
+            }
+        };
+        self.eval_with(&length, &var)
+    }
+
+    fn eval_with(
+        &self,
+        length: &impl Fn(&Length) -> f32,
+        var: &impl Fn(&str, Option<&CalcExpr>) -> Option<f32>,
+    ) -> Option<f32> {
+        let eval = |expr: &CalcExpr| expr.eval_with(length, var);
         match self {
-            CalcExpr::Value(length) => match length.unit {
-                LengthUnit::Px => length.value,
-                LengthUnit::Rem => length.value * rem_px,
-                LengthUnit::Em => length.value * em_px,
-                LengthUnit::Percent => length.value / 100.0 * base_px,
-                LengthUnit::Vw | LengthUnit::Vh | LengthUnit::Vmin | LengthUnit::Vmax => {
-                    // Viewport units need window size context
-                    length.value
+            CalcExpr::Value(l) => Some(length(l)),
+            CalcExpr::Number(n) => Some(*n),
+            CalcExpr::Var(name, fallback) => var(name, fallback.as_deref()),
+            CalcExpr::Add(a, b) => Some(eval(a)? + eval(b)?),
+            CalcExpr::Sub(a, b) => Some(eval(a)? - eval(b)?),
+            CalcExpr::Mul(a, b) => Some(eval(a)? * eval(b)?),
+            CalcExpr::Div(a, b) => {
+                let divisor = eval(b)?;
+                if divisor == 0.0 {
+                    None
+                } else {
+                    Some(eval(a)? / divisor)
                 }
-            },
-            CalcExpr::Add(a, b) => {
-                a.evaluate(base_px, rem_px, em_px) + b.evaluate(base_px, rem_px, em_px)
             }
-            CalcExpr::Sub(a, b) => {
-                a.evaluate(base_px, rem_px, em_px) - b.evaluate(base_px, rem_px, em_px)
+            CalcExpr::Min(exprs) => exprs.iter().map(eval).reduce(|a, b| Some(a?.min(b?)))?,
+            CalcExpr::Max(exprs) => exprs.iter().map(eval).reduce(|a, b| Some(a?.max(b?)))?,
+            CalcExpr::Clamp(min, value, max) => {
+                let (min, value, max) = (eval(min)?, eval(value)?, eval(max)?);
+                // Like CSS, the minimum wins if it exceeds the maximum
+                Some(value.min(max).max(min))
+            }
+        }
+    }
+
+    /// Check if the expression contains a `var()` reference.
+    pub fn contains_var(&self) -> bool {
+        match self {
+            CalcExpr::Value(_) | CalcExpr::Number(_) => false,
+            CalcExpr::Var(..) => true,
+            CalcExpr::Add(a, b) | CalcExpr::Sub(a, b) | CalcExpr::Mul(a, b) | CalcExpr::Div(a, b) => {
+                a.contains_var() || b.contains_var()
+            }
+            CalcExpr::Min(exprs) | CalcExpr::Max(exprs) => exprs.iter().any(CalcExpr::contains_var),
+            CalcExpr::Clamp(min, value, max) => {
+                min.contains_var() || value.contains_var() || max.contains_var()
             }
-            CalcExpr::Mul(expr, n) => expr.evaluate(base_px, rem_px, em_px) * n,
-            CalcExpr::Div(expr, n) => expr.evaluate(base_px, rem_px, em_px) / n,
+        }
+    }
+
+    /// Convert a plain CSS value into an expression, if it is numeric.
+    pub fn from_value(value: &CssValue) -> Option<CalcExpr> {
+        match value {
+            CssValue::Length(l) => Some(CalcExpr::Value(l.clone())),
+            CssValue::Percentage(p) => Some(CalcExpr::Value(Length::percent(*p))),
+            CssValue::Number(n) => Some(CalcExpr::Number(*n)),
+            CssValue::Calc(expr) => Some(expr.clone()),
+            CssValue::Var(name, fallback) => {
+                let fallback = match fallback {
+                    Some(fallback) => Some(Box::new(CalcExpr::from_value(fallback)?)),
+                    None => None,
+                };
+                Some(CalcExpr::Var(name.clone(), fallback))
+            }
+            _ => None,
         }
     }
 }
@@ -220,7 +347,6 @@ fn color_from_keyword(keyword: &str) -> Option<Color> {
 /// Timing function for transitions/animations.
 #[derive(Debug, Clone, PartialEq)]
 #[derive(Default)]
-#[allow(dead_code)]
 pub enum TimingFunction {
     Linear,
     #[default]
@@ -233,9 +359,52 @@ pub enum TimingFunction {
 }
 
 impl TimingFunction {
+    /// Parse a timing function keyword, `cubic-bezier()` or `steps()`.
+    pub fn from_value(value: &CssValue) -> Option<Self> {
+        match value {
+            CssValue::Keyword(k) => match k.to_ascii_lowercase().as_str() {
+                "linear" => Some(TimingFunction::Linear),
+                "ease" => Some(TimingFunction::Ease),
+                "ease-in" => Some(TimingFunction::EaseIn),
+                "ease-out" => Some(TimingFunction::EaseOut),
+                "ease-in-out" => Some(TimingFunction::EaseInOut),
+                "step-start" => Some(TimingFunction::Steps(1, StepPosition::Start)),
+                "step-end" => Some(TimingFunction::Steps(1, StepPosition::End)),
+                _ => None,
+            },
+            CssValue::Function(name, args) if name.eq_ignore_ascii_case("cubic-bezier") => {
+                match args.as_slice() {
+                    [CssValue::Number(x1), CssValue::Number(y1), CssValue::Number(x2), CssValue::Number(y2)]
+                        if (0.0..=1.0).contains(x1) && (0.0..=1.0).contains(x2) =>
+                    {
+                        Some(TimingFunction::CubicBezier(*x1, *y1, *x2, *y2))
+                    }
+                    _ => None,
+                }
+            }
+            CssValue::Function(name, args) if name.eq_ignore_ascii_case("steps") => {
+                let steps = match args.first() {
+                    Some(CssValue::Number(n)) if *n >= 1.0 && n.fract() == 0.0 => *n as u32,
+                    _ => return None,
+                };
+                let position = match args.get(1) {
+                    None => StepPosition::End,
+                    Some(CssValue::Keyword(k)) => match k.to_ascii_lowercase().as_str() {
+                        "start" | "jump-start" => StepPosition::Start,
+                        "end" | "jump-end" => StepPosition::End,
+                        _ => return None,
+                    },
+                    Some(_) => return None,
+                };
+                Some(TimingFunction::Steps(steps, position))
+            }
+            _ => None,
+        }
+    }
+
     /// Evaluate the timing function at time t (0.0 to 1.0).
-    #[allow(dead_code)]
     pub fn evaluate(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
         match self {
             TimingFunction::Linear => t,
             TimingFunction::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
@@ -245,10 +414,11 @@ impl TimingFunction {
             TimingFunction::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, t),
             TimingFunction::Steps(steps, position) => {
                 let step = (t * *steps as f32).floor() as u32;
-                match position {
-                    StepPosition::Start => (step + 1) as f32 / *steps as f32,
-                    StepPosition::End => step as f32 / *steps as f32,
-                }
+                let step = match position {
+                    StepPosition::Start => step + 1,
+                    StepPosition::End => step,
+                };
+                (step.min(*steps)) as f32 / *steps as f32
             }
         }
     }
@@ -257,25 +427,53 @@ impl TimingFunction {
 
 /// Step position for steps() timing function.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
 pub enum StepPosition {
     Start,
     End,
 }
 
-/// Evaluate a cubic bezier curve at time t.
-#[allow(dead_code)]
-fn cubic_bezier(_x1: f32, y1: f32, _x2: f32, y2: f32, t: f32) -> f32 {
-    // Simple approximation using Newton-Raphson method
-    // For a proper implementation, would need to solve for t from x
-    let t2 = t * t;
-    let t3 = t2 * t;
-    let mt = 1.0 - t;
-    let mt2 = mt * mt;
-    let _mt3 = mt2 * mt;
-
-    // Simplified: assume x roughly equals t for typical easing curves
-    3.0 * mt2 * t * y1 + 3.0 * mt * t2 * y2 + t3
+/// Evaluate a cubic bezier easing curve at time x.
+///
+/// Solves the curve's x(s) = x for the parameter s, then returns y(s).
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |p1: f32, p2: f32, s: f32| {
+        let ms = 1.0 - s;
+        3.0 * ms * ms * s * p1 + 3.0 * ms * s * s * p2 + s * s * s
+    };
+    let slope = |p1: f32, p2: f32, s: f32| {
+        let ms = 1.0 - s;
+        3.0 * ms * ms * p1 + 6.0 * ms * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    // Newton-Raphson, falling back to bisection where the slope is flat
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < 1e-5 {
+            return bezier(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / d).clamp(0.0, 1.0);
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < 1e-5 {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
 }
 
 #[cfg(test)]
@@ -302,6 +500,29 @@ mod tests {
         assert_eq!(v.as_color(), Some(Color::BLUE));
     }
 
+    #[test]
+    fn test_calc_evaluate() {
+        // calc(100% - 2 * 1rem)
+        let expr = CalcExpr::Sub(
+            Box::new(CalcExpr::Value(Length::percent(100.0))),
+            Box::new(CalcExpr::Mul(
+                Box::new(CalcExpr::Number(2.0)),
+                Box::new(CalcExpr::Value(Length::rem(1.0))),
+            )),
+        );
+        assert_eq!(expr.evaluate(400.0, 16.0, 16.0), 368.0);
+
+        // clamp(100px, 50%, 300px)
+        let clamp = CalcExpr::Clamp(
+            Box::new(CalcExpr::Value(Length::px(100.0))),
+            Box::new(CalcExpr::Value(Length::percent(50.0))),
+            Box::new(CalcExpr::Value(Length::px(300.0))),
+        );
+        assert_eq!(clamp.evaluate(100.0, 16.0, 16.0), 100.0);
+        assert_eq!(clamp.evaluate(400.0, 16.0, 16.0), 200.0);
+        assert_eq!(clamp.evaluate(1000.0, 16.0, 16.0), 300.0);
+    }
+
     #[test]
     fn test_timing_function() {
         let linear = TimingFunction::Linear;
@@ -310,5 +531,18 @@ mod tests {
         let ease = TimingFunction::Ease;
         let mid = ease.evaluate(0.5);
         assert!(mid > 0.0 && mid < 1.0);
+
+        // ease-in-out is symmetric; ease at x=0.5 is ~0.8024
+        assert!((TimingFunction::EaseInOut.evaluate(0.5) - 0.5).abs() < 1e-3);
+        assert!((mid - 0.8024).abs() < 1e-3);
+
+        let steps = TimingFunction::from_value(&CssValue::Function(
+            "steps".into(),
+            vec![CssValue::Number(4.0), CssValue::Keyword("start".into())],
+        ))
+        .unwrap();
+        assert_eq!(steps.evaluate(0.0), 0.25);
+        assert_eq!(steps.evaluate(1.0), 1.0);
+        assert_eq!(TimingFunction::Steps(4, StepPosition::End).evaluate(0.6), 0.5);
     }
 }
@@ -0,0 +1,230 @@
+//! `var()` substitution and custom property resolution.
+
+use crate::css::{CalcExpr, CssParser, CssValue, StyleContext};
+use std::collections::{HashMap, HashSet};
+
+/// Substitutes `var()` references in values.
+///
+/// Variables are looked up in `declared` (unresolved values, e.g. the custom
+/// properties declared on the element being styled), then `computed`
+/// (already-resolved values, e.g. inherited custom properties), then the
+/// `StyleManager` and theme variables. Reference cycles make every variable
+/// in the cycle invalid, so `var()` falls back as if it were undefined.
+pub(crate) struct VarResolver<'a> {
+    declared: &'a HashMap<String, CssValue>,
+    computed: &'a HashMap<String, CssValue>,
+    ctx: &'a StyleContext<'a>,
+    /// Variables currently being resolved, for cycle detection
+    resolving: Vec<String>,
+    /// Variables found to be part of a reference cycle
+    cyclic: HashSet<String>,
+}
+
+impl<'a> VarResolver<'a> {
+    pub(crate) fn new(
+        declared: &'a HashMap<String, CssValue>,
+        computed: &'a HashMap<String, CssValue>,
+        ctx: &'a StyleContext<'a>,
+    ) -> Self {
+        Self {
+            declared,
+            computed,
+            ctx,
+            resolving: Vec::new(),
+            cyclic: HashSet::new(),
+        }
+    }
+
+    /// Resolve a variable to a value free of `var()` references.
+    pub(crate) fn lookup(&mut self, name: &str) -> Option<CssValue> {
+        if let Some(start) = self.resolving.iter().position(|n| n == name) {
+            self.cyclic.extend(self.resolving[start..].iter().cloned());
+            return None;
+        }
+        if self.cyclic.contains(name) {
+            return None;
+        }
+
+        let raw = if let Some(value) = self.declared.get(name) {
+            value.clone()
+        } else if let Some(value) = self.computed.get(name) {
+            return Some(value.clone());
+        } else {
+            CssParser::parse_value_str(&self.ctx.resolve_var(name)?).ok()?
+        };
+
+        self.resolving.push(name.to_string());
+        let resolved = self.substitute(&raw);
+        self.resolving.pop();
+
+        if self.cyclic.contains(name) {
+            None
+        } else {
+            resolved
+        }
+    }
+
+    /// Substitute all `var()` references in a value.
+    ///
+    /// Returns `None` if a referenced variable is undefined (or cyclic) and
+    /// has no usable fallback.
+    pub(crate) fn substitute(&mut self, value: &CssValue) -> Option<CssValue> {
+        match value {
+            CssValue::Var(name, fallback) => match self.lookup(name) {
+                Some(value) => Some(value),
+                None => self.substitute(fallback.as_deref()?),
+            },
+            CssValue::List(values) => {
+                let mut out = Vec::with_capacity(values.len());
+                for value in values {
+                    // A variable holding several components splices into the list
+                    match (value, self.substitute(value)?) {
+                        (CssValue::Var(..), CssValue::List(inner)) => out.extend(inner),
+                        (_, value) => out.push(value),
+                    }
+                }
+                Some(CssValue::List(out))
+            }
+            CssValue::CommaList(values) => {
+                let mut out = Vec::with_capacity(values.len());
+                for value in values {
+                    match (value, self.substitute(value)?) {
+                        (CssValue::Var(..), CssValue::CommaList(inner)) => out.extend(inner),
+                        (_, value) => out.push(value),
+                    }
+                }
+                Some(CssValue::CommaList(out))
+            }
+            CssValue::Function(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.substitute(arg))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(CssValue::Function(name.clone(), args))
+            }
+            CssValue::Calc(expr) => self.substitute_calc(expr).map(CssValue::Calc),
+            value => Some(value.clone()),
+        }
+    }
+
+    fn substitute_calc(&mut self, expr: &CalcExpr) -> Option<CalcExpr> {
+        Some(match expr {
+            CalcExpr::Var(name, fallback) => {
+                let value = self.lookup(name);
+                match value.as_ref().and_then(CalcExpr::from_value) {
+                    Some(expr) => expr,
+                    None => self.substitute_calc(fallback.as_deref()?)?,
+                }
+            }
+            CalcExpr::Add(a, b) => CalcExpr::Add(self.substitute_boxed(a)?, self.substitute_boxed(b)?),
+            CalcExpr::Sub(a, b) => CalcExpr::Sub(self.substitute_boxed(a)?, self.substitute_boxed(b)?),
+            CalcExpr::Mul(a, b) => CalcExpr::Mul(self.substitute_boxed(a)?, self.substitute_boxed(b)?),
+            CalcExpr::Div(a, b) => CalcExpr::Div(self.substitute_boxed(a)?, self.substitute_boxed(b)?),
+            CalcExpr::Clamp(min, value, max) => CalcExpr::Clamp(
+                self.substitute_boxed(min)?,
+                self.substitute_boxed(value)?,
+                self.substitute_boxed(max)?,
+            ),
+            CalcExpr::Min(exprs) => CalcExpr::Min(
+                exprs
+                    .iter()
+                    .map(|e| self.substitute_calc(e))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            CalcExpr::Max(exprs) => CalcExpr::Max(
+                exprs
+                    .iter()
+                    .map(|e| self.substitute_calc(e))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            CalcExpr::Value(_) | CalcExpr::Number(_) => expr.clone(),
+        })
+    }
+
+    fn substitute_boxed(&mut self, expr: &CalcExpr) -> Option<Box<CalcExpr>> {
+        self.substitute_calc(expr).map(Box::new)
+    }
+}
+
+/// Compute an element's custom properties from the values it declares and
+/// those inherited from its parent.
+///
+/// Declared values are resolved against each other and the inherited set;
+/// any that are invalid (undefined references or cycles) are dropped.
+pub(crate) fn compute_custom_properties(
+    declared: &HashMap<String, CssValue>,
+    inherited: &HashMap<String, CssValue>,
+    ctx: &StyleContext,
+) -> HashMap<String, CssValue> {
+    let mut resolver = VarResolver::new(declared, inherited, ctx);
+    let mut computed = inherited.clone();
+
+    for name in declared.keys() {
+        match resolver.lookup(name) {
+            Some(value) => {
+                computed.insert(name.clone(), value);
+            }
+            None => {
+                computed.remove(name);
+            }
+        }
+    }
+
+    computed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::Length;
+    use crate::theme::ThemeData;
+
+    fn var(name: &str, fallback: Option<CssValue>) -> CssValue {
+        CssValue::Var(name.to_string(), fallback.map(Box::new))
+    }
+
+    #[test]
+    fn test_nested_and_fallback() {
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let declared = HashMap::from([
+            ("--gap".to_string(), CssValue::Length(Length::px(4.0))),
+            ("--pad".to_string(), CssValue::List(vec![var("--gap", None), var("--gap", None)])),
+        ]);
+        let computed = compute_custom_properties(&declared, &HashMap::new(), &ctx);
+
+        let px = CssValue::Length(Length::px(4.0));
+        assert_eq!(computed["--pad"], CssValue::List(vec![px.clone(), px.clone()]));
+
+        let empty = HashMap::new();
+        let mut resolver = VarResolver::new(&empty, &computed, &ctx);
+        let value = CssValue::List(vec![var("--pad", None), CssValue::Number(0.0)]);
+        assert_eq!(
+            resolver.substitute(&value),
+            Some(CssValue::List(vec![px.clone(), px.clone(), CssValue::Number(0.0)]))
+        );
+        assert_eq!(
+            resolver.substitute(&var("--missing", Some(CssValue::Number(1.0)))),
+            Some(CssValue::Number(1.0))
+        );
+        assert_eq!(resolver.substitute(&var("--missing", None)), None);
+    }
+
+    #[test]
+    fn test_cycles_are_invalid() {
+        let theme = ThemeData::light();
+        let ctx = StyleContext::new(&theme);
+        let declared = HashMap::from([
+            ("--a".to_string(), var("--b", None)),
+            ("--b".to_string(), var("--a", None)),
+            ("--c".to_string(), var("--c", Some(CssValue::Number(2.0)))),
+        ]);
+        let inherited = HashMap::from([("--a".to_string(), CssValue::Number(1.0))]);
+        let computed = compute_custom_properties(&declared, &inherited, &ctx);
+
+        assert!(!computed.contains_key("--a"));
+        assert!(!computed.contains_key("--b"));
+        // A self-reference is a cycle even when a fallback is given
+        assert!(!computed.contains_key("--c"));
+    }
+}
@@ -0,0 +1,608 @@
+//! Pointer event routing.
+//!
+//! Painting records where each widget ended up on screen in a [`HitTree`]
+//! (see [`PaintContext::paint_child`](crate::widget::PaintContext::paint_child)).
+//! The [`EventDispatcher`] hit-tests that tree to find the widget under the
+//! pointer, the topmost one in paint order, and routes pointer events to it:
+//!
+//! 1. **Capture**: every ancestor, root first, gets
+//!    [`Widget::capture_event`] and may stop the event.
+//! 2. **Target**: the widget under the pointer gets [`Widget::handle_event`].
+//! 3. **Bubble**: unless handled, every ancestor, parent first, gets
+//!    [`Widget::handle_event`].
+//!
+//! [`EventContext::phase`] and [`EventContext::target`] tell a widget which
+//! trip it is on. While a widget handles an event its bounds are its
+//! on-screen rectangle, so `self.bounds().contains(mouse.position)` works
+//! at any depth.
+//!
+//! A widget can take all pointer events during a drag with
+//! [`EventContext::capture_pointer`]; capture ends when the button is
+//! released. `MouseEventKind::Enter` and `Leave` are sent to each widget as
+//! the pointer enters or leaves it, outermost first for `Enter` and
+//! innermost first for `Leave`.
+//!
+//! Widgets that draw outside their bounds (open dropdown menus, popups)
+//! register the extra area with
+//! [`PaintContext::add_overlay`](crate::widget::PaintContext::add_overlay);
+//! overlays are hit before any regular widget.
+//!
+//! Other events (keys, focus, timers) are broadcast through the tree by
+//! the widgets themselves, as before.
+
+use crate::event::{Event, EventPhase, EventResult, MouseEvent, MouseEventKind, WidgetId};
+use crate::geometry::{Point, Rect};
+use crate::widget::{EventContext, Widget};
+
+/// Where a widget was painted.
+#[derive(Debug, Clone, Copy)]
+struct HitRegion {
+    widget_id: WidgetId,
+    parent: Option<WidgetId>,
+    rect: Rect,
+    clip: Option<Rect>,
+    overlay: bool,
+}
+
+impl HitRegion {
+    fn contains(&self, point: Point) -> bool {
+        self.rect.contains(point) && self.clip.is_none_or(|clip| clip.contains(point))
+    }
+}
+
+/// On-screen rectangles of the widgets painted in a frame, in paint order.
+#[derive(Debug, Clone, Default)]
+pub struct HitTree {
+    regions: Vec<HitRegion>,
+    /// Widgets being painted, innermost last
+    stack: Vec<WidgetId>,
+}
+
+impl HitTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `widget_id` painted at `rect` inside the widget being painted.
+    ///
+    /// `rect` and `clip` are in device coordinates. Pair with [`HitTree::pop`]
+    /// once the widget and its children are painted.
+    pub fn push(&mut self, widget_id: WidgetId, rect: Rect, clip: Option<Rect>) {
+        self.regions.push(HitRegion {
+            widget_id,
+            parent: self.stack.last().copied(),
+            rect,
+            clip,
+            overlay: false,
+        });
+        self.stack.push(widget_id);
+    }
+
+    /// Finish the widget recorded by the last [`HitTree::push`].
+    pub fn pop(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Make `rect` hit the widget being painted, above all regular widgets.
+    pub fn push_overlay(&mut self, rect: Rect, clip: Option<Rect>) {
+        let Some(&widget_id) = self.stack.last() else {
+            return;
+        };
+        let parent = self.region(widget_id).and_then(|region| region.parent);
+        self.regions.push(HitRegion {
+            widget_id,
+            parent,
+            rect,
+            clip,
+            overlay: true,
+        });
+    }
+
+    /// Whether nothing was painted.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    fn region(&self, widget_id: WidgetId) -> Option<&HitRegion> {
+        self.regions
+            .iter()
+            .find(|region| region.widget_id == widget_id && !region.overlay)
+    }
+
+    /// Where a widget was painted.
+    pub fn rect(&self, widget_id: WidgetId) -> Option<Rect> {
+        self.region(widget_id).map(|region| region.rect)
+    }
+
+    /// The topmost widget at `point`.
+    pub fn hit_test(&self, point: Point) -> Option<WidgetId> {
+        let hit = |overlay: bool| {
+            self.regions
+                .iter()
+                .rev()
+                .filter(|region| region.overlay == overlay)
+                .find(|region| region.contains(point))
+        };
+        hit(true).or_else(|| hit(false)).map(|region| region.widget_id)
+    }
+
+    /// Widget ids from the root down to `widget_id`.
+    pub fn path(&self, widget_id: WidgetId) -> Vec<WidgetId> {
+        let mut path = Vec::new();
+        let mut next = Some(widget_id);
+        while let Some(id) = next {
+            let Some(region) = self.region(id) else {
+                break;
+            };
+            path.push(id);
+            next = region.parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Find the widget at the end of `path`, which starts at `root`.
+fn widget_at<'w>(root: &'w mut dyn Widget, path: &[WidgetId]) -> Option<&'w mut dyn Widget> {
+    if path.first() != Some(&root.id()) {
+        return None;
+    }
+    let mut widget = root;
+    for id in &path[1..] {
+        widget = widget
+            .children_mut()
+            .iter_mut()
+            .find(|child| child.id() == *id)?
+            .as_mut();
+    }
+    Some(widget)
+}
+
+/// How much of `path` can be reached through `Widget::children`.
+///
+/// Widgets that paint private sub-widgets route events to them themselves,
+/// so a path through them ends at the owning widget.
+fn reachable(root: &dyn Widget, path: &[WidgetId]) -> usize {
+    if path.first() != Some(&root.id()) {
+        return 0;
+    }
+    let mut widget = root;
+    let mut len = 1;
+    for id in &path[1..] {
+        match widget.children().iter().find(|child| child.id() == *id) {
+            Some(child) => widget = child.as_ref(),
+            None => break,
+        }
+        len += 1;
+    }
+    len
+}
+
+/// Routes pointer events to the widgets under the pointer.
+#[derive(Debug, Default)]
+pub struct EventDispatcher {
+    hit_tree: HitTree,
+    /// Widgets under the pointer, root first
+    hovered: Vec<WidgetId>,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the hit tree with the one recorded by the latest paint.
+    pub fn set_hit_tree(&mut self, hit_tree: HitTree) {
+        self.hit_tree = hit_tree;
+    }
+
+    /// The hit tree used for routing.
+    pub fn hit_tree(&self) -> &HitTree {
+        &self.hit_tree
+    }
+
+    /// Widgets under the pointer, root first.
+    pub fn hovered(&self) -> &[WidgetId] {
+        &self.hovered
+    }
+
+    /// Deliver `event` to the widget tree.
+    ///
+    /// Pointer events are routed through the capture, target and bubble
+    /// phases. Before anything is painted, and for all other events, the
+    /// root handles the event directly.
+    pub fn dispatch(&mut self, root: &mut dyn Widget, event: &Event, ctx: &mut EventContext) -> EventResult {
+        ctx.phase = EventPhase::Target;
+        ctx.target = None;
+        let Event::Mouse(mouse) = event else {
+            return root.handle_event(event, ctx);
+        };
+        if self.hit_tree.is_empty() {
+            return root.handle_event(event, ctx);
+        }
+
+        // A captured pointer goes to the capturing widget while it's painted
+        let captured = ctx
+            .pointer_capture
+            .filter(|id| self.hit_tree.rect(*id).is_some());
+        ctx.pointer_capture = captured;
+
+        let mut path = match captured {
+            Some(id) => self.hit_tree.path(id),
+            None => self
+                .hit_tree
+                .hit_test(mouse.position)
+                .map(|id| self.hit_tree.path(id))
+                .unwrap_or_default(),
+        };
+        path.truncate(reachable(root, &path));
+
+        let mut hovered = path.clone();
+        match (mouse.kind, captured) {
+            (MouseEventKind::Leave, _) => hovered.clear(),
+            // A capturing widget is only hovered while the pointer is over it
+            (_, Some(id)) if !self.hit_tree.rect(id).is_some_and(|rect| rect.contains(mouse.position)) => {
+                hovered.pop();
+            }
+            _ => {}
+        }
+        self.update_hover(root, hovered, mouse, ctx);
+
+        let result = match mouse.kind {
+            // Sent per widget by `update_hover`
+            MouseEventKind::Enter | MouseEventKind::Leave => EventResult::Ignored,
+            _ if path.is_empty() => root.handle_event(event, ctx),
+            _ => self.route(root, &path, event, ctx),
+        };
+
+        if mouse.kind == MouseEventKind::Up {
+            ctx.pointer_capture = None;
+        }
+        ctx.phase = EventPhase::Target;
+        ctx.target = None;
+        result
+    }
+
+    /// Run the capture, target and bubble phases along `path`.
+    fn route(&self, root: &mut dyn Widget, path: &[WidgetId], event: &Event, ctx: &mut EventContext) -> EventResult {
+        ctx.target = path.last().copied();
+
+        ctx.phase = EventPhase::Capture;
+        for depth in 1..path.len() {
+            let result = self.deliver(root, &path[..depth], ctx, |widget, ctx| widget.capture_event(event, ctx));
+            if result.is_handled() {
+                return result;
+            }
+        }
+
+        ctx.phase = EventPhase::Target;
+        let result = self.deliver(root, path, ctx, |widget, ctx| widget.handle_event(event, ctx));
+        if result.is_handled() {
+            return result;
+        }
+
+        ctx.phase = EventPhase::Bubble;
+        for depth in (1..path.len()).rev() {
+            let result = self.deliver(root, &path[..depth], ctx, |widget, ctx| widget.handle_event(event, ctx));
+            if result.is_handled() {
+                return result;
+            }
+        }
+        EventResult::Ignored
+    }
+
+    /// Send `Leave` to widgets no longer hovered and `Enter` to new ones.
+    fn update_hover(&mut self, root: &mut dyn Widget, hovered: Vec<WidgetId>, mouse: &MouseEvent, ctx: &mut EventContext) {
+        let common = self
+            .hovered
+            .iter()
+            .zip(&hovered)
+            .take_while(|(old, new)| old == new)
+            .count();
+
+        let crossing = |kind: MouseEventKind| Event::Mouse(MouseEvent { kind, ..mouse.clone() });
+        let leave = crossing(MouseEventKind::Leave);
+        let enter = crossing(MouseEventKind::Enter);
+        ctx.phase = EventPhase::Target;
+        for depth in (common..self.hovered.len()).rev() {
+            let path = &self.hovered[..=depth];
+            ctx.target = path.last().copied();
+            self.deliver(root, path, ctx, |widget, ctx| widget.handle_event(&leave, ctx));
+        }
+        for depth in common..hovered.len() {
+            let path = &hovered[..=depth];
+            ctx.target = path.last().copied();
+            self.deliver(root, path, ctx, |widget, ctx| widget.handle_event(&enter, ctx));
+        }
+        ctx.target = None;
+        self.hovered = hovered;
+    }
+
+    /// Call `handle` on the widget at the end of `path` with its on-screen bounds.
+    fn deliver(
+        &self,
+        root: &mut dyn Widget,
+        path: &[WidgetId],
+        ctx: &mut EventContext,
+        handle: impl FnOnce(&mut dyn Widget, &mut EventContext) -> EventResult,
+    ) -> EventResult {
+        let Some(widget) = widget_at(root, path) else {
+            return EventResult::Ignored;
+        };
+        let bounds = widget.bounds();
+        if let Some(rect) = self.hit_tree.rect(widget.id()) {
+            widget.set_bounds(rect);
+        }
+        let result = handle(widget, ctx);
+        widget.set_bounds(bounds);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{ClassList, WidgetState};
+    use crate::event::{MouseButton, MouseEvent};
+    use crate::geometry::Size;
+    use crate::layout::{Constraints, LayoutResult};
+    use crate::render::Painter;
+    use crate::widget::{LayoutContext, PaintContext, WidgetBase};
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Log = Rc<RefCell<Vec<(WidgetId, EventPhase, MouseEventKind)>>>;
+
+    /// A widget that logs the pointer events it receives.
+    struct Probe {
+        base: WidgetBase,
+        children: Vec<Box<dyn Widget>>,
+        log: Log,
+        handles: bool,
+        captures: bool,
+    }
+
+    impl Probe {
+        fn new(log: &Log) -> Self {
+            Self {
+                base: WidgetBase::new(),
+                children: Vec::new(),
+                log: log.clone(),
+                handles: false,
+                captures: false,
+            }
+        }
+
+        fn child(mut self, child: Probe) -> Self {
+            self.children.push(Box::new(child));
+            self
+        }
+
+        fn record(&self, event: &Event, ctx: &EventContext) {
+            if let Event::Mouse(mouse) = event {
+                self.log.borrow_mut().push((self.base.id, ctx.phase, mouse.kind));
+            }
+        }
+    }
+
+    impl Widget for Probe {
+        fn id(&self) -> WidgetId {
+            self.base.id
+        }
+
+        fn type_name(&self) -> &'static str {
+            "probe"
+        }
+
+        fn classes(&self) -> &ClassList {
+            &self.base.classes
+        }
+
+        fn state(&self) -> WidgetState {
+            self.base.state
+        }
+
+        fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
+            Size::ZERO
+        }
+
+        fn layout(&mut self, _constraints: Constraints, _ctx: &LayoutContext) -> LayoutResult {
+            LayoutResult::new(Size::ZERO)
+        }
+
+        fn paint(&self, _painter: &mut Painter, _rect: Rect, _ctx: &PaintContext) {}
+
+        fn capture_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+            self.record(event, ctx);
+            if self.captures {
+                return EventResult::Handled;
+            }
+            EventResult::Ignored
+        }
+
+        fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+            self.record(event, ctx);
+            let Event::Mouse(mouse) = event else {
+                return EventResult::Ignored;
+            };
+            // Bounds are on-screen while handling
+            assert!(
+                mouse.kind == MouseEventKind::Leave
+                    || self.bounds().contains(mouse.position)
+                    || ctx.pointer_capture.is_some()
+            );
+            match mouse.kind {
+                MouseEventKind::Down if self.handles => {
+                    ctx.capture_pointer(self.base.id);
+                    EventResult::Handled
+                }
+                MouseEventKind::Up if self.handles => EventResult::Handled,
+                _ => EventResult::Ignored,
+            }
+        }
+
+        fn bounds(&self) -> Rect {
+            self.base.bounds
+        }
+
+        fn set_bounds(&mut self, bounds: Rect) {
+            self.base.bounds = bounds;
+        }
+
+        fn children(&self) -> &[Box<dyn Widget>] {
+            &self.children
+        }
+
+        fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
+            &mut self.children
+        }
+    }
+
+    fn mouse(kind: MouseEventKind, x: f32, y: f32) -> Event {
+        Event::Mouse(MouseEvent::new(kind, Point::new(x, y)).with_button(MouseButton::Left))
+    }
+
+    /// root (0..100) > [panel (0..50) > button (10..30), sibling (40..100)]
+    fn tree(log: &Log) -> (Probe, HitTree, [WidgetId; 4]) {
+        let mut button = Probe::new(log);
+        button.handles = true;
+        let panel = Probe::new(log).child(button);
+        let root = Probe::new(log).child(panel).child(Probe::new(log));
+        let ids = [
+            root.id(),
+            root.children[0].id(),
+            root.children[0].children()[0].id(),
+            root.children[1].id(),
+        ];
+
+        let mut hits = HitTree::new();
+        hits.push(ids[0], Rect::new(0.0, 0.0, 100.0, 100.0), None);
+        hits.push(ids[1], Rect::new(0.0, 0.0, 50.0, 100.0), None);
+        hits.push(ids[2], Rect::new(10.0, 10.0, 20.0, 20.0), None);
+        hits.pop();
+        hits.pop();
+        // Overlaps the panel and is painted later, so it's on top
+        hits.push(ids[3], Rect::new(40.0, 0.0, 60.0, 100.0), None);
+        hits.pop();
+        hits.pop();
+        (root, hits, ids)
+    }
+
+    #[test]
+    fn test_hit_test() {
+        let log = Log::default();
+        let (_, mut hits, ids) = tree(&log);
+        assert_eq!(hits.hit_test(Point::new(15.0, 15.0)), Some(ids[2]));
+        assert_eq!(hits.hit_test(Point::new(45.0, 50.0)), Some(ids[3]));
+        assert_eq!(hits.hit_test(Point::new(200.0, 0.0)), None);
+        assert_eq!(hits.path(ids[2]), vec![ids[0], ids[1], ids[2]]);
+
+        // The button's popup extends over its sibling
+        hits.push(ids[2], Rect::new(10.0, 10.0, 20.0, 20.0), None);
+        hits.push_overlay(Rect::new(10.0, 30.0, 60.0, 40.0), None);
+        hits.pop();
+        assert_eq!(hits.hit_test(Point::new(60.0, 50.0)), Some(ids[2]));
+        assert_eq!(hits.path(ids[2]), vec![ids[0], ids[1], ids[2]]);
+    }
+
+    #[test]
+    fn test_capture_and_bubble() {
+        use EventPhase::*;
+        use MouseEventKind::*;
+
+        let log = Log::default();
+        let (mut root, hits, ids) = tree(&log);
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.set_hit_tree(hits);
+        let mut ctx = EventContext::new();
+
+        // Entering the button enters its ancestors first
+        dispatcher.dispatch(&mut root, &mouse(Move, 15.0, 15.0), &mut ctx);
+        assert_eq!(
+            log.take(),
+            vec![
+                (ids[0], Target, Enter),
+                (ids[1], Target, Enter),
+                (ids[2], Target, Enter),
+                (ids[0], Capture, Move),
+                (ids[1], Capture, Move),
+                (ids[2], Target, Move),
+                (ids[1], Bubble, Move),
+                (ids[0], Bubble, Move),
+            ]
+        );
+
+        // The button handles the press and captures the pointer
+        let result = dispatcher.dispatch(&mut root, &mouse(Down, 15.0, 15.0), &mut ctx);
+        assert!(result.is_handled());
+        assert_eq!(ctx.pointer_capture, Some(ids[2]));
+        log.take();
+
+        // Dragging over the sibling still goes to the button, which is left
+        dispatcher.dispatch(&mut root, &mouse(Move, 60.0, 15.0), &mut ctx);
+        assert_eq!(
+            log.take(),
+            vec![
+                (ids[2], Target, Leave),
+                (ids[0], Capture, Move),
+                (ids[1], Capture, Move),
+                (ids[2], Target, Move),
+                (ids[1], Bubble, Move),
+                (ids[0], Bubble, Move),
+            ]
+        );
+
+        // Releasing ends the capture; hover moves to the sibling
+        dispatcher.dispatch(&mut root, &mouse(Up, 60.0, 15.0), &mut ctx);
+        assert_eq!(ctx.pointer_capture, None);
+        log.take();
+        dispatcher.dispatch(&mut root, &mouse(Move, 61.0, 15.0), &mut ctx);
+        assert_eq!(log.take()[..2], [(ids[1], Target, Leave), (ids[3], Target, Enter)]);
+        assert_eq!(dispatcher.hovered(), &[ids[0], ids[3]]);
+
+        // A capturing ancestor stops the event before the target
+        root.captures = true;
+        dispatcher.dispatch(&mut root, &mouse(Down, 15.0, 15.0), &mut ctx);
+        let log = log.take();
+        assert_eq!(log.last(), Some(&(ids[0], Capture, Down)));
+    }
+
+    #[test]
+    fn test_click_reaches_split_pane_children() {
+        use crate::theme::ThemeData;
+        use crate::widget::button::Button;
+        use crate::widget::container::Column;
+        use crate::widget::split_pane::SplitPane;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let button = Button::new("OK").on_click(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut root = SplitPane::horizontal()
+            .first(Column::new().child(button))
+            .second(Column::new());
+
+        // Lay out and paint to record where the panes ended up
+        let theme = ThemeData::light();
+        let style_ctx = crate::css::StyleContext::new(&theme);
+        let size = Size::new(200.0, 100.0);
+        let rect = Rect::from_origin_size(Point::ZERO, size);
+        root.layout(Constraints::tight(size), &LayoutContext::new(&style_ctx));
+        root.set_bounds(rect);
+        let paint_ctx = PaintContext::new(&style_ctx);
+        paint_ctx.paint_child(&root, &mut Painter::new(), rect);
+
+        let target = root.children()[0].children()[0].bounds().center();
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.set_hit_tree(paint_ctx.into_hit_tree());
+        let mut ctx = EventContext::new();
+        dispatcher.dispatch(&mut root, &mouse(MouseEventKind::Move, target.x, target.y), &mut ctx);
+        dispatcher.dispatch(&mut root, &mouse(MouseEventKind::Down, target.x, target.y), &mut ctx);
+        dispatcher.dispatch(&mut root, &mouse(MouseEventKind::Up, target.x, target.y), &mut ctx);
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+    }
+}
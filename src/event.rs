@@ -2,9 +2,17 @@
 
 use crate::geometry::Point;
 
+use std::time::Duration;
+
 /// A unique identifier for a widget.
 pub type WidgetId = u64;
 
+/// A unique identifier for a scheduled timer.
+pub type TimerId = u64;
+
+/// A unique identifier for an application window.
+pub type WindowId = u64;
+
 /// Top-level event types.
 #[derive(Debug, Clone)]
 pub enum Event {
@@ -16,6 +24,10 @@ pub enum Event {
     Key(KeyEvent),
     /// Focus events
     Focus(FocusEvent),
+    /// Timer and animation frame events
+    Timer(TimerEvent),
+    /// Input method events
+    Ime(ImeEvent),
 }
 
 /// Window-related events.
@@ -215,6 +227,53 @@ pub enum FocusEvent {
     FocusOut { widget_id: WidgetId },
 }
 
+/// Input method events, for composed text such as CJK input and dead keys.
+///
+/// Like key events, these go to the focused widget. The input method is
+/// only enabled while a focused widget reports its caret with
+/// `PaintContext::set_ime_cursor_area`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The input method was enabled
+    Enabled,
+    /// The text being composed changed; an empty `text` ends composition.
+    /// `cursor` is the byte range of the cursor within `text`, if shown
+    Preedit { text: String, cursor: Option<(usize, usize)> },
+    /// Composed text to insert at the cursor
+    Commit(String),
+    /// The input method was disabled
+    Disabled,
+}
+
+/// Timer and animation frame events.
+///
+/// Frames are broadcast to the whole tree, so animating widgets should not
+/// mark them handled. Timers carry the id of the widget that scheduled them.
+#[derive(Debug, Clone)]
+pub enum TimerEvent {
+    /// An animation frame; `elapsed` is the time since the previous frame,
+    /// or zero for the first frame of an animation
+    Frame { elapsed: Duration },
+    /// A timeout or interval expired
+    Fired { id: TimerId, widget_id: WidgetId },
+}
+
+/// Phase of a pointer event's trip through the widget tree.
+///
+/// Pointer events travel from the root down to the target widget under the
+/// pointer (capture), are handled by the target, then travel back up to the
+/// root (bubble) until a widget handles them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventPhase {
+    /// Delivered to an ancestor of the target, root first
+    Capture,
+    /// Delivered to the target, or broadcast to the whole tree
+    #[default]
+    Target,
+    /// Delivered to an ancestor of the target, parent first
+    Bubble,
+}
+
 /// Result of event handling.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventResult {
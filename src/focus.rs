@@ -0,0 +1,460 @@
+//! Keyboard focus management.
+//!
+//! `App` owns a [`FocusManager`] that decides which widget has keyboard
+//! focus. Widgets never flip their own `focused` state: they ask for focus
+//! with [`EventContext::request_focus`](crate::widget::EventContext::request_focus)
+//! and update their state when the manager delivers
+//! [`FocusEvent::FocusIn`] and [`FocusEvent::FocusOut`] (see
+//! [`WidgetBase::handle_focus_event`](crate::widget::WidgetBase::handle_focus_event)).
+//!
+//! Tab and Shift+Tab move focus through the widgets whose
+//! [`Widget::tab_index`] is set, in tree order. As with HTML `tabindex`,
+//! positive indices come first in ascending order, zero follows in tree
+//! order, and negative indices are focusable by pointer but skipped by Tab.
+//!
+//! A widget returning [`FocusScope::Scope`] from [`Widget::focus_scope`]
+//! keeps its descendants together in the tab order, ordered among
+//! themselves. [`FocusScope::Trap`] additionally confines focus to the
+//! widget while it is in the tree, which modal dialogs use: the last trap
+//! in tree order is active, focus moves into it when it appears and
+//! returns to the previously focused widget when it goes away.
+//!
+//! Focus moved with the keyboard is visible (`:focus-visible` matches and
+//! focus rings are painted); focus moved with the pointer is not.
+
+use crate::event::{Event, EventResult, FocusEvent, Key, KeyEventKind, MouseEventKind, WidgetId};
+use crate::widget::{EventContext, Widget};
+
+/// How a widget groups the focus of its descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusScope {
+    /// Descendants take part in the enclosing tab order
+    #[default]
+    None,
+    /// Descendants are kept together in the tab order
+    Scope,
+    /// Descendants are kept together and focus can't leave them
+    Trap,
+}
+
+/// A focus change requested by a widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusRequest {
+    /// Focus a widget
+    Focus(WidgetId),
+    /// Clear focus
+    Release,
+    /// Move to the next widget in tab order
+    Next,
+    /// Move to the previous widget in tab order
+    Previous,
+}
+
+/// Focusable widgets of a tree, honouring the active focus trap.
+#[derive(Debug, Default)]
+struct FocusTree {
+    trap: Option<WidgetId>,
+    /// Widgets that can be focused, in tree order
+    focusable: Vec<WidgetId>,
+    /// Widgets reachable with Tab, in tab order
+    order: Vec<WidgetId>,
+}
+
+impl FocusTree {
+    fn scan(root: &dyn Widget) -> Self {
+        let trap = find_trap(root);
+        let scope = trap.unwrap_or(root);
+        let mut focusable = Vec::new();
+        let order = scope_order(scope, &mut focusable);
+        Self {
+            trap: trap.map(|widget| widget.id()),
+            focusable,
+            order,
+        }
+    }
+
+    fn contains(&self, id: WidgetId) -> bool {
+        self.focusable.contains(&id)
+    }
+}
+
+/// The last focus trap in tree order.
+fn find_trap(widget: &dyn Widget) -> Option<&dyn Widget> {
+    let nested = widget.children().iter().rev().find_map(|child| find_trap(child.as_ref()));
+    nested.or_else(|| (widget.focus_scope() == FocusScope::Trap).then_some(widget))
+}
+
+/// Tab order of a scope's widgets, collecting all focusable widgets.
+fn scope_order(scope: &dyn Widget, focusable: &mut Vec<WidgetId>) -> Vec<WidgetId> {
+    // (tab index, widgets) in tree order; scopes contribute several widgets
+    let mut entries: Vec<(i32, Vec<WidgetId>)> = Vec::new();
+    if let Some(index) = scope.tab_index() {
+        focusable.push(scope.id());
+        if index >= 0 {
+            entries.push((index, vec![scope.id()]));
+        }
+    }
+    for child in scope.children() {
+        collect_entries(child.as_ref(), focusable, &mut entries);
+    }
+
+    // Positive indices first, ascending; then zero in tree order
+    entries.sort_by_key(|(index, _)| if *index > 0 { (0, *index) } else { (1, 0) });
+    entries.into_iter().flat_map(|(_, ids)| ids).collect()
+}
+
+fn collect_entries(widget: &dyn Widget, focusable: &mut Vec<WidgetId>, entries: &mut Vec<(i32, Vec<WidgetId>)>) {
+    if widget.focus_scope() != FocusScope::None {
+        let order = scope_order(widget, focusable);
+        if !order.is_empty() {
+            entries.push((widget.tab_index().unwrap_or(0).max(0), order));
+        }
+        return;
+    }
+
+    if let Some(index) = widget.tab_index() {
+        focusable.push(widget.id());
+        if index >= 0 {
+            entries.push((index, vec![widget.id()]));
+        }
+    }
+    for child in widget.children() {
+        collect_entries(child.as_ref(), focusable, entries);
+    }
+}
+
+/// Tracks the focused widget and whether its focus should be visible.
+#[derive(Debug, Default)]
+pub struct FocusManager {
+    focused: Option<WidgetId>,
+    focus_visible: bool,
+    /// The active trap, if any
+    trap: Option<WidgetId>,
+    /// Where focus was before the active trap took it
+    return_focus: Option<WidgetId>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The focused widget.
+    pub fn focused(&self) -> Option<WidgetId> {
+        self.focused
+    }
+
+    /// Whether focus was last moved or used with the keyboard.
+    pub fn focus_visible(&self) -> bool {
+        self.focused.is_some() && self.focus_visible
+    }
+
+    /// Update focus after `event` was dispatched to `root`.
+    ///
+    /// Applies the focus request left in `ctx`, moves focus on an unhandled
+    /// Tab or Shift+Tab, clears it on an unhandled click and keeps it valid
+    /// as the tree changes. Returns the focus events to dispatch, and
+    /// mirrors the focused widget into `ctx.focus`.
+    pub fn update(
+        &mut self,
+        root: &dyn Widget,
+        event: &Event,
+        result: EventResult,
+        ctx: &mut EventContext,
+    ) -> Vec<FocusEvent> {
+        let mut request = ctx.focus_request.take();
+        match event {
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down => {
+                self.focus_visible = false;
+                if request.is_none() && !result.is_handled() {
+                    request = Some(FocusRequest::Release);
+                }
+            }
+            // Shortcuts don't make focus visible
+            Event::Key(key)
+                if key.kind == KeyEventKind::Down
+                    && !key.modifiers.control
+                    && !key.modifiers.alt
+                    && !key.modifiers.super_key =>
+            {
+                self.focus_visible = true;
+                if key.key == Key::Tab && request.is_none() && !result.is_handled() {
+                    request = Some(if key.modifiers.shift {
+                        FocusRequest::Previous
+                    } else {
+                        FocusRequest::Next
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        let tree = FocusTree::scan(root);
+        let mut events = Vec::new();
+        self.sync_trap(&tree, &mut events);
+        if let Some(request) = request {
+            self.apply(&tree, request, &mut events);
+        }
+
+        // The focused widget left the tree or stopped being focusable
+        if let Some(focused) = self.focused {
+            if !tree.contains(focused) {
+                self.set_focus(tree.order.first().copied().filter(|_| tree.trap.is_some()), &mut events);
+            }
+        }
+
+        ctx.focus = self.focused;
+        events
+    }
+
+    /// Move focus into a newly active trap, or back out of a closed one.
+    fn sync_trap(&mut self, tree: &FocusTree, events: &mut Vec<FocusEvent>) {
+        if tree.trap == self.trap {
+            return;
+        }
+        self.trap = tree.trap;
+
+        if tree.trap.is_some() {
+            if self.focused.is_none_or(|id| !tree.contains(id)) {
+                // Only remember focus from outside any trap
+                if self.return_focus.is_none() {
+                    self.return_focus = self.focused;
+                }
+                self.set_focus(tree.order.first().copied(), events);
+            }
+        } else {
+            let previous = self.return_focus.take().filter(|id| tree.contains(*id));
+            if previous.is_some() || self.focused.is_some_and(|id| !tree.contains(id)) {
+                self.set_focus(previous, events);
+            }
+        }
+    }
+
+    fn apply(&mut self, tree: &FocusTree, request: FocusRequest, events: &mut Vec<FocusEvent>) {
+        match request {
+            // Widgets outside an active trap can't take focus
+            FocusRequest::Focus(id) if tree.contains(id) => self.set_focus(Some(id), events),
+            FocusRequest::Focus(_) => {}
+            FocusRequest::Release if tree.trap.is_none() => self.set_focus(None, events),
+            FocusRequest::Release => {}
+            FocusRequest::Next | FocusRequest::Previous => {
+                let order = &tree.order;
+                if order.is_empty() {
+                    return;
+                }
+                let current = self.focused.and_then(|id| order.iter().position(|other| *other == id));
+                let next = match (request, current) {
+                    (FocusRequest::Next, Some(i)) => (i + 1) % order.len(),
+                    (FocusRequest::Next, None) => 0,
+                    (_, Some(i)) => (i + order.len() - 1) % order.len(),
+                    (_, None) => order.len() - 1,
+                };
+                self.focus_visible = true;
+                self.set_focus(Some(order[next]), events);
+            }
+        }
+    }
+
+    fn set_focus(&mut self, target: Option<WidgetId>, events: &mut Vec<FocusEvent>) {
+        if self.focused == target {
+            return;
+        }
+        if let Some(widget_id) = self.focused {
+            events.push(FocusEvent::FocusOut { widget_id });
+        }
+        if let Some(widget_id) = target {
+            events.push(FocusEvent::FocusIn { widget_id });
+        }
+        self.focused = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{ClassList, WidgetState};
+    use crate::event::{KeyEvent, Modifiers, MouseButton, MouseEvent, TimerEvent};
+    use crate::geometry::{Point, Rect, Size};
+    use crate::layout::{Constraints, LayoutResult};
+    use crate::render::Painter;
+    use crate::widget::button::Button;
+    use crate::widget::container::{Column, Row};
+    use crate::widget::label::Label;
+    use crate::widget::{LayoutContext, PaintContext, WidgetBase};
+
+    fn tab(shift: bool) -> Event {
+        Event::Key(KeyEvent {
+            kind: KeyEventKind::Down,
+            key: Key::Tab,
+            physical_key: None,
+            text: None,
+            modifiers: Modifiers { shift, ..Modifiers::empty() },
+            is_repeat: false,
+        })
+    }
+
+    fn press(manager: &mut FocusManager, root: &dyn Widget, event: &Event) -> Vec<FocusEvent> {
+        manager.update(root, event, EventResult::Ignored, &mut EventContext::new())
+    }
+
+    #[test]
+    fn test_tab_order() {
+        let a = Button::new("A");
+        let b = Button::new("B").tab_index(2);
+        let c = Button::new("C").tab_index(-1);
+        let d = Button::new("D");
+        let e = Button::new("E").tab_index(1);
+        let ids = [Widget::id(&a), Widget::id(&b), Widget::id(&c), Widget::id(&d), Widget::id(&e)];
+        let root = Column::new()
+            .child(a)
+            .child(Label::new("text"))
+            .child(Row::new().child(b).child(c))
+            .child(d)
+            .child(e);
+
+        let mut manager = FocusManager::new();
+        let mut order = Vec::new();
+        for _ in 0..5 {
+            press(&mut manager, &root, &tab(false));
+            order.push(manager.focused().unwrap());
+        }
+        // Positive indices first, then tree order, skipping negative ones
+        assert_eq!(order, vec![ids[4], ids[1], ids[0], ids[3], ids[4]]);
+        assert!(manager.focus_visible());
+
+        let events = press(&mut manager, &root, &tab(true));
+        assert!(matches!(events[..], [FocusEvent::FocusOut { widget_id: out }, FocusEvent::FocusIn { widget_id: into }]
+            if out == ids[4] && into == ids[3]));
+
+        // Pointer focus is not visible, and an unhandled click clears focus
+        let mut ctx = EventContext::new();
+        ctx.request_focus(ids[2]);
+        let click = Event::Mouse(MouseEvent::new(MouseEventKind::Down, Point::ZERO).with_button(MouseButton::Left));
+        manager.update(&root, &click, EventResult::Handled, &mut ctx);
+        assert_eq!(ctx.focus, Some(ids[2]));
+        assert!(!manager.focus_visible());
+        press(&mut manager, &root, &click);
+        assert_eq!(manager.focused(), None);
+    }
+
+    /// A root whose children can change between events.
+    struct Root {
+        base: WidgetBase,
+        children: Vec<Box<dyn Widget>>,
+    }
+
+    impl Widget for Root {
+        fn id(&self) -> WidgetId {
+            self.base.id
+        }
+
+        fn type_name(&self) -> &'static str {
+            "root"
+        }
+
+        fn classes(&self) -> &ClassList {
+            &self.base.classes
+        }
+
+        fn state(&self) -> WidgetState {
+            self.base.state
+        }
+
+        fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
+            Size::ZERO
+        }
+
+        fn layout(&mut self, _constraints: Constraints, _ctx: &LayoutContext) -> LayoutResult {
+            LayoutResult::new(Size::ZERO)
+        }
+
+        fn paint(&self, _painter: &mut Painter, _rect: Rect, _ctx: &PaintContext) {}
+
+        fn bounds(&self) -> Rect {
+            self.base.bounds
+        }
+
+        fn set_bounds(&mut self, bounds: Rect) {
+            self.base.bounds = bounds;
+        }
+
+        fn children(&self) -> &[Box<dyn Widget>] {
+            &self.children
+        }
+    }
+
+    #[test]
+    fn test_focus_trap() {
+        let outside = Button::new("Outside");
+        let first = Button::new("OK");
+        let second = Button::new("Cancel");
+        let ids = [Widget::id(&outside), Widget::id(&first), Widget::id(&second)];
+
+        let mut root = Root {
+            base: WidgetBase::new(),
+            children: vec![Box::new(outside)],
+        };
+        let mut manager = FocusManager::new();
+        let mut ctx = EventContext::new();
+        ctx.request_focus(ids[0]);
+        manager.update(&root, &tab(false), EventResult::Handled, &mut ctx);
+        assert_eq!(manager.focused(), Some(ids[0]));
+
+        // Opening a dialog moves focus into it, and Tab cycles inside
+        let dialog = Column::new().focus_scope(FocusScope::Trap).child(first).child(second);
+        root.children.push(Box::new(dialog));
+        let redraw = Event::Timer(TimerEvent::Frame { elapsed: Default::default() });
+        press(&mut manager, &root, &redraw);
+        assert_eq!(manager.focused(), Some(ids[1]));
+        press(&mut manager, &root, &tab(false));
+        press(&mut manager, &root, &tab(false));
+        assert_eq!(manager.focused(), Some(ids[1]));
+
+        // Focus can't be taken from outside the trap
+        let mut ctx = EventContext::new();
+        ctx.request_focus(ids[0]);
+        manager.update(&root, &redraw, EventResult::Handled, &mut ctx);
+        assert_eq!(manager.focused(), Some(ids[1]));
+
+        // Closing the dialog restores focus
+        root.children.pop();
+        press(&mut manager, &root, &redraw);
+        assert_eq!(manager.focused(), Some(ids[0]));
+    }
+
+    #[test]
+    fn test_focus_inside_bar_and_split_pane() {
+        use crate::widget::bar::Bar;
+        use crate::widget::split_pane::SplitPane;
+        use crate::widget::textfield::TextField;
+
+        let start = Button::new("Start");
+        let field = TextField::new();
+        let end = Button::new("End");
+        let ids = [Widget::id(&start), Widget::id(&field), Widget::id(&end)];
+        let split = SplitPane::horizontal().first(Column::new().child(field)).second(end);
+        let root = Bar::new().start(start).center(split);
+
+        let mut manager = FocusManager::new();
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            press(&mut manager, &root, &tab(false));
+            order.push(manager.focused().unwrap());
+        }
+        assert_eq!(order, ids);
+
+        let mut ctx = EventContext::new();
+        ctx.request_focus(ids[1]);
+        manager.update(&root, &tab(false), EventResult::Handled, &mut ctx);
+        assert_eq!(manager.focused(), Some(ids[1]));
+
+        // A collapsed pane can't take focus
+        let mut collapsed = SplitPane::horizontal().first(TextField::new()).second(Button::new("Other"));
+        let other = collapsed.children()[1].id();
+        collapsed.collapse_first();
+        let mut manager = FocusManager::new();
+        press(&mut manager, &collapsed, &tab(false));
+        press(&mut manager, &collapsed, &tab(false));
+        assert_eq!(manager.focused(), Some(other));
+    }
+}
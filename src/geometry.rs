@@ -147,6 +147,15 @@ impl Rect {
             && self.max_y() > other.min_y()
     }
 
+    /// The overlapping area of two rectangles, if any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let x = self.min_x().max(other.min_x());
+        let y = self.min_y().max(other.min_y());
+        let max_x = self.max_x().min(other.max_x());
+        let max_y = self.max_y().min(other.max_y());
+        (max_x >= x && max_y >= y).then(|| Rect::new(x, y, max_x - x, max_y - y))
+    }
+
     pub fn inset(&self, amount: f32) -> Rect {
         Rect::new(
             self.origin.x + amount,
@@ -319,6 +328,16 @@ impl Color {
     pub fn with_alpha(&self, alpha: f32) -> Color {
         Color::rgba(self.r, self.g, self.b, alpha)
     }
+
+    /// Linearly interpolate towards `other` (`t` from 0.0 to 1.0).
+    pub fn lerp(&self, other: Color, t: f32) -> Color {
+        Color::rgba(
+            self.r + (other.r - self.r) * t,
+            self.g + (other.g - self.g) * t,
+            self.b + (other.b - self.b) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
 }
 
 impl Default for Color {
@@ -369,6 +388,16 @@ impl BorderRadius {
             && self.bottom_right == 0.0
             && self.bottom_left == 0.0
     }
+
+    /// Multiply every corner radius by `factor`.
+    pub fn scale(&self, factor: f32) -> Self {
+        Self::new(
+            self.top_left * factor,
+            self.top_right * factor,
+            self.bottom_right * factor,
+            self.bottom_left * factor,
+        )
+    }
 }
 
 impl From<f32> for BorderRadius {
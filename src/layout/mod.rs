@@ -69,6 +69,52 @@ impl Constraints {
     pub fn has_bounded_height(&self) -> bool {
         self.max_height.is_finite()
     }
+
+    /// Narrow to the `width`, `height` and min/max sizes in `style`.
+    ///
+    /// As in CSS, `min-width` wins over `max-width`, but the result always
+    /// stays within these constraints.
+    pub fn with_style(&self, style: &ComputedStyle) -> Self {
+        let (min_width, max_width) = constrain_axis(
+            self.min_width,
+            self.max_width,
+            style.width,
+            style.min_width,
+            style.max_width,
+        );
+        let (min_height, max_height) = constrain_axis(
+            self.min_height,
+            self.max_height,
+            style.height,
+            style.min_height,
+            style.max_height,
+        );
+        Self::new(min_width, max_width, min_height, max_height)
+    }
+}
+
+/// The bounds of one axis between `lower` and `upper` after applying a CSS
+/// size and its min/max limits.
+fn constrain_axis(
+    lower: f32,
+    upper: f32,
+    size: Option<f32>,
+    min: Option<f32>,
+    max: Option<f32>,
+) -> (f32, f32) {
+    let fit = |value: f32| value.max(lower).min(upper);
+    let min = min.unwrap_or(0.0);
+    let max = max.unwrap_or(f32::INFINITY).max(min);
+    match size {
+        Some(size) => {
+            let size = fit(size.max(min).min(max));
+            (size, size)
+        }
+        None => {
+            let low = fit(min);
+            (low, fit(max).max(low))
+        }
+    }
 }
 
 impl Default for Constraints {
@@ -351,6 +397,29 @@ mod tests {
         assert_eq!(size.height, 50.0);
     }
 
+    #[test]
+    fn test_constraints_with_style() {
+        let c = Constraints::loose(Size::new(500.0, 300.0));
+        let style = ComputedStyle {
+            width: Some(800.0),
+            min_height: Some(100.0),
+            max_height: Some(50.0),
+            ..Default::default()
+        };
+        let c = c.with_style(&style);
+        // The width is capped by the parent; min-height wins over max-height
+        assert_eq!((c.min_width, c.max_width), (500.0, 500.0));
+        assert_eq!((c.min_height, c.max_height), (100.0, 100.0));
+
+        let style = ComputedStyle {
+            max_width: Some(200.0),
+            ..Default::default()
+        };
+        let c = Constraints::unbounded().with_style(&style);
+        assert_eq!((c.min_width, c.max_width), (0.0, 200.0));
+        assert!(!c.has_bounded_height());
+    }
+
     #[test]
     fn test_flex_layout_positions() {
         let layout = FlexLayout::row();
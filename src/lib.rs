@@ -48,10 +48,13 @@
 //! ```
 
 pub mod app;
+pub mod clipboard;
 #[macro_use]
 pub mod component;
 pub mod css;
+pub mod dispatch;
 pub mod event;
+pub mod focus;
 pub mod geometry;
 pub mod layout;
 #[macro_use]
@@ -59,6 +62,7 @@ pub mod macros;
 pub mod platform;
 pub mod render;
 pub mod theme;
+pub mod timer;
 pub mod widget;
 
 // Re-export proc macros when the feature is enabled
@@ -69,11 +73,13 @@ pub mod prelude {
     //! Convenient re-exports for common types.
 
     // Core types
-    pub use crate::app::App;
+    pub use crate::app::{App, WindowOptions};
     pub use crate::css::{StyleManager, StyleBuilder, CssLoadError};
-    pub use crate::event::{Event, MouseButton, MouseEvent, KeyEvent, Key, Modifiers};
+    pub use crate::event::{Event, EventPhase, MouseButton, MouseEvent, KeyEvent, Key, Modifiers, FocusEvent, TimerEvent, ImeEvent};
+    pub use crate::focus::FocusScope;
     pub use crate::geometry::{Point, Size, Rect, Color, BorderRadius, EdgeInsets};
     pub use crate::layout::{Layout, Alignment, Padding};
+    pub use crate::render::{ImageCache, ImageHandle, ObjectFit, RichText, SpanStyle, TextSpan};
     pub use crate::theme::Theme;
 
     // Widget types
@@ -107,6 +113,7 @@ pub mod prelude {
     pub use crate::widget::switch::{ToggleSwitch, ToggleSwitchSize};
     pub use crate::widget::system_tray::{SystemTray, TrayIcon};
     pub use crate::widget::tabs::{Tabs, Tab, TabPosition, TabVariant};
+    pub use crate::widget::textarea::TextArea;
     pub use crate::widget::textfield::TextField;
     pub use crate::widget::tooltip::{Tooltip, TooltipPosition};
     pub use crate::widget::tree_view::{TreeView, TreeNode, TreeSelectionMode};
@@ -7,7 +7,7 @@ mod window;
 
 pub use window::{Window, WindowBuilder, WindowConfig};
 
-use crate::event::{Event, KeyEvent, KeyEventKind, Key, Modifiers, MouseButton, MouseEvent, MouseEventKind, WindowEvent};
+use crate::event::{Event, ImeEvent, KeyEvent, KeyEventKind, Key, Modifiers, MouseButton, MouseEvent, MouseEventKind, WindowEvent};
 use crate::geometry::Point;
 use crate::theme::Theme;
 
@@ -43,6 +43,7 @@ impl Platform {
         let mut app = PlatformApp {
             handler: Box::new(handler),
             cursor_position: Point::ZERO,
+            modifiers: Modifiers::empty(),
         };
 
         event_loop
@@ -82,6 +83,8 @@ struct PlatformApp {
     handler: Box<dyn FnMut(&ActiveEventLoop, PlatformEvent)>,
     /// Last known cursor position for mouse input events
     cursor_position: Point,
+    /// Current keyboard modifier state, updated on `ModifiersChanged`
+    modifiers: Modifiers,
 }
 
 impl ApplicationHandler for PlatformApp {
@@ -107,6 +110,8 @@ impl ApplicationHandler for PlatformApp {
                 if focused {
                     Some(Event::Window(WindowEvent::Focused))
                 } else {
+                    // Key releases are not delivered while unfocused
+                    self.modifiers = Modifiers::empty();
                     Some(Event::Window(WindowEvent::Unfocused))
                 }
             }
@@ -120,19 +125,25 @@ impl ApplicationHandler for PlatformApp {
             WinitWindowEvent::CursorMoved { position, .. } => {
                 let pos = Point::new(position.x as f32, position.y as f32);
                 self.cursor_position = pos;
-                Some(Event::Mouse(MouseEvent::new(MouseEventKind::Move, pos)))
+                Some(Event::Mouse(
+                    MouseEvent::new(MouseEventKind::Move, pos).with_modifiers(self.modifiers),
+                ))
             }
             WinitWindowEvent::CursorEntered { .. } => {
-                Some(Event::Mouse(MouseEvent::new(
-                    MouseEventKind::Enter,
-                    self.cursor_position,
-                )))
+                Some(Event::Mouse(
+                    MouseEvent::new(MouseEventKind::Enter, self.cursor_position)
+                        .with_modifiers(self.modifiers),
+                ))
             }
             WinitWindowEvent::CursorLeft { .. } => {
-                Some(Event::Mouse(MouseEvent::new(
-                    MouseEventKind::Leave,
-                    self.cursor_position,
-                )))
+                Some(Event::Mouse(
+                    MouseEvent::new(MouseEventKind::Leave, self.cursor_position)
+                        .with_modifiers(self.modifiers),
+                ))
+            }
+            WinitWindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = convert_modifiers(modifiers.state());
+                None
             }
             WinitWindowEvent::MouseInput { state, button, .. } => {
                 let kind = match state {
@@ -148,7 +159,9 @@ impl ApplicationHandler for PlatformApp {
                     winit::event::MouseButton::Other(id) => MouseButton::Other(id),
                 };
                 Some(Event::Mouse(
-                    MouseEvent::new(kind, self.cursor_position).with_button(button),
+                    MouseEvent::new(kind, self.cursor_position)
+                        .with_button(button)
+                        .with_modifiers(self.modifiers),
                 ))
             }
             WinitWindowEvent::MouseWheel { delta, .. } => {
@@ -164,7 +177,7 @@ impl ApplicationHandler for PlatformApp {
                     kind: MouseEventKind::Scroll { delta_x, delta_y },
                     position: self.cursor_position,
                     button: None,
-                    modifiers: Modifiers::empty(),
+                    modifiers: self.modifiers,
                 }))
             }
             WinitWindowEvent::KeyboardInput { event, .. } => {
@@ -179,10 +192,16 @@ impl ApplicationHandler for PlatformApp {
                     key,
                     physical_key: None,
                     text,
-                    modifiers: Modifiers::empty(), // TODO: Track modifiers
+                    modifiers: self.modifiers,
                     is_repeat: event.repeat,
                 }))
             }
+            WinitWindowEvent::Ime(ime) => Some(Event::Ime(match ime {
+                winit::event::Ime::Enabled => ImeEvent::Enabled,
+                winit::event::Ime::Preedit(text, cursor) => ImeEvent::Preedit { text, cursor },
+                winit::event::Ime::Commit(text) => ImeEvent::Commit(text),
+                winit::event::Ime::Disabled => ImeEvent::Disabled,
+            })),
             WinitWindowEvent::RedrawRequested => {
                 (self.handler)(
                     event_loop,
@@ -203,6 +222,16 @@ impl ApplicationHandler for PlatformApp {
     }
 }
 
+/// Convert winit modifier state to OpenKit modifiers.
+fn convert_modifiers(state: winit::keyboard::ModifiersState) -> Modifiers {
+    Modifiers {
+        shift: state.shift_key(),
+        control: state.control_key(),
+        alt: state.alt_key(),
+        super_key: state.super_key(),
+    }
+}
+
 /// Convert winit key to OpenKit key.
 fn convert_key(key: &winit::keyboard::Key) -> Key {
     use winit::keyboard::{Key as WKey, NamedKey};
@@ -1,10 +1,10 @@
 //! Window management.
 
-use crate::geometry::Size;
+use crate::geometry::{Rect, Size};
 use crate::theme::Theme;
 
 use std::sync::Arc;
-use winit::dpi::LogicalSize;
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
 use winit::event_loop::ActiveEventLoop;
 use winit::window::{Window as WinitWindow, WindowAttributes, WindowId};
 
@@ -19,6 +19,8 @@ pub struct WindowConfig {
     pub decorations: bool,
     pub transparent: bool,
     pub visible: bool,
+    /// Window this one is transient for
+    pub parent: Option<Arc<WinitWindow>>,
 }
 
 impl Default for WindowConfig {
@@ -32,6 +34,7 @@ impl Default for WindowConfig {
             decorations: true,
             transparent: false,
             visible: true,
+            parent: None,
         }
     }
 }
@@ -61,6 +64,19 @@ impl Window {
             attrs = attrs.with_max_inner_size(LogicalSize::new(max.width, max.height));
         }
 
+        // Owned windows stay above their owner and minimize with it
+        #[cfg(target_os = "windows")]
+        if let Some(parent) = &config.parent {
+            use winit::platform::windows::WindowAttributesExtWindows;
+            use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+            if let Ok(handle) = parent.window_handle() {
+                if let RawWindowHandle::Win32(handle) = handle.as_raw() {
+                    attrs = attrs.with_owner_window(handle.hwnd.get());
+                }
+            }
+        }
+
         let window = event_loop
             .create_window(attrs)
             .map_err(|e| super::PlatformError::WindowCreation(e.to_string()))?;
@@ -137,6 +153,20 @@ impl Window {
         self.inner.set_minimized(minimized);
     }
 
+    /// Enable or disable input method composition.
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.inner.set_ime_allowed(allowed);
+    }
+
+    /// Tell the input method where the caret is, in window coordinates, so
+    /// its candidate window can be placed next to it.
+    pub fn set_ime_cursor_area(&self, area: Rect) {
+        self.inner.set_ime_cursor_area(
+            PhysicalPosition::new(area.x() as f64, area.y() as f64),
+            PhysicalSize::new(area.width() as f64, area.height() as f64),
+        );
+    }
+
     /// Check if the window has focus.
     pub fn has_focus(&self) -> bool {
         self.inner.has_focus()
@@ -195,6 +225,15 @@ impl WindowBuilder {
         self
     }
 
+    /// Make the window transient for `parent`.
+    ///
+    /// Only Windows has a native owner relation in winit; elsewhere the
+    /// application keeps track of it.
+    pub fn parent(mut self, parent: &Window) -> Self {
+        self.config.parent = Some(parent.inner_arc());
+        self
+    }
+
     pub fn build(self, event_loop: &ActiveEventLoop) -> Result<Window, super::PlatformError> {
         Window::new(event_loop, self.config)
     }
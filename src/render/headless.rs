@@ -0,0 +1,467 @@
+//! Headless rendering without a window.
+//!
+//! Runs layout and paint for a widget tree into an in-memory RGBA buffer
+//! using the CPU renderer, so snapshot tests can run without a display server.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use openkit::prelude::*;
+//! use openkit::render::HeadlessRenderer;
+//! use openkit::theme::ThemeData;
+//!
+//! let mut root = col![8; label!("Hello"), button!("OK")];
+//! let mut renderer = HeadlessRenderer::new(320, 240);
+//! let snapshot = renderer.render(&mut root, &ThemeData::light(), None);
+//! snapshot.save_png("hello.png")?;
+//! ```
+
+use super::{CpuRenderer, DrawCommand, Painter, RenderError, TextRenderer};
+use crate::css::{StyleContext, StyleManager};
+use crate::geometry::{Color, Point, Rect, Size};
+use crate::layout::Constraints;
+use crate::theme::ThemeData;
+use crate::widget::{build_element_tree, LayoutContext, PaintContext, Widget};
+
+use std::path::Path;
+use std::sync::Arc;
+
+/// Renders widget trees to an RGBA pixel buffer without a window.
+pub struct HeadlessRenderer {
+    cpu: CpuRenderer,
+    text: TextRenderer,
+}
+
+impl HeadlessRenderer {
+    /// Create a headless renderer with the given output size in pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut cpu = CpuRenderer::new();
+        cpu.resize(Size::new(width as f32, height as f32));
+        Self {
+            cpu,
+            text: TextRenderer::new(),
+        }
+    }
+
+    /// Resize the output buffer.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.cpu.resize(Size::new(width as f32, height as f32));
+    }
+
+    /// Get the output size.
+    pub fn size(&self) -> Size {
+        self.cpu.size()
+    }
+
+    /// Lay out and paint a widget tree, returning the rendered pixels.
+    ///
+    /// The root widget is laid out with tight constraints matching the output
+    /// size, exactly as `App` does for a window's root widget.
+    pub fn render(
+        &mut self,
+        root: &mut dyn Widget,
+        theme: &ThemeData,
+        style_manager: Option<Arc<StyleManager>>,
+    ) -> Snapshot {
+        let size = self.size();
+        let style_ctx = if let Some(sm) = style_manager {
+            StyleContext::with_styles(theme, sm).with_viewport(size.width, size.height)
+        } else {
+            StyleContext::new(theme).with_viewport(size.width, size.height)
+        }
+        .with_element_tree(Arc::new(build_element_tree(root)));
+
+        // Layout
+        let layout_ctx = LayoutContext::new(&style_ctx);
+        root.layout(Constraints::tight(size), &layout_ctx);
+        let root_rect = Rect::from_origin_size(Point::ZERO, size);
+        root.set_bounds(root_rect);
+
+        // Paint
+        let paint_ctx = PaintContext::new(&style_ctx);
+        let mut painter = Painter::new();
+        root.paint(&mut painter, root_rect, &paint_ctx);
+
+        self.render_commands(&painter.finish(), theme.colors.background)
+    }
+
+    /// Execute raw draw commands over a cleared background.
+    pub fn render_commands(&mut self, commands: &[DrawCommand], background: Color) -> Snapshot {
+        self.cpu.begin_frame(background);
+        self.cpu.draw(commands, &mut self.text);
+        self.cpu.end_frame();
+
+        let size = self.size();
+        Snapshot {
+            width: size.width as u32,
+            height: size.height as u32,
+            pixels: self.cpu.pixels().to_vec(),
+        }
+    }
+}
+
+/// A rendered frame as straight RGBA8 pixels, row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    /// Get the RGBA value of a pixel, or `None` if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = ((y * self.width + x) * 4) as usize;
+        self.pixels
+            .get(idx..idx + 4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+    }
+
+    /// Convert to an `image::RgbaImage`.
+    pub fn to_image(&self) -> image::RgbaImage {
+        image::RgbaImage::from_raw(self.width, self.height, self.pixels.clone())
+            .expect("snapshot buffer matches its dimensions")
+    }
+
+    /// Encode the snapshot as PNG bytes.
+    pub fn to_png(&self) -> Result<Vec<u8>, RenderError> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        self.to_image()
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .map_err(|e| RenderError::ImageEncode(e.to_string()))?;
+        Ok(bytes.into_inner())
+    }
+
+    /// Write the snapshot to a PNG file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), RenderError> {
+        self.to_image()
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(|e| RenderError::ImageEncode(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::BorderRadius;
+    use crate::widget::container::Column;
+    use crate::widget::separator::Separator;
+
+    #[test]
+    fn test_render_background() {
+        let theme = ThemeData::light();
+        let mut root = Column::new();
+        let mut renderer = HeadlessRenderer::new(32, 16);
+        let snapshot = renderer.render(&mut root, &theme, None);
+
+        assert_eq!(snapshot.width, 32);
+        assert_eq!(snapshot.height, 16);
+        assert_eq!(snapshot.pixel(5, 5), Some(theme.colors.background.to_rgba8()));
+        assert_eq!(snapshot.pixel(32, 0), None);
+    }
+
+    #[test]
+    fn test_render_widget_tree() {
+        let theme = ThemeData::light();
+        let mut root = Column::new().child(Separator::horizontal());
+        let mut renderer = HeadlessRenderer::new(64, 64);
+        let snapshot = renderer.render(&mut root, &theme, None);
+
+        let changed = snapshot
+            .pixels
+            .chunks_exact(4)
+            .any(|p| p != theme.colors.background.to_rgba8());
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_css_overrides_theme() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles.load_css("#ok { background-color: #ff0000; }").unwrap();
+
+        let mut root = Column::new()
+            .child(crate::widget::button::Button::new("OK").id("ok"))
+            .child(crate::widget::button::Button::new("Cancel"));
+        let mut renderer = HeadlessRenderer::new(64, 96);
+        let snapshot = renderer.render(&mut root, &theme, Some(Arc::new(styles)));
+
+        // The styled button paints from CSS; the other keeps the theme color
+        assert_eq!(snapshot.pixel(4, 17), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(4, 51), Some(theme.colors.primary.to_rgba8()));
+    }
+
+    #[test]
+    fn test_css_opacity_and_transform() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                "#faded { background-color: #ff0000; opacity: 0.5; }
+                 #moved { background-color: #ff0000; transform: translateX(20px); }",
+            )
+            .unwrap();
+
+        let mut root = Column::new()
+            .child(crate::widget::button::Button::new("A").id("faded"))
+            .child(crate::widget::button::Button::new("B").id("moved"));
+        let mut renderer = HeadlessRenderer::new(64, 96);
+        let snapshot = renderer.render(&mut root, &theme, Some(Arc::new(styles)));
+
+        let background = theme.colors.background.to_rgba8();
+        // Half-transparent red over the light background
+        let [r, g, b, _] = snapshot.pixel(4, 17).unwrap();
+        assert!(r > 240 && (100..150).contains(&g) && g == b);
+
+        // The moved button starts 20px further right
+        assert_eq!(snapshot.pixel(4, 51), Some(background));
+        assert_eq!(snapshot.pixel(24, 51), Some([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_shell_widgets_use_css() {
+        use crate::widget::panel::Panel;
+
+        let theme = ThemeData::light();
+        let mut renderer = HeadlessRenderer::new(64, 64);
+
+        // The theme surface by default, the stylesheet's color when set
+        let snapshot = renderer.render(&mut Panel::new().shadow(false), &theme, None);
+        assert_eq!(snapshot.pixel(32, 32), Some(theme.colors.surface.to_rgba8()));
+
+        let mut styles = StyleManager::empty();
+        styles.load_css(".panel { background-color: #00ff00; }").unwrap();
+        let snapshot = renderer.render(&mut Panel::new().shadow(false), &theme, Some(Arc::new(styles)));
+        assert_eq!(snapshot.pixel(32, 32), Some([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let mut renderer = HeadlessRenderer::new(8, 8);
+        let commands = vec![DrawCommand::Rect {
+            rect: Rect::new(0.0, 0.0, 4.0, 8.0),
+            color: Color::RED,
+            radius: BorderRadius::ZERO,
+        }];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+        let png = snapshot.to_png().unwrap();
+
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded.get_pixel(1, 1).0, [255, 0, 0, 255]);
+        assert_eq!(decoded.get_pixel(6, 1).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_clip_commands() {
+        let mut renderer = HeadlessRenderer::new(32, 32);
+        let red = [255, 0, 0, 255];
+        let white = [255, 255, 255, 255];
+        let commands = vec![
+            // Square clip, then a rounded one
+            DrawCommand::PushClip { rect: Rect::new(0.0, 0.0, 8.0, 8.0), radius: BorderRadius::ZERO },
+            DrawCommand::Rect { rect: Rect::new(0.0, 0.0, 32.0, 32.0), color: Color::RED, radius: BorderRadius::ZERO },
+            DrawCommand::PopClip,
+            DrawCommand::PushClip { rect: Rect::new(16.0, 16.0, 16.0, 16.0), radius: BorderRadius::all(8.0) },
+            DrawCommand::Rect { rect: Rect::new(0.0, 0.0, 32.0, 32.0), color: Color::RED, radius: BorderRadius::ZERO },
+            DrawCommand::Line { from: Point::new(0.0, 24.0), to: Point::new(32.0, 24.0), color: Color::BLUE, width: 1.0 },
+            DrawCommand::PopClip,
+        ];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+
+        assert_eq!(snapshot.pixel(4, 4), Some(red));
+        assert_eq!(snapshot.pixel(12, 4), Some(white));
+        assert_eq!(snapshot.pixel(24, 20), Some(red));
+        // Rounded corners of the clip stay clear
+        assert_eq!(snapshot.pixel(16, 16), Some(white));
+        assert_eq!(snapshot.pixel(31, 31), Some(white));
+        // The line only shows inside the clip
+        assert_eq!(snapshot.pixel(8, 24), Some(white));
+        assert!(snapshot.pixel(24, 24).unwrap()[2] > 128);
+    }
+
+    #[test]
+    fn test_rich_text_background() {
+        use crate::render::{RichText, TextSpan};
+
+        let mut renderer = HeadlessRenderer::new(64, 32);
+        let text = RichText::new()
+            .push("a")
+            .push(TextSpan::new("    ").background(Color::RED))
+            .push("b");
+        let commands = vec![DrawCommand::RichText {
+            text,
+            position: Point::new(0.0, 20.0),
+            color: Color::BLACK,
+            size: 20.0,
+        }];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+
+        // The highlighted spaces sit between the letters, a line high
+        let highlighted: Vec<u32> = (0..64).filter(|&x| snapshot.pixel(x, 16) == Some([255, 0, 0, 255])).collect();
+        assert!(highlighted.len() >= 8);
+        assert_eq!(snapshot.pixel(highlighted[0], 2), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(highlighted[0], 6), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(0, 16), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_rounded_rects_are_anti_aliased() {
+        let mut renderer = HeadlessRenderer::new(32, 32);
+        let commands = vec![
+            // Only the top left corner is rounded
+            DrawCommand::Rect {
+                rect: Rect::new(0.0, 0.0, 16.0, 16.0),
+                color: Color::RED,
+                radius: BorderRadius::new(8.0, 0.0, 0.0, 0.0),
+            },
+            DrawCommand::StrokeRect {
+                rect: Rect::new(16.0, 16.0, 16.0, 16.0),
+                color: Color::BLUE,
+                width: 2.0,
+                radius: BorderRadius::all(4.0),
+            },
+        ];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+
+        assert_eq!(snapshot.pixel(0, 0), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(15, 0), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(0, 15), Some([255, 0, 0, 255]));
+        // The curve blends into the background
+        let edge = snapshot.pixel(2, 2).unwrap();
+        assert!(edge[1] > 0 && edge[1] < 255, "{edge:?}");
+
+        // The border is drawn inside the rect, around a clear middle
+        assert_eq!(snapshot.pixel(24, 16), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(24, 17), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(24, 18), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(24, 24), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(16, 16), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_translucent_fill_keeps_alpha() {
+        let mut renderer = HeadlessRenderer::new(8, 8);
+        let commands = vec![DrawCommand::Rect {
+            rect: Rect::new(0.0, 0.0, 8.0, 8.0),
+            color: Color::RED.with_alpha(0.5),
+            radius: BorderRadius::ZERO,
+        }];
+        let snapshot = renderer.render_commands(&commands, Color::TRANSPARENT);
+
+        let [r, _, _, a] = snapshot.pixel(4, 4).unwrap();
+        assert_eq!(r, 255);
+        assert!((127..=128).contains(&a), "{a}");
+    }
+
+    #[test]
+    fn test_box_shadows() {
+        let mut renderer = HeadlessRenderer::new(64, 64);
+        let commands = vec![
+            DrawCommand::Shadow {
+                rect: Rect::new(16.0, 16.0, 32.0, 32.0),
+                radius: BorderRadius::all(4.0),
+                offset: Point::new(0.0, 4.0),
+                blur: 8.0,
+                spread: 0.0,
+                color: Color::BLACK,
+                inset: false,
+            },
+            DrawCommand::Shadow {
+                rect: Rect::new(16.0, 16.0, 32.0, 32.0),
+                radius: BorderRadius::all(4.0),
+                offset: Point::ZERO,
+                blur: 0.0,
+                spread: 2.0,
+                color: Color::BLUE,
+                inset: true,
+            },
+        ];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+        let gray = |x, y| snapshot.pixel(x, y).unwrap()[0];
+
+        // The outer shadow fades out below the box and leaves it clear
+        assert!(gray(32, 49) < gray(32, 54) && gray(32, 54) < gray(32, 60), "{:?}", snapshot.pixel(32, 54));
+        assert!(gray(32, 14) > gray(32, 50));
+        assert_eq!(snapshot.pixel(32, 32), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(2, 2), Some([255, 255, 255, 255]));
+
+        // A sharp inset shadow is a 2px band inside the edge
+        assert_eq!(snapshot.pixel(32, 16), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(32, 17), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(32, 18), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_svg_paths() {
+        let mut renderer = HeadlessRenderer::new(48, 48);
+        let commands = vec![
+            // A triangle in a 24 unit viewbox, drawn twice the size
+            DrawCommand::Path {
+                path: "M2 22h20V2z".to_string(),
+                rect: Rect::new(0.0, 0.0, 48.0, 48.0),
+                color: Color::RED,
+                viewbox: (0.0, 0.0, 24.0, 24.0),
+            },
+            DrawCommand::StrokePath {
+                path: "m2 6h10".to_string(),
+                rect: Rect::new(0.0, 0.0, 48.0, 48.0),
+                color: Color::BLUE,
+                width: 1.0,
+                viewbox: (0.0, 0.0, 24.0, 24.0),
+            },
+        ];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+
+        assert_eq!(snapshot.pixel(40, 40), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(8, 8), Some([255, 255, 255, 255]));
+        // The diagonal edge is blended
+        let edge = snapshot.pixel(24, 23).unwrap();
+        assert!(edge[1] > 0 && edge[1] < 255, "{edge:?}");
+
+        // The stroke is 2 pixels wide around y = 12, with round caps
+        assert_eq!(snapshot.pixel(12, 11), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(12, 12), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(12, 9), Some([255, 255, 255, 255]));
+        assert_ne!(snapshot.pixel(3, 11), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(1, 11), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_images() {
+        use crate::render::{ImageCache, ImageData, ObjectFit};
+
+        // 2x1: red on the left, blue on the right
+        let image = ImageData::from_rgba(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]).unwrap();
+        let id = ImageCache::global().insert(image).id();
+        let mut renderer = HeadlessRenderer::new(32, 32);
+        let commands = vec![
+            // Covering a square crops to the middle of the image
+            DrawCommand::Image { rect: Rect::new(0.0, 0.0, 16.0, 16.0), image_id: id, fit: ObjectFit::Cover, opacity: 1.0 },
+            DrawCommand::Image { rect: Rect::new(16.0, 0.0, 16.0, 16.0), image_id: id, fit: ObjectFit::Tile, opacity: 1.0 },
+            DrawCommand::Image { rect: Rect::new(0.0, 16.0, 16.0, 16.0), image_id: id, fit: ObjectFit::Contain, opacity: 0.5 },
+            // Not in the cache, so nothing is drawn
+            DrawCommand::Image { rect: Rect::new(16.0, 16.0, 16.0, 16.0), image_id: u64::MAX, fit: ObjectFit::Fill, opacity: 1.0 },
+        ];
+        let snapshot = renderer.render_commands(&commands, Color::WHITE);
+
+        let [r, _, b, _] = snapshot.pixel(0, 8).unwrap();
+        assert!(r > 240 && b < 16, "{r} {b}");
+        let [r, _, b, _] = snapshot.pixel(15, 8).unwrap();
+        assert!(r < 16 && b > 240, "{r} {b}");
+        let middle = snapshot.pixel(8, 8).unwrap();
+        assert!(middle[0] > 64 && middle[2] > 64, "{middle:?}");
+
+        assert_eq!(snapshot.pixel(16, 0), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(17, 5), Some([0, 0, 255, 255]));
+        assert_eq!(snapshot.pixel(18, 5), Some([255, 0, 0, 255]));
+        assert_eq!(snapshot.pixel(31, 15), Some([0, 0, 255, 255]));
+
+        // Contained, the image is a band across the middle, half transparent
+        assert_eq!(snapshot.pixel(2, 18), Some([255, 255, 255, 255]));
+        assert_eq!(snapshot.pixel(2, 24), Some([255, 127, 127, 255]));
+        assert_eq!(snapshot.pixel(24, 24), Some([255, 255, 255, 255]));
+    }
+}
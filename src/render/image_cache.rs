@@ -0,0 +1,534 @@
+//! Decoded images, shared by widgets and renderers.
+//!
+//! Widgets load images through the global [`ImageCache`] and draw them by
+//! handle. Files and bytes are decoded on a background thread; until an image
+//! is ready, drawing it draws nothing, so widgets show their fallback.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use openkit::render::{ImageCache, ObjectFit};
+//!
+//! let photo = ImageCache::global().load_path("/usr/share/backgrounds/hills.jpg");
+//!
+//! // While painting
+//! painter.draw_image_fit(rect, photo.id(), ObjectFit::Cover);
+//! ```
+
+use crate::geometry::{Rect, Size};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// An image decoded to unpremultiplied RGBA pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageData {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl ImageData {
+    /// Wrap RGBA pixels, row by row. Returns `None` if there are not
+    /// `width * height` of them.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        Some(Self { width, height, pixels })
+    }
+
+    /// Decode a PNG, JPEG or WebP image.
+    pub fn decode(bytes: &[u8]) -> Result<Self, image::ImageError> {
+        let decoded = image::load_from_memory(bytes)?.to_rgba8();
+        Ok(Self {
+            width: decoded.width(),
+            height: decoded.height(),
+            pixels: decoded.into_raw(),
+        })
+    }
+
+    /// Read and decode a PNG, JPEG or WebP file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, image::ImageError> {
+        let decoded = image::open(path)?.to_rgba8();
+        Ok(Self {
+            width: decoded.width(),
+            height: decoded.height(),
+            pixels: decoded.into_raw(),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width as f32, self.height as f32)
+    }
+
+    /// The RGBA pixels, row by row.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Get the RGBA pixel at (x, y).
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        Some([self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2], self.pixels[idx + 3]])
+    }
+
+    /// A copy scaled to `width` by `height`, interpolated bilinearly when
+    /// enlarging and averaged over each pixel's area when shrinking.
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        self.resize_part(width, height, 0..width, 0..height)
+    }
+
+    /// The `columns` and `rows` of a copy scaled to `width` by `height`,
+    /// without scaling the rest, e.g. the part of an image left visible by
+    /// clipping.
+    ///
+    /// Memory use is bounded by the size of the part, not of the scaled
+    /// image: source rows are filtered as they are needed and dropped once
+    /// the rows below no longer use them.
+    pub fn resize_part(&self, width: u32, height: u32, columns: Range<u32>, rows: Range<u32>) -> Self {
+        let columns = columns.start.min(width)..columns.end.min(width);
+        let rows = rows.start.min(height)..rows.end.min(height);
+        if (width, height) == (self.width, self.height) && (columns.len(), rows.len()) == (width as usize, height as usize) {
+            return self.clone();
+        }
+        let (part_width, part_height) = (columns.len() as u32, rows.len() as u32);
+        if self.width == 0 || self.height == 0 || part_width == 0 || part_height == 0 {
+            return Self {
+                width: part_width,
+                height: part_height,
+                pixels: vec![0; part_width as usize * part_height as usize * 4],
+            };
+        }
+
+        // Scale rows, then columns, keeping the scaled source rows the
+        // current output row needs
+        let column_taps = filter_taps(self.width, width, columns);
+        let row_taps = filter_taps(self.height, height, rows);
+        let mut window: VecDeque<Vec<[f32; 4]>> = VecDeque::new();
+        let mut window_start = 0;
+        let mut pixels = Vec::with_capacity(part_width as usize * part_height as usize * 4);
+        for taps in &row_taps {
+            let (Some(&(first, _)), Some(&(last, _))) = (taps.first(), taps.last()) else {
+                pixels.resize(pixels.len() + part_width as usize * 4, 0);
+                continue;
+            };
+            while window_start < first && !window.is_empty() {
+                window.pop_front();
+                window_start += 1;
+            }
+            if window.is_empty() {
+                window_start = first;
+            }
+            while window_start + window.len() <= last {
+                window.push_back(self.scale_row(window_start + window.len(), &column_taps));
+            }
+
+            let mut sums = vec![[0.0_f32; 4]; part_width as usize];
+            for &(source, weight) in taps {
+                for (sum, scaled) in sums.iter_mut().zip(&window[source - window_start]) {
+                    for (value, channel) in sum.iter_mut().zip(scaled) {
+                        *value += channel * weight;
+                    }
+                }
+            }
+            for sum in sums {
+                let alpha = sum[3].clamp(0.0, 255.0);
+                let unpremultiply = if alpha > 0.0 { 255.0 / alpha } else { 0.0 };
+                pixels.extend([
+                    (sum[0] * unpremultiply).round().clamp(0.0, 255.0) as u8,
+                    (sum[1] * unpremultiply).round().clamp(0.0, 255.0) as u8,
+                    (sum[2] * unpremultiply).round().clamp(0.0, 255.0) as u8,
+                    alpha.round() as u8,
+                ]);
+            }
+        }
+
+        Self {
+            width: part_width,
+            height: part_height,
+            pixels,
+        }
+    }
+
+    /// Source row `y` scaled by `taps`, in premultiplied colors so
+    /// transparent pixels don't bleed.
+    fn scale_row(&self, y: usize, taps: &[Vec<(usize, f32)>]) -> Vec<[f32; 4]> {
+        let row = &self.pixels[y * self.width as usize * 4..(y + 1) * self.width as usize * 4];
+        taps.iter()
+            .map(|taps| {
+                let mut sum = [0.0_f32; 4];
+                for &(source, weight) in taps {
+                    let p = &row[source * 4..source * 4 + 4];
+                    let alpha = p[3] as f32 / 255.0;
+                    sum[0] += p[0] as f32 * alpha * weight;
+                    sum[1] += p[1] as f32 * alpha * weight;
+                    sum[2] += p[2] as f32 * alpha * weight;
+                    sum[3] += p[3] as f32 * weight;
+                }
+                sum
+            })
+            .collect()
+    }
+}
+
+/// Source pixels and weights making up each pixel in `range` of `to`
+/// pixels scaled from `from` pixels along one axis.
+fn filter_taps(from: u32, to: u32, range: Range<u32>) -> Vec<Vec<(usize, f32)>> {
+    let scale = from as f32 / to as f32;
+    let last = from as usize - 1;
+    range
+        .map(|i| {
+            if scale <= 1.0 {
+                // Enlarging: blend the two nearest pixel centers
+                let center = ((i as f32 + 0.5) * scale - 0.5).max(0.0);
+                let left = (center.floor() as usize).min(last);
+                let right = (left + 1).min(last);
+                let t = center - left as f32;
+                vec![(left, 1.0 - t), (right, t)]
+            } else {
+                // Shrinking: average the pixels under the footprint
+                let start = i as f32 * scale;
+                let end = start + scale;
+                let mut taps = Vec::new();
+                let mut source = start.floor() as usize;
+                while (source as f32) < end && source <= last {
+                    let overlap = (end.min(source as f32 + 1.0) - start.max(source as f32)) / scale;
+                    if overlap > 0.0 {
+                        taps.push((source, overlap));
+                    }
+                    source += 1;
+                }
+                taps
+            }
+        })
+        .collect()
+}
+
+/// How an image is sized and placed in the rectangle it is drawn in, as
+/// CSS `object-fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObjectFit {
+    /// Stretch to the rectangle, ignoring the aspect ratio
+    #[default]
+    Fill,
+    /// Scale to fit inside the rectangle, centered
+    Contain,
+    /// Scale to cover the rectangle, centered and cropped
+    Cover,
+    /// Keep the natural size, centered and cropped
+    None,
+    /// Repeat at the natural size from the top left corner
+    Tile,
+}
+
+impl ObjectFit {
+    /// Where an image of `image` size is drawn in `rect`. For `Tile`, this
+    /// is the first tile.
+    pub fn place(self, image: Size, rect: Rect) -> Rect {
+        let centered = |width: f32, height: f32| {
+            Rect::new(
+                rect.x() + (rect.width() - width) / 2.0,
+                rect.y() + (rect.height() - height) / 2.0,
+                width,
+                height,
+            )
+        };
+        if image.width <= 0.0 || image.height <= 0.0 {
+            return rect;
+        }
+        match self {
+            ObjectFit::Fill => rect,
+            ObjectFit::Contain | ObjectFit::Cover => {
+                let x_scale = rect.width() / image.width;
+                let y_scale = rect.height() / image.height;
+                let scale = if self == ObjectFit::Contain { x_scale.min(y_scale) } else { x_scale.max(y_scale) };
+                centered(image.width * scale, image.height * scale)
+            }
+            ObjectFit::None => centered(image.width, image.height),
+            ObjectFit::Tile => Rect::from_origin_size(rect.origin, image),
+        }
+    }
+}
+
+/// A handle to an image in an [`ImageCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageHandle(u64);
+
+impl ImageHandle {
+    /// The id to draw the image with.
+    pub fn id(self) -> u64 {
+        self.0
+    }
+}
+
+/// Loading state of an image.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageStatus {
+    /// Still decoding
+    Loading,
+    Ready,
+    /// Decoding failed, with the reason
+    Failed(String),
+}
+
+enum Entry {
+    Loading,
+    Ready(Arc<ImageData>),
+    Failed(String),
+}
+
+#[derive(Default)]
+struct Images {
+    next_id: u64,
+    entries: HashMap<u64, Entry>,
+    /// Handles of files already loaded or loading
+    paths: HashMap<PathBuf, ImageHandle>,
+    /// Whether an image finished decoding since `take_finished`
+    finished: bool,
+}
+
+/// Images by handle, decoded in the background.
+pub struct ImageCache {
+    images: Arc<Mutex<Images>>,
+}
+
+impl ImageCache {
+    /// Create an empty cache. Most code uses the shared [`ImageCache::global`].
+    pub fn new() -> Self {
+        Self {
+            images: Arc::new(Mutex::new(Images::default())),
+        }
+    }
+
+    /// The cache renderers draw images from.
+    pub fn global() -> &'static ImageCache {
+        static CACHE: OnceLock<ImageCache> = OnceLock::new();
+        CACHE.get_or_init(ImageCache::new)
+    }
+
+    /// Load an image file in the background. Loading the same path again
+    /// gives the same handle without reading it twice.
+    pub fn load_path(&self, path: impl AsRef<Path>) -> ImageHandle {
+        let path = path.as_ref().to_path_buf();
+        let mut images = self.lock();
+        if let Some(handle) = images.paths.get(&path) {
+            return *handle;
+        }
+        let handle = Self::reserve(&mut images);
+        images.paths.insert(path.clone(), handle);
+        drop(images);
+
+        self.decode_in_background(handle, move || ImageData::open(&path));
+        handle
+    }
+
+    /// Decode encoded image bytes in the background.
+    pub fn load_bytes(&self, bytes: impl Into<Vec<u8>>) -> ImageHandle {
+        let bytes = bytes.into();
+        let handle = Self::reserve(&mut self.lock());
+        self.decode_in_background(handle, move || ImageData::decode(&bytes));
+        handle
+    }
+
+    /// Add an already decoded image.
+    pub fn insert(&self, image: ImageData) -> ImageHandle {
+        let mut images = self.lock();
+        let handle = Self::reserve(&mut images);
+        images.entries.insert(handle.0, Entry::Ready(Arc::new(image)));
+        handle
+    }
+
+    /// The image, once decoded.
+    pub fn get(&self, handle: ImageHandle) -> Option<Arc<ImageData>> {
+        self.get_id(handle.0)
+    }
+
+    /// The image with `id`, once decoded.
+    pub fn get_id(&self, id: u64) -> Option<Arc<ImageData>> {
+        match self.lock().entries.get(&id) {
+            Some(Entry::Ready(image)) => Some(image.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether the image is decoding, ready or failed. Unknown handles count
+    /// as failed.
+    pub fn status(&self, handle: ImageHandle) -> ImageStatus {
+        match self.lock().entries.get(&handle.0) {
+            Some(Entry::Loading) => ImageStatus::Loading,
+            Some(Entry::Ready(_)) => ImageStatus::Ready,
+            Some(Entry::Failed(reason)) => ImageStatus::Failed(reason.clone()),
+            None => ImageStatus::Failed("unknown image".to_string()),
+        }
+    }
+
+    /// Drop an image. Its handle draws nothing afterwards.
+    pub fn remove(&self, handle: ImageHandle) {
+        let mut images = self.lock();
+        images.entries.remove(&handle.0);
+        images.paths.retain(|_, loaded| *loaded != handle);
+    }
+
+    /// Whether any image is still decoding.
+    pub fn is_loading(&self) -> bool {
+        self.lock().entries.values().any(|entry| matches!(entry, Entry::Loading))
+    }
+
+    /// Whether an image finished decoding since the last call, so windows
+    /// showing it need painting.
+    pub(crate) fn take_finished(&self) -> bool {
+        std::mem::take(&mut self.lock().finished)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Images> {
+        // A panic while holding the lock leaves the maps consistent
+        self.images.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn reserve(images: &mut Images) -> ImageHandle {
+        images.next_id += 1;
+        let handle = ImageHandle(images.next_id);
+        images.entries.insert(handle.0, Entry::Loading);
+        handle
+    }
+
+    fn decode_in_background<F>(&self, handle: ImageHandle, decode: F)
+    where
+        F: FnOnce() -> Result<ImageData, image::ImageError> + Send + 'static,
+    {
+        let images = self.images.clone();
+        std::thread::spawn(move || {
+            let entry = match decode() {
+                Ok(image) => Entry::Ready(Arc::new(image)),
+                Err(err) => {
+                    log::warn!("Failed to load image: {}", err);
+                    Entry::Failed(err.to_string())
+                }
+            };
+            let mut images = images.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            // The image may have been removed meanwhile
+            if let Some(slot) = images.entries.get_mut(&handle.0) {
+                *slot = entry;
+                images.finished = true;
+            }
+        });
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait_for(cache: &ImageCache, handle: ImageHandle) -> ImageStatus {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while cache.status(handle) == ImageStatus::Loading && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        cache.status(handle)
+    }
+
+    #[test]
+    fn test_load_bytes() {
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([10, 20, 30, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let cache = ImageCache::new();
+        let handle = cache.load_bytes(png);
+        assert_eq!(wait_for(&cache, handle), ImageStatus::Ready);
+        assert!(cache.take_finished());
+        assert!(!cache.take_finished());
+
+        let image = cache.get(handle).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.pixel(2, 1), Some([10, 20, 30, 255]));
+
+        let broken = cache.load_bytes(vec![1, 2, 3]);
+        assert!(matches!(wait_for(&cache, broken), ImageStatus::Failed(_)));
+        assert!(cache.get(broken).is_none());
+    }
+
+    #[test]
+    fn test_same_path_loads_once() {
+        let cache = ImageCache::new();
+        let first = cache.load_path("/nonexistent/wallpaper.png");
+        assert_eq!(cache.load_path("/nonexistent/wallpaper.png"), first);
+        assert!(matches!(wait_for(&cache, first), ImageStatus::Failed(_)));
+    }
+
+    #[test]
+    fn test_resize() {
+        // Left half black, right half white
+        let mut pixels = Vec::new();
+        for _ in 0..4 {
+            pixels.extend([0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255]);
+        }
+        let image = ImageData::from_rgba(4, 4, pixels).unwrap();
+
+        let small = image.resize(2, 2);
+        assert_eq!(small.pixel(0, 0), Some([0, 0, 0, 255]));
+        assert_eq!(small.pixel(1, 1), Some([255, 255, 255, 255]));
+
+        // Enlarged, the edge between the halves is interpolated
+        let large = image.resize(8, 8);
+        let middle = large.pixel(3, 0).unwrap();
+        assert!(middle[0] > 0 && middle[0] < 255, "{middle:?}");
+        assert_eq!(large.pixel(0, 0), Some([0, 0, 0, 255]));
+        assert_eq!(large.pixel(7, 7), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_resize_part() {
+        let pixels = (0..16 * 12).flat_map(|i| [(i * 7 % 256) as u8, (i * 13 % 256) as u8, (i % 256) as u8, 255]).collect();
+        let image = ImageData::from_rgba(16, 12, pixels).unwrap();
+
+        // Each part matches the same pixels of the whole scaled image
+        for (width, height) in [(40, 30), (6, 5), (16, 12)] {
+            let whole = image.resize(width, height);
+            let part = image.resize_part(width, height, 3..width - 2, 1..height - 1);
+            assert_eq!(part.size(), Size::new((width - 5) as f32, (height - 2) as f32));
+            for y in 0..part.height() {
+                for x in 0..part.width() {
+                    assert_eq!(part.pixel(x, y), whole.pixel(x + 3, y + 1), "{width}x{height} at {x},{y}");
+                }
+            }
+        }
+
+        // A part of a huge scaled copy costs only the part
+        let part = image.resize_part(1_000_000, 12, 500_000..500_010, 0..12);
+        assert_eq!(part.size(), Size::new(10.0, 12.0));
+    }
+
+    #[test]
+    fn test_object_fit() {
+        let image = Size::new(200.0, 100.0);
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        assert_eq!(ObjectFit::Fill.place(image, rect), rect);
+        assert_eq!(ObjectFit::Contain.place(image, rect), Rect::new(0.0, 25.0, 100.0, 50.0));
+        assert_eq!(ObjectFit::Cover.place(image, rect), Rect::new(-50.0, 0.0, 200.0, 100.0));
+        assert_eq!(ObjectFit::None.place(image, rect), Rect::new(-50.0, 0.0, 200.0, 100.0));
+        assert_eq!(ObjectFit::Tile.place(image, rect), Rect::new(0.0, 0.0, 200.0, 100.0));
+    }
+}
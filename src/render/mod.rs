@@ -2,19 +2,32 @@
 //!
 //! Provides GPU-accelerated rendering using wgpu with a CPU fallback using skia-rs.
 
+mod headless;
+mod image_cache;
 mod painter;
+mod raster;
+mod rich_text;
+mod shadow;
+mod svg_path;
 mod text;
 
-pub use painter::{Painter, DrawCommand};
-pub use text::TextRenderer;
+pub use headless::{HeadlessRenderer, Snapshot};
+pub use image_cache::{ImageCache, ImageData, ImageHandle, ImageStatus, ObjectFit};
+pub use painter::{Painter, DrawCommand, ICON_VIEWBOX};
+pub use rich_text::{RichText, SpanStyle, TextSpan};
+pub use svg_path::is_path_data;
+pub use text::{TextLayout, TextRenderer};
 
 use crate::geometry::{Color, Point, Rect, Size, BorderRadius};
 use crate::platform::Window;
 
+use std::collections::HashMap;
 use std::num::NonZeroU32;
+use std::ops::Range;
+use std::sync::Arc;
 
 use skia_rs_safe::canvas::raster::{PixelBuffer, Rasterizer};
-use skia_rs_safe::core::Color as SkiaColor;
+use skia_rs_safe::core::{Color as SkiaColor, Matrix, Rect as SkiaRect};
 use skia_rs_safe::paint::{Paint, Style};
 
 #[cfg(feature = "gpu")]
@@ -200,28 +213,11 @@ impl Renderer {
 
     /// Execute draw commands.
     pub fn draw(&mut self, commands: &[DrawCommand]) {
-        for cmd in commands {
-            match cmd {
-                DrawCommand::Rect { rect, color, radius } => {
-                    self.draw_rect(*rect, *color, *radius);
-                }
-                DrawCommand::Text { text, position, color, size } => {
-                    self.draw_text(text, *position, *color, *size);
-                }
-                DrawCommand::Line { from, to, color, width } => {
-                    self.draw_line(*from, *to, *color, *width);
-                }
-                DrawCommand::Image { rect, .. } => {
-                    // TODO: Image rendering
-                    self.draw_rect(*rect, Color::from_rgb8(200, 200, 200), BorderRadius::ZERO);
-                }
-                DrawCommand::Path { rect, color, .. } => {
-                    // TODO: SVG path rendering
-                    // For now, draw a placeholder rectangle
-                    self.draw_rect(*rect, *color, BorderRadius::ZERO);
-                }
-            }
+        #[cfg(feature = "gpu")]
+        if self.gpu.is_some() {
+            return;
         }
+        self.cpu.draw(commands, &mut self.text);
     }
 
     /// Draw a filled rectangle.
@@ -410,6 +406,31 @@ impl GpuRenderer {
 pub struct CpuRenderer {
     pixel_buffer: PixelBuffer,
     size: Size,
+    /// Clips in effect, innermost last
+    clip_stack: Vec<Clip>,
+    /// Visible parts of images resized to the sizes drawn, by id, size
+    /// and the columns and rows of the part
+    scaled_images: HashMap<ScaledKey, ScaledImage>,
+    /// Frames begun, to drop scaled images no longer drawn
+    frame: u64,
+}
+
+type ScaledKey = (u64, (u32, u32), Range<u32>, Range<u32>);
+
+/// The visible part of an image resized for drawing.
+struct ScaledImage {
+    image: Arc<ImageData>,
+    /// Frame it was last drawn in
+    drawn: u64,
+}
+
+/// An area drawing is limited to.
+#[derive(Debug, Clone)]
+struct Clip {
+    /// Bounds in device pixels, within the enclosing clips
+    bounds: Rect,
+    /// Rounded rectangles of this and the enclosing clips
+    rounded: Vec<(Rect, BorderRadius)>,
 }
 
 impl CpuRenderer {
@@ -417,6 +438,9 @@ impl CpuRenderer {
         Self {
             pixel_buffer: PixelBuffer::new(1, 1),
             size: Size::new(1.0, 1.0),
+            clip_stack: Vec::new(),
+            scaled_images: HashMap::new(),
+            frame: 0,
         }
     }
 
@@ -430,6 +454,11 @@ impl CpuRenderer {
     }
 
     pub fn begin_frame(&mut self, background: Color) {
+        self.clip_stack.clear();
+        // Keep the scaled images drawn last frame
+        let last_frame = self.frame;
+        self.scaled_images.retain(|_, scaled| scaled.drawn == last_frame);
+        self.frame += 1;
         let [r, g, b, a] = background.to_rgba8();
         // Fill all pixels with background color (RGBA format)
         let pixels = &mut self.pixel_buffer.pixels;
@@ -450,41 +479,319 @@ impl CpuRenderer {
         &self.pixel_buffer.pixels
     }
 
+    /// Execute a list of draw commands.
+    pub fn draw(&mut self, commands: &[DrawCommand], text_renderer: &mut TextRenderer) {
+        for cmd in commands {
+            match cmd {
+                DrawCommand::Rect { rect, color, radius } => {
+                    self.draw_rect(*rect, *color, *radius);
+                }
+                DrawCommand::Text { text, position, color, size } => {
+                    self.draw_text(text, *position, *color, *size, text_renderer);
+                }
+                DrawCommand::RichText { text, position, color, size } => {
+                    self.draw_rich_text(text, *position, *color, *size, text_renderer);
+                }
+                DrawCommand::StrokeRect { rect, color, width, radius } => {
+                    self.stroke_rect(*rect, *color, *width, *radius);
+                }
+                DrawCommand::Line { from, to, color, width } => {
+                    self.draw_line(*from, *to, *color, *width);
+                }
+                DrawCommand::Image { rect, image_id, fit, opacity } => {
+                    self.draw_image(*rect, *image_id, *fit, *opacity);
+                }
+                DrawCommand::Path { path, rect, color, viewbox } => {
+                    self.draw_path(path, *rect, *color, *viewbox);
+                }
+                DrawCommand::StrokePath { path, rect, color, width, viewbox } => {
+                    self.stroke_path(path, *rect, *color, *width, *viewbox);
+                }
+                DrawCommand::Shadow { rect, radius, offset, blur, spread, color, inset } => {
+                    self.draw_shadow(*rect, *radius, *offset, *blur, *spread, *color, *inset);
+                }
+                DrawCommand::PushClip { rect, radius } => {
+                    self.push_clip(*rect, *radius);
+                }
+                DrawCommand::PopClip => {
+                    self.pop_clip();
+                }
+            }
+        }
+    }
+
+    /// Clip drawing to a rounded rectangle, within the current clip, until
+    /// the matching `pop_clip`.
+    pub fn push_clip(&mut self, rect: Rect, radius: BorderRadius) {
+        let (bounds, mut rounded) = match self.clip_stack.last() {
+            Some(clip) => (clip.bounds, clip.rounded.clone()),
+            None => (Rect::from_origin_size(Point::ZERO, self.size), Vec::new()),
+        };
+        if !radius.is_zero() {
+            rounded.push((rect, radius));
+        }
+        let bounds = bounds.intersection(&rect).unwrap_or(Rect::ZERO);
+        self.clip_stack.push(Clip { bounds, rounded });
+    }
+
+    /// Remove the clip pushed last.
+    pub fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    /// The area drawing is limited to, in device pixels.
+    fn clip_bounds(&self) -> Rect {
+        self.clip_stack
+            .last()
+            .map_or(Rect::from_origin_size(Point::ZERO, self.size), |clip| clip.bounds)
+    }
+
+    /// How much of pixel (`x`, `y`) inside the clip bounds the rounded
+    /// corners of the clip let through, from 0 to 1.
+    fn clip_coverage(&self, x: i32, y: i32) -> f32 {
+        let Some(clip) = self.clip_stack.last() else {
+            return 1.0;
+        };
+        let center = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+        clip.rounded
+            .iter()
+            .map(|(rect, radius)| rounded_rect_coverage(*rect, *radius, center))
+            .product()
+    }
+
+    /// Draw with the skia-rs rasterizer, limited to the clip.
+    ///
+    /// The rasterizer only clips to rectangles, so under rounded clips the
+    /// drawing goes to a scratch buffer covering `area` and is blended in
+    /// through the clip.
+    fn rasterize(&mut self, area: Rect, draw: impl FnOnce(&mut Rasterizer)) {
+        let bounds = self.clip_bounds();
+        let rounded = self.clip_stack.last().is_some_and(|clip| !clip.rounded.is_empty());
+        if !rounded {
+            let mut rasterizer = Rasterizer::new(&mut self.pixel_buffer);
+            rasterizer.set_clip(SkiaRect::new(bounds.x(), bounds.y(), bounds.max_x(), bounds.max_y()));
+            draw(&mut rasterizer);
+            return;
+        }
+
+        let Some(area) = area.intersection(&bounds) else {
+            return;
+        };
+        let x0 = area.x().floor() as i32;
+        let y0 = area.y().floor() as i32;
+        let width = area.max_x().ceil() as i32 - x0;
+        let height = area.max_y().ceil() as i32 - y0;
+        let mut scratch = PixelBuffer::new(width, height);
+        {
+            let mut rasterizer = Rasterizer::new(&mut scratch);
+            rasterizer.set_matrix(&Matrix::translate(-x0 as f32, -y0 as f32));
+            draw(&mut rasterizer);
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let src_idx = ((y * width + x) * 4) as usize;
+                let src = [
+                    scratch.pixels[src_idx],
+                    scratch.pixels[src_idx + 1],
+                    scratch.pixels[src_idx + 2],
+                    scratch.pixels[src_idx + 3],
+                ];
+                self.blend_pixel(x0 + x, y0 + y, src, 1.0);
+            }
+        }
+    }
+
+    /// Get the size of the pixel buffer.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
     pub fn draw_rect(&mut self, rect: Rect, color: Color, radius: BorderRadius) {
-        let [r, g, b, a] = color.to_rgba8();
-        let _ = radius; // TODO: implement rounded corners
-        
-        // Manually fill pixels
-        let x_start = rect.x().max(0.0) as i32;
-        let y_start = rect.y().max(0.0) as i32;
-        let x_end = (rect.x() + rect.width()).min(self.size.width) as i32;
-        let y_end = (rect.y() + rect.height()).min(self.size.height) as i32;
-        let stride = self.pixel_buffer.width;
-        
-        // PixelBuffer.pixels is Vec<u8> in RGBA format (4 bytes per pixel)
-        for y in y_start..y_end {
-            for x in x_start..x_end {
-                let idx = ((y * stride + x) * 4) as usize;
-                if idx + 3 < self.pixel_buffer.pixels.len() {
-                    // Alpha blend if the color has transparency
-                    if a < 255 {
-                        let alpha = a as f32 / 255.0;
-                        let inv_alpha = 1.0 - alpha;
-                        self.pixel_buffer.pixels[idx] = (r as f32 * alpha + self.pixel_buffer.pixels[idx] as f32 * inv_alpha) as u8;
-                        self.pixel_buffer.pixels[idx + 1] = (g as f32 * alpha + self.pixel_buffer.pixels[idx + 1] as f32 * inv_alpha) as u8;
-                        self.pixel_buffer.pixels[idx + 2] = (b as f32 * alpha + self.pixel_buffer.pixels[idx + 2] as f32 * inv_alpha) as u8;
-                        self.pixel_buffer.pixels[idx + 3] = 255; // Result is opaque
-                    } else {
-                        self.pixel_buffer.pixels[idx] = r;
-                        self.pixel_buffer.pixels[idx + 1] = g;
-                        self.pixel_buffer.pixels[idx + 2] = b;
-                        self.pixel_buffer.pixels[idx + 3] = a;
-                    }
+        let rgba = color.to_rgba8();
+        let clip = self.clip_bounds();
+
+        // Square rectangles on whole pixels cover each pixel fully
+        let aligned = [rect.x(), rect.y(), rect.width(), rect.height()].iter().all(|v| v.fract() == 0.0);
+        if radius.is_zero() && aligned {
+            let Some(area) = rect.intersection(&clip) else {
+                return;
+            };
+            for y in area.y() as i32..area.max_y() as i32 {
+                for x in area.x() as i32..area.max_x() as i32 {
+                    self.blend_pixel(x, y, rgba, 1.0);
+                }
+            }
+            return;
+        }
+
+        let path = raster::rounded_rect_path(rect, radius);
+        raster::fill_path(&path, clip, |x, y, coverage| self.blend_pixel(x, y, rgba, coverage));
+    }
+
+    /// Draw the border of a rounded rectangle, `width` wide, inside `rect`.
+    pub fn stroke_rect(&mut self, rect: Rect, color: Color, width: f32, radius: BorderRadius) {
+        let rgba = color.to_rgba8();
+        let path = raster::rounded_border_path(rect, radius, width);
+        raster::fill_path(&path, self.clip_bounds(), |x, y, coverage| self.blend_pixel(x, y, rgba, coverage));
+    }
+
+    /// Draw an image from the `ImageCache`, placed in `rect` by `fit` and
+    /// cropped to it. Images that aren't decoded yet draw nothing.
+    pub fn draw_image(&mut self, rect: Rect, image_id: u64, fit: ObjectFit, opacity: f32) {
+        let Some(image) = ImageCache::global().get_id(image_id) else {
+            return;
+        };
+        let Some(area) = rect.intersection(&self.clip_bounds()) else {
+            return;
+        };
+        let placed = fit.place(image.size(), rect);
+        let (width, height) = (placed.width().round() as u32, placed.height().round() as u32);
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (left, top) = (placed.x().round() as i32, placed.y().round() as i32);
+        let (xs, ys) = (
+            area.x().round() as i32..area.max_x().round() as i32,
+            area.y().round() as i32..area.max_y().round() as i32,
+        );
+
+        // Scale only the visible part, so large or heavily cropped images
+        // cost no more than the area they cover. Tiles are drawn at their
+        // natural size.
+        let (columns, rows) = if fit == ObjectFit::Tile {
+            (0..width, 0..height)
+        } else {
+            let visible = |range: &Range<i32>, start: i32, len: u32| {
+                (range.start - start).clamp(0, len as i32) as u32..(range.end - start).clamp(0, len as i32) as u32
+            };
+            (visible(&xs, left, width), visible(&ys, top, height))
+        };
+        if columns.is_empty() || rows.is_empty() {
+            return;
+        }
+        let (part_left, part_top) = (left + columns.start as i32, top + rows.start as i32);
+        let image = self.scaled_image(image_id, image, (width, height), columns, rows);
+
+        for y in ys {
+            for x in xs.clone() {
+                let (mut image_x, mut image_y) = (x - part_left, y - part_top);
+                if fit == ObjectFit::Tile {
+                    image_x = image_x.rem_euclid(width as i32);
+                    image_y = image_y.rem_euclid(height as i32);
+                }
+                if image_x < 0 || image_y < 0 {
+                    continue;
+                }
+                if let Some([r, g, b, a]) = image.pixel(image_x as u32, image_y as u32) {
+                    self.blend_pixel(x, y, [r, g, b, (a as f32 * opacity).round() as u8], 1.0);
+                }
+            }
+        }
+    }
+
+    /// The `columns` and `rows` of `image` resized to `size`, reusing the
+    /// part resized for earlier frames.
+    fn scaled_image(
+        &mut self,
+        image_id: u64,
+        image: Arc<ImageData>,
+        size: (u32, u32),
+        columns: Range<u32>,
+        rows: Range<u32>,
+    ) -> Arc<ImageData> {
+        let (width, height) = size;
+        if (image.width(), image.height()) == size && (columns.len(), rows.len()) == (width as usize, height as usize) {
+            return image;
+        }
+        let frame = self.frame;
+        let scaled = self
+            .scaled_images
+            .entry((image_id, size, columns.clone(), rows.clone()))
+            .or_insert_with(|| ScaledImage {
+                image: Arc::new(image.resize_part(width, height, columns, rows)),
+                drawn: frame,
+            });
+        scaled.drawn = frame;
+        scaled.image.clone()
+    }
+
+    /// Fill SVG path data, mapping `viewbox` into `rect`.
+    pub fn draw_path(&mut self, data: &str, rect: Rect, color: Color, viewbox: (f32, f32, f32, f32)) {
+        let rgba = color.to_rgba8();
+        let path = svg_path::parse_path(data).transformed(&svg_path::viewbox_transform(viewbox, rect));
+        raster::fill_path(&path, self.clip_bounds(), |x, y, coverage| self.blend_pixel(x, y, rgba, coverage));
+    }
+
+    /// Stroke SVG path data `width` wide, in viewbox units, mapping `viewbox`
+    /// into `rect`.
+    pub fn stroke_path(&mut self, data: &str, rect: Rect, color: Color, width: f32, viewbox: (f32, f32, f32, f32)) {
+        let rgba = color.to_rgba8();
+        let transform = svg_path::viewbox_transform(viewbox, rect);
+        let path = svg_path::parse_path(data).transformed(&transform);
+        let outline = raster::stroke_path(&path, width * transform.scale_x());
+        raster::fill_path(&outline, self.clip_bounds(), |x, y, coverage| self.blend_pixel(x, y, rgba, coverage));
+    }
+
+    /// Draw the shadow of a rounded rectangle, blurred by `blur` as in CSS
+    /// `box-shadow`: outside `rect`, or inside it if `inset`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_shadow(
+        &mut self,
+        rect: Rect,
+        radius: BorderRadius,
+        offset: Point,
+        blur: f32,
+        spread: f32,
+        color: Color,
+        inset: bool,
+    ) {
+        let rgba = color.to_rgba8();
+        let clip = self.clip_bounds();
+        let Some(mask) = shadow::box_shadow_mask(rect, radius, offset, blur, spread, inset, clip) else {
+            return;
+        };
+        let mask_rect = Rect::new(mask.x as f32, mask.y as f32, mask.width as f32, mask.height as f32);
+        let Some(area) = mask_rect.intersection(&clip) else {
+            return;
+        };
+        for y in area.y().floor() as i32..area.max_y().ceil() as i32 {
+            for x in area.x().floor() as i32..area.max_x().ceil() as i32 {
+                let coverage = mask.get(x, y);
+                if coverage > 0.0 {
+                    self.blend_pixel(x, y, rgba, coverage);
                 }
             }
         }
     }
 
+    /// Blend `color` over pixel (`x`, `y`) with `coverage`, and the clip's,
+    /// scaling its alpha.
+    fn blend_pixel(&mut self, x: i32, y: i32, color: [u8; 4], coverage: f32) {
+        if x < 0 || y < 0 || x >= self.pixel_buffer.width || y >= self.pixel_buffer.height {
+            return;
+        }
+        let alpha = color[3] as f32 / 255.0 * coverage * self.clip_coverage(x, y);
+        if alpha <= 0.0 {
+            return;
+        }
+
+        let idx = ((y * self.pixel_buffer.width + x) * 4) as usize;
+        let dest = &mut self.pixel_buffer.pixels[idx..idx + 4];
+        if alpha >= 1.0 {
+            dest.copy_from_slice(&[color[0], color[1], color[2], 255]);
+            return;
+        }
+
+        // Source-over on unpremultiplied colors
+        let dest_alpha = dest[3] as f32 / 255.0 * (1.0 - alpha);
+        let out_alpha = alpha + dest_alpha;
+        for channel in 0..3 {
+            dest[channel] = ((color[channel] as f32 * alpha + dest[channel] as f32 * dest_alpha) / out_alpha).round() as u8;
+        }
+        dest[3] = (out_alpha * 255.0).round() as u8;
+    }
+
     pub fn draw_line(&mut self, from: Point, to: Point, color: Color, width: f32) {
         let [r, g, b, a] = color.to_rgba8();
         let skia_color = SkiaColor::from_argb(a, r, g, b);
@@ -495,11 +802,11 @@ impl CpuRenderer {
         paint.set_stroke_width(width);
         paint.set_anti_alias(true);
 
-        let mut rasterizer = Rasterizer::new(&mut self.pixel_buffer);
-
         let p0 = skia_rs_safe::core::Point::new(from.x, from.y);
         let p1 = skia_rs_safe::core::Point::new(to.x, to.y);
-        rasterizer.draw_line(p0, p1, &paint);
+        let area = Rect::new(from.x.min(to.x), from.y.min(to.y), (to.x - from.x).abs(), (to.y - from.y).abs())
+            .inset(-width - 1.0);
+        self.rasterize(area, |rasterizer| rasterizer.draw_line(p0, p1, &paint));
     }
 
     pub fn draw_text(&mut self, text: &str, position: Point, color: Color, size: f32, text_renderer: &mut TextRenderer) {
@@ -511,7 +818,21 @@ impl CpuRenderer {
         
         // Rasterize the text using cosmic-text
         let (text_width, text_height, text_pixels) = text_renderer.rasterize(text, size, [r, g, b, a]);
-        
+        self.blit_text(position, size, text_width, text_height, &text_pixels);
+    }
+
+    pub fn draw_rich_text(&mut self, text: &RichText, position: Point, color: Color, size: f32, text_renderer: &mut TextRenderer) {
+        if text.is_empty() {
+            return;
+        }
+
+        let (text_width, text_height, text_pixels) = text_renderer.rasterize_rich(text, size, color.to_rgba8());
+        self.blit_text(position, size, text_width, text_height, &text_pixels);
+    }
+
+    /// Copy rasterized text onto the pixel buffer, blending it over what's
+    /// there.
+    fn blit_text(&mut self, position: Point, size: f32, text_width: u32, text_height: u32, text_pixels: &[u8]) {
         if text_width == 0 || text_height == 0 {
             return;
         }
@@ -521,7 +842,9 @@ impl CpuRenderer {
         let dest_x = position.x as i32;
         let dest_y = (position.y - size * 0.8) as i32;
         let stride = self.pixel_buffer.width;
-        let dest_pixels = &mut self.pixel_buffer.pixels;
+        let clip = self.clip_bounds();
+        let (clip_x, clip_y) = (clip.x() as i32, clip.y() as i32);
+        let (clip_max_x, clip_max_y) = (clip.max_x() as i32, clip.max_y() as i32);
         
         for ty in 0..text_height as i32 {
             for tx in 0..text_width as i32 {
@@ -529,19 +852,22 @@ impl CpuRenderer {
                 let dx = dest_x + tx;
                 let dy = dest_y + ty;
                 
-                if dx >= 0 && dy >= 0 && dx < stride && dy < (self.size.height as i32) {
+                if dx >= clip_x && dy >= clip_y && dx < clip_max_x && dy < clip_max_y {
                     let dest_idx = ((dy * stride + dx) * 4) as usize;
                     
-                    if src_idx + 3 < text_pixels.len() && dest_idx + 3 < dest_pixels.len() {
-                        let src_a = text_pixels[src_idx + 3] as f32 / 255.0;
+                    if src_idx + 3 < text_pixels.len() && dest_idx + 3 < self.pixel_buffer.pixels.len() {
+                        // Text pixels are premultiplied, so clipping scales them all
+                        let coverage = self.clip_coverage(dx, dy);
+                        let src_a = text_pixels[src_idx + 3] as f32 / 255.0 * coverage;
                         
                         if src_a > 0.0 {
                             // Alpha blend
                             let inv_a = 1.0 - src_a;
-                            dest_pixels[dest_idx] = (text_pixels[src_idx] as f32 + dest_pixels[dest_idx] as f32 * inv_a) as u8;
-                            dest_pixels[dest_idx + 1] = (text_pixels[src_idx + 1] as f32 + dest_pixels[dest_idx + 1] as f32 * inv_a) as u8;
-                            dest_pixels[dest_idx + 2] = (text_pixels[src_idx + 2] as f32 + dest_pixels[dest_idx + 2] as f32 * inv_a) as u8;
-                            dest_pixels[dest_idx + 3] = (text_pixels[src_idx + 3] as f32 + dest_pixels[dest_idx + 3] as f32 * inv_a) as u8;
+                            let dest_pixels = &mut self.pixel_buffer.pixels;
+                            dest_pixels[dest_idx] = (text_pixels[src_idx] as f32 * coverage + dest_pixels[dest_idx] as f32 * inv_a) as u8;
+                            dest_pixels[dest_idx + 1] = (text_pixels[src_idx + 1] as f32 * coverage + dest_pixels[dest_idx + 1] as f32 * inv_a) as u8;
+                            dest_pixels[dest_idx + 2] = (text_pixels[src_idx + 2] as f32 * coverage + dest_pixels[dest_idx + 2] as f32 * inv_a) as u8;
+                            dest_pixels[dest_idx + 3] = (text_pixels[src_idx + 3] as f32 * coverage + dest_pixels[dest_idx + 3] as f32 * inv_a) as u8;
                         }
                     }
                 }
@@ -556,12 +882,45 @@ impl Default for CpuRenderer {
     }
 }
 
+/// How much of a pixel centered on `point` lies inside a rounded rectangle,
+/// from 0 to 1.
+fn rounded_rect_coverage(rect: Rect, radius: BorderRadius, point: Point) -> f32 {
+    // Distance from the edges, anti-aliased over one pixel
+    let edge = (point.x - rect.x())
+        .min(rect.max_x() - point.x)
+        .min(point.y - rect.y())
+        .min(rect.max_y() - point.y);
+    let mut coverage = (edge + 0.5).clamp(0.0, 1.0);
+
+    // Distance from the arc, for points in a corner
+    let left = point.x < rect.center().x;
+    let top = point.y < rect.center().y;
+    let r = match (left, top) {
+        (true, true) => radius.top_left,
+        (false, true) => radius.top_right,
+        (false, false) => radius.bottom_right,
+        (true, false) => radius.bottom_left,
+    };
+    let r = r.min(rect.width() / 2.0).min(rect.height() / 2.0);
+    if r > 0.0 {
+        let cx = if left { rect.x() + r } else { rect.max_x() - r };
+        let cy = if top { rect.y() + r } else { rect.max_y() - r };
+        let in_corner = (left == (point.x < cx)) && (top == (point.y < cy));
+        if in_corner {
+            let distance = ((point.x - cx).powi(2) + (point.y - cy).powi(2)).sqrt();
+            coverage = coverage.min((r - distance + 0.5).clamp(0.0, 1.0));
+        }
+    }
+    coverage
+}
+
 /// Render error types.
 #[derive(Debug, Clone)]
 pub enum RenderError {
     SurfaceCreation(String),
     NoAdapter,
     DeviceCreation(String),
+    ImageEncode(String),
 }
 
 impl std::fmt::Display for RenderError {
@@ -570,6 +929,7 @@ impl std::fmt::Display for RenderError {
             RenderError::SurfaceCreation(e) => write!(f, "Failed to create surface: {}", e),
             RenderError::NoAdapter => write!(f, "No suitable GPU adapter found"),
             RenderError::DeviceCreation(e) => write!(f, "Failed to create device: {}", e),
+            RenderError::ImageEncode(e) => write!(f, "Failed to encode image: {}", e),
         }
     }
 }
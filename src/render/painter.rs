@@ -1,6 +1,12 @@
 //! Painter API for drawing primitives.
 
+use super::{ObjectFit, RichText, SpanStyle};
 use crate::geometry::{BorderRadius, Color, Point, Rect};
+use crate::theme::BoxShadow;
+
+/// Viewbox of icons given as bare SVG path data, the 24 unit grid of most
+/// icon sets.
+pub const ICON_VIEWBOX: (f32, f32, f32, f32) = (0.0, 0.0, 24.0, 24.0);
 
 /// A painter for drawing primitives.
 #[derive(Debug, Default)]
@@ -8,6 +14,10 @@ pub struct Painter {
     commands: Vec<DrawCommand>,
     clip_stack: Vec<Rect>,
     transform_stack: Vec<Transform>,
+    /// Accumulated group opacities
+    opacity_stack: Vec<f32>,
+    /// Fonts of the widgets being painted, the innermost last
+    font_stack: Vec<SpanStyle>,
 }
 
 impl Painter {
@@ -16,6 +26,8 @@ impl Painter {
             commands: Vec::new(),
             clip_stack: Vec::new(),
             transform_stack: vec![Transform::identity()],
+            opacity_stack: Vec::new(),
+            font_stack: Vec::new(),
         }
     }
 
@@ -37,6 +49,7 @@ impl Painter {
     /// Draw a filled rounded rectangle.
     pub fn fill_rounded_rect(&mut self, rect: Rect, color: Color, radius: BorderRadius) {
         let rect = self.transform_rect(rect);
+        let color = self.apply_opacity(color);
         self.commands.push(DrawCommand::Rect { rect, color, radius });
     }
 
@@ -45,48 +58,73 @@ impl Painter {
         self.stroke_rounded_rect(rect, color, width, BorderRadius::ZERO);
     }
 
-    /// Draw a stroked rounded rectangle.
-    /// Note: Currently draws as a regular rectangle stroke; full rounded support is TODO.
-    pub fn stroke_rounded_rect(&mut self, rect: Rect, color: Color, width: f32, _radius: BorderRadius) {
+    /// Draw the border of a rounded rectangle, `width` wide, inside `rect`.
+    pub fn stroke_rounded_rect(&mut self, rect: Rect, color: Color, width: f32, radius: BorderRadius) {
         let rect = self.transform_rect(rect);
-        // Draw four lines for the stroke
-        // TODO: Implement actual rounded corner stroking
-        self.draw_line(
-            Point::new(rect.x(), rect.y()),
-            Point::new(rect.max_x(), rect.y()),
-            color,
-            width,
-        );
-        self.draw_line(
-            Point::new(rect.max_x(), rect.y()),
-            Point::new(rect.max_x(), rect.max_y()),
-            color,
-            width,
-        );
-        self.draw_line(
-            Point::new(rect.max_x(), rect.max_y()),
-            Point::new(rect.x(), rect.max_y()),
+        let scale = self.transform_stack.last().map_or(1.0, |t| t.sx.min(t.sy));
+        let color = self.apply_opacity(color);
+        self.commands.push(DrawCommand::StrokeRect {
+            rect,
             color,
-            width,
-        );
-        self.draw_line(
-            Point::new(rect.x(), rect.max_y()),
-            Point::new(rect.x(), rect.y()),
+            width: width * scale,
+            radius: radius.scale(scale),
+        });
+    }
+
+    /// Draw a box shadow of a rounded rectangle.
+    ///
+    /// Outer shadows belong before the rectangle's background and inset ones
+    /// after it.
+    pub fn draw_shadow(&mut self, rect: Rect, radius: BorderRadius, shadow: &BoxShadow) {
+        let (sx, sy) = self.transform_stack.last().map_or((1.0, 1.0), |t| (t.sx, t.sy));
+        let scale = sx.min(sy);
+        let rect = self.transform_rect(rect);
+        let color = self.apply_opacity(shadow.color);
+        if color.a <= 0.0 {
+            return;
+        }
+        self.commands.push(DrawCommand::Shadow {
+            rect,
+            radius: radius.scale(scale),
+            offset: Point::new(shadow.offset_x * sx, shadow.offset_y * sy),
+            blur: shadow.blur_radius.max(0.0) * scale,
+            spread: shadow.spread_radius * scale,
             color,
-            width,
-        );
+            inset: shadow.inset,
+        });
+    }
+
+    /// Draw the outer shadows in a `box-shadow` list, the first on top.
+    pub fn draw_shadows(&mut self, rect: Rect, radius: BorderRadius, shadows: &[BoxShadow]) {
+        for shadow in shadows.iter().rev().filter(|s| !s.inset) {
+            self.draw_shadow(rect, radius, shadow);
+        }
+    }
+
+    /// Draw the inset shadows in a `box-shadow` list, the first on top.
+    pub fn draw_inset_shadows(&mut self, rect: Rect, radius: BorderRadius, shadows: &[BoxShadow]) {
+        for shadow in shadows.iter().rev().filter(|s| s.inset) {
+            self.draw_shadow(rect, radius, shadow);
+        }
     }
 
     /// Draw a line.
     pub fn draw_line(&mut self, from: Point, to: Point, color: Color, width: f32) {
         let from = self.transform_point(from);
         let to = self.transform_point(to);
+        let color = self.apply_opacity(color);
         self.commands.push(DrawCommand::Line { from, to, color, width });
     }
 
     /// Draw text.
     pub fn draw_text(&mut self, text: &str, position: Point, color: Color, size: f32) {
+        if self.font().is_some_and(|font| *font != SpanStyle::default()) {
+            self.draw_rich_text(&RichText::plain(text), position, color, size);
+            return;
+        }
         let position = self.transform_point(position);
+        let size = self.transform_stack.last().map_or(size, |t| size * t.sy);
+        let color = self.apply_opacity(color);
         self.commands.push(DrawCommand::Text {
             text: text.to_string(),
             position,
@@ -95,10 +133,43 @@ impl Painter {
         });
     }
 
-    /// Draw an image.
+    /// Draw rich text, in `color` where spans don't set one.
+    pub fn draw_rich_text(&mut self, text: &RichText, position: Point, color: Color, size: f32) {
+        let position = self.transform_point(position);
+        let size = self.transform_stack.last().map_or(size, |t| size * t.sy);
+        let color = self.apply_opacity(color);
+        let mut text = match self.font() {
+            Some(font) => text.clone().with_font(font),
+            None => text.clone(),
+        };
+        for span in text.spans_mut() {
+            span.style.color = span.style.color.map(|color| self.apply_opacity(color));
+            span.style.background = span.style.background.map(|color| self.apply_opacity(color));
+        }
+        self.commands.push(DrawCommand::RichText {
+            text,
+            position,
+            color,
+            size,
+        });
+    }
+
+    /// Draw an image from the `ImageCache`, stretched to `rect`.
     pub fn draw_image(&mut self, rect: Rect, image_id: u64) {
+        self.draw_image_fit(rect, image_id, ObjectFit::Fill);
+    }
+
+    /// Draw an image from the `ImageCache`, sized and placed in `rect` by
+    /// `fit` and cropped to it.
+    pub fn draw_image_fit(&mut self, rect: Rect, image_id: u64, fit: ObjectFit) {
         let rect = self.transform_rect(rect);
-        self.commands.push(DrawCommand::Image { rect, image_id });
+        let opacity = self.opacity();
+        self.commands.push(DrawCommand::Image {
+            rect,
+            image_id,
+            fit,
+            opacity,
+        });
     }
 
     /// Draw an SVG path icon at the specified position and size.
@@ -110,6 +181,7 @@ impl Painter {
     /// Draw an SVG path within the specified rectangle.
     pub fn draw_path(&mut self, path: &str, rect: Rect, color: Color, viewbox: (f32, f32, f32, f32)) {
         let rect = self.transform_rect(rect);
+        let color = self.apply_opacity(color);
         self.commands.push(DrawCommand::Path {
             path: path.to_string(),
             rect,
@@ -118,15 +190,45 @@ impl Painter {
         });
     }
 
-    /// Push a clip rectangle.
+    /// Stroke an SVG path within the specified rectangle, `width` wide in
+    /// viewbox units.
+    pub fn stroke_path(&mut self, path: &str, rect: Rect, color: Color, width: f32, viewbox: (f32, f32, f32, f32)) {
+        let rect = self.transform_rect(rect);
+        let color = self.apply_opacity(color);
+        self.commands.push(DrawCommand::StrokePath {
+            path: path.to_string(),
+            rect,
+            color,
+            width,
+            viewbox,
+        });
+    }
+
+    /// Clip drawing to a rectangle until `pop_clip`.
     pub fn push_clip(&mut self, rect: Rect) {
+        self.push_rounded_clip(rect, BorderRadius::ZERO);
+    }
+
+    /// Clip drawing to a rounded rectangle until `pop_clip`.
+    pub fn push_rounded_clip(&mut self, rect: Rect, radius: BorderRadius) {
         let rect = self.transform_rect(rect);
+        let radius = radius.scale(self.transform_stack.last().map_or(1.0, |t| t.sx.min(t.sy)));
         self.clip_stack.push(rect);
+        self.commands.push(DrawCommand::PushClip { rect, radius });
     }
 
-    /// Pop the clip rectangle.
+    /// Pop the clip pushed last.
     pub fn pop_clip(&mut self) {
-        self.clip_stack.pop();
+        if self.clip_stack.pop().is_some() {
+            self.commands.push(DrawCommand::PopClip);
+        }
+    }
+
+    /// The area drawing is currently clipped to, in device coordinates.
+    pub fn clip_bounds(&self) -> Option<Rect> {
+        let mut clips = self.clip_stack.iter();
+        let first = *clips.next()?;
+        Some(clips.fold(first, |clip, rect| clip.intersection(rect).unwrap_or(Rect::ZERO)))
     }
 
     /// Save the current transform.
@@ -146,8 +248,8 @@ impl Painter {
     /// Translate the coordinate system.
     pub fn translate(&mut self, dx: f32, dy: f32) {
         if let Some(transform) = self.transform_stack.last_mut() {
-            transform.tx += dx;
-            transform.ty += dy;
+            transform.tx += dx * transform.sx;
+            transform.ty += dy * transform.sy;
         }
     }
 
@@ -159,6 +261,46 @@ impl Painter {
         }
     }
 
+    /// Multiply the opacity of everything drawn until `pop_opacity`.
+    pub fn push_opacity(&mut self, opacity: f32) {
+        let current = self.opacity();
+        self.opacity_stack.push(current * opacity.clamp(0.0, 1.0));
+    }
+
+    /// Restore the opacity before the last `push_opacity`.
+    pub fn pop_opacity(&mut self) {
+        self.opacity_stack.pop();
+    }
+
+    /// Draw text in `font` until `pop_font`, where it doesn't set its own.
+    pub fn push_font(&mut self, font: SpanStyle) {
+        self.font_stack.push(font);
+    }
+
+    /// Restore the font before the last `push_font`.
+    pub fn pop_font(&mut self) {
+        self.font_stack.pop();
+    }
+
+    /// Get the font text is currently drawn in, if one was pushed.
+    pub fn font(&self) -> Option<&SpanStyle> {
+        self.font_stack.last()
+    }
+
+    /// Get the current group opacity.
+    pub fn opacity(&self) -> f32 {
+        self.opacity_stack.last().copied().unwrap_or(1.0)
+    }
+
+    fn apply_opacity(&self, color: Color) -> Color {
+        let opacity = self.opacity();
+        if opacity < 1.0 {
+            color.with_alpha(color.a * opacity)
+        } else {
+            color
+        }
+    }
+
     fn transform_point(&self, point: Point) -> Point {
         if let Some(t) = self.transform_stack.last() {
             Point::new(point.x * t.sx + t.tx, point.y * t.sy + t.ty)
@@ -167,7 +309,8 @@ impl Painter {
         }
     }
 
-    fn transform_rect(&self, rect: Rect) -> Rect {
+    /// Map a rectangle through the current transform to device coordinates.
+    pub fn transform_rect(&self, rect: Rect) -> Rect {
         let origin = self.transform_point(rect.origin);
         if let Some(t) = self.transform_stack.last() {
             Rect::new(origin.x, origin.y, rect.width() * t.sx, rect.height() * t.sy)
@@ -185,28 +328,70 @@ pub enum DrawCommand {
         color: Color,
         radius: BorderRadius,
     },
+    /// The border of a rounded rectangle, `width` wide, inside `rect`
+    StrokeRect {
+        rect: Rect,
+        color: Color,
+        width: f32,
+        radius: BorderRadius,
+    },
     Text {
         text: String,
         position: Point,
         color: Color,
         size: f32,
     },
+    RichText {
+        text: RichText,
+        position: Point,
+        color: Color,
+        size: f32,
+    },
     Line {
         from: Point,
         to: Point,
         color: Color,
         width: f32,
     },
+    /// An image from the `ImageCache`, cropped to `rect`
     Image {
         rect: Rect,
         image_id: u64,
+        fit: ObjectFit,
+        opacity: f32,
     },
+    /// SVG path data filled with the nonzero rule, `viewbox` mapped into `rect`
     Path {
         path: String,
         rect: Rect,
         color: Color,
         viewbox: (f32, f32, f32, f32),
     },
+    /// SVG path data stroked `width` wide in viewbox units
+    StrokePath {
+        path: String,
+        rect: Rect,
+        color: Color,
+        width: f32,
+        viewbox: (f32, f32, f32, f32),
+    },
+    /// A shadow cast by a rounded rectangle, blurred by `blur`, outside
+    /// `rect` or inside it if `inset`
+    Shadow {
+        rect: Rect,
+        radius: BorderRadius,
+        offset: Point,
+        blur: f32,
+        spread: f32,
+        color: Color,
+        inset: bool,
+    },
+    /// Clip the commands up to the matching `PopClip` to a rounded rectangle
+    PushClip {
+        rect: Rect,
+        radius: BorderRadius,
+    },
+    PopClip,
 }
 
 /// A 2D transform (translation + scale).
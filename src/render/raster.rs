@@ -0,0 +1,365 @@
+//! Anti-aliased path filling for the CPU renderer.
+//!
+//! The skia-rs rasterizer fills paths with one aliased scanline per pixel
+//! row. Here each row is sampled at several heights and the horizontal
+//! coverage of every sample is accumulated exactly, which gives smooth edges
+//! on curves, diagonals and fractional positions.
+
+use crate::geometry::{BorderRadius, Rect};
+use skia_rs_safe::core::Point as SkiaPoint;
+use skia_rs_safe::path::{FillType, Path, PathBuilder, PathElement};
+use std::f32::consts::TAU;
+
+/// Scanlines sampled per pixel row.
+const SAMPLES: usize = 4;
+
+/// Control point distance, relative to the radius, of a cubic that
+/// approximates a quarter circle.
+const KAPPA: f32 = 0.552_284_8;
+
+/// A path of a rectangle with rounded corners.
+///
+/// Radii that don't fit are scaled down together, as in CSS.
+pub(super) fn rounded_rect_path(rect: Rect, radius: BorderRadius) -> Path {
+    let mut builder = PathBuilder::new();
+    add_rounded_rect(&mut builder, rect, radius);
+    builder.build()
+}
+
+/// A path of the border of a rounded rectangle, `width` wide, inside `rect`.
+pub(super) fn rounded_border_path(rect: Rect, radius: BorderRadius, width: f32) -> Path {
+    let mut builder = PathBuilder::with_fill_type(FillType::EvenOdd);
+    add_rounded_rect(&mut builder, rect, radius);
+    let inner = rect.inset(width);
+    if inner.width() > 0.0 && inner.height() > 0.0 {
+        let inner_radius = BorderRadius::new(
+            (radius.top_left - width).max(0.0),
+            (radius.top_right - width).max(0.0),
+            (radius.bottom_right - width).max(0.0),
+            (radius.bottom_left - width).max(0.0),
+        );
+        add_rounded_rect(&mut builder, inner, inner_radius);
+    }
+    builder.build()
+}
+
+fn add_rounded_rect(builder: &mut PathBuilder, rect: Rect, radius: BorderRadius) {
+    let (left, top, right, bottom) = (rect.x(), rect.y(), rect.max_x(), rect.max_y());
+
+    // Scale the radii down if adjacent corners overlap
+    let fit = |length: f32, a: f32, b: f32| if a + b > length { length / (a + b) } else { 1.0 };
+    let scale = fit(rect.width(), radius.top_left, radius.top_right)
+        .min(fit(rect.width(), radius.bottom_left, radius.bottom_right))
+        .min(fit(rect.height(), radius.top_left, radius.bottom_left))
+        .min(fit(rect.height(), radius.top_right, radius.bottom_right));
+    let radius = radius.scale(scale);
+    let (tl, tr, br, bl) = (radius.top_left, radius.top_right, radius.bottom_right, radius.bottom_left);
+
+    builder.move_to(left + tl, top);
+    builder.line_to(right - tr, top);
+    if tr > 0.0 {
+        let k = tr * KAPPA;
+        builder.cubic_to(right - tr + k, top, right, top + tr - k, right, top + tr);
+    }
+    builder.line_to(right, bottom - br);
+    if br > 0.0 {
+        let k = br * KAPPA;
+        builder.cubic_to(right, bottom - br + k, right - br + k, bottom, right - br, bottom);
+    }
+    builder.line_to(left + bl, bottom);
+    if bl > 0.0 {
+        let k = bl * KAPPA;
+        builder.cubic_to(left + bl - k, bottom, left, bottom - bl + k, left, bottom - bl);
+    }
+    builder.line_to(left, top + tl);
+    if tl > 0.0 {
+        let k = tl * KAPPA;
+        builder.cubic_to(left, top + tl - k, left + tl - k, top, left + tl, top);
+    }
+    builder.close();
+}
+
+/// A straight piece of a flattened path, top to bottom.
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    /// +1 if the path runs downwards here, -1 if upwards
+    winding: i32,
+}
+
+impl Edge {
+    fn new(from: SkiaPoint, to: SkiaPoint) -> Option<Self> {
+        if from.y == to.y {
+            return None;
+        }
+        let (top, bottom, winding) = if from.y < to.y { (from, to, 1) } else { (to, from, -1) };
+        Some(Self {
+            x0: top.x,
+            y0: top.y,
+            x1: bottom.x,
+            y1: bottom.y,
+            winding,
+        })
+    }
+
+    /// Where the edge crosses the scanline at `y`, if it does.
+    fn crossing(&self, y: f32) -> Option<f32> {
+        if y < self.y0 || y >= self.y1 {
+            return None;
+        }
+        Some(self.x0 + (y - self.y0) / (self.y1 - self.y0) * (self.x1 - self.x0))
+    }
+}
+
+/// Number of lines to split a curve into, from the length of its control
+/// polygon.
+fn segments(points: &[SkiaPoint]) -> usize {
+    let length: f32 = points
+        .windows(2)
+        .map(|pair| ((pair[1].x - pair[0].x).powi(2) + (pair[1].y - pair[0].y).powi(2)).sqrt())
+        .sum();
+    ((length / 2.0).ceil() as usize).clamp(2, 64)
+}
+
+/// A flattened subpath.
+struct Contour {
+    points: Vec<SkiaPoint>,
+    closed: bool,
+}
+
+/// Flatten the curves of `path` into lines, one polyline per subpath.
+fn contours(path: &Path) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    let mut points: Vec<SkiaPoint> = Vec::new();
+    let mut start = SkiaPoint::new(0.0, 0.0);
+    let mut closed = false;
+    let finish = |contours: &mut Vec<Contour>, points: &mut Vec<SkiaPoint>, closed: bool| {
+        if !points.is_empty() {
+            contours.push(Contour {
+                points: std::mem::take(points),
+                closed,
+            });
+        }
+    };
+
+    for element in path.iter() {
+        if let PathElement::Move(p) = element {
+            finish(&mut contours, &mut points, closed);
+            closed = false;
+            start = p;
+            points.push(p);
+            continue;
+        }
+        if closed || points.is_empty() {
+            // Drawing on after a close starts a new subpath where it began
+            finish(&mut contours, &mut points, closed);
+            closed = false;
+            points.push(start);
+        }
+        let current = *points.last().unwrap_or(&start);
+
+        match element {
+            PathElement::Line(p) => points.push(p),
+            PathElement::Quad(c, p) => {
+                let n = segments(&[current, c, p]);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let mt = 1.0 - t;
+                    points.push(SkiaPoint::new(
+                        mt * mt * current.x + 2.0 * mt * t * c.x + t * t * p.x,
+                        mt * mt * current.y + 2.0 * mt * t * c.y + t * t * p.y,
+                    ));
+                }
+            }
+            PathElement::Conic(c, p, w) => {
+                let n = segments(&[current, c, p]);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let mt = 1.0 - t;
+                    let (a, b, d) = (mt * mt, 2.0 * w * mt * t, t * t);
+                    let denominator = a + b + d;
+                    points.push(SkiaPoint::new(
+                        (a * current.x + b * c.x + d * p.x) / denominator,
+                        (a * current.y + b * c.y + d * p.y) / denominator,
+                    ));
+                }
+            }
+            PathElement::Cubic(c1, c2, p) => {
+                let n = segments(&[current, c1, c2, p]);
+                for i in 1..=n {
+                    let t = i as f32 / n as f32;
+                    let mt = 1.0 - t;
+                    let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+                    points.push(SkiaPoint::new(
+                        a * current.x + b * c1.x + c * c2.x + d * p.x,
+                        a * current.y + b * c1.y + c * c2.y + d * p.y,
+                    ));
+                }
+            }
+            PathElement::Close => closed = true,
+            PathElement::Move(_) => {}
+        }
+    }
+    finish(&mut contours, &mut points, closed);
+    contours
+}
+
+/// Flatten `path` into edges, closing open contours as fills do.
+fn flatten(path: &Path) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for contour in contours(path) {
+        let points = &contour.points;
+        let closing = points.last().zip(points.first()).map(|(last, first)| [*last, *first]);
+        for pair in points.windows(2).chain(closing.as_ref().map(|pair| &pair[..])) {
+            if let Some(edge) = Edge::new(pair[0], pair[1]) {
+                edges.push(edge);
+            }
+        }
+    }
+    edges
+}
+
+/// The outline of `path` stroked `width` wide with round joins and caps,
+/// as a path to fill.
+pub(super) fn stroke_path(path: &Path, width: f32) -> Path {
+    let mut builder = PathBuilder::new();
+    let radius = width / 2.0;
+    if radius <= 0.0 {
+        return builder.build();
+    }
+
+    // The union of a quad per segment and a disc per point. Every piece is
+    // wound the same way so the nonzero fill merges them.
+    let mut add = |polygon: &[SkiaPoint]| {
+        let area: f32 = polygon
+            .iter()
+            .zip(polygon.iter().cycle().skip(1))
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum();
+        let mut ordered = polygon.to_vec();
+        if area < 0.0 {
+            ordered.reverse();
+        }
+        builder.move_to(ordered[0].x, ordered[0].y);
+        for point in &ordered[1..] {
+            builder.line_to(point.x, point.y);
+        }
+        builder.close();
+    };
+
+    let sides = ((radius * TAU).ceil() as usize).clamp(8, 64);
+    for contour in contours(path) {
+        let points = &contour.points;
+        let closing = if contour.closed { points.last().zip(points.first()).map(|(last, first)| [*last, *first]) } else { None };
+        for pair in points.windows(2).chain(closing.as_ref().map(|pair| &pair[..])) {
+            let (dx, dy) = (pair[1].x - pair[0].x, pair[1].y - pair[0].y);
+            let length = (dx * dx + dy * dy).sqrt();
+            if length == 0.0 {
+                continue;
+            }
+            let (nx, ny) = (-dy / length * radius, dx / length * radius);
+            add(&[
+                SkiaPoint::new(pair[0].x + nx, pair[0].y + ny),
+                SkiaPoint::new(pair[1].x + nx, pair[1].y + ny),
+                SkiaPoint::new(pair[1].x - nx, pair[1].y - ny),
+                SkiaPoint::new(pair[0].x - nx, pair[0].y - ny),
+            ]);
+        }
+        for point in points {
+            let disc: Vec<_> = (0..sides)
+                .map(|i| {
+                    let (sin, cos) = (i as f32 / sides as f32 * TAU).sin_cos();
+                    SkiaPoint::new(point.x + cos * radius, point.y + sin * radius)
+                })
+                .collect();
+            add(&disc);
+        }
+    }
+    builder.build()
+}
+
+/// Fill `path`, calling `plot` with the coverage, from 0 to 1, of every
+/// pixel it touches within `clip`.
+pub(super) fn fill_path(path: &Path, clip: Rect, mut plot: impl FnMut(i32, i32, f32)) {
+    let edges = flatten(path);
+    if edges.is_empty() {
+        return;
+    }
+    let even_odd = matches!(path.fill_type(), FillType::EvenOdd | FillType::InverseEvenOdd);
+
+    // Rows and columns the path and clip share
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    for edge in &edges {
+        min_x = min_x.min(edge.x0.min(edge.x1));
+        max_x = max_x.max(edge.x0.max(edge.x1));
+        min_y = min_y.min(edge.y0);
+        max_y = max_y.max(edge.y1);
+    }
+    let x_start = min_x.max(clip.x()).floor() as i32;
+    let x_end = max_x.min(clip.max_x()).ceil() as i32;
+    let y_start = min_y.max(clip.y()).floor() as i32;
+    let y_end = max_y.min(clip.max_y()).ceil() as i32;
+    if x_end <= x_start || y_end <= y_start {
+        return;
+    }
+
+    let width = (x_end - x_start) as usize;
+    let left = x_start as f32;
+    let mut row = vec![0.0_f32; width];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let weight = 1.0 / SAMPLES as f32;
+
+    for y in y_start..y_end {
+        row.fill(0.0);
+        for sample in 0..SAMPLES {
+            let sample_y = y as f32 + (sample as f32 + 0.5) * weight;
+            crossings.clear();
+            crossings.extend(
+                edges
+                    .iter()
+                    .filter_map(|edge| edge.crossing(sample_y).map(|x| (x, edge.winding))),
+            );
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Walk the crossings left to right, adding the spans inside
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = if even_odd { winding % 2 != 0 } else { winding != 0 };
+                if inside {
+                    add_span(&mut row, pair[0].0 - left, pair[1].0 - left, weight);
+                }
+            }
+        }
+
+        for (i, &coverage) in row.iter().enumerate() {
+            if coverage > 0.0 {
+                plot(x_start + i as i32, y, coverage.min(1.0));
+            }
+        }
+    }
+}
+
+/// Add `weight` times the coverage of the span from `a` to `b` to `row`.
+fn add_span(row: &mut [f32], a: f32, b: f32, weight: f32) {
+    let a = a.max(0.0);
+    let b = b.min(row.len() as f32);
+    if b <= a {
+        return;
+    }
+    let first = a.floor() as usize;
+    let last = b.floor() as usize;
+    if first == last {
+        row[first] += (b - a) * weight;
+        return;
+    }
+    row[first] += (first as f32 + 1.0 - a) * weight;
+    for coverage in &mut row[first + 1..last] {
+        *coverage += weight;
+    }
+    if last < row.len() {
+        row[last] += (b - last as f32) * weight;
+    }
+}
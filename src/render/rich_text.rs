@@ -0,0 +1,292 @@
+//! Attributed text: runs of text with their own weight, style, color,
+//! font and decorations.
+
+use crate::geometry::Color;
+use cosmic_text::{Attrs, Family, Style, Weight};
+use std::ops::Range;
+
+/// Style of one span of rich text.
+///
+/// Unset fields fall back to the font and color the text is drawn with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub color: Option<Color>,
+    /// Fill behind the span, e.g. for highlighted search matches
+    pub background: Option<Color>,
+    /// Font family name, or `monospace`, `serif` or `sans-serif`
+    pub font_family: Option<String>,
+    /// Numeric weight from 100 to 900, as in CSS `font-weight`; overrides `bold`
+    pub weight: Option<u16>,
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// Target reported when the span is clicked
+    pub link: Option<String>,
+}
+
+/// A run of text with one style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            style: SpanStyle::default(),
+        }
+    }
+
+    /// Use a bold weight.
+    pub fn bold(mut self) -> Self {
+        self.style.bold = true;
+        self
+    }
+
+    /// Use an italic style.
+    pub fn italic(mut self) -> Self {
+        self.style.italic = true;
+        self
+    }
+
+    /// Set the text color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+
+    /// Fill the background behind the span.
+    pub fn background(mut self, color: Color) -> Self {
+        self.style.background = Some(color);
+        self
+    }
+
+    /// Set the font family.
+    pub fn font_family(mut self, family: impl Into<String>) -> Self {
+        self.style.font_family = Some(family.into());
+        self
+    }
+
+    /// Use the monospace font, e.g. for inline code.
+    pub fn monospace(self) -> Self {
+        self.font_family("monospace")
+    }
+
+    /// Draw a line under the span.
+    pub fn underline(mut self) -> Self {
+        self.style.underline = true;
+        self
+    }
+
+    /// Draw a line through the span.
+    pub fn strikethrough(mut self) -> Self {
+        self.style.strikethrough = true;
+        self
+    }
+
+    /// Make the span a link to `target`.
+    pub fn link(mut self, target: impl Into<String>) -> Self {
+        self.style.link = Some(target.into());
+        self
+    }
+
+    /// Font attributes of the span, tagged with its index in the text.
+    pub(crate) fn attrs(&self, index: usize) -> Attrs<'_> {
+        self.style.attrs().metadata(index)
+    }
+}
+
+impl SpanStyle {
+    /// Font attributes for text in this style.
+    pub(crate) fn attrs(&self) -> Attrs<'_> {
+        let mut attrs = Attrs::new();
+        if let Some(weight) = self.weight {
+            attrs = attrs.weight(Weight(weight));
+        } else if self.bold {
+            attrs = attrs.weight(Weight::BOLD);
+        }
+        if self.italic {
+            attrs = attrs.style(Style::Italic);
+        }
+        if let Some(color) = self.color {
+            let [r, g, b, a] = color.to_rgba8();
+            attrs = attrs.color(cosmic_text::Color::rgba(r, g, b, a));
+        }
+        if let Some(family) = &self.font_family {
+            attrs = attrs.family(match family.as_str() {
+                "monospace" => Family::Monospace,
+                "serif" => Family::Serif,
+                "sans-serif" => Family::SansSerif,
+                name => Family::Name(name),
+            });
+        }
+        attrs
+    }
+}
+
+impl From<&str> for TextSpan {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TextSpan {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Text made of differently styled spans, drawn on one line.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use openkit::prelude::*;
+///
+/// let text = RichText::new()
+///     .push("Run ")
+///     .push(TextSpan::new("cargo test").monospace())
+///     .push(" to check, or see the ")
+///     .push(TextSpan::new("guide").link("https://example.com/guide"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    spans: Vec<TextSpan>,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unstyled text.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self::new().push(TextSpan::new(text))
+    }
+
+    /// `text` with the byte ranges in `matches` styled with `style`.
+    ///
+    /// Ranges must be in order and not overlap.
+    pub fn highlighted(text: &str, matches: &[Range<usize>], style: SpanStyle) -> Self {
+        let mut rich = Self::new();
+        let mut end = 0;
+        for range in matches {
+            if range.start > end {
+                rich = rich.push(&text[end..range.start]);
+            }
+            rich = rich.push(TextSpan {
+                text: text[range.clone()].to_string(),
+                style: style.clone(),
+            });
+            end = range.end;
+        }
+        if end < text.len() {
+            rich = rich.push(&text[end..]);
+        }
+        rich
+    }
+
+    /// Append a span.
+    pub fn push(mut self, span: impl Into<TextSpan>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    /// The spans, in order.
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Mutable access to the spans, e.g. to restyle them.
+    pub fn spans_mut(&mut self) -> &mut [TextSpan] {
+        &mut self.spans
+    }
+
+    /// This text with spans that don't set a family or weight taking them
+    /// from `font`, e.g. the widget's CSS font.
+    pub fn with_font(mut self, font: &SpanStyle) -> Self {
+        for span in &mut self.spans {
+            if span.style.font_family.is_none() {
+                span.style.font_family = font.font_family.clone();
+            }
+            if span.style.weight.is_none() && !span.style.bold {
+                span.style.weight = font.weight;
+            }
+        }
+        self
+    }
+
+    /// The text without styling.
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Whether there is no text.
+    pub fn is_empty(&self) -> bool {
+        self.spans.iter().all(|span| span.text.is_empty())
+    }
+
+    /// The span holding byte offset `offset` of the text.
+    pub fn span_at(&self, offset: usize) -> Option<&TextSpan> {
+        let mut start = 0;
+        self.spans.iter().find(|span| {
+            start += span.text.len();
+            offset < start
+        })
+    }
+
+    /// Text and font attributes of each span, for cosmic-text.
+    pub(crate) fn attrs(&self) -> impl Iterator<Item = (&str, Attrs<'_>)> {
+        self.spans
+            .iter()
+            .enumerate()
+            .map(|(index, span)| (span.text.as_str(), span.attrs(index)))
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        Self::plain(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlighted() {
+        let style = SpanStyle {
+            bold: true,
+            ..Default::default()
+        };
+        let rich = RichText::highlighted("find the needle in the stack", &[9..15, 23..28], style);
+
+        let texts: Vec<_> = rich.spans().iter().map(|span| span.text.as_str()).collect();
+        assert_eq!(texts, vec!["find the ", "needle", " in the ", "stack"]);
+        assert!(rich.spans()[1].style.bold);
+        assert!(!rich.spans()[2].style.bold);
+        assert_eq!(rich.text(), "find the needle in the stack");
+    }
+
+    #[test]
+    fn test_span_at() {
+        let rich = RichText::new()
+            .push("see ")
+            .push(TextSpan::new("docs").link("docs.html"));
+
+        assert_eq!(rich.span_at(0).unwrap().text, "see ");
+        assert_eq!(rich.span_at(4).unwrap().style.link.as_deref(), Some("docs.html"));
+        assert!(rich.span_at(8).is_none());
+        assert!(RichText::new().is_empty());
+    }
+}
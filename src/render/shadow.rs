@@ -0,0 +1,266 @@
+//! Blurred box shadows for the CPU renderer.
+//!
+//! The shadow shape is rasterized into a coverage mask and blurred with
+//! three box blurs in each direction, which together approximate a gaussian
+//! with a standard deviation of half the CSS blur radius.
+
+use super::raster;
+use crate::geometry::{BorderRadius, Point, Rect};
+
+/// Box blur passes used to approximate a gaussian.
+const PASSES: usize = 3;
+
+/// Largest blur radius drawn. The mask grows with the blur on every side,
+/// and wider blurs look much the same across a window.
+const MAX_BLUR: f32 = 300.0;
+
+/// Coverage of a box shadow, before its color is applied.
+pub(super) struct ShadowMask {
+    /// Device pixel of the first value
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    alpha: Vec<f32>,
+}
+
+impl ShadowMask {
+    /// Coverage of device pixel (`x`, `y`), from 0 to 1.
+    pub fn get(&self, x: i32, y: i32) -> f32 {
+        let (x, y) = (x - self.x, y - self.y);
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return 0.0;
+        }
+        self.alpha[(y * self.width + x) as usize]
+    }
+}
+
+/// The mask of a shadow cast by the rounded rectangle `rect`, as in CSS
+/// `box-shadow`.
+///
+/// Outer shadows are cut away under `rect`; inset shadows only fall inside
+/// it. Only the part that can reach `clip` is computed.
+pub(super) fn box_shadow_mask(
+    rect: Rect,
+    radius: BorderRadius,
+    offset: Point,
+    blur: f32,
+    spread: f32,
+    inset: bool,
+    clip: Rect,
+) -> Option<ShadowMask> {
+    let radii = blur_radii(blur.min(MAX_BLUR) / 2.0);
+    let reach = radii.iter().sum::<usize>() as f32 + 1.0;
+
+    // Past the span of the box and the clip, a larger spread changes nothing:
+    // the shape already covers everything the blur reads, or nothing at all
+    let shifted = rect.offset(offset.x, offset.y);
+    let read = clip.inset(-reach);
+    let span = (shifted.max_x().max(read.max_x()) - shifted.x().min(read.x()))
+        + (shifted.max_y().max(read.max_y()) - shifted.y().min(read.y()));
+    let spread = spread.clamp(-span, span);
+
+    // The shape is grown by the spread for outer shadows and shrunk for inset
+    // ones, and the corners with it
+    let spread = if inset { -spread } else { spread };
+    let shape = shifted.inset(-spread);
+    let corner = |r: f32| if r > 0.0 { (r + spread).max(0.0) } else { 0.0 };
+    let shape_radius = BorderRadius::new(
+        corner(radius.top_left),
+        corner(radius.top_right),
+        corner(radius.bottom_right),
+        corner(radius.bottom_left),
+    );
+
+    // Inset shadows stay inside `rect`; the blur reads `reach` pixels around
+    let area = if inset { rect } else { shape };
+    let area = area.inset(-reach).intersection(&clip.inset(-reach))?;
+    let x = area.x().floor() as i32;
+    let y = area.y().floor() as i32;
+    let width = area.max_x().ceil() as i32 - x;
+    let height = area.max_y().ceil() as i32 - y;
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+    let bounds = Rect::new(x as f32, y as f32, width as f32, height as f32);
+    let coverage = |path| {
+        let mut values = vec![0.0_f32; (width * height) as usize];
+        raster::fill_path(&path, bounds, |px, py, c| values[((py - y) * width + px - x) as usize] = c);
+        values
+    };
+
+    let mut alpha = if shape.width() > 0.0 && shape.height() > 0.0 {
+        coverage(raster::rounded_rect_path(shape, shape_radius))
+    } else if inset {
+        vec![0.0; (width * height) as usize]
+    } else {
+        return None;
+    };
+    if inset {
+        alpha.iter_mut().for_each(|a| *a = 1.0 - *a);
+    }
+    blur_mask(&mut alpha, width as usize, height as usize, &radii);
+
+    let under = coverage(raster::rounded_rect_path(rect, radius));
+    for (a, under) in alpha.iter_mut().zip(under) {
+        *a *= if inset { under } else { 1.0 - under };
+    }
+
+    Some(ShadowMask {
+        x,
+        y,
+        width,
+        height,
+        alpha,
+    })
+}
+
+/// Radii of the box blurs that together approximate a gaussian blur with
+/// standard deviation `sigma`.
+fn blur_radii(sigma: f32) -> [usize; PASSES] {
+    if sigma < 0.5 {
+        return [0; PASSES];
+    }
+    // Odd box widths just below and above the ideal one, mixed so the
+    // variances add up to sigma squared
+    let n = PASSES as f32;
+    let variance = 12.0 * sigma * sigma;
+    let mut lower = (variance / n + 1.0).sqrt().floor() as usize;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let l = lower as f32;
+    let narrow = ((variance - n * l * l - 4.0 * n * l - 3.0 * n) / (-4.0 * l - 4.0)).round();
+    let mut radii = [0; PASSES];
+    for (i, radius) in radii.iter_mut().enumerate() {
+        let width = if (i as f32) < narrow { lower } else { lower + 2 };
+        *radius = (width - 1) / 2;
+    }
+    radii
+}
+
+/// Blur a `width` by `height` mask with a box blur of each radius, across
+/// and then down.
+fn blur_mask(alpha: &mut [f32], width: usize, height: usize, radii: &[usize]) {
+    if radii.iter().all(|&r| r == 0) {
+        return;
+    }
+    let mut line = Vec::new();
+    let mut sums = Vec::new();
+    for row in alpha.chunks_exact_mut(width) {
+        for &radius in radii {
+            box_blur(row, radius, &mut sums);
+        }
+    }
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| alpha[y * width + x]));
+        for &radius in radii {
+            box_blur(&mut line, radius, &mut sums);
+        }
+        for (y, &value) in line.iter().enumerate() {
+            alpha[y * width + x] = value;
+        }
+    }
+}
+
+/// Replace every value with the mean of those within `radius`, repeating
+/// the values at the ends beyond them.
+fn box_blur(values: &mut [f32], radius: usize, sums: &mut Vec<f32>) {
+    let len = values.len();
+    if radius == 0 || len == 0 {
+        return;
+    }
+    sums.clear();
+    sums.push(0.0);
+    let mut total = 0.0;
+    for &value in values.iter() {
+        total += value;
+        sums.push(total);
+    }
+
+    let (first, last) = (values[0], values[len - 1]);
+    let r = radius as isize;
+    for (i, value) in values.iter_mut().enumerate() {
+        let (a, b) = (i as isize - r, i as isize + r);
+        let mut sum = sums[(b.min(len as isize - 1) + 1) as usize] - sums[a.max(0) as usize];
+        if a < 0 {
+            sum += first * -a as f32;
+        }
+        if b >= len as isize {
+            sum += last * (b - len as isize + 1) as f32;
+        }
+        *value = sum / (2 * radius + 1) as f32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blur_radii_match_sigma() {
+        assert_eq!(blur_radii(0.0), [0, 0, 0]);
+        for sigma in [1.0_f32, 2.5, 4.0, 12.0] {
+            // Each box of radius r adds a variance of r(r + 1) / 3
+            let variance: f32 = blur_radii(sigma).iter().map(|&r| (r * (r + 1)) as f32 / 3.0).sum();
+            assert!((variance.sqrt() - sigma).abs() < 0.5, "{sigma}: {variance}");
+        }
+    }
+
+    #[test]
+    fn test_box_blur_keeps_total() {
+        let mut values = vec![0.0; 21];
+        values[10] = 1.0;
+        let mut sums = Vec::new();
+        box_blur(&mut values, 2, &mut sums);
+        assert!((values.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        assert_eq!(values[8], 0.2);
+        assert_eq!(values[7], 0.0);
+    }
+
+    #[test]
+    fn test_outer_shadow_mask() {
+        let rect = Rect::new(20.0, 20.0, 20.0, 20.0);
+        let clip = Rect::new(0.0, 0.0, 60.0, 60.0);
+        let mask = box_shadow_mask(rect, BorderRadius::ZERO, Point::new(0.0, 4.0), 8.0, 0.0, false, clip).unwrap();
+
+        // Nothing under the box; the shadow fades out below it
+        assert_eq!(mask.get(30, 30), 0.0);
+        let near = mask.get(30, 41);
+        let far = mask.get(30, 48);
+        assert!(near > far && far > 0.0, "{near} {far}");
+        assert!(mask.get(30, 59) < 0.01);
+        // The offset puts more shadow below than above
+        assert!(mask.get(30, 17) < mask.get(30, 42));
+    }
+
+    #[test]
+    fn test_inset_shadow_mask() {
+        let rect = Rect::new(10.0, 10.0, 40.0, 40.0);
+        let clip = Rect::new(0.0, 0.0, 60.0, 60.0);
+        let mask = box_shadow_mask(rect, BorderRadius::all(4.0), Point::ZERO, 4.0, 2.0, true, clip).unwrap();
+
+        // Darkest at the edges, clear in the middle and outside
+        assert!(mask.get(30, 10) > 0.5);
+        assert!(mask.get(30, 30) < 0.01);
+        assert_eq!(mask.get(30, 5), 0.0);
+    }
+    #[test]
+    fn test_huge_blur_and_spread_are_clamped() {
+        let rect = Rect::new(20.0, 20.0, 20.0, 20.0);
+        let clip = Rect::new(0.0, 0.0, 60.0, 60.0);
+
+        // The mask stays within reach of the clip
+        let mask = box_shadow_mask(rect, BorderRadius::ZERO, Point::ZERO, 1e9, 1e9, false, clip).unwrap();
+        assert!(mask.width < 2000 && mask.height < 2000, "{} {}", mask.width, mask.height);
+
+        // A spread past the clip covers it the same as any larger one
+        let huge = box_shadow_mask(rect, BorderRadius::ZERO, Point::ZERO, 0.0, 1e9, false, clip).unwrap();
+        let large = box_shadow_mask(rect, BorderRadius::ZERO, Point::ZERO, 0.0, 500.0, false, clip).unwrap();
+        assert_eq!(huge.get(5, 5), large.get(5, 5));
+        assert_eq!(huge.get(5, 5), 1.0);
+        let gone = box_shadow_mask(rect, BorderRadius::ZERO, Point::ZERO, 0.0, 1e9, true, clip).unwrap();
+        assert_eq!(gone.get(30, 30), 1.0);
+    }
+}
@@ -0,0 +1,390 @@
+//! SVG path data, as in the `d` attribute of `<path>` elements.
+//!
+//! Supports every command (M, L, H, V, C, S, Q, T, A and Z) in absolute and
+//! relative form. As in browsers, data after an error is ignored and the
+//! path up to it is kept.
+
+use crate::geometry::Rect;
+use skia_rs_safe::core::{Matrix, Point as SkiaPoint};
+use skia_rs_safe::path::{Path, PathBuilder};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Whether `text` looks like SVG path data rather than text, such as an
+/// emoji or icon name.
+pub fn is_path_data(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with(['M', 'm'])
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_whitespace() || "MmLlHhVvCcSsQqTtAaZz.,+-eE".contains(c))
+}
+
+/// Parse path data into a path in viewbox units.
+pub(super) fn parse_path(data: &str) -> Path {
+    let mut parser = Parser::new(data);
+    // Stops at the first error, keeping what was drawn
+    while parser.command().is_some() {}
+    parser.builder.build()
+}
+
+/// Transform mapping `viewbox` (x, y, width, height) into `rect`, scaled
+/// uniformly and centered, as SVG's default `xMidYMid meet`.
+pub(super) fn viewbox_transform(viewbox: (f32, f32, f32, f32), rect: Rect) -> Matrix {
+    let (x, y, width, height) = viewbox;
+    if width <= 0.0 || height <= 0.0 {
+        return Matrix::translate(rect.x(), rect.y());
+    }
+    let scale = (rect.width() / width).min(rect.height() / height);
+    let dx = rect.x() + (rect.width() - width * scale) / 2.0 - x * scale;
+    let dy = rect.y() + (rect.height() - height * scale) / 2.0 - y * scale;
+    Matrix::translate(dx, dy).concat(&Matrix::scale(scale, scale))
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    builder: PathBuilder,
+    /// Command repeated when more arguments follow it
+    last_command: Option<u8>,
+    current: SkiaPoint,
+    /// Start of the current subpath, where Z returns to
+    start: SkiaPoint,
+    /// Second control point of the last C or S, reflected by S
+    last_cubic: Option<SkiaPoint>,
+    /// Control point of the last Q or T, reflected by T
+    last_quad: Option<SkiaPoint>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self {
+            data: data.as_bytes(),
+            pos: 0,
+            builder: PathBuilder::new(),
+            last_command: None,
+            current: SkiaPoint::new(0.0, 0.0),
+            start: SkiaPoint::new(0.0, 0.0),
+            last_cubic: None,
+            last_quad: None,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.data.len() && (self.data[self.pos].is_ascii_whitespace() || self.data[self.pos] == b',') {
+            self.pos += 1;
+        }
+    }
+
+    /// Parse and draw one command, returning `None` at the end or on an error.
+    fn command(&mut self) -> Option<()> {
+        self.skip_separators();
+        let next = *self.data.get(self.pos)?;
+        let command = if next.is_ascii_alphabetic() {
+            self.pos += 1;
+            next
+        } else {
+            // More arguments repeat the last command, with M becoming L;
+            // Z takes none
+            match self.last_command? {
+                b'M' => b'L',
+                b'm' => b'l',
+                b'Z' | b'z' => return None,
+                command => command,
+            }
+        };
+        if self.last_command.is_none() && !matches!(command, b'M' | b'm') {
+            return None;
+        }
+        self.last_command = Some(command);
+
+        let relative = command.is_ascii_lowercase();
+        let origin = if relative { self.current } else { SkiaPoint::new(0.0, 0.0) };
+        let mut last_cubic = None;
+        let mut last_quad = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = self.point(origin)?;
+                self.builder.move_to(point.x, point.y);
+                self.current = point;
+                self.start = point;
+            }
+            b'L' => {
+                let point = self.point(origin)?;
+                self.line_to(point);
+            }
+            b'H' => {
+                let x = self.number()? + origin.x;
+                self.line_to(SkiaPoint::new(x, self.current.y));
+            }
+            b'V' => {
+                let y = self.number()? + origin.y;
+                self.line_to(SkiaPoint::new(self.current.x, y));
+            }
+            b'C' => {
+                let c1 = self.point(origin)?;
+                let c2 = self.point(origin)?;
+                let end = self.point(origin)?;
+                self.cubic_to(c1, c2, end);
+                last_cubic = Some(c2);
+            }
+            b'S' => {
+                let c1 = self.reflect(self.last_cubic);
+                let c2 = self.point(origin)?;
+                let end = self.point(origin)?;
+                self.cubic_to(c1, c2, end);
+                last_cubic = Some(c2);
+            }
+            b'Q' => {
+                let control = self.point(origin)?;
+                let end = self.point(origin)?;
+                self.quad_to(control, end);
+                last_quad = Some(control);
+            }
+            b'T' => {
+                let control = self.reflect(self.last_quad);
+                let end = self.point(origin)?;
+                self.quad_to(control, end);
+                last_quad = Some(control);
+            }
+            b'A' => {
+                let rx = self.number()?;
+                let ry = self.number()?;
+                let rotation = self.number()?;
+                let large_arc = self.flag()?;
+                let sweep = self.flag()?;
+                let end = self.point(origin)?;
+                self.arc_to(rx, ry, rotation, large_arc, sweep, end);
+            }
+            b'Z' => {
+                self.builder.close();
+                self.current = self.start;
+            }
+            _ => return None,
+        }
+
+        self.last_cubic = last_cubic;
+        self.last_quad = last_quad;
+        Some(())
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while parser.data.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos > from
+        };
+
+        if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut any = digits(self);
+        // A second dot starts the next number, as in "0.5.5"
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if !any {
+            return None;
+        }
+        if matches!(self.data.get(self.pos), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mantissa_end;
+            }
+        }
+
+        std::str::from_utf8(&self.data[start..self.pos]).ok()?.parse().ok()
+    }
+
+    /// An arc flag, which may be written without a following separator.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.data.get(self.pos)? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    fn point(&mut self, origin: SkiaPoint) -> Option<SkiaPoint> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Some(SkiaPoint::new(origin.x + x, origin.y + y))
+    }
+
+    /// The reflection of `control` about the current point, or the current
+    /// point if the previous command had no such control point.
+    fn reflect(&self, control: Option<SkiaPoint>) -> SkiaPoint {
+        match control {
+            Some(control) => SkiaPoint::new(2.0 * self.current.x - control.x, 2.0 * self.current.y - control.y),
+            None => self.current,
+        }
+    }
+
+    fn line_to(&mut self, point: SkiaPoint) {
+        self.builder.line_to(point.x, point.y);
+        self.current = point;
+    }
+
+    fn quad_to(&mut self, control: SkiaPoint, end: SkiaPoint) {
+        self.builder.quad_to(control.x, control.y, end.x, end.y);
+        self.current = end;
+    }
+
+    fn cubic_to(&mut self, c1: SkiaPoint, c2: SkiaPoint, end: SkiaPoint) {
+        self.builder.cubic_to(c1.x, c1.y, c2.x, c2.y, end.x, end.y);
+        self.current = end;
+    }
+
+    /// Draw an elliptical arc as cubics, following the SVG implementation
+    /// notes for converting endpoint to center parameters.
+    fn arc_to(&mut self, rx: f32, ry: f32, rotation: f32, large_arc: bool, sweep: bool, end: SkiaPoint) {
+        let start = self.current;
+        if start.x == end.x && start.y == end.y {
+            return;
+        }
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(end);
+            return;
+        }
+
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let dx = (start.x - end.x) / 2.0;
+        let dy = (start.y - end.y) / 2.0;
+        let x1 = cos * dx + sin * dy;
+        let y1 = -sin * dx + cos * dy;
+
+        // Radii too small to reach the end point are scaled up
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = (rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1).max(0.0);
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut factor = (numerator / denominator).sqrt();
+        if large_arc == sweep {
+            factor = -factor;
+        }
+        let cx1 = factor * rx * y1 / ry;
+        let cy1 = -factor * ry * x1 / rx;
+        let cx = cos * cx1 - sin * cy1 + (start.x + end.x) / 2.0;
+        let cy = sin * cx1 + cos * cy1 + (start.y + end.y) / 2.0;
+
+        let angle = |ux: f32, uy: f32| uy.atan2(ux);
+        let theta = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - theta;
+        if sweep && delta < 0.0 {
+            delta += TAU;
+        } else if !sweep && delta > 0.0 {
+            delta -= TAU;
+        }
+
+        // Split into pieces of at most a quarter turn, each close to a cubic
+        let pieces = (delta.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = delta / pieces as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let at = |t: f32| {
+            let (s, c) = t.sin_cos();
+            (rx * c, ry * s, -rx * s, ry * c)
+        };
+        let map = |x: f32, y: f32| SkiaPoint::new(cx + cos * x - sin * y, cy + sin * x + cos * y);
+
+        let mut t = theta;
+        for i in 0..pieces {
+            let (x0, y0, dx0, dy0) = at(t);
+            let (x3, y3, dx3, dy3) = at(t + step);
+            let c1 = map(x0 + k * dx0, y0 + k * dy0);
+            let c2 = map(x3 - k * dx3, y3 - k * dy3);
+            // Land exactly on the end point
+            let to = if i + 1 == pieces { end } else { map(x3, y3) };
+            self.cubic_to(c1, c2, to);
+            t += step;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use skia_rs_safe::path::PathElement;
+
+    fn points(path: &Path) -> Vec<(f32, f32)> {
+        path.iter()
+            .filter_map(|element| match element {
+                PathElement::Move(p) | PathElement::Line(p) => Some((p.x, p.y)),
+                PathElement::Quad(_, p) | PathElement::Cubic(_, _, p) => Some((p.x, p.y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_relative_and_absolute() {
+        let path = parse_path("M10 10h5v5H10zm2 2l1 1 1-1L20 20");
+        assert_eq!(
+            points(&path),
+            vec![(10.0, 10.0), (15.0, 10.0), (15.0, 15.0), (10.0, 15.0), (12.0, 12.0), (13.0, 13.0), (14.0, 12.0), (20.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn test_compact_numbers() {
+        // Signs and second dots separate numbers
+        let path = parse_path("M.5.5-1-1e1L1.5e-1.25");
+        assert_eq!(points(&path), vec![(0.5, 0.5), (-1.0, -10.0), (0.15, 0.25)]);
+    }
+
+    #[test]
+    fn test_smooth_curves_reflect() {
+        let path = parse_path("M0 0C0 10 10 10 10 0S20-10 20 0");
+        let cubics: Vec<_> = path
+            .iter()
+            .filter_map(|element| match element {
+                PathElement::Cubic(c1, ..) => Some((c1.x, c1.y)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cubics, vec![(0.0, 10.0), (10.0, -10.0)]);
+    }
+
+    #[test]
+    fn test_arc_ends_on_target() {
+        let path = parse_path("M2 12a10 10 0 1 1 20 0A10 10 0 0 1 2 12");
+        let ends = points(&path);
+        assert_eq!(ends.first(), Some(&(2.0, 12.0)));
+        assert!(ends.contains(&(22.0, 12.0)));
+        assert_eq!(ends.last(), Some(&(2.0, 12.0)));
+        // The top half bulges up to the circle's top
+        let bounds = path.bounds();
+        assert!((bounds.top - 2.0).abs() < 0.5, "{bounds:?}");
+    }
+
+    #[test]
+    fn test_error_keeps_prefix() {
+        let path = parse_path("M0 0L10 0L10 x");
+        assert_eq!(points(&path), vec![(0.0, 0.0), (10.0, 0.0)]);
+        assert!(parse_path("L10 10").is_empty());
+    }
+
+    #[test]
+    fn test_is_path_data() {
+        assert!(is_path_data("M12 2L2 22h20z"));
+        assert!(is_path_data(" m0 0 a1 1 0 0 1 2 0"));
+        assert!(!is_path_data("📶"));
+        assert!(!is_path_data("Menu"));
+    }
+}
@@ -1,8 +1,20 @@
 //! Text rendering using cosmic-text.
 
-use crate::geometry::Size;
-use cosmic_text::{Attrs, Buffer, FontSystem, Metrics, Shaping, SwashCache, SwashContent};
-use std::sync::{Arc, Mutex};
+use super::rich_text::{RichText, SpanStyle, TextSpan};
+use crate::geometry::{Point, Rect, Size};
+use cosmic_text::{Attrs, AttrsList, Buffer, BufferLine, Cursor, FontSystem, LayoutGlyph, LayoutRun, LineEnding, Metrics, Shaping, SwashCache, SwashContent};
+use std::ops::Range;
+use std::sync::{Arc, Mutex, OnceLock};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The font system shared by every renderer and text layout, so system
+/// fonts are only loaded once.
+fn shared_font_system() -> Arc<Mutex<FontSystem>> {
+    static FONT_SYSTEM: OnceLock<Arc<Mutex<FontSystem>>> = OnceLock::new();
+    FONT_SYSTEM
+        .get_or_init(|| Arc::new(Mutex::new(FontSystem::new())))
+        .clone()
+}
 
 /// Text renderer using cosmic-text.
 pub struct TextRenderer {
@@ -12,34 +24,30 @@ pub struct TextRenderer {
 
 impl TextRenderer {
     pub fn new() -> Self {
-        let font_system = FontSystem::new();
-        let swash_cache = SwashCache::new();
-
         Self {
-            font_system: Arc::new(Mutex::new(font_system)),
-            swash_cache,
+            font_system: shared_font_system(),
+            swash_cache: SwashCache::new(),
         }
     }
 
     /// Measure text dimensions.
     pub fn measure(&self, text: &str, font_size: f32) -> Size {
-        let mut font_system = self.font_system.lock().unwrap();
-
-        let metrics = Metrics::new(font_size, font_size * 1.2);
-        let mut buffer = Buffer::new(&mut font_system, metrics);
-
-        buffer.set_size(&mut font_system, Some(f32::MAX), Some(f32::MAX));
-        buffer.set_text(&mut font_system, text, Attrs::new(), Shaping::Advanced);
-        buffer.shape_until_scroll(&mut font_system, false);
+        self.layout(text, font_size).size()
+    }
 
-        let width = buffer
-            .layout_runs()
-            .map(|run| run.line_w)
-            .fold(0.0_f32, |a, b| a.max(b));
+    /// Measure rich text dimensions.
+    pub fn measure_rich(&self, text: &RichText, font_size: f32) -> Size {
+        self.layout_rich(text, font_size).size()
+    }
 
-        let height = buffer.layout_runs().count() as f32 * font_size * 1.2;
+    /// Lay out text for hit-testing and caret placement, without wrapping.
+    pub fn layout(&self, text: &str, font_size: f32) -> TextLayout {
+        TextLayout::shape(&self.font_system, text, font_size, None, Attrs::new())
+    }
 
-        Size::new(width, height.max(font_size * 1.2))
+    /// Lay out rich text for hit-testing, without wrapping.
+    pub fn layout_rich(&self, text: &RichText, font_size: f32) -> TextLayout {
+        TextLayout::shape_rich(&self.font_system, text, font_size, None)
     }
 
     /// Get a reference to the font system.
@@ -56,143 +64,452 @@ impl TextRenderer {
         color: [u8; 4],
     ) -> (u32, u32, Vec<u8>) {
         let mut font_system = self.font_system.lock().unwrap();
+        let mut buffer = line_buffer(&mut font_system, font_size);
+        buffer.set_text(&mut font_system, text, Attrs::new(), Shaping::Advanced);
+        buffer.shape_until_scroll(&mut font_system, false);
 
-        let metrics = Metrics::new(font_size, font_size * 1.2);
-        let mut buffer = Buffer::new(&mut font_system, metrics);
+        rasterize_buffer(&mut font_system, &mut self.swash_cache, &buffer, font_size, color, &[])
+    }
 
-        buffer.set_size(&mut font_system, Some(1000.0), Some(font_size * 2.0));
-        buffer.set_text(&mut font_system, text, Attrs::new(), Shaping::Advanced);
+    /// Rasterize rich text to pixels, in `color` where spans don't set one.
+    /// Returns (width, height, pixels) where pixels is RGBA.
+    pub fn rasterize_rich(
+        &mut self,
+        text: &RichText,
+        font_size: f32,
+        color: [u8; 4],
+    ) -> (u32, u32, Vec<u8>) {
+        let mut font_system = self.font_system.lock().unwrap();
+        let mut buffer = line_buffer(&mut font_system, font_size);
+        buffer.set_rich_text(&mut font_system, text.attrs(), Attrs::new(), Shaping::Advanced);
         buffer.shape_until_scroll(&mut font_system, false);
 
-        // Measure dimensions
-        let width = buffer
-            .layout_runs()
-            .map(|run| run.line_w)
-            .fold(0.0_f32, |a, b| a.max(b))
-            .ceil() as u32;
-        let height = (buffer.layout_runs().count() as f32 * font_size * 1.2).ceil() as u32;
+        rasterize_buffer(&mut font_system, &mut self.swash_cache, &buffer, font_size, color, text.spans())
+    }
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A buffer for drawing one line of text.
+fn line_buffer(font_system: &mut FontSystem, font_size: f32) -> Buffer {
+    let metrics = Metrics::new(font_size, font_size * 1.2);
+    let mut buffer = Buffer::new(font_system, metrics);
+    buffer.set_size(font_system, Some(1000.0), Some(font_size * 2.0));
+    buffer
+}
+
+/// Rasterize shaped text, with the backgrounds and decorations of `spans`.
+///
+/// Glyphs carry the index of their span as metadata.
+fn rasterize_buffer(
+    font_system: &mut FontSystem,
+    swash_cache: &mut SwashCache,
+    buffer: &Buffer,
+    font_size: f32,
+    color: [u8; 4],
+    spans: &[TextSpan],
+) -> (u32, u32, Vec<u8>) {
+    // Measure dimensions
+    let width = buffer
+        .layout_runs()
+        .map(|run| run.line_w)
+        .fold(0.0_f32, |a, b| a.max(b))
+        .ceil() as u32;
+    let height = (buffer.layout_runs().count() as f32 * font_size * 1.2).ceil() as u32;
+
+    if width == 0 || height == 0 {
+        return (0, 0, Vec::new());
+    }
+
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+    let span_style = |glyph: &LayoutGlyph| spans.get(glyph.metadata).map(|span| &span.style);
 
-        if width == 0 || height == 0 {
-            return (0, 0, Vec::new());
+    // Span backgrounds go under the glyphs
+    for run in buffer.layout_runs() {
+        for glyph in run.glyphs.iter() {
+            if let Some(background) = span_style(glyph).and_then(|style| style.background) {
+                let [r, g, b, a] = background.to_rgba8();
+                let area = Rect::new(glyph.x, run.line_top, glyph.w, run.line_height);
+                fill(&mut pixels, width, area, [r, g, b], a);
+            }
         }
+    }
+
+    // Rasterize glyphs
+    for run in buffer.layout_runs() {
+        for glyph in run.glyphs.iter() {
+            let physical_glyph = glyph.physical((0.0, 0.0), 1.0);
+            let color = glyph.color_opt.map_or(color, |c| [c.r(), c.g(), c.b(), c.a()]);
+            let rgb = [color[0], color[1], color[2]];
+            // Coverage scaled by the color's own alpha
+            let coverage = |alpha: u8| (alpha as u16 * color[3] as u16 / 255) as u8;
+
+            if let Some(image) = swash_cache.get_image(font_system, physical_glyph.cache_key) {
+                let glyph_x = physical_glyph.x + image.placement.left;
+                let glyph_y = physical_glyph.y + run.line_y as i32 - image.placement.top;
+                let glyph_w = image.placement.width as i32;
+                let glyph_h = image.placement.height as i32;
+
+                match image.content {
+                    SwashContent::Mask => {
+                        // Grayscale alpha mask - 1 byte per pixel
+                        for gy in 0..glyph_h {
+                            for gx in 0..glyph_w {
+                                let src_idx = (gy * glyph_w + gx) as usize;
+                                if src_idx >= image.data.len() {
+                                    continue;
+                                }
+
+                                let alpha = image.data[src_idx];
+                                if alpha == 0 {
+                                    continue;
+                                }
+
+                                let px = glyph_x + gx;
+                                let py = glyph_y + gy;
 
-        let mut pixels = vec![0u8; (width * height * 4) as usize];
-
-        // Rasterize glyphs
-        for run in buffer.layout_runs() {
-            for glyph in run.glyphs.iter() {
-                let physical_glyph = glyph.physical((0.0, 0.0), 1.0);
-
-                if let Some(image) = self.swash_cache.get_image(&mut font_system, physical_glyph.cache_key) {
-                    let glyph_x = physical_glyph.x + image.placement.left;
-                    let glyph_y = physical_glyph.y + run.line_y as i32 - image.placement.top;
-                    let glyph_w = image.placement.width as i32;
-                    let glyph_h = image.placement.height as i32;
-
-                    match image.content {
-                        SwashContent::Mask => {
-                            // Grayscale alpha mask - 1 byte per pixel
-                            for gy in 0..glyph_h {
-                                for gx in 0..glyph_w {
-                                    let src_idx = (gy * glyph_w + gx) as usize;
-                                    if src_idx >= image.data.len() {
-                                        continue;
-                                    }
-                                    
-                                    let alpha = image.data[src_idx];
-                                    if alpha == 0 {
-                                        continue;
-                                    }
-                                    
-                                    let px = glyph_x + gx;
-                                    let py = glyph_y + gy;
-
-                                    if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
-                                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                                        if idx + 3 < pixels.len() {
-                                            let a = alpha as f32 / 255.0;
-                                            pixels[idx] = ((color[0] as f32 * a) as u8).saturating_add(pixels[idx]);
-                                            pixels[idx + 1] = ((color[1] as f32 * a) as u8).saturating_add(pixels[idx + 1]);
-                                            pixels[idx + 2] = ((color[2] as f32 * a) as u8).saturating_add(pixels[idx + 2]);
-                                            pixels[idx + 3] = alpha.saturating_add(pixels[idx + 3]);
-                                        }
-                                    }
+                                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                                    let idx = ((py as u32 * width + px as u32) * 4) as usize;
+                                    blend(&mut pixels, idx, rgb, coverage(alpha));
                                 }
                             }
                         }
-                        SwashContent::Color => {
-                            // RGBA color - 4 bytes per pixel
-                            for gy in 0..glyph_h {
-                                for gx in 0..glyph_w {
-                                    let src_idx = ((gy * glyph_w + gx) * 4) as usize;
-                                    if src_idx + 3 >= image.data.len() {
-                                        continue;
-                                    }
-                                    
-                                    let px = glyph_x + gx;
-                                    let py = glyph_y + gy;
-
-                                    if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
-                                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                                        if idx + 3 < pixels.len() {
-                                            let a = image.data[src_idx + 3] as f32 / 255.0;
-                                            if a > 0.0 {
-                                                pixels[idx] = ((image.data[src_idx] as f32 * a) as u8).saturating_add(pixels[idx]);
-                                                pixels[idx + 1] = ((image.data[src_idx + 1] as f32 * a) as u8).saturating_add(pixels[idx + 1]);
-                                                pixels[idx + 2] = ((image.data[src_idx + 2] as f32 * a) as u8).saturating_add(pixels[idx + 2]);
-                                                pixels[idx + 3] = image.data[src_idx + 3].saturating_add(pixels[idx + 3]);
-                                            }
-                                        }
-                                    }
+                    }
+                    SwashContent::Color => {
+                        // RGBA color - 4 bytes per pixel
+                        for gy in 0..glyph_h {
+                            for gx in 0..glyph_w {
+                                let src_idx = ((gy * glyph_w + gx) * 4) as usize;
+                                if src_idx + 3 >= image.data.len() {
+                                    continue;
+                                }
+
+                                let px = glyph_x + gx;
+                                let py = glyph_y + gy;
+
+                                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                                    let idx = ((py as u32 * width + px as u32) * 4) as usize;
+                                    let src = &image.data[src_idx..src_idx + 4];
+                                    blend(&mut pixels, idx, [src[0], src[1], src[2]], src[3]);
                                 }
                             }
                         }
-                        SwashContent::SubpixelMask => {
-                            // Subpixel RGB - 3 bytes per pixel, treat as grayscale for now
-                            for gy in 0..glyph_h {
-                                for gx in 0..glyph_w {
-                                    let src_idx = ((gy * glyph_w + gx) * 3) as usize;
-                                    if src_idx + 2 >= image.data.len() {
-                                        continue;
-                                    }
-                                    
-                                    // Average the RGB subpixel values for grayscale alpha
-                                    let alpha = ((image.data[src_idx] as u16 + image.data[src_idx + 1] as u16 + image.data[src_idx + 2] as u16) / 3) as u8;
-                                    if alpha == 0 {
-                                        continue;
-                                    }
-                                    
-                                    let px = glyph_x + gx;
-                                    let py = glyph_y + gy;
-
-                                    if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
-                                        let idx = ((py as u32 * width + px as u32) * 4) as usize;
-                                        if idx + 3 < pixels.len() {
-                                            let a = alpha as f32 / 255.0;
-                                            pixels[idx] = ((color[0] as f32 * a) as u8).saturating_add(pixels[idx]);
-                                            pixels[idx + 1] = ((color[1] as f32 * a) as u8).saturating_add(pixels[idx + 1]);
-                                            pixels[idx + 2] = ((color[2] as f32 * a) as u8).saturating_add(pixels[idx + 2]);
-                                            pixels[idx + 3] = alpha.saturating_add(pixels[idx + 3]);
-                                        }
-                                    }
+                    }
+                    SwashContent::SubpixelMask => {
+                        // Subpixel RGB - 3 bytes per pixel, treat as grayscale for now
+                        for gy in 0..glyph_h {
+                            for gx in 0..glyph_w {
+                                let src_idx = ((gy * glyph_w + gx) * 3) as usize;
+                                if src_idx + 2 >= image.data.len() {
+                                    continue;
+                                }
+
+                                // Average the RGB subpixel values for grayscale alpha
+                                let alpha = ((image.data[src_idx] as u16 + image.data[src_idx + 1] as u16 + image.data[src_idx + 2] as u16) / 3) as u8;
+                                if alpha == 0 {
+                                    continue;
+                                }
+
+                                let px = glyph_x + gx;
+                                let py = glyph_y + gy;
+
+                                if px >= 0 && py >= 0 && (px as u32) < width && (py as u32) < height {
+                                    let idx = ((py as u32 * width + px as u32) * 4) as usize;
+                                    blend(&mut pixels, idx, rgb, coverage(alpha));
                                 }
                             }
                         }
                     }
                 }
             }
+
+            // Underline and strikethrough, in the glyph's color
+            if let Some(style) = span_style(glyph) {
+                let thickness = (font_size / 14.0).round().max(1.0);
+                if style.underline {
+                    let area = Rect::new(glyph.x, run.line_y + thickness, glyph.w, thickness);
+                    fill(&mut pixels, width, area, rgb, color[3]);
+                }
+                if style.strikethrough {
+                    let area = Rect::new(glyph.x, run.line_y - font_size * 0.3, glyph.w, thickness);
+                    fill(&mut pixels, width, area, rgb, color[3]);
+                }
+            }
         }
+    }
 
-        (width, height, pixels)
+    (width, height, pixels)
+}
+
+/// Draw `color` at coverage `alpha` over pixel `idx` of premultiplied RGBA
+/// `pixels`.
+fn blend(pixels: &mut [u8], idx: usize, color: [u8; 3], alpha: u8) {
+    let a = alpha as f32 / 255.0;
+    let inv_a = 1.0 - a;
+    for (channel, value) in color.into_iter().enumerate() {
+        pixels[idx + channel] = (value as f32 * a + pixels[idx + channel] as f32 * inv_a) as u8;
     }
+    pixels[idx + 3] = (alpha as f32 + pixels[idx + 3] as f32 * inv_a) as u8;
 }
 
-impl Default for TextRenderer {
-    fn default() -> Self {
-        Self::new()
+/// Fill `area` of premultiplied RGBA `pixels`, `width` pixels wide.
+fn fill(pixels: &mut [u8], width: u32, area: Rect, color: [u8; 3], alpha: u8) {
+    let height = pixels.len() as u32 / 4 / width;
+    let x_end = (area.max_x().round().max(0.0) as u32).min(width);
+    let y_end = (area.max_y().round().max(0.0) as u32).min(height);
+    for y in area.y().round().max(0.0) as u32..y_end {
+        for x in area.x().round().max(0.0) as u32..x_end {
+            blend(pixels, ((y * width + x) * 4) as usize, color, alpha);
+        }
     }
 }
 
+/// Shaped text, for mapping between positions and byte offsets.
+///
+/// Uses the same fonts and metrics as text drawn with `Painter::draw_text`,
+/// so carets and selections line up with the glyphs. Positions are relative
+/// to the top-left corner of the text; offsets are byte offsets into it.
+pub struct TextLayout {
+    buffer: Buffer,
+    /// Byte offset at which each line of the text starts
+    line_starts: Vec<usize>,
+    font_size: f32,
+}
+
+impl TextLayout {
+    /// Lay out `text` without wrapping.
+    ///
+    /// Widgets use this to place carets; it shares its fonts with the
+    /// renderers.
+    pub fn new(text: &str, font_size: f32) -> Self {
+        Self::shape(&shared_font_system(), text, font_size, None, Attrs::new())
+    }
+
+    /// Lay out `text`, wrapping lines longer than `width` at word boundaries.
+    pub fn wrapped(text: &str, font_size: f32, width: f32) -> Self {
+        Self::shape(&shared_font_system(), text, font_size, Some(width), Attrs::new())
+    }
+
+    /// Lay out `text` in `font`, as `Painter::draw_text` draws it under a
+    /// widget whose CSS sets a font family or weight.
+    pub fn styled(text: &str, font_size: f32, font: &SpanStyle) -> Self {
+        Self::shape(&shared_font_system(), text, font_size, None, font.attrs())
+    }
+
+    /// Lay out `text` in `font`, wrapping lines longer than `width`.
+    pub fn styled_wrapped(text: &str, font_size: f32, width: f32, font: &SpanStyle) -> Self {
+        Self::shape(&shared_font_system(), text, font_size, Some(width), font.attrs())
+    }
+
+    /// Lay out rich text without wrapping.
+    pub fn rich(text: &RichText, font_size: f32) -> Self {
+        Self::shape_rich(&shared_font_system(), text, font_size, None)
+    }
+
+    fn shape(font_system: &Mutex<FontSystem>, text: &str, font_size: f32, width: Option<f32>, attrs: Attrs) -> Self {
+        Self::shape_with(font_system, text, font_size, width, |font_system, buffer| {
+            buffer.set_text(font_system, text, attrs, Shaping::Advanced);
+        })
+    }
+
+    fn shape_rich(font_system: &Mutex<FontSystem>, text: &RichText, font_size: f32, width: Option<f32>) -> Self {
+        Self::shape_with(font_system, &text.text(), font_size, width, |font_system, buffer| {
+            buffer.set_rich_text(font_system, text.attrs(), Attrs::new(), Shaping::Advanced);
+        })
+    }
+
+    /// Shape `text`, which `set_text` puts in the buffer.
+    fn shape_with(
+        font_system: &Mutex<FontSystem>,
+        text: &str,
+        font_size: f32,
+        width: Option<f32>,
+        set_text: impl FnOnce(&mut FontSystem, &mut Buffer),
+    ) -> Self {
+        let mut font_system = font_system.lock().unwrap();
+
+        let metrics = Metrics::new(font_size, font_size * 1.2);
+        let mut buffer = Buffer::new(&mut font_system, metrics);
+
+        buffer.set_size(&mut font_system, width, None);
+        set_text(&mut font_system, &mut buffer);
+        // cosmic-text drops the empty line after a trailing line break, which
+        // is where the caret goes after pressing Enter
+        if text.ends_with('\n') {
+            buffer.lines.push(BufferLine::new(
+                "",
+                LineEnding::None,
+                AttrsList::new(Attrs::new()),
+                Shaping::Advanced,
+            ));
+        }
+        buffer.shape_until_scroll(&mut font_system, false);
+
+        let line_starts = buffer
+            .lines
+            .iter()
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.text().len() + line.ending().as_str().len();
+                Some(line_start)
+            })
+            .collect();
+
+        Self {
+            buffer,
+            line_starts,
+            font_size,
+        }
+    }
+
+    /// Height of a line of text.
+    pub fn line_height(&self) -> f32 {
+        self.font_size * 1.2
+    }
+
+    /// Size of the laid out text, at least one line high.
+    pub fn size(&self) -> Size {
+        let width = self
+            .buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0_f32, f32::max);
+        Size::new(width, self.line_count() as f32 * self.line_height())
+    }
+
+    /// Number of lines after wrapping, at least one.
+    pub fn line_count(&self) -> usize {
+        self.buffer.layout_runs().count().max(1)
+    }
+
+    /// Byte range and top of each line after wrapping.
+    ///
+    /// The ranges leave out line endings.
+    pub fn lines(&self) -> Vec<(Range<usize>, f32)> {
+        self.buffer
+            .layout_runs()
+            .map(|run| {
+                let line_start = self.line_starts[run.line_i];
+                let range = if run.glyphs.is_empty() {
+                    line_start..line_start
+                } else {
+                    line_start + run_start(&run)..line_start + run_end(&run)
+                };
+                (range, run.line_top)
+            })
+            .collect()
+    }
+
+    /// Byte offset of the caret position nearest to `point`.
+    pub fn hit_test(&self, point: Point) -> usize {
+        match self.buffer.hit(point.x, point.y) {
+            Some(cursor) => self.offset(cursor),
+            None => 0,
+        }
+    }
+
+    /// Horizontal position of the caret at byte offset `offset`.
+    pub fn caret_x(&self, offset: usize) -> f32 {
+        self.caret_position(offset).x
+    }
+
+    /// Top of the caret at byte offset `offset`.
+    ///
+    /// At the end of a wrapped line, the caret goes to the start of the
+    /// next one.
+    pub fn caret_position(&self, offset: usize) -> Point {
+        let cursor = self.cursor(offset);
+        let mut position = None;
+        for run in self.buffer.layout_runs().filter(|run| run.line_i == cursor.line) {
+            if run.glyphs.is_empty() {
+                return Point::new(0.0, run.line_top);
+            }
+            if let Some(x) = run_x(&run, cursor.index) {
+                position = Some(Point::new(x, run.line_top));
+                // Keep looking for the start of the next run
+                if cursor.index < run_end(&run) {
+                    break;
+                }
+            }
+        }
+        position.unwrap_or(Point::ZERO)
+    }
+
+    /// Rectangles covering the text in the byte range `range`, one per
+    /// visual line.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects = Vec::new();
+        for run in self.buffer.layout_runs() {
+            let line_start = self.line_starts[run.line_i];
+            let start = range.start.max(line_start + run_start(&run));
+            let end = range.end.min(line_start + run_end(&run));
+            if start >= end {
+                continue;
+            }
+            if let (Some(x0), Some(x1)) = (run_x(&run, start - line_start), run_x(&run, end - line_start)) {
+                rects.push(Rect::new(x0.min(x1), run.line_top, (x1 - x0).abs(), run.line_height));
+            }
+        }
+        rects
+    }
+
+    /// Cursor at byte offset `offset`, clamped to the text.
+    fn cursor(&self, offset: usize) -> Cursor {
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        let Some(buffer_line) = self.buffer.lines.get(line) else {
+            return Cursor::new(0, 0);
+        };
+        let index = (offset - self.line_starts[line]).min(buffer_line.text().len());
+        Cursor::new(line, index)
+    }
+
+    /// Byte offset of `cursor`.
+    fn offset(&self, cursor: Cursor) -> usize {
+        self.line_starts.get(cursor.line).map_or(0, |start| start + cursor.index)
+    }
+}
+
+/// First byte of the line covered by a layout run.
+fn run_start(run: &LayoutRun) -> usize {
+    run.glyphs.iter().map(|glyph| glyph.start).min().unwrap_or(0)
+}
+
+/// End byte of the line covered by a layout run.
+fn run_end(run: &LayoutRun) -> usize {
+    run.glyphs.iter().map(|glyph| glyph.end).max().unwrap_or(0)
+}
+
+/// Horizontal position of byte `index` of the line within a layout run, if
+/// the run covers it.
+fn run_x(run: &LayoutRun, index: usize) -> Option<f32> {
+    for glyph in run.glyphs {
+        if (glyph.start..glyph.end).contains(&index) {
+            // Split ligatures and clusters evenly between their graphemes
+            let cluster = &run.text[glyph.start..glyph.end];
+            let total = cluster.graphemes(true).count().max(1);
+            let before = run.text[glyph.start..index].graphemes(true).count();
+            let advance = glyph.w * before as f32 / total as f32;
+            return Some(if glyph.level.is_rtl() {
+                glyph.x + glyph.w - advance
+            } else {
+                glyph.x + advance
+            });
+        }
+    }
+    // Past the last glyph of the run
+    run.glyphs
+        .iter()
+        .find(|glyph| glyph.end == index)
+        .map(|glyph| if glyph.level.is_rtl() { glyph.x } else { glyph.x + glyph.w })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +530,75 @@ mod tests {
 
         assert_eq!(size.width, 0.0);
     }
+
+    #[test]
+    fn test_text_layout_carets() {
+        let text = "Wide WWW, narrow iii";
+        let layout = TextLayout::new(text, 16.0);
+
+        // Carets move right through the text, by glyph widths
+        let carets: Vec<f32> = (0..=text.len()).map(|offset| layout.caret_x(offset)).collect();
+        assert_eq!(carets[0], 0.0);
+        assert!(carets.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(carets[6] - carets[5] > carets[19] - carets[18]);
+        assert!((carets[text.len()] - layout.size().width).abs() < 0.5);
+
+        // Hit-testing maps each caret back to its offset
+        for (offset, &x) in carets.iter().enumerate() {
+            assert_eq!(layout.hit_test(Point::new(x, 8.0)), offset);
+        }
+        assert_eq!(layout.hit_test(Point::new(-10.0, 8.0)), 0);
+        assert_eq!(layout.hit_test(Point::new(10_000.0, 8.0)), text.len());
+    }
+
+    #[test]
+    fn test_text_layout_lines() {
+        let layout = TextLayout::new("one\ntwo", 10.0);
+
+        assert_eq!(layout.caret_position(0), Point::new(0.0, 0.0));
+        assert_eq!(layout.caret_position(4), Point::new(0.0, 12.0));
+        assert_eq!(layout.hit_test(Point::new(0.0, 15.0)), 4);
+        assert_eq!(layout.selection_rects(1..6).len(), 2);
+        assert_eq!(layout.lines(), vec![(0..3, 0.0), (4..7, 12.0)]);
+
+        let trailing = TextLayout::new("one\n", 10.0);
+        assert_eq!(trailing.lines(), vec![(0..3, 0.0), (4..4, 12.0)]);
+        assert_eq!(trailing.caret_position(4), Point::new(0.0, 12.0));
+    }
+
+    #[test]
+    fn test_text_layout_wrapping() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        let unwrapped = TextLayout::new(text, 16.0);
+        let wrapped = TextLayout::wrapped(text, 16.0, unwrapped.size().width / 2.0);
+        assert_eq!(unwrapped.line_count(), 1);
+        assert!(wrapped.line_count() >= 2);
+
+        // Wrapped lines cover the text, breaking after a space
+        let lines = wrapped.lines();
+        assert_eq!(lines[0].0.start, 0);
+        assert_eq!(lines.last().unwrap().0.end, text.len());
+        assert!(text[..lines[1].0.start].ends_with(' '));
+
+        // The caret at a wrap point sits at the start of the next line
+        let second = lines[1].0.start;
+        assert_eq!(wrapped.caret_position(second), Point::new(0.0, lines[1].1));
+    }
+
+    #[test]
+    fn test_text_layout_styled() {
+        let text = "iiiii";
+        let plain = TextLayout::new(text, 16.0);
+        let monospace = SpanStyle {
+            font_family: Some("monospace".into()),
+            ..Default::default()
+        };
+        let styled = TextLayout::styled(text, 16.0, &monospace);
+
+        // Monospace glyphs are as wide as any other, unlike a proportional i
+        assert!(styled.size().width > plain.size().width);
+        let rich = TextLayout::rich(&RichText::plain(text).with_font(&monospace), 16.0);
+        assert_eq!(styled.caret_x(3), rich.caret_x(3));
+        assert_eq!(TextLayout::styled(text, 16.0, &SpanStyle::default()).caret_x(3), plain.caret_x(3));
+    }
 }
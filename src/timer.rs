@@ -0,0 +1,235 @@
+//! Timers and animation frame scheduling.
+//!
+//! Widgets schedule timers and request animation frames through
+//! [`EventContext`](crate::widget::EventContext). The application keeps the
+//! event loop asleep (`ControlFlow::WaitUntil`) until the next deadline and
+//! then delivers [`Event::Timer`](crate::event::Event::Timer) events.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+//!     match event {
+//!         Event::Timer(TimerEvent::Frame { elapsed }) => {
+//!             self.angle += elapsed.as_secs_f32() * 4.0;
+//!             ctx.request_animation_frame();
+//!             ctx.request_redraw();
+//!         }
+//!         Event::Timer(TimerEvent::Fired { widget_id, .. }) if *widget_id == self.id() => {
+//!             self.blink = !self.blink;
+//!             ctx.request_redraw();
+//!             return EventResult::Handled;
+//!         }
+//!         _ => {}
+//!     }
+//!     EventResult::Ignored
+//! }
+//! ```
+
+use crate::event::{TimerEvent, TimerId, WidgetId};
+
+use std::time::{Duration, Instant};
+
+/// Minimum time between animation frames (~60 Hz).
+pub const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// A scheduled timeout or interval.
+#[derive(Debug, Clone)]
+struct Timer {
+    id: TimerId,
+    widget_id: WidgetId,
+    deadline: Instant,
+    interval: Option<Duration>,
+}
+
+/// Pending timers and animation frame requests.
+#[derive(Debug, Default)]
+pub struct Timers {
+    next_id: TimerId,
+    timers: Vec<Timer>,
+    frame_requested: bool,
+    /// When the last frame was delivered, while an animation is running
+    last_frame: Option<Instant>,
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request an animation frame.
+    pub fn request_frame(&mut self) {
+        self.frame_requested = true;
+    }
+
+    /// Whether an animation frame is pending.
+    pub fn frame_requested(&self) -> bool {
+        self.frame_requested
+    }
+
+    /// Schedule a timer for `widget_id` firing `delay` after `now`.
+    ///
+    /// With an `interval` the timer re-arms itself after firing. Intervals
+    /// shorter than [`FRAME_INTERVAL`] are lengthened to it, so a zero
+    /// period can't keep the event loop busy.
+    pub fn schedule(
+        &mut self,
+        widget_id: WidgetId,
+        delay: Duration,
+        interval: Option<Duration>,
+        now: Instant,
+    ) -> TimerId {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.timers.push(Timer {
+            id,
+            widget_id,
+            deadline: now + delay,
+            interval: interval.map(|interval| interval.max(FRAME_INTERVAL)),
+        });
+        id
+    }
+
+    /// Cancel a timer. Returns `false` if it already fired or was cancelled.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    /// Whether nothing is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty() && !self.frame_requested
+    }
+
+    /// The earliest time something is due, or `None` if nothing is scheduled.
+    pub fn next_deadline(&self, now: Instant) -> Option<Instant> {
+        let frame = self
+            .frame_requested
+            .then(|| self.last_frame.map_or(now, |last| last + FRAME_INTERVAL));
+        self.timers
+            .iter()
+            .map(|timer| timer.deadline)
+            .chain(frame)
+            .min()
+    }
+
+    /// Remove and return everything due at `now`, re-arming intervals.
+    ///
+    /// Timers are returned in deadline order, followed by the animation frame
+    /// if one is due. A frame's `elapsed` time is zero when an animation starts
+    /// (no frame was requested since the previous call).
+    pub fn take_due(&mut self, now: Instant) -> Vec<TimerEvent> {
+        if !self.frame_requested {
+            self.last_frame = None;
+        }
+
+        let mut due: Vec<Timer> = Vec::new();
+        self.timers.retain_mut(|timer| {
+            if timer.deadline > now {
+                return true;
+            }
+            due.push(timer.clone());
+            match timer.interval {
+                Some(interval) => {
+                    // Skip missed ticks instead of firing a burst
+                    timer.deadline += interval;
+                    if timer.deadline <= now {
+                        timer.deadline = now + interval;
+                    }
+                    true
+                }
+                None => false,
+            }
+        });
+        due.sort_by_key(|timer| timer.deadline);
+
+        let mut events: Vec<TimerEvent> = due
+            .into_iter()
+            .map(|timer| TimerEvent::Fired {
+                id: timer.id,
+                widget_id: timer.widget_id,
+            })
+            .collect();
+
+        if self.frame_requested {
+            let elapsed = self.last_frame.map_or(Duration::ZERO, |last| now - last);
+            if self.last_frame.is_none() || elapsed >= FRAME_INTERVAL {
+                self.frame_requested = false;
+                self.last_frame = Some(now);
+                events.push(TimerEvent::Frame { elapsed });
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeouts_and_intervals() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        let timeout = timers.schedule(1, Duration::from_millis(50), None, start);
+        let interval = timers.schedule(2, Duration::from_millis(20), Some(Duration::from_millis(20)), start);
+        assert_eq!(timers.next_deadline(start), Some(start + Duration::from_millis(20)));
+
+        assert!(timers.take_due(start + Duration::from_millis(10)).is_empty());
+
+        let due = timers.take_due(start + Duration::from_millis(50));
+        assert!(matches!(due[0], TimerEvent::Fired { id, widget_id: 2 } if id == interval));
+        assert!(matches!(due[1], TimerEvent::Fired { id, widget_id: 1 } if id == timeout));
+        assert_eq!(due.len(), 2);
+
+        // The timeout is gone; the interval re-armed
+        assert!(!timers.cancel(timeout));
+        assert!(timers.cancel(interval));
+        assert!(timers.is_empty());
+    }
+
+    #[test]
+    fn test_frame_elapsed() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        timers.request_frame();
+        assert_eq!(timers.next_deadline(start), Some(start));
+
+        let frame = |events: Vec<TimerEvent>| match events.as_slice() {
+            [TimerEvent::Frame { elapsed }] => Some(*elapsed),
+            _ => None,
+        };
+        assert_eq!(frame(timers.take_due(start)), Some(Duration::ZERO));
+
+        // Frames are paced to the frame interval
+        timers.request_frame();
+        assert_eq!(frame(timers.take_due(start + Duration::from_millis(5))), None);
+        let next = start + Duration::from_millis(20);
+        assert_eq!(frame(timers.take_due(next)), Some(Duration::from_millis(20)));
+
+        // A new animation after an idle period starts from zero
+        assert!(timers.take_due(next + Duration::from_secs(1)).is_empty());
+        timers.request_frame();
+        assert_eq!(frame(timers.take_due(next + Duration::from_secs(2))), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_zero_interval_is_paced() {
+        let start = Instant::now();
+        let mut timers = Timers::new();
+        timers.schedule(1, Duration::ZERO, Some(Duration::ZERO), start);
+
+        assert_eq!(timers.take_due(start).len(), 1);
+        // Not due again until a frame interval later
+        assert!(timers.take_due(start).is_empty());
+        assert_eq!(timers.next_deadline(start), Some(start + FRAME_INTERVAL));
+        assert_eq!(timers.take_due(start + FRAME_INTERVAL).len(), 1);
+
+        // Falling behind skips to the next tick after now
+        let late = start + Duration::from_secs(1);
+        assert_eq!(timers.take_due(late).len(), 1);
+        assert_eq!(timers.next_deadline(late), Some(late + FRAME_INTERVAL));
+    }
+}
@@ -5,7 +5,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -107,7 +107,6 @@ pub struct ActionCenter {
     quick_actions: Vec<QuickAction>,
     notifications: Vec<ActionNotification>,
     is_open: bool,
-    current_section: usize,
     width: f32,
     height: f32,
     brightness: f32,
@@ -139,7 +138,6 @@ impl ActionCenter {
             ],
             notifications: Vec::new(),
             is_open: false,
-            current_section: 0,
             width: 380.0,
             height: 600.0,
             brightness: 0.75,
@@ -328,47 +326,58 @@ impl Widget for ActionCenter {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         if !self.is_open {
             return;
         }
 
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let text_color = style.color_or(theme.colors.popover_foreground);
+        let font_size = style.font_size_or(14.0);
+        let padding = style.padding_or(EdgeInsets::all(8.0));
+
         // Background
-        painter.fill_rounded_rect(rect, Color::rgba(0.1, 0.1, 0.1, 0.95), BorderRadius::all(8.0));
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.popover), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Header
         let header_rect = Rect::new(rect.x(), rect.y(), rect.width(), 48.0);
-        painter.fill_rect(header_rect, Color::rgba(1.0, 1.0, 1.0, 0.05));
+        painter.fill_rect(header_rect, theme.colors.muted.with_alpha(0.5));
 
         // Header title
         painter.draw_text(
             "Quick actions",
-            Point::new(rect.x() + 16.0, rect.y() + 30.0),
-            Color::WHITE,
-            14.0,
+            Point::new(rect.x() + padding.left * 2.0, rect.y() + 30.0),
+            text_color,
+            font_size,
         );
 
         // Quick actions grid
-        let grid_top = rect.y() + 56.0;
+        let grid_top = rect.y() + 48.0 + padding.top;
         let tile_size = 80.0;
         let tiles_per_row = 4;
-        let padding = 8.0;
+        let gap = 8.0;
 
         for (i, action) in self.quick_actions.iter().enumerate() {
             let row = i / tiles_per_row;
             let col = i % tiles_per_row;
 
             let tile_rect = Rect::new(
-                rect.x() + padding + (col as f32 * (tile_size + padding)),
-                grid_top + (row as f32 * (tile_size + padding)),
+                rect.x() + padding.left + (col as f32 * (tile_size + gap)),
+                grid_top + (row as f32 * (tile_size + gap)),
                 tile_size,
                 tile_size,
             );
 
-            let bg_color = if action.is_active {
-                Color::rgba(0.0, 0.47, 0.84, 0.8)
+            let (bg_color, tile_text) = if action.is_active {
+                (theme.colors.primary, theme.colors.primary_foreground)
             } else {
-                Color::rgba(1.0, 1.0, 1.0, 0.1)
+                (theme.colors.muted, text_color)
             };
             painter.fill_rounded_rect(tile_rect, bg_color, BorderRadius::all(4.0));
 
@@ -376,7 +385,7 @@ impl Widget for ActionCenter {
             painter.draw_text(
                 &action.icon,
                 Point::new(tile_rect.x() + tile_size / 2.0 - 10.0, tile_rect.y() + 36.0),
-                Color::WHITE,
+                tile_text,
                 20.0,
             );
 
@@ -384,20 +393,20 @@ impl Widget for ActionCenter {
             painter.draw_text(
                 &action.name,
                 Point::new(tile_rect.x() + 4.0, tile_rect.y() + tile_size - 8.0),
-                Color::WHITE,
-                10.0,
+                tile_text,
+                font_size - 4.0,
             );
         }
 
         // Brightness slider
         if self.show_brightness {
             let rows = (self.quick_actions.len() as f32 / tiles_per_row as f32).ceil();
-            let slider_top = grid_top + (rows * (tile_size + padding)) + 16.0;
+            let slider_top = grid_top + (rows * (tile_size + gap)) + 16.0;
 
-            painter.draw_text("☀️", Point::new(rect.x() + 16.0, slider_top + 4.0), Color::WHITE, 16.0);
+            painter.draw_text("☀️", Point::new(rect.x() + 16.0, slider_top + 4.0), text_color, 16.0);
 
             let track_rect = Rect::new(rect.x() + 48.0, slider_top, rect.width() - 64.0, 4.0);
-            painter.fill_rounded_rect(track_rect, Color::rgba(1.0, 1.0, 1.0, 0.2), BorderRadius::all(2.0));
+            painter.fill_rounded_rect(track_rect, theme.colors.muted, BorderRadius::all(2.0));
 
             let fill_width = (rect.width() - 64.0) * self.brightness;
             let fill_rect = Rect::new(rect.x() + 48.0, slider_top, fill_width, 4.0);
@@ -1,11 +1,16 @@
 //! Browser address bar widget with URL input and security indicator.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
+use crate::clipboard::{single_line, EditCommand};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton, KeyEventKind, Key};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, SpanStyle, TextLayout};
+
+use super::text_edit::{selection, Editor};
+
+use std::ops::Range;
 
 /// Security state of the current page.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -47,13 +52,26 @@ pub struct AddressBar {
     display_text: String,
     is_focused: bool,
     cursor_position: usize,
+    /// Other end of the selection, the cursor being one end
+    selection_anchor: Option<usize>,
     security_state: SecurityState,
     placeholder: String,
     on_navigate: Option<Box<dyn Fn(&str) + Send + Sync>>,
     on_input: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    /// Left padding and font resolved at layout, for mapping clicks to the cursor
+    padding: f32,
+    font_size: f32,
+    font: SpanStyle,
 }
 
 impl AddressBar {
+    /// Padding used when CSS doesn't set one.
+    const PADDING: EdgeInsets = EdgeInsets::symmetric(0.0, 12.0);
+    /// Font size used when CSS doesn't set one.
+    const FONT_SIZE: f32 = 14.0;
+    /// Space taken by the security indicator before the URL text.
+    const SECURITY_WIDTH: f32 = 28.0;
+
     pub fn new() -> Self {
         Self {
             base: WidgetBase::new().with_class("address-bar"),
@@ -61,10 +79,14 @@ impl AddressBar {
             display_text: String::new(),
             is_focused: false,
             cursor_position: 0,
+            selection_anchor: None,
             security_state: SecurityState::Unknown,
             placeholder: "Search or enter address".to_string(),
             on_navigate: None,
             on_input: None,
+            padding: Self::PADDING.left,
+            font_size: Self::FONT_SIZE,
+            font: SpanStyle::default(),
         }
     }
 
@@ -108,6 +130,31 @@ impl AddressBar {
         display
     }
 
+    /// Byte range of the selected text, if any.
+    fn selection(&self) -> Option<Range<usize>> {
+        selection(self.cursor_position, self.selection_anchor)
+    }
+
+    /// The typed text, cursor and selection, for editing.
+    fn editor(&mut self) -> Editor<'_> {
+        Editor {
+            text: &mut self.display_text,
+            cursor: &mut self.cursor_position,
+            anchor: &mut self.selection_anchor,
+        }
+    }
+
+    /// Run a clipboard shortcut.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) {
+        // Pasted URLs often carry stray whitespace
+        let changed = self.editor().run(command, ctx, |text| single_line(text).trim().to_string());
+        if changed {
+            if let Some(ref handler) = self.on_input {
+                handler(&self.display_text);
+            }
+        }
+    }
+
     fn looks_like_url(&self, input: &str) -> bool {
         input.contains('.') && !input.contains(' ')
             || input.starts_with("http://")
@@ -135,21 +182,30 @@ impl Widget for AddressBar {
         Size::new(400.0, 36.0)
     }
 
-    fn layout(&mut self, constraints: Constraints, _ctx: &LayoutContext) -> LayoutResult {
+    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.padding = style.padding_or(Self::PADDING).left;
+        self.font_size = style.font_size_or(Self::FONT_SIZE);
+        self.font = style.font();
         LayoutResult::new(Size::new(constraints.max_width.clamp(200.0, 800.0), 36.0))
     }
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(8.0));
 
-        let bg_color = if self.is_focused { theme.colors.background } else { theme.colors.card };
-        let border_color = if self.is_focused { theme.colors.accent } else { theme.colors.border };
+        let bg_color = style.background_or(if self.is_focused { theme.colors.background } else { theme.colors.card });
+        let border_color = style.border_color_or(if self.is_focused { theme.colors.accent } else { theme.colors.border });
 
-        painter.fill_rounded_rect(rect, bg_color, BorderRadius::all(8.0));
-        painter.stroke_rounded_rect(rect, border_color, 1.0, BorderRadius::all(8.0));
+        painter.fill_rounded_rect(rect, bg_color, radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rounded_rect(rect, border_color, border_width, radius);
+        }
 
         // Security indicator
-        let security_x = rect.x() + 12.0;
+        let security_x = rect.x() + style.padding_or(Self::PADDING).left;
         painter.draw_text(
             self.security_state.icon(),
             Point::new(security_x, rect.y() + rect.height() / 2.0 - 8.0),
@@ -158,7 +214,8 @@ impl Widget for AddressBar {
         );
 
         // URL text
-        let text_x = security_x + 28.0;
+        let text_x = security_x + Self::SECURITY_WIDTH;
+        let font_size = style.font_size_or(Self::FONT_SIZE);
         let display = if self.display_text.is_empty() && !self.is_focused {
             &self.placeholder
         } else {
@@ -168,14 +225,26 @@ impl Widget for AddressBar {
         let text_color = if self.display_text.is_empty() {
             theme.colors.muted_foreground
         } else {
-            theme.colors.foreground
+            style.color_or(theme.colors.foreground)
         };
 
-        painter.draw_text(display, Point::new(text_x, rect.y() + rect.height() / 2.0 - 7.0), text_color, 14.0);
+        painter.draw_text(display, Point::new(text_x, rect.y() + rect.height() / 2.0 - 7.0), text_color, font_size);
 
-        // Cursor
         if self.is_focused {
-            let cursor_x = text_x + (self.cursor_position as f32 * 8.0);
+            let layout = TextLayout::styled(&self.display_text, font_size, &style.font());
+
+            // Selection highlight
+            if let Some(range) = self.selection() {
+                for selection in layout.selection_rects(range) {
+                    painter.fill_rect(
+                        Rect::new(text_x + selection.x(), rect.y() + 8.0, selection.width(), rect.height() - 16.0),
+                        theme.colors.primary.with_alpha(0.3),
+                    );
+                }
+            }
+
+            // Cursor
+            let cursor_x = text_x + layout.caret_x(self.cursor_position);
             painter.fill_rect(Rect::new(cursor_x, rect.y() + 8.0, 1.5, rect.height() - 16.0), theme.colors.accent);
         }
 
@@ -187,23 +256,40 @@ impl Widget for AddressBar {
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
         let bounds = self.base.bounds;
 
+        if self.base.handle_focus_event(event) {
+            self.is_focused = self.base.state.focused;
+            self.selection_anchor = None;
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
-                if mouse.kind == MouseEventKind::Down && mouse.button == Some(MouseButton::Left) {
-                    if bounds.contains(mouse.position) {
-                        self.is_focused = true;
-                        self.cursor_position = self.display_text.len();
-                        ctx.request_focus(self.base.id);
-                        ctx.request_redraw();
-                        return EventResult::Handled;
-                    } else if self.is_focused {
-                        self.is_focused = false;
-                        ctx.release_focus();
-                        ctx.request_redraw();
-                    }
+                if mouse.kind == MouseEventKind::Down
+                    && mouse.button == Some(MouseButton::Left)
+                    && bounds.contains(mouse.position)
+                {
+                    // The first click focuses with the cursor at the end,
+                    // later ones place it
+                    self.cursor_position = if self.is_focused {
+                        let relative_x = mouse.position.x - bounds.x() - self.padding - Self::SECURITY_WIDTH;
+                        TextLayout::styled(&self.display_text, self.font_size, &self.font)
+                            .hit_test(Point::new(relative_x, self.font_size / 2.0))
+                    } else {
+                        self.display_text.len()
+                    };
+                    self.selection_anchor = None;
+                    ctx.request_focus(self.base.id);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
                 }
             }
             Event::Key(key) if self.is_focused => {
+                if let Some(command) = EditCommand::from_key(key) {
+                    self.edit(command, ctx);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
+                }
                 if key.kind == KeyEventKind::Down {
                     match key.key {
                         Key::Enter => {
@@ -226,13 +312,17 @@ impl Widget for AddressBar {
                         }
                         Key::Escape => {
                             self.display_text = self.format_display_url(&self.url);
-                            self.is_focused = false;
                             ctx.release_focus();
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Backspace => {
-                            if self.cursor_position > 0 {
+                            if self.editor().delete_selection() {
+                                if let Some(ref handler) = self.on_input {
+                                    handler(&self.display_text);
+                                }
+                                ctx.request_redraw();
+                            } else if self.cursor_position > 0 {
                                 self.display_text.remove(self.cursor_position - 1);
                                 self.cursor_position -= 1;
                                 if let Some(ref handler) = self.on_input {
@@ -243,21 +333,25 @@ impl Widget for AddressBar {
                             return EventResult::Handled;
                         }
                         Key::Left => {
+                            self.selection_anchor = None;
                             if self.cursor_position > 0 { self.cursor_position -= 1; }
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Right => {
+                            self.selection_anchor = None;
                             if self.cursor_position < self.display_text.len() { self.cursor_position += 1; }
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Home => {
+                            self.selection_anchor = None;
                             self.cursor_position = 0;
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::End => {
+                            self.selection_anchor = None;
                             self.cursor_position = self.display_text.len();
                             ctx.request_redraw();
                             return EventResult::Handled;
@@ -265,6 +359,7 @@ impl Widget for AddressBar {
                         _ => {
                             // Handle text input
                             if let Some(ref text) = key.text {
+                                self.editor().delete_selection();
                                 for ch in text.chars() {
                                     self.display_text.insert(self.cursor_position, ch);
                                     self.cursor_position += 1;
@@ -287,4 +382,5 @@ impl Widget for AddressBar {
 
     fn bounds(&self) -> Rect { self.base.bounds }
     fn set_bounds(&mut self, bounds: Rect) { self.base.bounds = bounds; }
+    fn tab_index(&self) -> Option<i32> { self.base.focusable_tab_index() }
 }
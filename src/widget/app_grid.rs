@@ -292,7 +292,23 @@ impl Widget for AppGrid {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        let text_color = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(12.0);
+
+        // Background
+        let background = style.background_or(Color::TRANSPARENT);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, radius);
+        }
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
+
         let apps = self.filtered_apps();
 
         for (i, app) in apps.iter().enumerate() {
@@ -312,9 +328,9 @@ impl Widget for AppGrid {
             let is_hovered = self.hovered_index == Some(i);
 
             if is_selected {
-                painter.fill_rounded_rect(cell_rect, Color::rgba(0.0, 0.47, 0.84, 0.3), BorderRadius::all(8.0));
+                painter.fill_rounded_rect(cell_rect, theme.colors.primary.with_alpha(0.3), radius);
             } else if is_hovered {
-                painter.fill_rounded_rect(cell_rect, Color::rgba(1.0, 1.0, 1.0, 0.1), BorderRadius::all(8.0));
+                painter.fill_rounded_rect(cell_rect, theme.colors.surface_hover, radius);
             }
 
             // Icon placeholder
@@ -327,7 +343,7 @@ impl Widget for AppGrid {
                     self.icon_size,
                 ),
             };
-            painter.fill_rounded_rect(icon_rect, Color::rgba(1.0, 1.0, 1.0, 0.2), BorderRadius::all(8.0));
+            painter.fill_rounded_rect(icon_rect, theme.colors.muted, radius);
 
             // App name
             if self.show_labels {
@@ -339,7 +355,7 @@ impl Widget for AppGrid {
                     GridLayout::List => cell_rect.y() + 28.0,
                     _ => cell_rect.y() + self.icon_size + 24.0,
                 };
-                painter.draw_text(&app.name, Point::new(text_x, text_y), Color::WHITE, 12.0);
+                painter.draw_text(&app.name, Point::new(text_x, text_y), text_color, font_size);
             }
         }
     }
@@ -5,7 +5,7 @@ use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind};
 use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{ImageCache, ImageHandle, ObjectFit, Painter};
 
 /// Avatar size presets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -76,7 +76,8 @@ pub enum AvatarShape {
 pub struct Avatar {
     base: WidgetBase,
     initials: Option<String>,
-    image_path: Option<String>,
+    /// Photo, cropped to fill the avatar once loaded
+    image: Option<ImageHandle>,
     fallback_initials: Option<String>,
     size: AvatarSize,
     shape: AvatarShape,
@@ -90,7 +91,7 @@ impl Avatar {
         Self {
             base: WidgetBase::new().with_class("avatar"),
             initials: None,
-            image_path: None,
+            image: None,
             fallback_initials: None,
             size: AvatarSize::default(),
             shape: AvatarShape::default(),
@@ -112,9 +113,16 @@ impl Avatar {
         self
     }
 
-    /// Set the image path.
+    /// Set the image path. The file loads in the background, showing the
+    /// initials until it is ready or if it fails.
     pub fn image(mut self, path: impl Into<String>) -> Self {
-        self.image_path = Some(path.into());
+        self.image = Some(ImageCache::global().load_path(path.into()));
+        self
+    }
+
+    /// Set an image already in the `ImageCache`, e.g. loaded from bytes.
+    pub fn image_handle(mut self, handle: ImageHandle) -> Self {
+        self.image = Some(handle);
         self
     }
 
@@ -242,10 +250,11 @@ impl Widget for Avatar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = self.border_radius();
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(self.border_radius());
 
         // Determine background color
-        let bg_color = self.background_color.unwrap_or_else(|| {
+        let bg_color = style.background_or(self.background_color.unwrap_or_else(|| {
             if let Some(ref initials) = self.initials {
                 Self::color_from_string(initials)
             } else if let Some(ref fallback) = self.fallback_initials {
@@ -253,18 +262,23 @@ impl Widget for Avatar {
             } else {
                 theme.colors.muted
             }
-        });
+        }));
 
         // Draw background
         painter.fill_rounded_rect(rect, bg_color, radius);
 
-        // Draw initials (if no image or image failed)
-        // TODO: Add image rendering when image loading is implemented
+        // Draw the image cropped to the shape, or the initials if there is
+        // none or it isn't loaded
+        let image = self.image.filter(|handle| ImageCache::global().get(*handle).is_some());
         let text = self.initials.as_ref()
             .or(self.fallback_initials.as_ref());
 
-        if let Some(initials) = text {
-            let font_size = self.size.font_size();
+        if let Some(image) = image {
+            painter.push_rounded_clip(rect, radius);
+            painter.draw_image_fit(rect, image.id(), ObjectFit::Cover);
+            painter.pop_clip();
+        } else if let Some(initials) = text {
+            let font_size = style.font_size_or(self.size.font_size());
             let text_width = initials.len() as f32 * font_size * 0.6;
             let text_x = rect.x() + (rect.width() - text_width) / 2.0;
             let text_y = rect.y() + (rect.height() + font_size * 0.8) / 2.0;
@@ -272,7 +286,7 @@ impl Widget for Avatar {
             painter.draw_text(
                 initials,
                 Point::new(text_x, text_y),
-                Color::WHITE,
+                style.color_or(Color::WHITE),
                 font_size,
             );
         }
@@ -1,6 +1,6 @@
 //! Bar widget for taskbar/panel-like UI elements.
 
-use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
+use super::{Slots, Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
 use crate::geometry::{BorderRadius, Color, Rect, Size, EdgeInsets};
@@ -47,6 +47,13 @@ pub enum BarVariant {
     Minimal,
 }
 
+/// Slot of the start section in `Bar::sections`.
+const START: usize = 0;
+/// Slot of the center section.
+const CENTER: usize = 1;
+/// Slot of the end section.
+const END: usize = 2;
+
 /// A bar widget for taskbars, panels, docks, and status bars.
 ///
 /// The bar can be positioned at any edge of the screen and contains
@@ -91,12 +98,9 @@ pub struct Bar {
     thickness: f32,
     /// Padding inside the bar
     padding: EdgeInsets,
-    /// Content for the start section (left for horizontal, top for vertical)
-    start: Option<Box<dyn Widget>>,
-    /// Content for the center section
-    center: Option<Box<dyn Widget>>,
-    /// Content for the end section (right for horizontal, bottom for vertical)
-    end: Option<Box<dyn Widget>>,
+    /// Content for the start section (left for horizontal, top for
+    /// vertical), the center section and the end section
+    sections: Slots<3>,
     /// Gap between sections
     gap: f32,
     /// Whether the bar should auto-hide
@@ -118,9 +122,7 @@ impl Bar {
             variant: BarVariant::default(),
             thickness: 40.0,
             padding: EdgeInsets::symmetric(8.0, 12.0),
-            start: None,
-            center: None,
-            end: None,
+            sections: Slots::new(),
             gap: 8.0,
             auto_hide: false,
             visible: true,
@@ -171,19 +173,19 @@ impl Bar {
 
     /// Set the start section content.
     pub fn start<W: Widget + 'static>(mut self, widget: W) -> Self {
-        self.start = Some(Box::new(widget));
+        self.sections.set(START, Box::new(widget));
         self
     }
 
     /// Set the center section content.
     pub fn center<W: Widget + 'static>(mut self, widget: W) -> Self {
-        self.center = Some(Box::new(widget));
+        self.sections.set(CENTER, Box::new(widget));
         self
     }
 
     /// Set the end section content.
     pub fn end<W: Widget + 'static>(mut self, widget: W) -> Self {
-        self.end = Some(Box::new(widget));
+        self.sections.set(END, Box::new(widget));
         self
     }
 
@@ -314,11 +316,12 @@ impl Widget for Bar {
         self.base.bounds.size = size;
 
         // Calculate content area
+        let padding = ctx.style(self).padding_or(self.padding);
         let content_rect = Rect::new(
-            self.base.bounds.x() + margin + self.padding.left,
-            self.base.bounds.y() + margin + self.padding.top,
-            size.width - margin * 2.0 - self.padding.left - self.padding.right,
-            size.height - margin * 2.0 - self.padding.top - self.padding.bottom,
+            self.base.bounds.x() + margin + padding.left,
+            self.base.bounds.y() + margin + padding.top,
+            size.width - margin * 2.0 - padding.left - padding.right,
+            size.height - margin * 2.0 - padding.top - padding.bottom,
         );
 
         // Layout sections based on orientation
@@ -333,9 +336,10 @@ impl Widget for Bar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let margin = self.get_margin();
-        let radius = self.get_border_radius(theme);
-        let bg_color = self.get_background_color(theme);
+        let radius = style.border_radius_or(self.get_border_radius(theme));
+        let bg_color = style.background_or(self.get_background_color(theme));
 
         // Calculate bar rect (with margin for floating variant)
         let bar_rect = Rect::new(
@@ -363,7 +367,7 @@ impl Widget for Bar {
 
         // Draw border for solid variant
         if self.variant == BarVariant::Solid {
-            let border_color = theme.colors.border;
+            let border_color = style.border_color_or(theme.colors.border);
             match self.position {
                 BarPosition::Top => {
                     painter.fill_rect(
@@ -394,39 +398,31 @@ impl Widget for Bar {
 
         // Draw border for floating variant
         if self.variant == BarVariant::Floating {
-            painter.stroke_rect(bar_rect, theme.colors.border.with_alpha(0.3), 1.0);
+            painter.stroke_rect(bar_rect, style.border_color_or(theme.colors.border.with_alpha(0.3)), 1.0);
         }
 
         // Paint children
-        if let Some(start) = &self.start {
-            start.paint(painter, start.bounds(), ctx);
+        if let Some(start) = self.sections.get(START) {
+            ctx.paint_child(start, painter, start.bounds());
         }
-        if let Some(center) = &self.center {
-            center.paint(painter, center.bounds(), ctx);
+        if let Some(center) = self.sections.get(CENTER) {
+            ctx.paint_child(center, painter, center.bounds());
         }
-        if let Some(end) = &self.end {
-            end.paint(painter, end.bounds(), ctx);
+        if let Some(end) = self.sections.get(END) {
+            ctx.paint_child(end, painter, end.bounds());
         }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
-        // Forward events to children
-        if let Some(end) = &mut self.end {
-            if end.handle_event(event, ctx) == EventResult::Handled {
-                return EventResult::Handled;
-            }
+        // Forward events to children; pointer events are routed by the dispatcher
+        if matches!(event, Event::Mouse(_)) {
+            return EventResult::Ignored;
         }
-        if let Some(center) = &mut self.center {
-            if center.handle_event(event, ctx) == EventResult::Handled {
+        for section in self.sections.range_mut(START, END + 1).iter_mut().rev() {
+            if section.handle_event(event, ctx) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
-        if let Some(start) = &mut self.start {
-            if start.handle_event(event, ctx) == EventResult::Handled {
-                return EventResult::Handled;
-            }
-        }
-
         EventResult::Ignored
     }
 
@@ -439,12 +435,11 @@ impl Widget for Bar {
     }
 
     fn children(&self) -> &[Box<dyn Widget>] {
-        // Can't easily return all children as a slice, return empty
-        &[]
+        self.sections.range(START, END + 1)
     }
 
     fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
-        &mut []
+        self.sections.range_mut(START, END + 1)
     }
 }
 
@@ -462,7 +457,7 @@ impl Bar {
         };
 
         // Layout start section
-        let start_width = if let Some(start) = &mut self.start {
+        let start_width = if let Some(start) = self.sections.get_mut(START) {
             let result = start.layout(child_constraints, ctx);
             start.set_bounds(Rect::new(
                 content_rect.x(),
@@ -476,7 +471,7 @@ impl Bar {
         };
 
         // Layout end section
-        let end_width = if let Some(end) = &mut self.end {
+        let end_width = if let Some(end) = self.sections.get_mut(END) {
             let result = end.layout(child_constraints, ctx);
             end.set_bounds(Rect::new(
                 content_rect.x() + available_width - result.size.width,
@@ -490,7 +485,7 @@ impl Bar {
         };
 
         // Layout center section (centered in remaining space)
-        if let Some(center) = &mut self.center {
+        if let Some(center) = self.sections.get_mut(CENTER) {
             let center_constraints = Constraints {
                 min_width: 0.0,
                 min_height: 0.0,
@@ -523,7 +518,7 @@ impl Bar {
         };
 
         // Layout start section (top)
-        let start_height = if let Some(start) = &mut self.start {
+        let start_height = if let Some(start) = self.sections.get_mut(START) {
             let result = start.layout(child_constraints, ctx);
             start.set_bounds(Rect::new(
                 content_rect.x() + (content_width - result.size.width) / 2.0,
@@ -537,7 +532,7 @@ impl Bar {
         };
 
         // Layout end section (bottom)
-        let end_height = if let Some(end) = &mut self.end {
+        let end_height = if let Some(end) = self.sections.get_mut(END) {
             let result = end.layout(child_constraints, ctx);
             end.set_bounds(Rect::new(
                 content_rect.x() + (content_width - result.size.width) / 2.0,
@@ -551,7 +546,7 @@ impl Bar {
         };
 
         // Layout center section (centered in remaining space)
-        if let Some(center) = &mut self.center {
+        if let Some(center) = self.sections.get_mut(CENTER) {
             let center_constraints = Constraints {
                 min_width: 0.0,
                 min_height: 0.0,
@@ -5,7 +5,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -251,27 +251,34 @@ impl Widget for BatteryIndicator {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
-        // Background on hover
-        if self.is_open {
-            painter.fill_rounded_rect(rect, Color::rgba(1.0, 1.0, 1.0, 0.1), BorderRadius::all(4.0));
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let color = style.color_or(theme.colors.foreground);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 4.0));
+
+        // Background, or a highlight while open
+        let background = if self.is_open { theme.colors.surface_hover } else { Color::TRANSPARENT };
+        let background = style.background_or(background);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, style.border_radius_or(BorderRadius::all(4.0)));
         }
 
         // Battery outline
         let battery_width = 20.0;
         let battery_height = 10.0;
-        let battery_x = rect.x() + 4.0;
+        let battery_x = rect.x() + padding.left;
         let battery_y = rect.y() + (rect.height() - battery_height) / 2.0;
 
         painter.stroke_rect(
             Rect::new(battery_x, battery_y, battery_width, battery_height),
-            Color::WHITE,
-            1.0,
+            style.border_color_or(color),
+            style.border_width_or(1.0),
         );
 
         // Battery tip
         let tip_rect = Rect::new(battery_x + battery_width, battery_y + 3.0, 2.0, 4.0);
-        painter.fill_rect(tip_rect, Color::WHITE);
+        painter.fill_rect(tip_rect, style.border_color_or(color));
 
         // Battery fill
         let fill_width = (battery_width - 4.0) * (self.info.level as f32 / 100.0);
@@ -282,9 +289,9 @@ impl Widget for BatteryIndicator {
         let percent = format!("{}%", self.info.level);
         painter.draw_text(
             &percent,
-            Point::new(rect.x() + 28.0, rect.y() + rect.height() / 2.0 + 4.0),
-            Color::WHITE,
-            11.0,
+            Point::new(battery_x + 24.0, rect.y() + rect.height() / 2.0 + 4.0),
+            color,
+            style.font_size_or(11.0),
         );
     }
 
@@ -118,10 +118,16 @@ impl Widget for BookmarkBar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let foreground = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(12.0);
 
         // Background
-        painter.fill_rect(rect, theme.colors.card);
-        painter.fill_rect(Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0), theme.colors.border);
+        painter.fill_rect(rect, style.background_or(theme.colors.card));
+        painter.fill_rect(
+            Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0),
+            style.border_color_or(theme.colors.border),
+        );
 
         let mut x = rect.x() + 8.0;
 
@@ -136,14 +142,14 @@ impl Widget for BookmarkBar {
 
             // Icon
             let icon = if bookmark.is_folder { "📁" } else { bookmark.favicon.as_deref().unwrap_or("🔗") };
-            painter.draw_text(icon, Point::new(x + 8.0, rect.y() + rect.height() / 2.0 - 7.0), theme.colors.foreground, 12.0);
+            painter.draw_text(icon, Point::new(x + 8.0, rect.y() + rect.height() / 2.0 - 7.0), foreground, font_size);
 
             // Title
             painter.draw_text(
                 &bookmark.display_title(20),
                 Point::new(x + 26.0, rect.y() + rect.height() / 2.0 - 6.0),
-                theme.colors.foreground,
-                12.0,
+                foreground,
+                font_size,
             );
 
             x += width + 4.0;
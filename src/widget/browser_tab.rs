@@ -171,10 +171,11 @@ impl Widget for BrowserTabBar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let tab_width = self.calculate_tab_width(rect.width());
 
         // Draw background
-        painter.fill_rect(rect, theme.colors.card);
+        painter.fill_rect(rect, style.background_or(theme.colors.card));
 
         // Draw tabs
         for (i, tab) in self.tabs.iter().enumerate() {
@@ -225,8 +226,8 @@ impl Widget for BrowserTabBar {
                     painter.draw_text(
                         &title,
                         Point::new(content_x, rect.y() + rect.height() / 2.0 - 7.0),
-                        if is_selected { theme.colors.foreground } else { theme.colors.muted_foreground },
-                        13.0,
+                        if is_selected { style.color_or(theme.colors.foreground) } else { theme.colors.muted_foreground },
+                        style.font_size_or(13.0),
                     );
                 }
 
@@ -255,7 +256,10 @@ impl Widget for BrowserTabBar {
         }
 
         // Bottom border
-        painter.fill_rect(Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0), theme.colors.border);
+        painter.fill_rect(
+            Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0),
+            style.border_color_or(theme.colors.border),
+        );
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
@@ -128,8 +128,9 @@ impl Widget for BrowserToolbar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
-        painter.fill_rect(rect, theme.colors.card);
+        painter.fill_rect(rect, style.background_or(theme.colors.card));
 
         let y_center = rect.y() + rect.height() / 2.0;
         let button_size = 32.0;
@@ -173,7 +174,8 @@ impl Widget for BrowserToolbar {
 
         // URL
         let url_display = self.format_url();
-        painter.draw_text(&url_display, Point::new(addr_x + 32.0, y_center - 7.0), theme.colors.foreground, 13.0);
+        let url_color = style.color_or(theme.colors.foreground);
+        painter.draw_text(&url_display, Point::new(addr_x + 32.0, y_center - 7.0), url_color, style.font_size_or(13.0));
 
         // Loading indicator
         if self.is_loading {
@@ -212,7 +214,10 @@ impl Widget for BrowserToolbar {
         }
 
         // Bottom border
-        painter.fill_rect(Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0), theme.colors.border);
+        painter.fill_rect(
+            Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0),
+            style.border_color_or(theme.colors.border),
+        );
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
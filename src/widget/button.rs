@@ -1,9 +1,9 @@
 //! Button widget.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, ComputedStyle, StyleProperty, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -72,21 +72,30 @@ impl Button {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Set the element ID.
     pub fn id(mut self, id: &str) -> Self {
         self.base.element_id = Some(id.to_string());
         self
     }
 
+    /// Tailwind-style padding (px-4 py-2.5) used when CSS doesn't set one.
+    const PADDING: EdgeInsets = EdgeInsets::symmetric(10.0, 16.0);
+
     /// Get the background color based on state and variant.
-    fn background_color(&self, theme: &crate::theme::ThemeData) -> Color {
-        let base = match self.variant {
+    fn background_color(&self, style: &ComputedStyle, theme: &crate::theme::ThemeData) -> Color {
+        let base = style.background_or(match self.variant {
             ButtonVariant::Primary => theme.colors.primary,
             ButtonVariant::Secondary => theme.colors.secondary,
             ButtonVariant::Outline => Color::TRANSPARENT,
             ButtonVariant::Ghost => Color::TRANSPARENT,
             ButtonVariant::Destructive => theme.colors.destructive,
-        };
+        });
 
         // Ghost and outline get hover backgrounds
         let hover_bg = match self.variant {
@@ -109,14 +118,14 @@ impl Button {
     }
 
     /// Get the text color based on variant.
-    fn text_color(&self, theme: &crate::theme::ThemeData) -> Color {
-        match self.variant {
+    fn text_color(&self, style: &ComputedStyle, theme: &crate::theme::ThemeData) -> Color {
+        style.color_or(match self.variant {
             ButtonVariant::Primary => theme.colors.primary_foreground,
             ButtonVariant::Secondary => theme.colors.secondary_foreground,
             ButtonVariant::Outline => theme.colors.foreground,
             ButtonVariant::Ghost => theme.colors.foreground,
             ButtonVariant::Destructive => theme.colors.destructive_foreground,
-        }
+        })
     }
 }
 
@@ -141,14 +150,17 @@ impl Widget for Button {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
         // Tailwind-style button sizing (px-4 py-2.5 with medium font)
-        let font_size = 14.0;
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(14.0);
+        let padding = style.padding_or(Self::PADDING);
         let char_width = font_size * 0.55; // Slightly tighter character spacing
         let text_width = self.label.len() as f32 * char_width;
-        let padding_h = 16.0 * 2.0; // px-4 (16px each side)
-        let padding_v = 10.0 * 2.0; // py-2.5 (10px each side)
-        Size::new(text_width + padding_h, font_size + padding_v)
+        Size::new(
+            text_width + padding.horizontal(),
+            font_size + padding.vertical(),
+        )
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
@@ -160,9 +172,10 @@ impl Widget for Button {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Tailwind-style rounded corners (rounded-lg = 8px)
-        let radius = BorderRadius::all(8.0);
+        let radius = style.border_radius_or(BorderRadius::all(8.0));
 
         // Shadow for depth (subtle shadow for primary/destructive, none for ghost/outline)
         if !self.base.state.pressed && matches!(self.variant, ButtonVariant::Primary | ButtonVariant::Secondary | ButtonVariant::Destructive) {
@@ -173,22 +186,24 @@ impl Widget for Button {
         }
 
         // Background
-        let bg_color = self.background_color(theme);
+        let bg_color = self.background_color(&style, theme);
         painter.fill_rounded_rect(rect, bg_color, radius);
 
         // Border for outline variant (Tailwind-style border)
-        if self.variant == ButtonVariant::Outline {
-            let border_color = if self.base.state.hovered {
+        let default_border = if self.variant == ButtonVariant::Outline { 1.0 } else { 0.0 };
+        let border_width = style.border_width_or(default_border);
+        if border_width > 0.0 {
+            let border_color = if self.base.state.hovered && !style.is_set(&StyleProperty::BorderColor) {
                 theme.colors.foreground.with_alpha(0.2)
             } else {
-                theme.colors.border
+                style.border_color_or(theme.colors.border)
             };
-            painter.stroke_rounded_rect(rect, border_color, 1.0, radius);
+            painter.stroke_rounded_rect(rect, border_color, border_width, radius);
         }
 
         // Text - centered with proper font sizing
-        let text_color = self.text_color(theme);
-        let font_size = 14.0;
+        let text_color = self.text_color(&style, theme);
+        let font_size = style.font_size_or(14.0);
         let char_width = font_size * 0.55;
         let text_width = self.label.len() as f32 * char_width;
         let text_x = rect.x() + (rect.width() - text_width) / 2.0;
@@ -204,12 +219,22 @@ impl Widget for Button {
                 rect.width() + ring_offset * 2.0,
                 rect.height() + ring_offset * 2.0,
             );
-            let ring_radius = BorderRadius::all(8.0 + ring_offset);
+            let ring_radius = BorderRadius::new(
+                radius.top_left + ring_offset,
+                radius.top_right + ring_offset,
+                radius.bottom_right + ring_offset,
+                radius.bottom_left + ring_offset,
+            );
             painter.stroke_rounded_rect(ring_rect, theme.colors.ring.with_alpha(0.5), 2.0, ring_radius);
         }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         if let Event::Mouse(mouse) = event {
             let in_bounds = self.bounds().contains(mouse.position);
 
@@ -263,4 +288,8 @@ impl Widget for Button {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
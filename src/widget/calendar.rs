@@ -272,23 +272,33 @@ impl Widget for Calendar {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let text_color = style.color_or(theme.colors.popover_foreground);
+        let font_size = style.font_size_or(14.0);
         let cell_size = rect.width() / 7.0;
 
         // Background
-        painter.fill_rounded_rect(rect, Color::rgba(0.12, 0.12, 0.12, 0.98), BorderRadius::all(8.0));
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.popover), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Header
         let header_rect = Rect::new(rect.x(), rect.y(), rect.width(), 48.0);
-        painter.fill_rect(header_rect, Color::rgba(1.0, 1.0, 1.0, 0.05));
+        painter.fill_rect(header_rect, theme.colors.muted.with_alpha(0.5));
 
         // Month/year title
         let title = format!("{} {}", self.month_name(), self.year);
-        painter.draw_text(&title, Point::new(rect.x() + 16.0, rect.y() + 30.0), Color::WHITE, 16.0);
+        let title_size = font_size + 2.0;
+        painter.draw_text(&title, Point::new(rect.x() + 16.0, rect.y() + 30.0), text_color, title_size);
 
         // Navigation arrows
-        painter.draw_text("◀", Point::new(rect.max_x() - 64.0, rect.y() + 30.0), Color::WHITE, 16.0);
-        painter.draw_text("▶", Point::new(rect.max_x() - 32.0, rect.y() + 30.0), Color::WHITE, 16.0);
+        painter.draw_text("◀", Point::new(rect.max_x() - 64.0, rect.y() + 30.0), text_color, title_size);
+        painter.draw_text("▶", Point::new(rect.max_x() - 32.0, rect.y() + 30.0), text_color, title_size);
 
         // Weekday headers
         let weekdays = ["S", "M", "T", "W", "T", "F", "S"];
@@ -299,8 +309,8 @@ impl Widget for Calendar {
             painter.draw_text(
                 day,
                 Point::new(cell_x + cell_size / 2.0 - 4.0, weekday_y + 20.0),
-                Color::rgba(1.0, 1.0, 1.0, 0.6),
-                12.0,
+                theme.colors.muted_foreground,
+                font_size - 2.0,
             );
         }
 
@@ -323,18 +333,18 @@ impl Widget for Calendar {
             let is_selected = self.selected_day == Some(day);
 
             if is_selected {
-                painter.fill_rounded_rect(cell_rect, Color::rgb(0.0, 0.47, 0.84), BorderRadius::all(cell_size / 2.0));
+                painter.fill_rounded_rect(cell_rect, theme.colors.primary, BorderRadius::all(cell_size / 2.0));
             } else if is_today {
-                painter.stroke_rect(cell_rect, Color::rgb(0.0, 0.47, 0.84), 2.0);
+                painter.stroke_rect(cell_rect, theme.colors.primary, 2.0);
             }
 
             // Day number
-            let text_color = Color::WHITE;
+            let day_color = if is_selected { theme.colors.primary_foreground } else { text_color };
             painter.draw_text(
                 &day.to_string(),
                 Point::new(cell_rect.x() + cell_size / 2.0 - 6.0, cell_rect.y() + cell_size / 2.0 + 5.0),
-                text_color,
-                14.0,
+                day_color,
+                font_size,
             );
 
             // Event indicator
@@ -1,11 +1,13 @@
 //! Card container widget with styled background.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, ComputedStyle, WidgetState};
 use crate::event::{Event, EventResult};
+use crate::focus::FocusScope;
 use crate::geometry::{BorderRadius, Color, Rect, Size, EdgeInsets};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
+use crate::theme::BoxShadow;
 
 /// Card variant for different styles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -48,6 +50,11 @@ pub struct Card {
     variant: CardVariant,
     padding: EdgeInsets,
     border_radius: Option<f32>,
+    /// Padding resolved from CSS at layout, falling back to `padding`
+    content_padding: EdgeInsets,
+    /// Content box the child was laid out in, for resolving percentages in
+    /// its styles
+    content_box: Size,
 }
 
 impl Card {
@@ -59,6 +66,8 @@ impl Card {
             variant: CardVariant::default(),
             padding: EdgeInsets::all(16.0),
             border_radius: None,
+            content_padding: EdgeInsets::all(16.0),
+            content_box: Size::ZERO,
         }
     }
 
@@ -98,28 +107,39 @@ impl Card {
         self
     }
 
-    fn get_border_radius(&self, theme: &crate::theme::ThemeData) -> BorderRadius {
+    /// Group the focus of the content, or trap it for modal content.
+    pub fn focus_scope(mut self, scope: FocusScope) -> Self {
+        self.base.focus_scope = scope;
+        self
+    }
+
+    fn get_border_radius(&self, style: &ComputedStyle, theme: &crate::theme::ThemeData) -> BorderRadius {
         let radius = self.border_radius
             .unwrap_or(theme.radii.lg * theme.typography.base_size);
-        BorderRadius::all(radius)
+        style.border_radius_or(BorderRadius::all(radius))
     }
 
-    fn background_color(&self, theme: &crate::theme::ThemeData) -> Color {
-        match self.variant {
+    fn background_color(&self, style: &ComputedStyle, theme: &crate::theme::ThemeData) -> Color {
+        style.background_or(match self.variant {
             CardVariant::Default | CardVariant::Elevated | CardVariant::Outlined => {
                 theme.colors.card
             }
             CardVariant::Ghost => Color::TRANSPARENT,
             CardVariant::Glass => theme.colors.card.with_alpha(0.8),
-        }
+        })
     }
 
     fn should_draw_border(&self) -> bool {
         matches!(self.variant, CardVariant::Default | CardVariant::Outlined | CardVariant::Glass)
     }
 
-    fn should_draw_shadow(&self) -> bool {
-        matches!(self.variant, CardVariant::Default | CardVariant::Elevated)
+    /// Shadows drawn when no `box-shadow` rule applies.
+    fn default_shadows(&self, theme: &crate::theme::ThemeData) -> Vec<BoxShadow> {
+        match self.variant {
+            CardVariant::Default => vec![theme.shadows.default.clone()],
+            CardVariant::Elevated => vec![theme.shadows.lg.clone()],
+            _ => Vec::new(),
+        }
     }
 }
 
@@ -151,35 +171,46 @@ impl Widget for Card {
     }
 
     fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
-        if let Some(child) = &self.child {
-            let child_size = child.intrinsic_size(ctx);
+        let style = ctx.style(self);
+        let padding = style.padding_or(self.padding);
+        let sizes = Constraints::unbounded().with_style(&style);
+        let size = if let Some(child) = &self.child {
+            let content = Size::new(sizes.max_width - padding.horizontal(), sizes.max_height - padding.vertical());
+            let child_size = ctx.in_containing_block(content, |ctx| child.intrinsic_size(ctx));
             Size::new(
-                child_size.width + self.padding.left + self.padding.right,
-                child_size.height + self.padding.top + self.padding.bottom,
+                child_size.width + padding.left + padding.right,
+                child_size.height + padding.top + padding.bottom,
             )
         } else {
             Size::new(
-                self.padding.left + self.padding.right + 100.0,
-                self.padding.top + self.padding.bottom + 100.0,
+                padding.left + padding.right + 100.0,
+                padding.top + padding.bottom + 100.0,
             )
-        }
+        };
+        sizes.constrain(size)
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.content_padding = style.padding_or(self.padding);
+        let padding = self.content_padding;
+        let constraints = constraints.with_style(&style);
+
         let child_constraints = Constraints {
             min_width: 0.0,
             min_height: 0.0,
-            max_width: constraints.max_width - self.padding.left - self.padding.right,
-            max_height: constraints.max_height - self.padding.top - self.padding.bottom,
+            max_width: (constraints.max_width - padding.left - padding.right).max(0.0),
+            max_height: (constraints.max_height - padding.top - padding.bottom).max(0.0),
         };
 
         let child_size = if let Some(child) = &mut self.child {
-            let result = child.layout(child_constraints, ctx);
+            self.content_box = Size::new(child_constraints.max_width, child_constraints.max_height);
+            let result = ctx.in_containing_block(self.content_box, |ctx| child.layout(child_constraints, ctx));
 
             // Position child with padding offset
             child.set_bounds(Rect::new(
-                self.base.bounds.x() + self.padding.left,
-                self.base.bounds.y() + self.padding.top,
+                self.base.bounds.x() + padding.left,
+                self.base.bounds.y() + padding.top,
                 result.size.width,
                 result.size.height,
             ));
@@ -190,8 +221,8 @@ impl Widget for Card {
         };
 
         let size = Size::new(
-            child_size.width + self.padding.left + self.padding.right,
-            child_size.height + self.padding.top + self.padding.bottom,
+            child_size.width + padding.left + padding.right,
+            child_size.height + padding.top + padding.bottom,
         );
 
         let size = constraints.constrain(size);
@@ -201,49 +232,41 @@ impl Widget for Card {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = self.get_border_radius(theme);
-
-        // Shadow (simplified - real shadows would use blur)
-        if self.should_draw_shadow() {
-            let shadow_offset = match self.variant {
-                CardVariant::Elevated => 8.0,
-                _ => 2.0,
-            };
-            let shadow_rect = Rect::new(
-                rect.x() + shadow_offset / 2.0,
-                rect.y() + shadow_offset,
-                rect.width(),
-                rect.height(),
-            );
-            painter.fill_rounded_rect(
-                shadow_rect,
-                Color::BLACK.with_alpha(0.1),
-                radius,
-            );
-        }
+        let style = ctx.style(self);
+        let radius = self.get_border_radius(&style, theme);
+
+        // Shadow
+        let shadows = style.box_shadow_or(&self.default_shadows(theme));
+        painter.draw_shadows(rect, radius, &shadows);
 
         // Background
-        painter.fill_rounded_rect(rect, self.background_color(theme), radius);
+        painter.fill_rounded_rect(rect, self.background_color(&style, theme), radius);
+        painter.draw_inset_shadows(rect, radius, &shadows);
 
         // Border
-        if self.should_draw_border() {
-            painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(if self.should_draw_border() { 1.0 } else { 0.0 });
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
         }
 
         // Paint child
         if let Some(child) = &self.child {
+            let padding = self.content_padding;
             let child_rect = Rect::new(
-                rect.x() + self.padding.left,
-                rect.y() + self.padding.top,
-                rect.width() - self.padding.left - self.padding.right,
-                rect.height() - self.padding.top - self.padding.bottom,
+                rect.x() + padding.left,
+                rect.y() + padding.top,
+                rect.width() - padding.left - padding.right,
+                rect.height() - padding.top - padding.bottom,
             );
-            child.paint(painter, child_rect, ctx);
+            ctx.in_containing_block(self.content_box, |ctx| ctx.paint_child(child.as_ref(), painter, child_rect));
         }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
-        // Forward events to child
+        // Forward events to child; pointer events are routed by the dispatcher
+        if matches!(event, Event::Mouse(_)) {
+            return EventResult::Ignored;
+        }
         if let Some(child) = &mut self.child {
             return child.handle_event(event, ctx);
         }
@@ -259,11 +282,12 @@ impl Widget for Card {
 
         // Update child bounds
         if let Some(child) = &mut self.child {
+            let padding = self.content_padding;
             child.set_bounds(Rect::new(
-                bounds.x() + self.padding.left,
-                bounds.y() + self.padding.top,
-                bounds.width() - self.padding.left - self.padding.right,
-                bounds.height() - self.padding.top - self.padding.bottom,
+                bounds.x() + padding.left,
+                bounds.y() + padding.top,
+                bounds.width() - padding.left - padding.right,
+                bounds.height() - padding.top - padding.bottom,
             ));
         }
     }
@@ -283,4 +307,7 @@ impl Widget for Card {
             &mut []
         }
     }
+    fn focus_scope(&self) -> FocusScope {
+        self.base.focus_scope
+    }
 }
@@ -4,11 +4,15 @@
 //! Supports multiple AI providers including local models and cloud APIs.
 
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
+use crate::clipboard::{single_line, EditCommand};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult, Key, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::event::{Event, EventResult, ImeEvent, Key, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, SpanStyle, TextLayout};
+use crate::theme::ThemeColors;
+
+use super::text_edit::{selection, Editor};
 
 /// A single message in the chat
 #[derive(Debug, Clone)]
@@ -215,6 +219,10 @@ pub struct ChatWindow {
     input_text: String,
     input_focused: bool,
     cursor_position: usize,
+    /// Other end of the input selection, the cursor being one end
+    selection_anchor: Option<usize>,
+    /// Text being composed by the input method, shown at the cursor
+    preedit: String,
 
     // Scroll
     scroll_offset: f32,
@@ -229,15 +237,14 @@ pub struct ChatWindow {
     height: f32,
     input_height: f32,
     message_padding: f32,
+    /// Font resolved at layout
+    font_size: f32,
+    font: SpanStyle,
 
-    // Colors
-    user_bubble_color: Color,
-    assistant_bubble_color: Color,
-    system_bubble_color: Color,
-    input_bg_color: Color,
-    text_color: Color,
-    placeholder_color: Color,
-    accent_color: Color,
+    // Colors, from the theme unless set
+    user_bubble_color: Option<Color>,
+    assistant_bubble_color: Option<Color>,
+    accent_color: Option<Color>,
 
     // Callbacks
     #[allow(clippy::type_complexity)]
@@ -247,6 +254,9 @@ pub struct ChatWindow {
 }
 
 impl ChatWindow {
+    /// Font size used when CSS doesn't set one.
+    const FONT_SIZE: f32 = 14.0;
+
     /// Create a new chat window
     pub fn new() -> Self {
         Self {
@@ -257,6 +267,8 @@ impl ChatWindow {
             input_text: String::new(),
             input_focused: false,
             cursor_position: 0,
+            selection_anchor: None,
+            preedit: String::new(),
             scroll_offset: 0.0,
             auto_scroll: true,
             state: ChatState::Idle,
@@ -265,13 +277,11 @@ impl ChatWindow {
             height: 600.0,
             input_height: 48.0,
             message_padding: 12.0,
-            user_bubble_color: Color::rgb(0.0, 0.47, 0.84),
-            assistant_bubble_color: Color::rgb(0.2, 0.2, 0.25),
-            system_bubble_color: Color::rgb(0.3, 0.25, 0.2),
-            input_bg_color: Color::rgb(0.15, 0.15, 0.18),
-            text_color: Color::WHITE,
-            placeholder_color: Color::rgba(1.0, 1.0, 1.0, 0.5),
-            accent_color: Color::rgb(0.0, 0.47, 0.84),
+            font_size: Self::FONT_SIZE,
+            font: SpanStyle::default(),
+            user_bubble_color: None,
+            assistant_bubble_color: None,
+            accent_color: None,
             on_send: None,
             on_provider_change: None,
         }
@@ -304,19 +314,19 @@ impl ChatWindow {
 
     /// Set user bubble color
     pub fn user_color(mut self, color: Color) -> Self {
-        self.user_bubble_color = color;
+        self.user_bubble_color = Some(color);
         self
     }
 
     /// Set assistant bubble color
     pub fn assistant_color(mut self, color: Color) -> Self {
-        self.assistant_bubble_color = color;
+        self.assistant_bubble_color = Some(color);
         self
     }
 
     /// Set accent color
     pub fn accent_color(mut self, color: Color) -> Self {
-        self.accent_color = color;
+        self.accent_color = Some(color);
         self
     }
 
@@ -398,6 +408,26 @@ impl ChatWindow {
         self.scroll_offset = f32::MAX;
     }
 
+    /// Selected range of the input text, if any.
+    fn selection(&self) -> Option<std::ops::Range<usize>> {
+        selection(self.cursor_position, self.selection_anchor)
+    }
+
+    /// The input text, cursor and selection, for editing.
+    fn editor(&mut self) -> Editor<'_> {
+        Editor {
+            text: &mut self.input_text,
+            cursor: &mut self.cursor_position,
+            anchor: &mut self.selection_anchor,
+        }
+    }
+
+    /// Run a clipboard shortcut on the input.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) {
+        // Enter sends, so the input holds a single line
+        self.editor().run(command, ctx, single_line);
+    }
+
     /// Send current input
     fn send_message(&mut self) {
         if self.input_text.trim().is_empty() {
@@ -406,6 +436,7 @@ impl ChatWindow {
 
         let content = std::mem::take(&mut self.input_text);
         self.cursor_position = 0;
+        self.selection_anchor = None;
 
         // Add user message
         self.add_message(ChatMessage::user(&content));
@@ -440,11 +471,20 @@ impl ChatWindow {
     }
 
     /// Draw a message bubble
-    fn draw_message(&self, painter: &mut Painter, message: &ChatMessage, rect: Rect) {
-        let (bg_color, align_right) = match message.role {
-            MessageRole::User => (self.user_bubble_color, true),
-            MessageRole::Assistant => (self.assistant_bubble_color, false),
-            MessageRole::System => (self.system_bubble_color, false),
+    fn draw_message(&self, painter: &mut Painter, message: &ChatMessage, rect: Rect, colors: &ChatColors) {
+        let theme_colors = colors.theme;
+        let (bg_color, text_color, align_right) = match message.role {
+            MessageRole::User => (
+                self.user_bubble_color.unwrap_or(theme_colors.primary),
+                theme_colors.primary_foreground,
+                true,
+            ),
+            MessageRole::Assistant => (
+                self.assistant_bubble_color.unwrap_or(theme_colors.muted),
+                colors.text,
+                false,
+            ),
+            MessageRole::System => (theme_colors.secondary, theme_colors.secondary_foreground, false),
         };
 
         let bubble_width = (rect.width() * 0.8).min(rect.width() - 32.0);
@@ -471,8 +511,8 @@ impl ChatWindow {
         painter.draw_text(
             &display_content,
             Point::new(text_x, text_y),
-            self.text_color,
-            14.0,
+            text_color,
+            colors.font_size,
         );
 
         // Streaming indicator
@@ -481,8 +521,8 @@ impl ChatWindow {
             painter.draw_text(
                 cursor,
                 Point::new(text_x + (message.content.len() as f32 * 8.0).min(bubble_width - 24.0), text_y),
-                self.accent_color,
-                14.0,
+                colors.accent,
+                colors.font_size,
             );
         }
 
@@ -491,13 +531,23 @@ impl ChatWindow {
             painter.draw_text(
                 &message.timestamp,
                 Point::new(bubble_rect.x() + self.message_padding, bubble_rect.max_y() - 6.0),
-                self.placeholder_color,
-                10.0,
+                text_color.with_alpha(0.6),
+                colors.font_size - 4.0,
             );
         }
     }
 }
 
+/// Colors resolved from the theme and the window's style for one paint.
+struct ChatColors<'a> {
+    theme: &'a ThemeColors,
+    text: Color,
+    placeholder: Color,
+    accent: Color,
+    font_size: f32,
+    font: SpanStyle,
+}
+
 impl Default for ChatWindow {
     fn default() -> Self {
         Self::new()
@@ -530,21 +580,40 @@ impl Widget for ChatWindow {
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.font_size = style.font_size_or(Self::FONT_SIZE);
+        self.font = style.font();
         let size = constraints.constrain(self.intrinsic_size(ctx));
         self.base.bounds.size = size;
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let colors = ChatColors {
+            theme: &theme.colors,
+            text: style.color_or(theme.colors.card_foreground),
+            placeholder: theme.colors.muted_foreground,
+            accent: self.accent_color.unwrap_or(theme.colors.primary),
+            font_size: style.font_size_or(Self::FONT_SIZE),
+            font: style.font(),
+        };
+        let padding = style.padding_or(EdgeInsets::symmetric(8.0, 12.0));
+
         // Background
-        painter.fill_rounded_rect(rect, Color::rgb(0.1, 0.1, 0.12), BorderRadius::all(12.0));
+        let radius = style.border_radius_or(BorderRadius::all(12.0));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.card), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Header
-        let _header_rect = Rect::new(rect.x(), rect.y(), rect.width(), 48.0);
         painter.fill_rounded_rect(
             Rect::new(rect.x(), rect.y(), rect.width(), 48.0),
-            Color::rgb(0.12, 0.12, 0.15),
-            BorderRadius::new(12.0, 12.0, 0.0, 0.0),
+            theme.colors.muted,
+            BorderRadius::new(radius.top_left, radius.top_right, 0.0, 0.0),
         );
 
         // Title
@@ -556,16 +625,16 @@ impl Widget for ChatWindow {
         painter.draw_text(
             &title,
             Point::new(rect.x() + 16.0, rect.y() + 30.0),
-            self.text_color,
-            14.0,
+            colors.text,
+            colors.font_size,
         );
 
         // Status indicator
         let status_color = match self.state {
-            ChatState::Idle => Color::rgb(0.2, 0.8, 0.2),
-            ChatState::Connecting => Color::rgb(0.8, 0.8, 0.2),
-            ChatState::Streaming => Color::rgb(0.2, 0.6, 1.0),
-            ChatState::Error => Color::rgb(0.8, 0.2, 0.2),
+            ChatState::Idle => theme.colors.success,
+            ChatState::Connecting => theme.colors.warning,
+            ChatState::Streaming => colors.accent,
+            ChatState::Error => theme.colors.destructive,
         };
         let status_rect = Rect::new(rect.max_x() - 24.0, rect.y() + 20.0, 8.0, 8.0);
         painter.fill_rounded_rect(status_rect, status_color, BorderRadius::all(4.0));
@@ -575,7 +644,7 @@ impl Widget for ChatWindow {
             rect.x(),
             rect.y() + 48.0,
             rect.width(),
-            rect.height() - 48.0 - self.input_height - 16.0,
+            rect.height() - 48.0 - self.input_height - padding.top - padding.bottom,
         );
 
         // Draw messages
@@ -584,7 +653,7 @@ impl Widget for ChatWindow {
             let msg_height = self.message_height(message);
             if y + msg_height > messages_rect.y() && y < messages_rect.max_y() {
                 let msg_rect = Rect::new(messages_rect.x(), y, messages_rect.width(), msg_height);
-                self.draw_message(painter, message, msg_rect);
+                self.draw_message(painter, message, msg_rect, &colors);
             }
             y += msg_height + 8.0;
         }
@@ -594,16 +663,16 @@ impl Widget for ChatWindow {
             painter.draw_text(
                 "Start a conversation...",
                 Point::new(messages_rect.x() + 16.0, messages_rect.y() + 40.0),
-                self.placeholder_color,
-                14.0,
+                colors.placeholder,
+                colors.font_size,
             );
 
             if self.provider.is_none() {
                 painter.draw_text(
                     "No AI provider configured",
                     Point::new(messages_rect.x() + 16.0, messages_rect.y() + 70.0),
-                    Color::rgb(0.8, 0.5, 0.2),
-                    12.0,
+                    theme.colors.warning,
+                    colors.font_size - 2.0,
                 );
             }
         }
@@ -616,30 +685,31 @@ impl Widget for ChatWindow {
                 rect.width() - 32.0,
                 32.0,
             );
-            painter.fill_rounded_rect(error_rect, Color::rgba(0.8, 0.2, 0.2, 0.9), BorderRadius::all(6.0));
+            painter.fill_rounded_rect(error_rect, theme.colors.destructive, BorderRadius::all(6.0));
             painter.draw_text(
                 error,
                 Point::new(error_rect.x() + 8.0, error_rect.y() + 20.0),
-                Color::WHITE,
-                12.0,
+                theme.colors.destructive_foreground,
+                colors.font_size - 2.0,
             );
         }
 
         // Input area
         let input_rect = Rect::new(
-            rect.x() + 12.0,
-            rect.max_y() - self.input_height - 8.0,
-            rect.width() - 24.0,
+            rect.x() + padding.left,
+            rect.max_y() - self.input_height - padding.bottom,
+            rect.width() - padding.left - padding.right,
             self.input_height,
         );
 
         // Input background
+        let input_bg_color = theme.colors.background;
         let input_border_color = if self.input_focused {
-            self.accent_color
+            colors.accent
         } else {
-            Color::rgba(1.0, 1.0, 1.0, 0.1)
+            theme.colors.input
         };
-        painter.fill_rounded_rect(input_rect, self.input_bg_color, BorderRadius::all(24.0));
+        painter.fill_rounded_rect(input_rect, input_bg_color, BorderRadius::all(24.0));
 
         // Input border when focused
         if self.input_focused {
@@ -650,27 +720,56 @@ impl Widget for ChatWindow {
                 input_rect.height() + 2.0,
             );
             painter.fill_rounded_rect(border_rect, input_border_color, BorderRadius::all(25.0));
-            painter.fill_rounded_rect(input_rect, self.input_bg_color, BorderRadius::all(24.0));
+            painter.fill_rounded_rect(input_rect, input_bg_color, BorderRadius::all(24.0));
         }
 
-        // Input text or placeholder
-        let text_to_draw = if self.input_text.is_empty() {
-            ("Type a message...", self.placeholder_color)
+        // Input text or placeholder, with the composed text at the cursor
+        let composing = self.input_focused && !self.preedit.is_empty();
+        let mut composed = self.input_text.clone();
+        if composing {
+            composed.insert_str(self.cursor_position, &self.preedit);
+        }
+        let text_to_draw = if composed.is_empty() {
+            ("Type a message...", colors.placeholder)
         } else {
-            (self.input_text.as_str(), self.text_color)
+            (composed.as_str(), colors.text)
         };
         painter.draw_text(
             text_to_draw.0,
             Point::new(input_rect.x() + 16.0, input_rect.y() + 30.0),
             text_to_draw.1,
-            14.0,
+            colors.font_size,
         );
 
+        let text_x = input_rect.x() + 16.0;
+        let layout = TextLayout::styled(&composed, colors.font_size, &colors.font);
+
+        // Underline the composed text
+        if composing {
+            painter.draw_line(
+                Point::new(text_x + layout.caret_x(self.cursor_position), input_rect.y() + 35.0),
+                Point::new(text_x + layout.caret_x(self.cursor_position + self.preedit.len()), input_rect.y() + 35.0),
+                colors.text,
+                1.0,
+            );
+        }
+
+        // Selection
+        if let Some(range) = self.selection().filter(|_| self.input_focused && !composing) {
+            for selection in layout.selection_rects(range) {
+                let selection_rect = Rect::new(text_x + selection.x(), input_rect.y() + 12.0, selection.width(), 24.0);
+                painter.fill_rect(selection_rect, colors.accent.with_alpha(0.3));
+            }
+        }
+
         // Cursor
-        if self.input_focused && !self.input_text.is_empty() {
-            let cursor_x = input_rect.x() + 16.0 + (self.cursor_position as f32 * 8.0);
-            let cursor_rect = Rect::new(cursor_x, input_rect.y() + 12.0, 2.0, 24.0);
-            painter.fill_rect(cursor_rect, self.accent_color);
+        let cursor_x = text_x + layout.caret_x(self.cursor_position);
+        let cursor_rect = Rect::new(cursor_x, input_rect.y() + 12.0, 2.0, 24.0);
+        if self.input_focused && !self.input_text.is_empty() && !composing {
+            painter.fill_rect(cursor_rect, colors.accent);
+        }
+        if self.input_focused {
+            ctx.set_ime_cursor_area(painter, cursor_rect);
         }
 
         // Send button
@@ -682,9 +781,9 @@ impl Widget for ChatWindow {
         );
         let send_enabled = !self.input_text.trim().is_empty() && self.state == ChatState::Idle;
         let send_color = if send_enabled {
-            self.accent_color
+            colors.accent
         } else {
-            Color::rgba(1.0, 1.0, 1.0, 0.3)
+            theme.colors.muted
         };
         painter.fill_rounded_rect(send_btn_rect, send_color, BorderRadius::all(16.0));
 
@@ -692,13 +791,41 @@ impl Widget for ChatWindow {
         painter.draw_text(
             "→",
             Point::new(send_btn_rect.x() + 8.0, send_btn_rect.y() + 22.0),
-            Color::WHITE,
-            16.0,
+            if send_enabled { theme.colors.primary_foreground } else { theme.colors.muted_foreground },
+            colors.font_size + 2.0,
         );
     }
 
-    fn handle_event(&mut self, event: &Event, _ctx: &mut EventContext) -> EventResult {
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            self.input_focused = self.base.state.focused;
+            self.selection_anchor = None;
+            self.preedit.clear();
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
+        if let Event::Key(key) = event {
+            if let Some(command) = EditCommand::from_key(key).filter(|_| self.input_focused) {
+                self.edit(command, ctx);
+                ctx.request_redraw();
+                return EventResult::Handled;
+            }
+        }
+
         match event {
+            Event::Ime(ime) if self.input_focused => {
+                match ime {
+                    ImeEvent::Preedit { text, .. } => self.preedit = text.clone(),
+                    ImeEvent::Commit(text) => {
+                        self.preedit.clear();
+                        self.editor().insert(text);
+                    }
+                    ImeEvent::Enabled | ImeEvent::Disabled => self.preedit.clear(),
+                }
+                ctx.request_redraw();
+                return EventResult::Handled;
+            }
             Event::Mouse(MouseEvent { kind, position, button, .. }) => {
                 let in_bounds = self.base.bounds.contains(*position);
 
@@ -727,11 +854,16 @@ impl Widget for ChatWindow {
                             return EventResult::Handled;
                         }
                         if in_input {
+                            let relative_x = position.x - input_rect.x() - 16.0;
+                            self.cursor_position = TextLayout::styled(&self.input_text, self.font_size, &self.font)
+                                .hit_test(Point::new(relative_x, self.font_size / 2.0));
                             self.input_focused = true;
+                            self.selection_anchor = None;
+                            ctx.request_focus(self.base.id);
                             return EventResult::Handled;
                         }
-                        if in_bounds && !in_input {
-                            self.input_focused = false;
+                        if in_bounds && self.input_focused {
+                            ctx.release_focus();
                         }
                     }
                     MouseEventKind::Scroll { delta_y, .. } if in_bounds => {
@@ -742,57 +874,60 @@ impl Widget for ChatWindow {
                     _ => {}
                 }
             }
-            Event::Key(KeyEvent { kind: KeyEventKind::Down, key, text, .. }) => {
-                if self.input_focused {
-                    match key {
-                        Key::Enter => {
-                            if self.state == ChatState::Idle {
-                                self.send_message();
-                            }
-                            return EventResult::Handled;
-                        }
-                        Key::Backspace => {
-                            if self.cursor_position > 0 {
-                                self.cursor_position -= 1;
-                                self.input_text.remove(self.cursor_position);
-                            }
-                            return EventResult::Handled;
-                        }
-                        Key::Left => {
-                            if self.cursor_position > 0 {
-                                self.cursor_position -= 1;
-                            }
-                            return EventResult::Handled;
-                        }
-                        Key::Right => {
-                            if self.cursor_position < self.input_text.len() {
-                                self.cursor_position += 1;
-                            }
-                            return EventResult::Handled;
+            Event::Key(KeyEvent { kind: KeyEventKind::Down, key, text, .. }) if self.input_focused => {
+                match key {
+                    Key::Enter => {
+                        if self.state == ChatState::Idle {
+                            self.send_message();
                         }
-                        Key::Home => {
-                            self.cursor_position = 0;
-                            return EventResult::Handled;
+                        return EventResult::Handled;
+                    }
+                    Key::Backspace => {
+                        if !self.editor().delete_selection() && self.cursor_position > 0 {
+                            self.cursor_position -= 1;
+                            self.input_text.remove(self.cursor_position);
                         }
-                        Key::End => {
-                            self.cursor_position = self.input_text.len();
-                            return EventResult::Handled;
+                        return EventResult::Handled;
+                    }
+                    Key::Left => {
+                        self.selection_anchor = None;
+                        if self.cursor_position > 0 {
+                            self.cursor_position -= 1;
                         }
-                        Key::Escape => {
-                            self.input_focused = false;
-                            return EventResult::Handled;
+                        return EventResult::Handled;
+                    }
+                    Key::Right => {
+                        self.selection_anchor = None;
+                        if self.cursor_position < self.input_text.len() {
+                            self.cursor_position += 1;
                         }
-                        _ => {
-                            // Handle text input
-                            if let Some(t) = text {
-                                for c in t.chars() {
-                                    if !c.is_control() {
-                                        self.input_text.insert(self.cursor_position, c);
-                                        self.cursor_position += 1;
-                                    }
+                        return EventResult::Handled;
+                    }
+                    Key::Home => {
+                        self.selection_anchor = None;
+                        self.cursor_position = 0;
+                        return EventResult::Handled;
+                    }
+                    Key::End => {
+                        self.selection_anchor = None;
+                        self.cursor_position = self.input_text.len();
+                        return EventResult::Handled;
+                    }
+                    Key::Escape => {
+                        ctx.release_focus();
+                        return EventResult::Handled;
+                    }
+                    _ => {
+                        // Handle text input
+                        if let Some(t) = text {
+                            self.editor().delete_selection();
+                            for c in t.chars() {
+                                if !c.is_control() {
+                                    self.input_text.insert(self.cursor_position, c);
+                                    self.cursor_position += 1;
                                 }
-                                return EventResult::Handled;
                             }
+                            return EventResult::Handled;
                         }
                     }
                 }
@@ -809,6 +944,10 @@ impl Widget for ChatWindow {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
 
 // Simple UUID generator
@@ -832,3 +971,31 @@ fn current_time() -> String {
     let mins = (secs % 3600) / 60;
     format!("{:02}:{:02}", hours, mins)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{StyleContext, StyleManager};
+    use crate::theme::ThemeData;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_click_places_caret_with_css_font_size() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles.load_css(".chat-window { font-size: 28px; }").unwrap();
+        let style_ctx = StyleContext::with_styles(&theme, Arc::new(styles));
+
+        let mut chat = ChatWindow::new();
+        chat.input_text = "abcdefghij".to_string();
+        chat.layout(Constraints::tight(Size::new(400.0, 600.0)), &LayoutContext::new(&style_ctx));
+
+        // Click between the third and fourth characters as drawn at 28px
+        let bounds = chat.bounds();
+        let x = bounds.x() + 12.0 + 16.0 + TextLayout::new(&chat.input_text, 28.0).caret_x(3);
+        let y = bounds.max_y() - chat.input_height / 2.0 - 8.0;
+        let click = MouseEvent::new(MouseEventKind::Down, Point::new(x, y)).with_button(MouseButton::Left);
+        chat.handle_event(&Event::Mouse(click), &mut EventContext::new());
+        assert_eq!(chat.cursor_position, 3);
+    }
+}
@@ -53,6 +53,12 @@ impl Checkbox {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Set the element ID.
     pub fn id(mut self, id: &str) -> Self {
         self.base.element_id = Some(id.to_string());
@@ -106,10 +112,11 @@ impl Widget for Checkbox {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
         let box_size = 20.0;
         let gap = 8.0;
-        let label_width = self.label.as_ref().map(|l| l.len() as f32 * 8.0).unwrap_or(0.0);
+        let font_size = ctx.style(self).font_size_or(14.0);
+        let label_width = self.label.as_ref().map(|l| l.len() as f32 * font_size * 0.57).unwrap_or(0.0);
 
         if self.label.is_some() {
             Size::new(box_size + gap + label_width, box_size.max(20.0))
@@ -127,29 +134,33 @@ impl Widget for Checkbox {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let box_size = 20.0;
 
         // Checkbox box
         let box_rect = Rect::new(rect.x(), rect.y(), box_size, box_size);
-        let radius = BorderRadius::all(4.0);
+        let radius = style.border_radius_or(BorderRadius::all(4.0));
 
         // Background
-        let bg_color = if self.checked {
+        let bg_color = style.background_or(if self.checked {
             theme.colors.primary
         } else if self.base.state.hovered {
             theme.colors.accent
         } else {
             theme.colors.background
-        };
+        });
         painter.fill_rounded_rect(box_rect, bg_color, radius);
 
         // Border
-        let border_color = if self.checked {
+        let border_color = style.border_color_or(if self.checked {
             theme.colors.primary
         } else {
             theme.colors.border
-        };
-        painter.stroke_rect(box_rect, border_color, 1.0);
+        });
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(box_rect, border_color, border_width);
+        }
 
         // Checkmark
         if self.checked {
@@ -174,9 +185,10 @@ impl Widget for Checkbox {
 
         // Label
         if let Some(label) = &self.label {
+            let font_size = style.font_size_or(14.0);
             let text_x = rect.x() + box_size + 8.0;
-            let text_y = rect.y() + (rect.height() + 14.0 * 0.8) / 2.0;
-            painter.draw_text(label, Point::new(text_x, text_y), theme.colors.foreground, 14.0);
+            let text_y = rect.y() + (rect.height() + font_size * 0.8) / 2.0;
+            painter.draw_text(label, Point::new(text_x, text_y), style.color_or(theme.colors.foreground), font_size);
         }
 
         // Focus ring
@@ -193,6 +205,11 @@ impl Widget for Checkbox {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         if let Event::Mouse(mouse) = event {
             let in_bounds = self.bounds().contains(mouse.position);
 
@@ -243,4 +260,8 @@ impl Widget for Checkbox {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
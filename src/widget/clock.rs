@@ -2,11 +2,13 @@
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{Point, Rect, Size};
+use crate::event::{Event, EventResult, MouseEventKind, MouseButton, TimerEvent, TimerId};
+use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
+use std::time::Duration;
+
 /// Clock format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ClockFormat {
@@ -55,9 +57,11 @@ pub struct Clock {
     show_date: bool,
     date_format: DateFormat,
     on_click: Option<Box<dyn Fn() + Send + Sync>>,
-    // Cached time string (would be updated by timer in real implementation)
+    // Cached time and date strings, refreshed every second
     cached_time: String,
     cached_date: String,
+    /// Interval timer refreshing the time
+    timer: Option<TimerId>,
 }
 
 impl Clock {
@@ -72,6 +76,7 @@ impl Clock {
             on_click: None,
             cached_time: "12:00".to_string(),
             cached_date: "January 1".to_string(),
+            timer: None,
         }
     }
 
@@ -114,7 +119,9 @@ impl Clock {
         self
     }
 
-    /// Update the displayed time (call periodically).
+    /// Update the displayed time.
+    ///
+    /// Called every second once the clock receives its first animation frame.
     pub fn update(&mut self) {
         // In a real implementation, this would get the current system time
         // For now, we'll use placeholder values
@@ -217,10 +224,19 @@ impl Widget for Clock {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let color = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(14.0);
 
-        // Hover background
-        if self.base.state.hovered && self.on_click.is_some() {
-            painter.fill_rect(rect, theme.colors.accent.with_alpha(0.1));
+        // Background, or a hover highlight when clickable
+        let background = if self.base.state.hovered && self.on_click.is_some() {
+            theme.colors.accent.with_alpha(0.1)
+        } else {
+            Color::TRANSPARENT
+        };
+        let background = style.background_or(background);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, style.border_radius_or(BorderRadius::ZERO));
         }
 
         let center_x = rect.x() + rect.width() / 2.0;
@@ -231,8 +247,8 @@ impl Widget for Clock {
             painter.draw_text(
                 &self.cached_time,
                 Point::new(center_x - time_width / 2.0, rect.y() + 18.0),
-                theme.colors.foreground,
-                14.0,
+                color,
+                font_size,
             );
 
             // Date
@@ -249,13 +265,26 @@ impl Widget for Clock {
             painter.draw_text(
                 &self.cached_time,
                 Point::new(center_x - time_width / 2.0, rect.y() + rect.height() * 0.7),
-                theme.colors.foreground,
-                14.0,
+                color,
+                font_size,
             );
         }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        match event {
+            // Start ticking on the first frame
+            Event::Timer(TimerEvent::Frame { .. }) if self.timer.is_none() => {
+                self.timer = Some(ctx.set_interval(self.id(), Duration::from_secs(1)));
+            }
+            Event::Timer(TimerEvent::Fired { id, .. }) if Some(*id) == self.timer => {
+                self.update();
+                ctx.request_redraw();
+                return EventResult::Handled;
+            }
+            _ => {}
+        }
+
         if let Event::Mouse(mouse) = event {
             let in_bounds = self.bounds().contains(mouse.position);
 
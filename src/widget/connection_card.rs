@@ -5,7 +5,7 @@
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use super::vpn_status::VpnConnectionStatus;
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, ComputedStyle, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
 use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
@@ -344,7 +344,9 @@ impl ConnectionCard {
         painter.draw_text(icon, Point::new(icon_x, icon_y), Color::WHITE, icon_size);
     }
 
-    fn paint_stats(&self, painter: &mut Painter, rect: Rect, theme: &crate::theme::ThemeData) {
+    fn paint_stats(&self, painter: &mut Painter, rect: Rect, theme: &crate::theme::ThemeData, style: &ComputedStyle) {
+        let color = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(14.0);
         let stats_y = rect.y() + 280.0;
         let col_width = rect.width() / 3.0;
 
@@ -354,14 +356,14 @@ impl ConnectionCard {
         painter.draw_text(
             &ConnectionStats::format_bytes(self.stats.bytes_rx),
             Point::new(dl_x, stats_y + 14.0),
-            theme.colors.foreground,
-            14.0,
+            color,
+            font_size,
         );
         painter.draw_text(
             &ConnectionStats::format_speed(self.stats.speed_rx),
             Point::new(dl_x, stats_y + 32.0),
             theme.colors.muted_foreground,
-            11.0,
+            font_size - 3.0,
         );
 
         // Upload stats
@@ -370,24 +372,24 @@ impl ConnectionCard {
         painter.draw_text(
             &ConnectionStats::format_bytes(self.stats.bytes_tx),
             Point::new(ul_x, stats_y + 14.0),
-            theme.colors.foreground,
-            14.0,
+            color,
+            font_size,
         );
         painter.draw_text(
             &ConnectionStats::format_speed(self.stats.speed_tx),
             Point::new(ul_x, stats_y + 32.0),
             theme.colors.muted_foreground,
-            11.0,
+            font_size - 3.0,
         );
 
         // Duration
         let dur_x = rect.x() + col_width * 2.0 + col_width / 2.0;
-        painter.draw_text("⏱", Point::new(dur_x - 20.0, stats_y + 14.0), theme.colors.muted_foreground, 14.0);
+        painter.draw_text("⏱", Point::new(dur_x - 20.0, stats_y + 14.0), theme.colors.muted_foreground, font_size);
         painter.draw_text(
             &ConnectionStats::format_duration(self.stats.duration_secs),
             Point::new(dur_x, stats_y + 14.0),
-            theme.colors.foreground,
-            14.0,
+            color,
+            font_size,
         );
     }
 }
@@ -436,11 +438,16 @@ impl Widget for ConnectionCard {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(14.0);
 
         // Card background
-        let radius = BorderRadius::all(16.0);
-        painter.fill_rounded_rect(rect, theme.colors.card, radius);
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let radius = style.border_radius_or(BorderRadius::all(16.0));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.card), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Status indicator (large circle)
         if !self.compact {
@@ -454,8 +461,8 @@ impl Widget for ConnectionCard {
         painter.draw_text(
             self.status.label(),
             Point::new(status_text_x, status_text_y),
-            theme.colors.foreground,
-            18.0,
+            style.color_or(theme.colors.card_foreground),
+            font_size + 4.0,
         );
 
         // Server info
@@ -467,7 +474,7 @@ impl Widget for ConnectionCard {
                 name,
                 Point::new(server_x, server_y),
                 theme.colors.muted_foreground,
-                14.0,
+                font_size,
             );
         }
 
@@ -481,7 +488,7 @@ impl Widget for ConnectionCard {
                 &auth_label,
                 Point::new(auth_x, auth_y),
                 theme.colors.primary,
-                12.0,
+                font_size - 2.0,
             );
         }
 
@@ -510,11 +517,11 @@ impl Widget for ConnectionCard {
         let text_width = button_text.len() as f32 * 10.0;
         let text_x = button_rect.x() + (button_rect.width() - text_width) / 2.0;
         let text_y = button_rect.y() + (button_rect.height() + 14.0) / 2.0;
-        painter.draw_text(button_text, Point::new(text_x, text_y), button_fg, 16.0);
+        painter.draw_text(button_text, Point::new(text_x, text_y), button_fg, font_size + 2.0);
 
         // Connection stats (when connected)
         if self.show_stats && self.is_connected() && !self.compact {
-            self.paint_stats(painter, rect, theme);
+            self.paint_stats(painter, rect, theme, &style);
         }
     }
 
@@ -525,17 +532,13 @@ impl Widget for ConnectionCard {
             let in_button = button_rect.contains(mouse.position);
 
             match mouse.kind {
-                MouseEventKind::Move | MouseEventKind::Enter => {
-                    if self.connect_button_hovered != in_button {
-                        self.connect_button_hovered = in_button;
-                        ctx.request_redraw();
-                    }
+                MouseEventKind::Move | MouseEventKind::Enter if self.connect_button_hovered != in_button => {
+                    self.connect_button_hovered = in_button;
+                    ctx.request_redraw();
                 }
-                MouseEventKind::Leave => {
-                    if self.connect_button_hovered {
-                        self.connect_button_hovered = false;
-                        ctx.request_redraw();
-                    }
+                MouseEventKind::Leave if self.connect_button_hovered => {
+                    self.connect_button_hovered = false;
+                    ctx.request_redraw();
                 }
                 MouseEventKind::Up if mouse.button == Some(MouseButton::Left) && in_button => {
                     if self.is_connected() {
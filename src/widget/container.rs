@@ -1,10 +1,11 @@
 //! Container widgets (Column, Row).
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, ComputedStyle, WidgetState};
 use crate::css::FlexDirection;
 use crate::event::{Event, EventResult};
-use crate::geometry::{EdgeInsets, Point, Rect, Size};
+use crate::focus::FocusScope;
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Alignment, Constraints, FlexLayout, LayoutResult};
 use crate::render::Painter;
 
@@ -17,6 +18,9 @@ pub struct Column {
     justify: Alignment,
     padding: EdgeInsets,
     child_positions: Vec<Point>,
+    /// Content box the children were laid out in, for resolving
+    /// percentages in their styles
+    content_box: Size,
 }
 
 impl Column {
@@ -29,6 +33,7 @@ impl Column {
             justify: Alignment::Start,
             padding: EdgeInsets::ZERO,
             child_positions: Vec::new(),
+            content_box: Size::ZERO,
         }
     }
 
@@ -67,58 +72,16 @@ impl Column {
         self.base.classes.add(class);
         self
     }
-}
-
-impl Default for Column {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl Widget for Column {
-    fn id(&self) -> WidgetId {
-        self.base.id
-    }
-
-    fn type_name(&self) -> &'static str {
-        "column"
-    }
-
-    fn element_id(&self) -> Option<&str> {
-        self.base.element_id.as_deref()
-    }
-
-    fn classes(&self) -> &ClassList {
-        &self.base.classes
-    }
-
-    fn state(&self) -> WidgetState {
-        self.base.state
-    }
 
-    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
-        let mut width: f32 = 0.0;
-        let mut height: f32 = 0.0;
-
-        for child in &self.children {
-            let child_size = child.intrinsic_size(ctx);
-            width = width.max(child_size.width);
-            height += child_size.height;
-        }
-
-        // Add gaps
-        if self.children.len() > 1 {
-            height += self.gap * (self.children.len() - 1) as f32;
-        }
-
-        // Add padding
-        Size::new(
-            width + self.padding.horizontal(),
-            height + self.padding.vertical(),
-        )
+    /// Group the focus of the children, or trap it for modal content.
+    pub fn focus_scope(mut self, scope: FocusScope) -> Self {
+        self.base.focus_scope = scope;
+        self
     }
 
-    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+    /// Lay out the children in `constraints`, with percentages in their
+    /// styles resolving against the content box.
+    fn layout_children(&mut self, constraints: Constraints, padding: EdgeInsets, ctx: &LayoutContext) -> LayoutResult {
         // First pass: get intrinsic sizes
         let child_sizes: Vec<Size> = self.children
             .iter()
@@ -132,8 +95,8 @@ impl Widget for Column {
 
         // Container size
         let container_size = constraints.constrain(Size::new(
-            max_width + self.padding.horizontal(),
-            total_height + self.padding.vertical(),
+            max_width + padding.horizontal(),
+            total_height + padding.vertical(),
         ));
 
         // Calculate positions using flex layout
@@ -144,13 +107,13 @@ impl Widget for Column {
             gap: self.gap,
         };
 
-        self.child_positions = flex.calculate_positions(container_size, &child_sizes, self.padding);
+        self.child_positions = flex.calculate_positions(container_size, &child_sizes, padding);
 
         // Second pass: layout children with their positions
         for (i, child) in self.children.iter_mut().enumerate() {
             let child_size = child_sizes[i];
             let available_width = match self.align {
-                Alignment::Stretch => container_size.width - self.padding.horizontal(),
+                Alignment::Stretch => container_size.width - padding.horizontal(),
                 _ => child_size.width,
             };
 
@@ -170,55 +133,95 @@ impl Widget for Column {
         self.base.bounds.size = container_size;
         LayoutResult::new(container_size)
     }
+}
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
-        // Paint children
-        for (i, child) in self.children.iter().enumerate() {
-            if let Some(pos) = self.child_positions.get(i) {
-                let child_rect = Rect::from_origin_size(
-                    Point::new(rect.x() + pos.x, rect.y() + pos.y),
-                    child.bounds().size,
-                );
-                child.paint(painter, child_rect, ctx);
+impl Default for Column {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Column {
+    fn id(&self) -> WidgetId {
+        self.base.id
+    }
+
+    fn type_name(&self) -> &'static str {
+        "column"
+    }
+
+    fn element_id(&self) -> Option<&str> {
+        self.base.element_id.as_deref()
+    }
+
+    fn classes(&self) -> &ClassList {
+        &self.base.classes
+    }
+
+    fn state(&self) -> WidgetState {
+        self.base.state
+    }
+
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        let style = ctx.style(self);
+        let padding = style.padding_or(self.padding);
+        let sizes = Constraints::unbounded().with_style(&style);
+
+        let (mut width, mut height) = ctx.in_containing_block(content_box(sizes, padding), |ctx| {
+            let mut width: f32 = 0.0;
+            let mut height: f32 = 0.0;
+            for child in &self.children {
+                let child_size = child.intrinsic_size(ctx);
+                width = width.max(child_size.width);
+                height += child_size.height;
             }
+            (width, height)
+        });
+
+        // Add gaps
+        if self.children.len() > 1 {
+            height += self.gap * (self.children.len() - 1) as f32;
         }
+
+        // Add padding
+        width += padding.horizontal();
+        height += padding.vertical();
+        sizes.constrain(Size::new(width, height))
     }
 
-    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
-        // Transform mouse events to child coordinates and propagate
-        if let Event::Mouse(mouse) = event {
-            // Check children in reverse order (front to back)
-            for (i, child) in self.children.iter_mut().enumerate().rev() {
+    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        let padding = style.padding_or(self.padding);
+        let constraints = constraints.with_style(&style);
+        self.content_box = content_box(constraints, padding);
+        ctx.in_containing_block(self.content_box, |ctx| self.layout_children(constraints, padding, ctx))
+    }
+
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        paint_box(painter, rect, &ctx.style(self), ctx);
+
+        // Paint children
+        ctx.in_containing_block(self.content_box, |ctx| {
+            for (i, child) in self.children.iter().enumerate() {
                 if let Some(pos) = self.child_positions.get(i) {
-                    // Calculate child's global bounds
-                    let parent_origin = self.base.bounds.origin;
-                    let child_global_rect = Rect::new(
-                        parent_origin.x + pos.x,
-                        parent_origin.y + pos.y,
-                        child.bounds().width(),
-                        child.bounds().height(),
+                    let child_rect = Rect::from_origin_size(
+                        Point::new(rect.x() + pos.x, rect.y() + pos.y),
+                        child.bounds().size,
                     );
-                    
-                    // Check if mouse is in child's bounds
-                    if child_global_rect.contains(mouse.position) {
-                        // Temporarily set child's bounds to global for event handling
-                        let old_bounds = child.bounds();
-                        child.set_bounds(child_global_rect);
-                        let result = child.handle_event(event, ctx);
-                        child.set_bounds(old_bounds);
-                        
-                        if result == EventResult::Handled {
-                            return EventResult::Handled;
-                        }
-                    }
+                    ctx.paint_child(child.as_ref(), painter, child_rect);
                 }
             }
-        } else {
-            // For non-mouse events, propagate normally
-            for child in self.children.iter_mut().rev() {
-                if child.handle_event(event, ctx) == EventResult::Handled {
-                    return EventResult::Handled;
-                }
+        });
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        // Pointer events are routed to children by the event dispatcher
+        if matches!(event, Event::Mouse(_)) {
+            return EventResult::Ignored;
+        }
+        for child in self.children.iter_mut().rev() {
+            if child.handle_event(event, ctx) == EventResult::Handled {
+                return EventResult::Handled;
             }
         }
         EventResult::Ignored
@@ -232,6 +235,10 @@ impl Widget for Column {
         self.base.bounds = bounds;
     }
 
+    fn focus_scope(&self) -> FocusScope {
+        self.base.focus_scope
+    }
+
     fn children(&self) -> &[Box<dyn Widget>] {
         &self.children
     }
@@ -250,6 +257,9 @@ pub struct Row {
     justify: Alignment,
     padding: EdgeInsets,
     child_positions: Vec<Point>,
+    /// Content box the children were laid out in, for resolving
+    /// percentages in their styles
+    content_box: Size,
 }
 
 impl Row {
@@ -262,6 +272,7 @@ impl Row {
             justify: Alignment::Start,
             padding: EdgeInsets::ZERO,
             child_positions: Vec::new(),
+            content_box: Size::ZERO,
         }
     }
 
@@ -300,58 +311,16 @@ impl Row {
         self.base.classes.add(class);
         self
     }
-}
 
-impl Default for Row {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl Widget for Row {
-    fn id(&self) -> WidgetId {
-        self.base.id
-    }
-
-    fn type_name(&self) -> &'static str {
-        "row"
-    }
-
-    fn element_id(&self) -> Option<&str> {
-        self.base.element_id.as_deref()
-    }
-
-    fn classes(&self) -> &ClassList {
-        &self.base.classes
-    }
-
-    fn state(&self) -> WidgetState {
-        self.base.state
-    }
-
-    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
-        let mut width: f32 = 0.0;
-        let mut height: f32 = 0.0;
-
-        for child in &self.children {
-            let child_size = child.intrinsic_size(ctx);
-            width += child_size.width;
-            height = height.max(child_size.height);
-        }
-
-        // Add gaps
-        if self.children.len() > 1 {
-            width += self.gap * (self.children.len() - 1) as f32;
-        }
-
-        // Add padding
-        Size::new(
-            width + self.padding.horizontal(),
-            height + self.padding.vertical(),
-        )
+    /// Group the focus of the children, or trap it for modal content.
+    pub fn focus_scope(mut self, scope: FocusScope) -> Self {
+        self.base.focus_scope = scope;
+        self
     }
 
-    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+    /// Lay out the children in `constraints`, with percentages in their
+    /// styles resolving against the content box.
+    fn layout_children(&mut self, constraints: Constraints, padding: EdgeInsets, ctx: &LayoutContext) -> LayoutResult {
         // First pass: get intrinsic sizes
         let child_sizes: Vec<Size> = self.children
             .iter()
@@ -365,8 +334,8 @@ impl Widget for Row {
 
         // Container size
         let container_size = constraints.constrain(Size::new(
-            total_width + self.padding.horizontal(),
-            max_height + self.padding.vertical(),
+            total_width + padding.horizontal(),
+            max_height + padding.vertical(),
         ));
 
         // Calculate positions using flex layout
@@ -377,13 +346,13 @@ impl Widget for Row {
             gap: self.gap,
         };
 
-        self.child_positions = flex.calculate_positions(container_size, &child_sizes, self.padding);
+        self.child_positions = flex.calculate_positions(container_size, &child_sizes, padding);
 
         // Second pass: layout children with their positions
         for (i, child) in self.children.iter_mut().enumerate() {
             let child_size = child_sizes[i];
             let available_height = match self.align {
-                Alignment::Stretch => container_size.height - self.padding.vertical(),
+                Alignment::Stretch => container_size.height - padding.vertical(),
                 _ => child_size.height,
             };
 
@@ -403,55 +372,95 @@ impl Widget for Row {
         self.base.bounds.size = container_size;
         LayoutResult::new(container_size)
     }
+}
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
-        // Paint children
-        for (i, child) in self.children.iter().enumerate() {
-            if let Some(pos) = self.child_positions.get(i) {
-                let child_rect = Rect::from_origin_size(
-                    Point::new(rect.x() + pos.x, rect.y() + pos.y),
-                    child.bounds().size,
-                );
-                child.paint(painter, child_rect, ctx);
+impl Default for Row {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Row {
+    fn id(&self) -> WidgetId {
+        self.base.id
+    }
+
+    fn type_name(&self) -> &'static str {
+        "row"
+    }
+
+    fn element_id(&self) -> Option<&str> {
+        self.base.element_id.as_deref()
+    }
+
+    fn classes(&self) -> &ClassList {
+        &self.base.classes
+    }
+
+    fn state(&self) -> WidgetState {
+        self.base.state
+    }
+
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        let style = ctx.style(self);
+        let padding = style.padding_or(self.padding);
+        let sizes = Constraints::unbounded().with_style(&style);
+
+        let (mut width, mut height) = ctx.in_containing_block(content_box(sizes, padding), |ctx| {
+            let mut width: f32 = 0.0;
+            let mut height: f32 = 0.0;
+            for child in &self.children {
+                let child_size = child.intrinsic_size(ctx);
+                width += child_size.width;
+                height = height.max(child_size.height);
             }
+            (width, height)
+        });
+
+        // Add gaps
+        if self.children.len() > 1 {
+            width += self.gap * (self.children.len() - 1) as f32;
         }
+
+        // Add padding
+        width += padding.horizontal();
+        height += padding.vertical();
+        sizes.constrain(Size::new(width, height))
     }
 
-    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
-        // Transform mouse events to child coordinates and propagate
-        if let Event::Mouse(mouse) = event {
-            // Check children in reverse order (front to back)
-            for (i, child) in self.children.iter_mut().enumerate().rev() {
+    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        let padding = style.padding_or(self.padding);
+        let constraints = constraints.with_style(&style);
+        self.content_box = content_box(constraints, padding);
+        ctx.in_containing_block(self.content_box, |ctx| self.layout_children(constraints, padding, ctx))
+    }
+
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        paint_box(painter, rect, &ctx.style(self), ctx);
+
+        // Paint children
+        ctx.in_containing_block(self.content_box, |ctx| {
+            for (i, child) in self.children.iter().enumerate() {
                 if let Some(pos) = self.child_positions.get(i) {
-                    // Calculate child's global bounds
-                    let parent_origin = self.base.bounds.origin;
-                    let child_global_rect = Rect::new(
-                        parent_origin.x + pos.x,
-                        parent_origin.y + pos.y,
-                        child.bounds().width(),
-                        child.bounds().height(),
+                    let child_rect = Rect::from_origin_size(
+                        Point::new(rect.x() + pos.x, rect.y() + pos.y),
+                        child.bounds().size,
                     );
-                    
-                    // Check if mouse is in child's bounds
-                    if child_global_rect.contains(mouse.position) {
-                        // Temporarily set child's bounds to global for event handling
-                        let old_bounds = child.bounds();
-                        child.set_bounds(child_global_rect);
-                        let result = child.handle_event(event, ctx);
-                        child.set_bounds(old_bounds);
-                        
-                        if result == EventResult::Handled {
-                            return EventResult::Handled;
-                        }
-                    }
+                    ctx.paint_child(child.as_ref(), painter, child_rect);
                 }
             }
-        } else {
-            // For non-mouse events, propagate normally
-            for child in self.children.iter_mut().rev() {
-                if child.handle_event(event, ctx) == EventResult::Handled {
-                    return EventResult::Handled;
-                }
+        });
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        // Pointer events are routed to children by the event dispatcher
+        if matches!(event, Event::Mouse(_)) {
+            return EventResult::Ignored;
+        }
+        for child in self.children.iter_mut().rev() {
+            if child.handle_event(event, ctx) == EventResult::Handled {
+                return EventResult::Handled;
             }
         }
         EventResult::Ignored
@@ -465,6 +474,10 @@ impl Widget for Row {
         self.base.bounds = bounds;
     }
 
+    fn focus_scope(&self) -> FocusScope {
+        self.base.focus_scope
+    }
+
     fn children(&self) -> &[Box<dyn Widget>] {
         &self.children
     }
@@ -473,3 +486,131 @@ impl Widget for Row {
         &mut self.children
     }
 }
+
+/// Paint a container's CSS background and border.
+///
+/// Containers are transparent and borderless unless a stylesheet says otherwise.
+fn paint_box(painter: &mut Painter, rect: Rect, style: &ComputedStyle, ctx: &PaintContext) {
+    let radius = style.border_radius_or(BorderRadius::ZERO);
+    let background = style.background_or(Color::TRANSPARENT);
+    if background.a > 0.0 {
+        painter.fill_rounded_rect(rect, background, radius);
+    }
+    let border_width = style.border_width_or(0.0);
+    if border_width > 0.0 {
+        painter.stroke_rounded_rect(rect, style.border_color_or(ctx.style_ctx.theme.colors.border), border_width, radius);
+    }
+}
+
+/// The largest content box that fits in `constraints` inside `padding`.
+fn content_box(constraints: Constraints, padding: EdgeInsets) -> Size {
+    Size::new(
+        (constraints.max_width - padding.horizontal()).max(0.0),
+        (constraints.max_height - padding.vertical()).max(0.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{StyleContext, StyleManager};
+    use crate::theme::ThemeData;
+    use std::sync::{Arc, Mutex};
+
+    /// A leaf that records the left padding its style resolves to in layout
+    /// and in paint.
+    struct Probe {
+        base: WidgetBase,
+        padding: Arc<Mutex<(f32, f32)>>,
+    }
+
+    impl Widget for Probe {
+        fn id(&self) -> WidgetId {
+            self.base.id
+        }
+
+        fn type_name(&self) -> &'static str {
+            "probe"
+        }
+
+        fn classes(&self) -> &ClassList {
+            &self.base.classes
+        }
+
+        fn state(&self) -> WidgetState {
+            self.base.state
+        }
+
+        fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
+            Size::new(50.0, 50.0)
+        }
+
+        fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+            self.padding.lock().unwrap().0 = ctx.style(self).padding_or(EdgeInsets::ZERO).left;
+            LayoutResult::new(constraints.constrain(Size::new(50.0, 50.0)))
+        }
+
+        fn paint(&self, _painter: &mut Painter, _rect: Rect, ctx: &PaintContext) {
+            self.padding.lock().unwrap().1 = ctx.style(self).padding_or(EdgeInsets::ZERO).left;
+        }
+
+        fn bounds(&self) -> Rect {
+            self.base.bounds
+        }
+
+        fn set_bounds(&mut self, bounds: Rect) {
+            self.base.bounds = bounds;
+        }
+    }
+
+    #[test]
+    fn test_percent_width_resolves_against_parent() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(
+                ".sidebar { width: 240px; }
+                 .content { width: calc(100% - 240px); }
+                 .half { height: 50%; }",
+            )
+            .unwrap();
+        let style_ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_viewport(1920.0, 1080.0);
+        let ctx = LayoutContext::new(&style_ctx);
+
+        let mut row = Row::new()
+            .align(Alignment::Stretch)
+            .child(Column::new().class("sidebar"))
+            .child(Column::new().class("content").child(Column::new().class("half")));
+        let result = row.layout(Constraints::tight(Size::new(1000.0, 600.0)), &ctx);
+        assert_eq!(result.size, Size::new(1000.0, 600.0));
+
+        let sidebar = row.children()[0].bounds();
+        let content = row.children()[1].bounds();
+        assert_eq!(sidebar.width(), 240.0);
+        assert_eq!(content.width(), 760.0);
+        assert_eq!(content.x(), 240.0);
+        // Nested percentages resolve against the content column
+        assert_eq!(row.children()[1].children()[0].bounds().height(), 300.0);
+    }
+
+    #[test]
+    fn test_percent_padding_matches_in_layout_and_paint() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles.load_css("probe { padding: 10%; }").unwrap();
+        let style_ctx = StyleContext::with_styles(&theme, Arc::new(styles)).with_viewport(1920.0, 1080.0);
+
+        let padding = Arc::new(Mutex::new((0.0, 0.0)));
+        let probe = Probe {
+            base: WidgetBase::new(),
+            padding: padding.clone(),
+        };
+        let mut row = Row::new().padding(50.0).child(probe);
+        row.layout(Constraints::tight(Size::new(300.0, 200.0)), &LayoutContext::new(&style_ctx));
+        let paint_ctx = PaintContext::new(&style_ctx);
+        paint_ctx.paint_child(&row, &mut Painter::new(), Rect::new(0.0, 0.0, 300.0, 200.0));
+
+        // 10% of the row's 200px content box, not of the viewport
+        assert_eq!(*padding.lock().unwrap(), (20.0, 20.0));
+    }
+}
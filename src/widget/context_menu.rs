@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton, Key, KeyEventKind};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -265,6 +265,10 @@ impl Widget for ContextMenu {
             return;
         }
 
+        // Catch clicks anywhere so one outside the menu dismisses it
+        let viewport = Rect::new(0.0, 0.0, ctx.style_ctx.viewport_width, ctx.style_ctx.viewport_height);
+        ctx.add_overlay(painter, viewport);
+
         let theme = ctx.style_ctx.theme;
         let menu_rect = Rect::new(
             self.position.x,
@@ -272,20 +276,21 @@ impl Widget for ContextMenu {
             Self::menu_width(),
             self.calculate_height(),
         );
-        let radius = BorderRadius::all(theme.radii.md * theme.typography.base_size);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.md * theme.typography.base_size));
+        let font_size = style.font_size_or(14.0);
 
         // Shadow
-        let shadow_rect = Rect::new(
-            menu_rect.x() + 2.0,
-            menu_rect.y() + 4.0,
-            menu_rect.width(),
-            menu_rect.height(),
-        );
-        painter.fill_rounded_rect(shadow_rect, Color::BLACK.with_alpha(0.2), radius);
+        let shadows = style.box_shadow_or(std::slice::from_ref(&theme.shadows.lg));
+        painter.draw_shadows(menu_rect, radius, &shadows);
 
         // Background
-        painter.fill_rounded_rect(menu_rect, theme.colors.popover, radius);
-        painter.stroke_rect(menu_rect, theme.colors.border, 1.0);
+        painter.fill_rounded_rect(menu_rect, style.background_or(theme.colors.popover), radius);
+        painter.draw_inset_shadows(menu_rect, radius, &shadows);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(menu_rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Items
         let mut y = self.position.y;
@@ -313,7 +318,7 @@ impl Widget for ContextMenu {
             } else if self.hovered_index == Some(i) {
                 theme.colors.accent_foreground
             } else {
-                theme.colors.popover_foreground
+                style.color_or(theme.colors.popover_foreground)
             };
 
             // Icon
@@ -323,7 +328,7 @@ impl Widget for ContextMenu {
                     icon,
                     Point::new(text_x, y + 22.0),
                     text_color,
-                    14.0,
+                    font_size,
                 );
                 text_x += 24.0;
             }
@@ -333,7 +338,7 @@ impl Widget for ContextMenu {
                 &item.label,
                 Point::new(text_x, y + 22.0),
                 text_color,
-                14.0,
+                font_size,
             );
 
             // Shortcut
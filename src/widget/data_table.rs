@@ -257,6 +257,8 @@ pub struct DataTable {
     scroll_offset: f32,
     hovered_row: Option<usize>,
     hovered_header: Option<usize>,
+    /// Anchor for shift-click range selection
+    anchor_row: Option<usize>,
     show_row_numbers: bool,
     zebra_stripes: bool,
     computed_widths: Vec<f32>,
@@ -281,6 +283,7 @@ impl DataTable {
             scroll_offset: 0.0,
             hovered_row: None,
             hovered_header: None,
+            anchor_row: None,
             show_row_numbers: false,
             zebra_stripes: true,
             computed_widths: Vec::new(),
@@ -387,6 +390,12 @@ impl DataTable {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Select a row by ID.
     pub fn select(&mut self, id: &str) {
         if self.selection_mode == TableSelectionMode::None {
@@ -409,6 +418,39 @@ impl DataTable {
         }
     }
 
+    /// Toggle selection for a row (multiple selection mode only).
+    pub fn toggle_selection(&mut self, id: &str) {
+        if self.selection_mode != TableSelectionMode::Multiple {
+            return;
+        }
+
+        if let Some(row) = self.rows.iter_mut().find(|r| r.id == id) {
+            if !row.disabled {
+                row.selected = !row.selected;
+                if let Some(handler) = &self.on_select {
+                    handler(id);
+                }
+            }
+        }
+    }
+
+    /// Select all rows between two indices (inclusive), replacing the current selection.
+    pub fn select_range(&mut self, from: usize, to: usize) {
+        if self.selection_mode != TableSelectionMode::Multiple {
+            return;
+        }
+
+        let (start, end) = if from <= to { (from, to) } else { (to, from) };
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            row.selected = i >= start && i <= end && !row.disabled;
+        }
+        if let Some(handler) = &self.on_select {
+            if let Some(row) = self.rows.get(to) {
+                handler(&row.id);
+            }
+        }
+    }
+
     /// Get selected row IDs.
     pub fn selected(&self) -> Vec<&str> {
         self.rows.iter()
@@ -536,10 +578,14 @@ impl Widget for DataTable {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let row_num_width = if self.show_row_numbers { 40.0 } else { 0.0 };
+        let background = style.background_or(theme.colors.background);
+        let foreground = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(13.0);
 
         // Background
-        painter.fill_rect(rect, theme.colors.background);
+        painter.fill_rect(rect, background);
 
         // Header
         let header_rect = Rect::new(rect.x(), rect.y(), rect.width(), self.header_height);
@@ -565,8 +611,8 @@ impl Widget for DataTable {
             painter.draw_text(
                 &col.header,
                 Point::new(text_x, rect.y() + self.header_height / 2.0 + 4.0),
-                theme.colors.foreground,
-                12.0,
+                foreground,
+                font_size - 1.0,
             );
 
             // Sort indicator
@@ -619,7 +665,7 @@ impl Widget for DataTable {
             } else if self.zebra_stripes && i % 2 == 1 {
                 theme.colors.surface.with_alpha(0.5)
             } else {
-                theme.colors.background
+                background
             };
             painter.fill_rect(row_rect, bg_color);
 
@@ -654,7 +700,7 @@ impl Widget for DataTable {
                     let text_color = if row.disabled {
                         theme.colors.muted_foreground
                     } else {
-                        theme.colors.foreground
+                        foreground
                     };
 
                     let content_x = match col.align {
@@ -676,7 +722,7 @@ impl Widget for DataTable {
                                 &truncated,
                                 Point::new(content_x, row_y + self.row_height / 2.0 + 4.0),
                                 text_color,
-                                13.0,
+                                font_size,
                             );
                         }
                         CellValue::Number(n) => {
@@ -684,7 +730,7 @@ impl Widget for DataTable {
                                 &format!("{:.2}", n),
                                 Point::new(content_x, row_y + self.row_height / 2.0 + 4.0),
                                 text_color,
-                                13.0,
+                                font_size,
                             );
                         }
                         CellValue::Progress(p) => {
@@ -752,10 +798,18 @@ impl Widget for DataTable {
         }
 
         // Border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 match mouse.kind {
@@ -799,7 +853,20 @@ impl Widget for DataTable {
                             // Row click (select)
                             if let Some(row_idx) = self.row_at_point(mouse.position) {
                                 let id = self.rows[row_idx].id.clone();
-                                self.select(&id);
+                                let multiple = self.selection_mode == TableSelectionMode::Multiple;
+                                match self.anchor_row {
+                                    Some(anchor) if multiple && mouse.modifiers.shift => {
+                                        self.select_range(anchor, row_idx);
+                                    }
+                                    _ if multiple && mouse.modifiers.command() => {
+                                        self.toggle_selection(&id);
+                                        self.anchor_row = Some(row_idx);
+                                    }
+                                    _ => {
+                                        self.select(&id);
+                                        self.anchor_row = Some(row_idx);
+                                    }
+                                }
                                 ctx.request_focus(self.base.id);
                                 ctx.request_redraw();
                                 return EventResult::Handled;
@@ -870,4 +937,78 @@ impl Widget for DataTable {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{MouseEvent, Modifiers};
+
+    fn table(mode: TableSelectionMode) -> DataTable {
+        let mut table = DataTable::new()
+            .column(TableColumn::new("name", "Name"))
+            .rows((0..6).map(|i| TableRow::new(format!("{i}")).disabled(i == 3)).collect())
+            .header_height(40.0)
+            .row_height(30.0)
+            .selection_mode(mode);
+        table.set_bounds(Rect::new(0.0, 0.0, 300.0, 220.0));
+        table
+    }
+
+    fn click(table: &mut DataTable, row: usize, modifiers: Modifiers) {
+        let down = MouseEvent::new(MouseEventKind::Down, Point::new(150.0, 40.0 + row as f32 * 30.0 + 15.0))
+            .with_button(MouseButton::Left)
+            .with_modifiers(modifiers);
+        table.handle_event(&Event::Mouse(down), &mut EventContext::new());
+    }
+
+    fn shift() -> Modifiers {
+        Modifiers { shift: true, ..Modifiers::empty() }
+    }
+
+    fn command() -> Modifiers {
+        if cfg!(target_os = "macos") {
+            Modifiers { super_key: true, ..Modifiers::empty() }
+        } else {
+            Modifiers { control: true, ..Modifiers::empty() }
+        }
+    }
+
+    #[test]
+    fn test_shift_click_selects_range() {
+        // Upwards from the anchor, skipping the disabled row
+        let mut table = table(TableSelectionMode::Multiple);
+        click(&mut table, 4, Modifiers::empty());
+        click(&mut table, 1, shift());
+        assert_eq!(table.selected(), vec!["1", "2", "4"]);
+
+        // The anchor stays put, so the next range replaces this one, downwards
+        click(&mut table, 5, shift());
+        assert_eq!(table.selected(), vec!["4", "5"]);
+
+        // Toggling a row moves the anchor
+        click(&mut table, 0, command());
+        click(&mut table, 2, shift());
+        assert_eq!(table.selected(), vec!["0", "1", "2"]);
+    }
+
+    #[test]
+    fn test_shift_click_needs_multiple_selection() {
+        let mut single = table(TableSelectionMode::Single);
+        click(&mut single, 1, Modifiers::empty());
+        click(&mut single, 4, shift());
+        assert_eq!(single.selected(), vec!["4"]);
+
+        single.select_range(0, 2);
+        assert_eq!(single.selected(), vec!["4"]);
+
+        let mut unselectable = table(TableSelectionMode::None);
+        click(&mut unselectable, 1, Modifiers::empty());
+        click(&mut unselectable, 4, shift());
+        assert!(unselectable.selected().is_empty());
+    }
 }
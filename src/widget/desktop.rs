@@ -1,11 +1,11 @@
 //! Desktop widget with wallpaper and icon grid.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, StyleProperty, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
 use crate::geometry::{Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{ImageCache, ObjectFit, Painter};
 use std::path::PathBuf;
 
 /// How to scale/position the wallpaper image.
@@ -26,6 +26,19 @@ pub enum WallpaperMode {
     Span,
 }
 
+impl WallpaperMode {
+    /// How the image is fitted to the screen in this mode.
+    pub fn object_fit(self) -> ObjectFit {
+        match self {
+            WallpaperMode::Fill | WallpaperMode::Span => ObjectFit::Cover,
+            WallpaperMode::Fit => ObjectFit::Contain,
+            WallpaperMode::Stretch => ObjectFit::Fill,
+            WallpaperMode::Tile => ObjectFit::Tile,
+            WallpaperMode::Center => ObjectFit::None,
+        }
+    }
+}
+
 /// Gradient direction for gradient backgrounds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum GradientDirection {
@@ -422,21 +435,20 @@ impl Desktop {
             Wallpaper::Color(color) => {
                 painter.fill_rect(rect, *color);
             }
-            Wallpaper::Image { path: _, mode: _, fallback } => {
-                // TODO: Implement actual image loading and rendering
-                // For now, draw the fallback color
+            Wallpaper::Image { path, mode, fallback } => {
+                // The fallback shows while loading, if loading fails and
+                // around fitted images
                 painter.fill_rect(rect, *fallback);
-
-                // Placeholder: draw a pattern to indicate image area
-                self.draw_image_placeholder(painter, rect);
+                let image = ImageCache::global().load_path(path);
+                painter.draw_image_fit(rect, image.id(), mode.object_fit());
             }
             Wallpaper::Gradient { start, end, direction } => {
                 self.paint_gradient(painter, rect, *start, *end, *direction);
             }
-            Wallpaper::ImageWithOverlay { path: _, mode: _, overlay, fallback } => {
-                // TODO: Implement actual image loading
+            Wallpaper::ImageWithOverlay { path, mode, overlay, fallback } => {
                 painter.fill_rect(rect, *fallback);
-                self.draw_image_placeholder(painter, rect);
+                let image = ImageCache::global().load_path(path);
+                painter.draw_image_fit(rect, image.id(), mode.object_fit());
 
                 // Draw overlay
                 painter.fill_rect(rect, *overlay);
@@ -444,25 +456,6 @@ impl Desktop {
         }
     }
 
-    /// Draw a placeholder pattern for images (until image loading is implemented).
-    fn draw_image_placeholder(&self, painter: &mut Painter, rect: Rect) {
-        // Draw a subtle grid pattern to indicate where image would be
-        let grid_size: f32 = 40.0;
-        let line_color = Color::WHITE.with_alpha(0.03);
-
-        let mut x = rect.x();
-        while x < rect.x() + rect.width() {
-            painter.fill_rect(Rect::new(x, rect.y(), 1.0, rect.height()), line_color);
-            x += grid_size;
-        }
-
-        let mut y = rect.y();
-        while y < rect.y() + rect.height() {
-            painter.fill_rect(Rect::new(rect.x(), y, rect.width(), 1.0), line_color);
-            y += grid_size;
-        }
-    }
-
     /// Paint a gradient background.
     fn paint_gradient(&self, painter: &mut Painter, rect: Rect, start: Color, end: Color, direction: GradientDirection) {
         // Simulate gradient with multiple color bands
@@ -612,9 +605,15 @@ impl Widget for Desktop {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
-        // Draw wallpaper background
-        self.paint_wallpaper(painter, rect);
+        // Draw wallpaper background, unless CSS sets a background color
+        if style.is_set(&StyleProperty::BackgroundColor) {
+            painter.fill_rect(rect, style.background_color);
+        } else {
+            self.paint_wallpaper(painter, rect);
+        }
+        let label_color = style.color_or(Color::WHITE);
 
         // Draw icons
         for icon in &self.icons {
@@ -645,12 +644,12 @@ impl Widget for Desktop {
 
             // Label
             let label_y = icon_y + self.icon_size + 8.0;
-            let font_size = 11.0;
+            let font_size = style.font_size_or(11.0);
             let label_x = cell_rect.x() + (cell_rect.width() - icon.name.len() as f32 * font_size * 0.5) / 2.0;
             painter.draw_text(
                 &icon.name,
                 Point::new(label_x, label_y + font_size),
-                Color::WHITE,
+                label_color,
                 font_size,
             );
         }
@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{BorderRadius, Point, Rect, Size};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -140,15 +140,24 @@ impl Widget for DownloadItem {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
-
-        let bg_color = if self.is_hovered { theme.colors.surface_hover } else { theme.colors.card };
-        painter.fill_rounded_rect(rect, bg_color, BorderRadius::all(8.0));
+        let style = ctx.style(self);
+        let color = style.color_or(theme.colors.foreground);
+        let font_size = style.font_size_or(13.0);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 12.0));
+
+        let bg_color = if self.is_hovered { theme.colors.surface_hover } else { style.background_or(theme.colors.card) };
+        let radius = style.border_radius_or(BorderRadius::all(8.0));
+        painter.fill_rounded_rect(rect, bg_color, radius);
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // File icon
-        painter.draw_text(self.data.file_icon(), Point::new(rect.x() + 12.0, rect.y() + rect.height() / 2.0 - 12.0), theme.colors.foreground, 24.0);
+        painter.draw_text(self.data.file_icon(), Point::new(rect.x() + padding.left, rect.y() + rect.height() / 2.0 - 12.0), color, 24.0);
 
         // Filename
-        let text_x = rect.x() + 48.0;
+        let text_x = rect.x() + padding.left + 36.0;
         let filename = if self.data.filename.chars().count() > 30 {
             let mut s: String = self.data.filename.chars().take(27).collect();
             s.push_str("...");
@@ -156,15 +165,15 @@ impl Widget for DownloadItem {
         } else {
             self.data.filename.clone()
         };
-        painter.draw_text(&filename, Point::new(text_x, rect.y() + 14.0), theme.colors.foreground, 13.0);
+        painter.draw_text(&filename, Point::new(text_x, rect.y() + 14.0), color, font_size);
 
         // Progress text
-        painter.draw_text(&self.data.progress_text(), Point::new(text_x, rect.y() + 32.0), theme.colors.muted_foreground, 11.0);
+        painter.draw_text(&self.data.progress_text(), Point::new(text_x, rect.y() + 32.0), theme.colors.muted_foreground, font_size - 2.0);
 
         // Progress bar
         if self.data.state == DownloadState::Downloading || self.data.state == DownloadState::Paused {
             let bar_y = rect.y() + 50.0;
-            let bar_width = rect.width() - text_x + rect.x() - 60.0;
+            let bar_width = rect.width() - text_x + rect.x() - 48.0 - padding.right;
 
             painter.fill_rounded_rect(Rect::new(text_x, bar_y, bar_width, 4.0), theme.colors.border, BorderRadius::all(2.0));
 
@@ -175,7 +184,7 @@ impl Widget for DownloadItem {
 
         // Cancel button
         if self.is_hovered && self.data.state == DownloadState::Downloading {
-            let btn_x = rect.x() + rect.width() - 36.0;
+            let btn_x = rect.x() + rect.width() - 24.0 - padding.right;
             painter.draw_text("✕", Point::new(btn_x, rect.y() + rect.height() / 2.0 - 8.0), theme.colors.muted_foreground, 16.0);
         }
     }
@@ -192,14 +201,15 @@ impl Widget for DownloadItem {
                         ctx.request_redraw();
                     }
                 }
-                MouseEventKind::Down => {
-                    if mouse.button == Some(MouseButton::Left) && self.is_hovered
-                        && self.data.state == DownloadState::Completed {
-                            if let Some(ref handler) = self.on_open {
-                                handler(&self.data.id);
-                            }
-                            return EventResult::Handled;
-                        }
+                MouseEventKind::Down
+                    if mouse.button == Some(MouseButton::Left)
+                        && self.is_hovered
+                        && self.data.state == DownloadState::Completed =>
+                {
+                    if let Some(ref handler) = self.on_open {
+                        handler(&self.data.id);
+                    }
+                    return EventResult::Handled;
                 }
                 _ => {}
             }
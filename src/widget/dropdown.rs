@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, Key, KeyEventKind};
-use crate::geometry::{BorderRadius, Point, Rect, Size};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -126,6 +126,12 @@ impl Dropdown {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Get the currently selected value.
     pub fn value(&self) -> Option<&str> {
         self.selected_value.as_deref()
@@ -226,10 +232,11 @@ impl Widget for Dropdown {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(theme.radii.md * theme.typography.base_size);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.md * theme.typography.base_size));
 
         // Main button background
-        let bg_color = if self.base.state.disabled {
+        let bg_color = style.background_or(if self.base.state.disabled {
             theme.colors.muted
         } else if self.is_open || self.base.state.pressed {
             theme.colors.accent
@@ -237,25 +244,28 @@ impl Widget for Dropdown {
             theme.colors.secondary
         } else {
             theme.colors.background
-        };
+        });
         painter.fill_rounded_rect(rect, bg_color, radius);
 
         // Border
-        let border_color = if self.base.state.focused || self.is_open {
+        let border_color = style.border_color_or(if self.base.state.focused || self.is_open {
             theme.colors.ring
         } else {
             theme.colors.border
-        };
-        painter.stroke_rect(rect, border_color, 1.0);
+        });
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, border_color, border_width);
+        }
 
         // Selected text or placeholder
-        let font_size = 14.0;
-        let padding = 12.0;
+        let font_size = style.font_size_or(14.0);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 12.0)).left;
         let text_x = rect.x() + padding;
         let text_y = rect.y() + (rect.height() + font_size * 0.8) / 2.0;
 
         let (text, text_color) = if let Some(option) = self.selected_option() {
-            (&option.label, theme.colors.foreground)
+            (&option.label, style.color_or(theme.colors.foreground))
         } else {
             (&self.placeholder, theme.colors.muted_foreground)
         };
@@ -280,8 +290,10 @@ impl Widget for Dropdown {
             let menu_y = rect.y() + rect.height() + 4.0;
             let menu_height = self.dropdown_max_height();
             let menu_rect = Rect::new(rect.x(), menu_y, rect.width(), menu_height);
+            ctx.add_overlay(painter, menu_rect);
 
-            // Menu background
+            // Menu shadow and background
+            painter.draw_shadow(menu_rect, radius, &theme.shadows.md);
             painter.fill_rounded_rect(menu_rect, theme.colors.popover, radius);
             painter.stroke_rect(menu_rect, theme.colors.border, 1.0);
 
@@ -342,6 +354,15 @@ impl Widget for Dropdown {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            // Clicking elsewhere takes focus away and closes the menu
+            if !self.base.state.focused {
+                self.close();
+            }
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 let in_button = self.bounds().contains(mouse.position);
@@ -395,6 +416,8 @@ impl Widget for Dropdown {
                         ctx.request_redraw();
                         return EventResult::Handled;
                     }
+                    // Keep focus so the menu stays open until the option is released
+                    MouseEventKind::Down if in_menu => return EventResult::Handled,
                     MouseEventKind::Up => {
                         if self.base.state.pressed && in_button {
                             self.base.state.pressed = false;
@@ -414,10 +437,6 @@ impl Widget for Dropdown {
                                     return EventResult::Handled;
                                 }
                             }
-                        } else if self.is_open {
-                            // Click outside closes dropdown
-                            self.close();
-                            ctx.request_redraw();
                         }
                     }
                     _ => {}
@@ -485,4 +504,8 @@ impl Widget for Dropdown {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
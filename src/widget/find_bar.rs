@@ -94,9 +94,14 @@ impl Widget for FindBar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(13.0);
 
-        painter.fill_rect(rect, theme.colors.card);
-        painter.fill_rect(Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0), theme.colors.border);
+        painter.fill_rect(rect, style.background_or(theme.colors.card));
+        painter.fill_rect(
+            Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0),
+            style.border_color_or(theme.colors.border),
+        );
 
         let y_center = rect.y() + rect.height() / 2.0;
 
@@ -118,9 +123,10 @@ impl Widget for FindBar {
         // Query text
         let text_x = input_x + 8.0;
         if self.query.is_empty() {
-            painter.draw_text("Find in page...", Point::new(text_x, y_center - 6.0), theme.colors.muted_foreground, 13.0);
+            painter.draw_text("Find in page...", Point::new(text_x, y_center - 6.0), theme.colors.muted_foreground, font_size);
         } else {
-            painter.draw_text(&self.query, Point::new(text_x, y_center - 6.0), theme.colors.foreground, 13.0);
+            let color = style.color_or(theme.colors.foreground);
+            painter.draw_text(&self.query, Point::new(text_x, y_center - 6.0), color, font_size);
         }
 
         // Cursor
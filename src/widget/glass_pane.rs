@@ -5,7 +5,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -35,7 +35,8 @@ impl BlurIntensity {
 /// Glass pane / blur container
 pub struct GlassPane {
     base: WidgetBase,
-    background: Color,
+    /// Custom background; translucent popover color by default
+    background: Option<Color>,
     blur: BlurIntensity,
     border_color: Option<Color>,
     border_width: f32,
@@ -49,9 +50,9 @@ impl GlassPane {
     pub fn new() -> Self {
         Self {
             base: WidgetBase::new().with_class("glass-pane"),
-            background: Color::rgba(0.1, 0.1, 0.1, 0.7),
+            background: None,
             blur: BlurIntensity::Medium,
-            border_color: Some(Color::rgba(1.0, 1.0, 1.0, 0.1)),
+            border_color: None,
             border_width: 1.0,
             border_radius: 12.0,
             padding: 16.0,
@@ -61,7 +62,7 @@ impl GlassPane {
     }
 
     pub fn background(mut self, color: Color) -> Self {
-        self.background = color;
+        self.background = Some(color);
         self
     }
 
@@ -76,7 +77,7 @@ impl GlassPane {
     }
 
     pub fn no_border(mut self) -> Self {
-        self.border_color = None;
+        self.border_width = 0.0;
         self
     }
 
@@ -157,8 +158,9 @@ impl Widget for GlassPane {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
-        Size::new(self.padding * 2.0, self.padding * 2.0)
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        let padding = ctx.style(self).padding_or(EdgeInsets::all(self.padding));
+        Size::new(padding.horizontal(), padding.vertical())
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
@@ -167,20 +169,28 @@ impl Widget for GlassPane {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+
         // Background
-        painter.fill_rounded_rect(rect, self.background, BorderRadius::all(self.border_radius));
+        let radius = style.border_radius_or(BorderRadius::all(self.border_radius));
+        let background = style.background_or(self.background.unwrap_or(theme.colors.popover.with_alpha(0.7)));
+        painter.fill_rounded_rect(rect, background, radius);
 
         // Border
-        if let Some(border) = self.border_color {
-            painter.stroke_rect(rect, border, self.border_width);
+        let border_width = style.border_width_or(self.border_width);
+        if border_width > 0.0 {
+            let border = style.border_color_or(self.border_color.unwrap_or(theme.colors.border.with_alpha(0.5)));
+            painter.stroke_rect(rect, border, border_width);
         }
 
         // Inner highlight (top edge)
+        let inset = radius.top_left.max(radius.top_right);
         let highlight_rect = Rect::new(
-            rect.x() + self.border_radius,
+            rect.x() + inset,
             rect.y() + 1.0,
-            rect.width() - self.border_radius * 2.0,
+            rect.width() - inset * 2.0,
             1.0,
         );
         painter.fill_rect(highlight_rect, Color::rgba(1.0, 1.0, 1.0, 0.1));
@@ -5,7 +5,7 @@ use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
 use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{is_path_data, Painter, ICON_VIEWBOX};
 
 /// Icon button size presets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -55,7 +55,8 @@ pub enum IconButtonVariant {
 
 /// A button that displays an icon.
 ///
-/// Used for toolbar actions, close buttons, power buttons, etc.
+/// Used for toolbar actions, close buttons, power buttons, etc. The icon is
+/// text, such as a symbol or emoji, or SVG path data in `ICON_VIEWBOX`.
 ///
 /// # Example
 ///
@@ -71,7 +72,7 @@ pub enum IconButtonVariant {
 /// ```
 pub struct IconButton {
     base: WidgetBase,
-    /// Icon character or emoji
+    /// Icon character, emoji or SVG path data
     icon: String,
     tooltip: Option<String>,
     size: IconButtonSize,
@@ -134,6 +135,12 @@ impl IconButton {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     fn background_color(&self, theme: &crate::theme::ThemeData) -> Color {
         if self.disabled {
             return Color::TRANSPARENT;
@@ -225,35 +232,38 @@ impl Widget for IconButton {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(theme.radii.md * theme.typography.base_size);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.md * theme.typography.base_size));
 
         // Background
-        let bg_color = self.background_color(theme);
+        let bg_color = style.background_or(self.background_color(theme));
         if bg_color != Color::TRANSPARENT {
             painter.fill_rounded_rect(rect, bg_color, radius);
         }
 
         // Border for outline variant
-        if self.variant == IconButtonVariant::Outline {
-            let border_color = if self.base.state.focused {
+        let border_width = style.border_width_or(if self.variant == IconButtonVariant::Outline { 1.0 } else { 0.0 });
+        if border_width > 0.0 {
+            let border_color = style.border_color_or(if self.base.state.focused {
                 theme.colors.ring
             } else {
                 theme.colors.border
-            };
-            painter.stroke_rect(rect, border_color, 1.0);
+            });
+            painter.stroke_rect(rect, border_color, border_width);
         }
 
-        // Icon
-        let icon_size = self.size.icon_size();
-        let icon_x = rect.x() + (rect.width() - icon_size * 0.6) / 2.0;
-        let icon_y = rect.y() + (rect.height() + icon_size * 0.8) / 2.0;
-
-        painter.draw_text(
-            &self.icon,
-            Point::new(icon_x, icon_y),
-            self.foreground_color(theme),
-            icon_size,
-        );
+        // Icon, as SVG path data or text
+        let icon_size = style.font_size_or(self.size.icon_size());
+        let icon_color = style.color_or(self.foreground_color(theme));
+        if is_path_data(&self.icon) {
+            let icon_x = rect.x() + (rect.width() - icon_size) / 2.0;
+            let icon_y = rect.y() + (rect.height() - icon_size) / 2.0;
+            painter.draw_icon(&self.icon, Point::new(icon_x, icon_y), icon_size, icon_color, ICON_VIEWBOX);
+        } else {
+            let icon_x = rect.x() + (rect.width() - icon_size * 0.6) / 2.0;
+            let icon_y = rect.y() + (rect.height() + icon_size * 0.8) / 2.0;
+            painter.draw_text(&self.icon, Point::new(icon_x, icon_y), icon_color, icon_size);
+        }
 
         // Focus ring
         if self.base.state.focused && ctx.focus_visible {
@@ -270,6 +280,11 @@ impl Widget for IconButton {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         if self.disabled {
             return EventResult::Ignored;
         }
@@ -326,4 +341,8 @@ impl Widget for IconButton {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
@@ -1,16 +1,27 @@
 //! Label widget for displaying text.
 
-use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext};
+use super::{EventContext, Widget, WidgetBase, WidgetId, LayoutContext, PaintContext};
 use crate::css::{ClassList, ComputedStyle, StyleContext, WidgetState};
+use crate::event::{Event, EventResult, MouseButton, MouseEventKind};
 use crate::geometry::{Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, RichText, SpanStyle, TextLayout};
 
 /// A text label widget.
+///
+/// Shows plain text, or rich text with styled spans and links.
+#[allow(clippy::type_complexity)]
 pub struct Label {
     base: WidgetBase,
     text: String,
-    computed_style: Option<ComputedStyle>,
+    /// Styled spans of `text`, when set with `rich`
+    rich: Option<RichText>,
+    on_link: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    /// Link under the pointer when the button went down
+    pressed_link: Option<String>,
+    /// Font resolved at layout, for finding links
+    font_size: f32,
+    font: SpanStyle,
 }
 
 impl Label {
@@ -18,13 +29,32 @@ impl Label {
         Self {
             base: WidgetBase::new().with_class("label"),
             text: text.into(),
-            computed_style: None,
+            rich: None,
+            on_link: None,
+            pressed_link: None,
+            font_size: 16.0,
+            font: SpanStyle::default(),
         }
     }
 
     /// Set the text content.
     pub fn text(mut self, text: impl Into<String>) -> Self {
-        self.text = text.into();
+        self.set_text(text);
+        self
+    }
+
+    /// Set styled text content.
+    pub fn rich(mut self, text: impl Into<RichText>) -> Self {
+        self.set_rich(text);
+        self
+    }
+
+    /// Set the handler for clicks on links, called with the link target.
+    pub fn on_link<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_link = Some(Box::new(handler));
         self
     }
 
@@ -48,6 +78,37 @@ impl Label {
     /// Set the text content (mutably).
     pub fn set_text(&mut self, text: impl Into<String>) {
         self.text = text.into();
+        self.rich = None;
+    }
+
+    /// Set styled text content (mutably).
+    pub fn set_rich(&mut self, text: impl Into<RichText>) {
+        let text = text.into();
+        self.text = text.text();
+        self.rich = Some(text);
+    }
+
+    /// Get the styled text content, if set.
+    pub fn get_rich(&self) -> Option<&RichText> {
+        self.rich.as_ref()
+    }
+
+    /// Target of the link at window point `point`, if any.
+    fn link_at(&self, point: Point) -> Option<String> {
+        let rich = self.rich.as_ref()?;
+        let layout = TextLayout::rich(&rich.clone().with_font(&self.font), self.font_size);
+        // Text is drawn from just below the top of the bounds
+        let top = self.bounds().y() + self.font_size * 0.2;
+        let x = point.x - self.bounds().x();
+        let y = point.y - top;
+        if x < 0.0 || y < 0.0 || x > layout.size().width || y > layout.size().height {
+            return None;
+        }
+        let offset = layout.hit_test(Point::new(x, y));
+        // The hit offset is the nearest caret position; take the span on the
+        // side the pointer is on
+        let offset = if layout.caret_x(offset) > x { offset.saturating_sub(1) } else { offset };
+        rich.span_at(offset)?.style.link.clone()
     }
     
     /// Get font size based on CSS class (built-in typography support).
@@ -109,18 +170,26 @@ impl Widget for Label {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
-        // Use class-based font size or default
-        let font_size = self.font_size_for_class()
-            .or_else(|| self.computed_style.as_ref().map(|s| s.font_size))
-            .unwrap_or(16.0);
-        let char_width = font_size * 0.6; // Approximate average character width
-        let width = self.text.len() as f32 * char_width;
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        // Use CSS font size, then class-based font size, then the default
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(self.font_size_for_class().unwrap_or(16.0));
+        let width = match &self.rich {
+            // Spans differ in font, so measure them
+            Some(rich) => TextLayout::rich(&rich.clone().with_font(&style.font()), font_size).size().width,
+            None => {
+                let char_width = font_size * 0.6; // Approximate average character width
+                self.text.len() as f32 * char_width
+            }
+        };
         let height = font_size * 1.5; // Line height
         Size::new(width, height)
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.font_size = style.font_size_or(self.font_size_for_class().unwrap_or(16.0));
+        self.font = style.font();
         let intrinsic = self.intrinsic_size(ctx);
         let size = constraints.constrain(intrinsic);
         self.base.bounds.size = size;
@@ -128,15 +197,13 @@ impl Widget for Label {
     }
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
-        // Use class-based font size or computed/default
-        let font_size = self.font_size_for_class()
-            .or_else(|| self.computed_style.as_ref().map(|s| s.font_size))
-            .unwrap_or(16.0);
-        
-        // Base color from theme
-        let base_color = self.computed_style.as_ref()
-            .map(|s| s.color)
-            .unwrap_or(ctx.style_ctx.theme.colors.foreground);
+        let style = ctx.style(self);
+
+        // Use CSS font size, then class-based font size, then the default
+        let font_size = style.font_size_or(self.font_size_for_class().unwrap_or(16.0));
+
+        // Base color from CSS, falling back to the theme
+        let base_color = style.color_or(ctx.style_ctx.theme.colors.foreground);
         
         // Apply opacity for muted text
         let opacity = self.opacity_for_class();
@@ -148,7 +215,54 @@ impl Widget for Label {
 
         // Draw text at the baseline
         let baseline_y = rect.y() + font_size;
-        painter.draw_text(&self.text, Point::new(rect.x(), baseline_y), color, font_size);
+        match &self.rich {
+            Some(rich) => {
+                // Links default to the theme's link style
+                let mut rich = rich.clone();
+                for span in rich.spans_mut() {
+                    if span.style.link.is_some() {
+                        span.style.color.get_or_insert(ctx.style_ctx.theme.colors.primary);
+                        span.style.underline = true;
+                    }
+                }
+                painter.draw_rich_text(&rich, Point::new(rect.x(), baseline_y), color, font_size);
+            }
+            None => painter.draw_text(&self.text, Point::new(rect.x(), baseline_y), color, font_size),
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, _ctx: &mut EventContext) -> EventResult {
+        if self.on_link.is_none() {
+            return EventResult::Ignored;
+        }
+
+        if let Event::Mouse(mouse) = event {
+            if mouse.button != Some(MouseButton::Left) {
+                return EventResult::Ignored;
+            }
+
+            match mouse.kind {
+                MouseEventKind::Down => {
+                    self.pressed_link = self.link_at(mouse.position);
+                    if self.pressed_link.is_some() {
+                        return EventResult::Handled;
+                    }
+                }
+                MouseEventKind::Up => {
+                    // A link is followed when pressed and released on it
+                    if let Some(link) = self.pressed_link.take() {
+                        if self.link_at(mouse.position).as_ref() == Some(&link) {
+                            if let Some(handler) = &self.on_link {
+                                handler(&link);
+                            }
+                        }
+                        return EventResult::Handled;
+                    }
+                }
+                _ => {}
+            }
+        }
+        EventResult::Ignored
     }
 
     fn style(&self, ctx: &StyleContext) -> ComputedStyle {
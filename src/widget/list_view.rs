@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton, Key, KeyEventKind};
-use crate::geometry::{Point, Rect, Size};
+use crate::geometry::{EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -93,6 +93,8 @@ pub struct ListView {
     selection_mode: SelectionMode,
     scroll_offset: f32,
     hovered_index: Option<usize>,
+    /// Anchor for shift-click range selection
+    anchor_index: Option<usize>,
     on_select: Option<Box<dyn Fn(&str) + Send + Sync>>,
     on_activate: Option<Box<dyn Fn(&str) + Send + Sync>>,
 }
@@ -107,6 +109,7 @@ impl ListView {
             selection_mode: SelectionMode::default(),
             scroll_offset: 0.0,
             hovered_index: None,
+            anchor_index: None,
             on_select: None,
             on_activate: None,
         }
@@ -160,6 +163,12 @@ impl ListView {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Get selected item IDs.
     pub fn selected_items(&self) -> Vec<&str> {
         self.items.iter()
@@ -207,6 +216,23 @@ impl ListView {
         }
     }
 
+    /// Select all items between two indices (inclusive), replacing the current selection.
+    pub fn select_range(&mut self, from: usize, to: usize) {
+        if self.selection_mode != SelectionMode::Multiple {
+            return;
+        }
+
+        let (start, end) = if from <= to { (from, to) } else { (to, from) };
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.selected = i >= start && i <= end && !item.disabled;
+        }
+        if let Some(handler) = &self.on_select {
+            if let Some(item) = self.items.get(to) {
+                handler(&item.id);
+            }
+        }
+    }
+
     /// Clear all selections.
     pub fn clear_selection(&mut self) {
         for item in &mut self.items {
@@ -273,9 +299,12 @@ impl Widget for ListView {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 12.0));
+        let font_size = style.font_size_or(14.0);
 
         // Background
-        painter.fill_rect(rect, theme.colors.background);
+        painter.fill_rect(rect, style.background_or(theme.colors.background));
 
         // Calculate visible range
         let first_visible = (self.scroll_offset / self.item_height) as usize;
@@ -305,10 +334,10 @@ impl Widget for ListView {
             } else if item.selected {
                 theme.colors.accent_foreground
             } else {
-                theme.colors.foreground
+                style.color_or(theme.colors.foreground)
             };
 
-            let mut content_x = rect.x() + 12.0;
+            let mut content_x = rect.x() + padding.left;
 
             // Icon
             if let Some(ref icon) = item.icon {
@@ -327,7 +356,7 @@ impl Widget for ListView {
             } else {
                 item_y + self.item_height * 0.6
             };
-            painter.draw_text(&item.text, Point::new(content_x, text_y), text_color, 14.0);
+            painter.draw_text(&item.text, Point::new(content_x, text_y), text_color, font_size);
 
             // Subtitle
             if let Some(ref subtitle) = item.subtitle {
@@ -348,17 +377,25 @@ impl Widget for ListView {
             if i < self.items.len() - 1 {
                 let sep_y = item_y + self.item_height - 1.0;
                 painter.fill_rect(
-                    Rect::new(rect.x() + 12.0, sep_y, rect.width() - 24.0, 1.0),
+                    Rect::new(rect.x() + padding.left, sep_y, rect.width() - padding.horizontal(), 1.0),
                     theme.colors.border.with_alpha(0.5),
                 );
             }
         }
 
         // Border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 match mouse.kind {
@@ -379,9 +416,18 @@ impl Widget for ListView {
                         if let Some(index) = self.item_at_point(mouse.position) {
                             let id = self.items[index].id.clone();
                             if self.selection_mode == SelectionMode::Multiple {
-                                self.toggle_selection(&id);
+                                match self.anchor_index {
+                                    Some(anchor) if mouse.modifiers.shift => {
+                                        self.select_range(anchor, index);
+                                    }
+                                    _ => {
+                                        self.toggle_selection(&id);
+                                        self.anchor_index = Some(index);
+                                    }
+                                }
                             } else {
                                 self.select(&id);
+                                self.anchor_index = Some(index);
                             }
                             ctx.request_focus(self.base.id);
                             ctx.request_redraw();
@@ -442,4 +488,60 @@ impl Widget for ListView {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{MouseEvent, Modifiers};
+
+    fn list(mode: SelectionMode) -> ListView {
+        let mut list = ListView::new()
+            .items((0..6).map(|i| ListItem::new(format!("{i}"), format!("Item {i}")).disabled(i == 3)).collect())
+            .item_height(40.0)
+            .selection_mode(mode);
+        list.set_bounds(Rect::new(0.0, 0.0, 200.0, 240.0));
+        list
+    }
+
+    fn click(list: &mut ListView, index: usize, shift: bool) {
+        let modifiers = Modifiers { shift, ..Modifiers::empty() };
+        let down = MouseEvent::new(MouseEventKind::Down, Point::new(100.0, index as f32 * 40.0 + 20.0))
+            .with_button(MouseButton::Left)
+            .with_modifiers(modifiers);
+        list.handle_event(&Event::Mouse(down), &mut EventContext::new());
+    }
+
+    #[test]
+    fn test_shift_click_selects_range() {
+        // Downwards from the anchor, skipping the disabled item
+        let mut list = list(SelectionMode::Multiple);
+        click(&mut list, 1, false);
+        click(&mut list, 4, true);
+        assert_eq!(list.selected_items(), vec!["1", "2", "4"]);
+
+        // The anchor stays put, so the next range replaces this one, upwards
+        click(&mut list, 0, true);
+        assert_eq!(list.selected_items(), vec!["0", "1"]);
+
+        // A plain click moves the anchor
+        click(&mut list, 5, false);
+        click(&mut list, 4, true);
+        assert_eq!(list.selected_items(), vec!["4", "5"]);
+    }
+
+    #[test]
+    fn test_shift_click_needs_multiple_selection() {
+        let mut list = list(SelectionMode::Single);
+        click(&mut list, 1, false);
+        click(&mut list, 4, true);
+        assert_eq!(list.selected_items(), vec!["4"]);
+
+        list.select_range(0, 2);
+        assert_eq!(list.selected_items(), vec!["4"]);
+    }
 }
@@ -537,12 +537,13 @@ impl Widget for MenuBar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Menu bar background
-        painter.fill_rect(rect, theme.colors.surface);
+        painter.fill_rect(rect, style.background_or(theme.colors.surface));
         painter.fill_rect(
             Rect::new(rect.x(), rect.y() + rect.height() - 1.0, rect.width(), 1.0),
-            theme.colors.border,
+            style.border_color_or(theme.colors.border),
         );
 
         // Draw menu headers
@@ -564,14 +565,14 @@ impl Widget for MenuBar {
             let text_color = if is_active {
                 theme.colors.accent
             } else {
-                theme.colors.foreground
+                style.color_or(theme.colors.foreground)
             };
 
             painter.draw_text(
                 &menu.label,
                 Point::new(header_rect.x() + 16.0, header_rect.y() + self.height / 2.0 + 4.0),
                 text_color,
-                13.0,
+                style.font_size_or(13.0),
             );
         }
 
@@ -834,18 +835,12 @@ impl MenuBar {
         let menu = &self.menus[menu_idx];
         let dropdown_rect = self.calculate_dropdown_rect(menu_idx);
 
+        // Catch clicks anywhere while open so one outside the menu closes it
+        let viewport = Rect::new(0.0, 0.0, ctx.style_ctx.viewport_width, ctx.style_ctx.viewport_height);
+        ctx.add_overlay(painter, viewport);
+
         // Dropdown shadow
-        let shadow_offset = 4.0;
-        painter.fill_rounded_rect(
-            Rect::new(
-                dropdown_rect.x() + shadow_offset,
-                dropdown_rect.y() + shadow_offset,
-                dropdown_rect.width(),
-                dropdown_rect.height(),
-            ),
-            theme.colors.background.with_alpha(0.3),
-            BorderRadius::all(4.0),
-        );
+        painter.draw_shadow(dropdown_rect, BorderRadius::all(4.0), &theme.shadows.lg);
 
         // Dropdown background
         painter.fill_rounded_rect(dropdown_rect, theme.colors.card, BorderRadius::all(4.0));
@@ -1,50 +1,75 @@
 //! Widget system for OpenKit.
 
+pub mod action_center;
 pub mod address_bar;
+pub mod app_grid;
 pub mod avatar;
 pub mod bar;
+pub mod battery_indicator;
 pub mod bookmark_bar;
 pub mod browser_tab;
 pub mod browser_toolbar;
 pub mod button;
+pub mod calendar;
 pub mod card;
+pub mod chat_window;
 pub mod checkbox;
 pub mod clock;
+pub mod connection_card;
 pub mod container;
 pub mod context_menu;
 pub mod data_table;
 pub mod desktop;
+pub mod download_item;
 pub mod dropdown;
 pub mod find_bar;
+pub mod glass_pane;
 pub mod icon_button;
 pub mod label;
 pub mod list_view;
 pub mod menu_bar;
+pub mod navigation_bar;
+pub mod network_indicator;
 pub mod notification;
+pub mod panel;
 pub mod password_field;
 pub mod piece_map;
 pub mod progress;
 pub mod scroll_view;
+pub mod search_bar;
 pub mod separator;
+pub mod server_list;
 pub mod slider;
 pub mod spacer;
 pub mod speed_graph;
 pub mod spinner;
 pub mod split_pane;
+pub mod start_button;
+pub mod start_menu;
 pub mod switch;
 pub mod system_tray;
 pub mod tabs;
+pub mod taskbar_button;
+mod text_edit;
+pub mod textarea;
 pub mod textfield;
 pub mod tooltip;
 pub mod tree_view;
+pub mod volume_control;
+pub mod vpn_status;
 pub mod window;
 pub mod workspace;
 
-use crate::css::{ClassList, ComputedStyle, StyleContext, WidgetState};
-use crate::event::{Event, EventResult};
+use crate::css::{ClassList, ComputedStyle, ElementInfo, ElementTree, StyleContext, WidgetState};
+use crate::dispatch::HitTree;
+use crate::app::{next_window_id, WindowOptions, WindowRequest};
+use crate::clipboard::{Clipboard, MemoryClipboard};
+use crate::event::{Event, EventPhase, EventResult, FocusEvent, TimerId, WindowId};
+use crate::focus::{FocusRequest, FocusScope};
 use crate::geometry::{Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
+use crate::timer::Timers;
 
 /// Type alias for event callback handlers to reduce type complexity.
 pub type EventCallback<T = ()> = Option<Box<dyn Fn() -> T + Send + Sync>>;
@@ -52,7 +77,10 @@ pub type EventCallback<T = ()> = Option<Box<dyn Fn() -> T + Send + Sync>>;
 /// Type alias for event handlers that take a string parameter.
 pub type StringCallback = Option<Box<dyn Fn(&str) + Send + Sync>>;
 
+use std::cell::{Cell, RefCell};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Unique widget identifier.
 pub type WidgetId = u64;
@@ -98,6 +126,13 @@ pub trait Widget {
         EventResult::Ignored
     }
 
+    /// Intercept a pointer event on its way down to a descendant.
+    ///
+    /// Returning `Handled` stops the event before it reaches the target.
+    fn capture_event(&mut self, _event: &Event, _ctx: &mut EventContext) -> EventResult {
+        EventResult::Ignored
+    }
+
     /// Get the computed style for this widget.
     fn style(&self, _ctx: &StyleContext) -> ComputedStyle {
         ComputedStyle::default()
@@ -123,6 +158,67 @@ pub trait Widget {
     fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
         &mut []
     }
+
+    /// Position in the keyboard tab order, or `None` if the widget can't
+    /// take focus.
+    ///
+    /// Works like HTML `tabindex`: positive values come first in ascending
+    /// order, zero follows tree order, and negative values can be focused
+    /// with the pointer but are skipped by Tab.
+    fn tab_index(&self) -> Option<i32> {
+        None
+    }
+
+    /// How this widget groups the focus of its descendants.
+    fn focus_scope(&self) -> FocusScope {
+        FocusScope::None
+    }
+}
+
+/// Build the element tree used to match CSS combinators from a widget tree.
+///
+/// Each node records the widget's selector data along with its structural
+/// state (`first_child`, `last_child`, `nth_child`).
+pub fn build_element_tree(root: &dyn Widget) -> ElementTree {
+    let mut tree = ElementTree::new();
+    let state = WidgetState {
+        first_child: true,
+        last_child: true,
+        nth_child: 1,
+        ..root.state()
+    };
+    push_element(&mut tree, root, state, None, None);
+    tree
+}
+
+fn push_element(
+    tree: &mut ElementTree,
+    widget: &dyn Widget,
+    state: WidgetState,
+    parent: Option<usize>,
+    prev_sibling: Option<usize>,
+) {
+    let info = ElementInfo {
+        widget_type: widget.type_name().to_string(),
+        element_id: widget.element_id().map(str::to_string),
+        classes: widget.classes().as_slice().to_vec(),
+        state,
+    };
+    let node = tree.push(widget.id(), info, parent, prev_sibling);
+
+    let children = widget.children();
+    let mut prev = None;
+    for (i, child) in children.iter().enumerate() {
+        let state = WidgetState {
+            first_child: i == 0,
+            last_child: i + 1 == children.len(),
+            nth_child: i + 1,
+            ..child.state()
+        };
+        let index = tree.len();
+        push_element(tree, child.as_ref(), state, Some(node), prev);
+        prev = Some(index);
+    }
 }
 
 /// Context for layout operations.
@@ -143,6 +239,25 @@ impl<'a> LayoutContext<'a> {
         self.scale_factor = scale;
         self
     }
+
+    /// Compute a widget's style from the stylesheets in effect.
+    pub fn style<W: Widget + ?Sized>(&self, widget: &W) -> ComputedStyle {
+        widget_style(self.style_ctx, widget)
+    }
+
+    /// Run `f` with percentages in child styles resolving against `content`,
+    /// the content box of the widget being laid out.
+    ///
+    /// Unbounded sides keep the current containing block.
+    pub fn in_containing_block<R>(&self, content: Size, f: impl FnOnce(&LayoutContext) -> R) -> R {
+        let width = if content.width.is_finite() { content.width } else { self.style_ctx.containing_width };
+        let height = if content.height.is_finite() { content.height } else { self.style_ctx.containing_height };
+        let style_ctx = self.style_ctx.clone().with_containing_block(width.max(0.0), height.max(0.0));
+        f(&LayoutContext {
+            style_ctx: &style_ctx,
+            scale_factor: self.scale_factor,
+        })
+    }
 }
 
 /// Context for paint operations.
@@ -150,6 +265,12 @@ pub struct PaintContext<'a> {
     pub style_ctx: &'a StyleContext<'a>,
     pub scale_factor: f32,
     pub focus_visible: bool,
+    /// Where widgets were painted, for routing pointer events
+    pub hit_tree: RefCell<HitTree>,
+    /// Caret of the focused text widget, in window coordinates
+    pub ime_cursor_area: Cell<Option<Rect>>,
+    /// Content box percentages in styles resolve against, as in layout
+    containing_block: Cell<Size>,
 }
 
 impl<'a> PaintContext<'a> {
@@ -157,9 +278,119 @@ impl<'a> PaintContext<'a> {
         Self {
             style_ctx,
             scale_factor: 1.0,
-            focus_visible: false,
+            focus_visible: style_ctx.focus_visible,
+            hit_tree: RefCell::new(HitTree::new()),
+            ime_cursor_area: Cell::new(None),
+            containing_block: Cell::new(Size::new(style_ctx.containing_width, style_ctx.containing_height)),
         }
     }
+
+    /// Take the hit tree recorded while painting.
+    pub fn into_hit_tree(self) -> HitTree {
+        self.hit_tree.into_inner()
+    }
+
+    /// Make pointer events at `rect` go to the widget being painted.
+    ///
+    /// For content drawn outside the widget's bounds, such as an open menu.
+    /// Overlays are hit before regular widgets.
+    pub fn add_overlay(&self, painter: &Painter, rect: Rect) {
+        self.hit_tree
+            .borrow_mut()
+            .push_overlay(painter.transform_rect(rect), None);
+    }
+
+    /// Report the caret of the focused text widget being painted.
+    ///
+    /// Enables input method composition for the frame and places the input
+    /// method's candidate window next to `rect`.
+    pub fn set_ime_cursor_area(&self, painter: &Painter, rect: Rect) {
+        self.ime_cursor_area.set(Some(painter.transform_rect(rect)));
+    }
+
+    /// Compute a widget's style from the stylesheets in effect.
+    ///
+    /// Properties no rule sets keep their defaults, so widgets should read
+    /// them through the `ComputedStyle::*_or` helpers with a theme fallback.
+    pub fn style<W: Widget + ?Sized>(&self, widget: &W) -> ComputedStyle {
+        let block = self.containing_block.get();
+        if block == Size::new(self.style_ctx.containing_width, self.style_ctx.containing_height) {
+            widget_style(self.style_ctx, widget)
+        } else {
+            let style_ctx = self.style_ctx.clone().with_containing_block(block.width, block.height);
+            widget_style(&style_ctx, widget)
+        }
+    }
+
+    /// Run `f` with percentages in child styles resolving against `content`,
+    /// the content box the children were laid out in.
+    ///
+    /// Containers pass the same box as to
+    /// [`LayoutContext::in_containing_block`], so styles resolve alike in
+    /// layout and paint. Unbounded sides keep the current containing block.
+    pub fn in_containing_block<R>(&self, content: Size, f: impl FnOnce(&Self) -> R) -> R {
+        let outer = self.containing_block.get();
+        let width = if content.width.is_finite() { content.width } else { outer.width };
+        let height = if content.height.is_finite() { content.height } else { outer.height };
+        self.containing_block.set(Size::new(width.max(0.0), height.max(0.0)));
+        let result = f(self);
+        self.containing_block.set(outer);
+        result
+    }
+
+    /// Paint a child widget, applying its CSS `opacity` and `transform`.
+    ///
+    /// Containers paint their children through this so both can be styled
+    /// and transitioned. Transforms scale about the center of `rect`. The
+    /// child is also recorded in the hit tree for pointer event routing.
+    pub fn paint_child<W: Widget + ?Sized>(&self, child: &W, painter: &mut Painter, rect: Rect) {
+        let style = self.style(child);
+        if style.opacity <= 0.0 {
+            return;
+        }
+
+        let transform = style.transform;
+        let transformed = !transform.is_identity();
+        if transformed {
+            let center = rect.center();
+            painter.save();
+            painter.translate(center.x + transform.translate_x, center.y + transform.translate_y);
+            painter.scale(transform.scale_x, transform.scale_y);
+            painter.translate(-center.x, -center.y);
+        }
+        let faded = style.opacity < 1.0;
+        if faded {
+            painter.push_opacity(style.opacity);
+        }
+        // Like font-size, family and weight aren't inherited, so every widget
+        // draws in its own
+        painter.push_font(style.font());
+
+        let clip = painter.clip_bounds();
+        self.hit_tree
+            .borrow_mut()
+            .push(child.id(), painter.transform_rect(rect), clip);
+        child.paint(painter, rect, self);
+        self.hit_tree.borrow_mut().pop();
+        painter.pop_font();
+
+        if faded {
+            painter.pop_opacity();
+        }
+        if transformed {
+            painter.restore();
+        }
+    }
+}
+
+fn widget_style<W: Widget + ?Sized>(style_ctx: &StyleContext, widget: &W) -> ComputedStyle {
+    style_ctx.compute_style_for(
+        widget.id(),
+        widget.type_name(),
+        widget.element_id(),
+        widget.classes().as_slice(),
+        &widget.state(),
+    )
 }
 
 /// Context for event handling.
@@ -177,38 +408,133 @@ pub enum ThemeRequest {
 }
 
 pub struct EventContext {
+    /// The focused widget, maintained by the application's focus manager
     pub focus: Option<WidgetId>,
+    /// Focus change requested while handling the current event
+    pub focus_request: Option<FocusRequest>,
     pub mouse_position: Point,
     pub should_redraw: bool,
+    /// A widget's size changed with its content
+    pub should_layout: bool,
     /// Requested theme change
     pub theme_request: ThemeRequest,
     /// Current theme is dark
     pub is_dark_theme: bool,
+    /// Scheduled timers and animation frame requests
+    pub timers: Timers,
+    /// Phase of the pointer event being delivered
+    pub phase: EventPhase,
+    /// Widget the pointer event being delivered is aimed at
+    pub target: Option<WidgetId>,
+    /// Widget receiving all pointer events until the button is released
+    pub pointer_capture: Option<WidgetId>,
+    /// The window the event was delivered to
+    pub window: WindowId,
+    /// Windows to open or close once the current event is handled
+    pub window_requests: Vec<WindowRequest>,
+    /// Clipboard shared by the application's windows
+    pub clipboard: Arc<dyn Clipboard>,
 }
 
 impl EventContext {
     pub fn new() -> Self {
         Self {
             focus: None,
+            focus_request: None,
             mouse_position: Point::ZERO,
             should_redraw: false,
+            should_layout: false,
             theme_request: ThemeRequest::None,
             is_dark_theme: false,
+            timers: Timers::new(),
+            phase: EventPhase::Target,
+            target: None,
+            pointer_capture: None,
+            window: 0,
+            window_requests: Vec::new(),
+            clipboard: Arc::new(MemoryClipboard::new()),
         }
     }
 
+    /// Ask for keyboard focus to move to `widget_id`.
+    ///
+    /// The widget receives `FocusEvent::FocusIn` once the focus manager
+    /// grants the request.
     pub fn request_focus(&mut self, widget_id: WidgetId) {
-        self.focus = Some(widget_id);
+        self.focus_request = Some(FocusRequest::Focus(widget_id));
     }
 
+    /// Ask for keyboard focus to be cleared.
     pub fn release_focus(&mut self) {
-        self.focus = None;
+        self.focus_request = Some(FocusRequest::Release);
+    }
+
+    /// Move focus to the next widget in tab order.
+    pub fn focus_next(&mut self) {
+        self.focus_request = Some(FocusRequest::Next);
+    }
+
+    /// Move focus to the previous widget in tab order.
+    pub fn focus_previous(&mut self) {
+        self.focus_request = Some(FocusRequest::Previous);
+    }
+
+    /// Send all pointer events to `widget_id` until the button is released.
+    ///
+    /// Used for drags that should keep tracking the pointer after it leaves
+    /// the widget.
+    pub fn capture_pointer(&mut self, widget_id: WidgetId) {
+        self.pointer_capture = Some(widget_id);
+    }
+
+    /// End a pointer capture early.
+    pub fn release_pointer(&mut self) {
+        self.pointer_capture = None;
+    }
+
+    /// Whether `widget_id` has captured the pointer.
+    pub fn has_pointer_capture(&self, widget_id: WidgetId) -> bool {
+        self.pointer_capture == Some(widget_id)
     }
 
     pub fn request_redraw(&mut self) {
         self.should_redraw = true;
     }
 
+    /// Lay out the window again before the next paint, for widgets whose
+    /// size follows their content.
+    pub fn request_layout(&mut self) {
+        self.should_layout = true;
+        self.should_redraw = true;
+    }
+
+    /// The text on the clipboard, if any.
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.clipboard.read_text()
+    }
+
+    /// Put `text` on the clipboard.
+    pub fn set_clipboard_text(&mut self, text: &str) {
+        self.clipboard.write_text(text);
+    }
+
+    /// Open a new window once the current event is handled.
+    ///
+    /// Returns the id the window will have, for closing it later or making
+    /// it the parent of another window.
+    pub fn open_window(&mut self, options: WindowOptions) -> WindowId {
+        let id = next_window_id();
+        self.window_requests.push(WindowRequest::Open(id, options));
+        id
+    }
+
+    /// Close a window, along with the windows transient for it.
+    ///
+    /// Closing the main window quits the application.
+    pub fn close_window(&mut self, id: WindowId) {
+        self.window_requests.push(WindowRequest::Close(id));
+    }
+
     /// Request a theme change.
     pub fn set_theme(&mut self, dark: bool) {
         self.theme_request = if dark { ThemeRequest::Dark } else { ThemeRequest::Light };
@@ -220,6 +546,31 @@ impl EventContext {
         self.theme_request = ThemeRequest::Toggle;
         self.should_redraw = true;
     }
+
+    /// Request an animation frame.
+    ///
+    /// A `TimerEvent::Frame` is broadcast once the next frame is due. Request
+    /// again from the frame handler to keep animating.
+    pub fn request_animation_frame(&mut self) {
+        self.timers.request_frame();
+    }
+
+    /// Deliver `TimerEvent::Fired` for `widget_id` once after `delay`.
+    pub fn set_timeout(&mut self, widget_id: WidgetId, delay: Duration) -> TimerId {
+        self.timers.schedule(widget_id, delay, None, Instant::now())
+    }
+
+    /// Deliver `TimerEvent::Fired` for `widget_id` every `period` until cancelled.
+    ///
+    /// Periods shorter than a frame fire once per frame.
+    pub fn set_interval(&mut self, widget_id: WidgetId, period: Duration) -> TimerId {
+        self.timers.schedule(widget_id, period, Some(period), Instant::now())
+    }
+
+    /// Cancel a timeout or interval.
+    pub fn cancel_timer(&mut self, id: TimerId) {
+        self.timers.cancel(id);
+    }
 }
 
 impl Default for EventContext {
@@ -236,6 +587,10 @@ pub struct WidgetBase {
     pub classes: ClassList,
     pub bounds: Rect,
     pub state: WidgetState,
+    /// Tab order position for focusable widgets
+    pub tab_index: i32,
+    /// Focus grouping for container widgets
+    pub focus_scope: FocusScope,
 }
 
 impl WidgetBase {
@@ -246,6 +601,8 @@ impl WidgetBase {
             classes: ClassList::new(),
             bounds: Rect::ZERO,
             state: WidgetState::default(),
+            tab_index: 0,
+            focus_scope: FocusScope::None,
         }
     }
 
@@ -258,6 +615,28 @@ impl WidgetBase {
         self.element_id = Some(id.to_string());
         self
     }
+
+    /// Tab index for focusable widgets, `None` while disabled.
+    pub fn focusable_tab_index(&self) -> Option<i32> {
+        (!self.state.disabled).then_some(self.tab_index)
+    }
+
+    /// Update `state.focused` from a focus event addressed to this widget.
+    ///
+    /// Returns `true` if the event was for this widget.
+    pub fn handle_focus_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Focus(FocusEvent::FocusIn { widget_id }) if *widget_id == self.id => {
+                self.state.focused = true;
+                true
+            }
+            Event::Focus(FocusEvent::FocusOut { widget_id }) if *widget_id == self.id => {
+                self.state.focused = false;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Default for WidgetBase {
@@ -265,3 +644,67 @@ impl Default for WidgetBase {
         Self::new()
     }
 }
+
+/// Child widgets kept in a fixed number of optional slots, like the start,
+/// center and end sections of a bar.
+///
+/// The widgets are stored together in slot order, so they can be returned
+/// from [`Widget::children`] and reached by event routing and focus.
+pub(crate) struct Slots<const N: usize> {
+    widgets: Vec<Box<dyn Widget>>,
+    /// Whether each slot holds a widget
+    filled: [bool; N],
+}
+
+impl<const N: usize> Slots<N> {
+    pub fn new() -> Self {
+        Self {
+            widgets: Vec::new(),
+            filled: [false; N],
+        }
+    }
+
+    /// Put `widget` in `slot`, replacing the widget already there.
+    pub fn set(&mut self, slot: usize, widget: Box<dyn Widget>) {
+        let index = self.index(slot);
+        if self.filled[slot] {
+            self.widgets[index] = widget;
+        } else {
+            self.widgets.insert(index, widget);
+            self.filled[slot] = true;
+        }
+    }
+
+    /// The widget in `slot`.
+    pub fn get(&self, slot: usize) -> Option<&dyn Widget> {
+        self.filled[slot].then(|| self.widgets[self.index(slot)].as_ref())
+    }
+
+    /// The widget in `slot`, mutably.
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut Box<dyn Widget>> {
+        let index = self.index(slot);
+        if self.filled[slot] { self.widgets.get_mut(index) } else { None }
+    }
+
+    /// The widgets in slots `from` up to `to`, in slot order.
+    pub fn range(&self, from: usize, to: usize) -> &[Box<dyn Widget>] {
+        &self.widgets[self.index(from)..self.index(to)]
+    }
+
+    /// The widgets in slots `from` up to `to`, mutably.
+    pub fn range_mut(&mut self, from: usize, to: usize) -> &mut [Box<dyn Widget>] {
+        let (start, end) = (self.index(from), self.index(to));
+        &mut self.widgets[start..end]
+    }
+
+    /// Where the widget in `slot` is, or would go, in `widgets`.
+    fn index(&self, slot: usize) -> usize {
+        self.filled[..slot].iter().filter(|filled| **filled).count()
+    }
+}
+
+impl<const N: usize> Default for Slots<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
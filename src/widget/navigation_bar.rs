@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{BorderRadius, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -110,6 +110,21 @@ impl Widget for NavigationBar {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = &ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(6.0));
+        let active_color = style.color_or(theme.colors.foreground);
+        let icon_size = style.font_size_or(18.0);
+
+        // Background
+        let background = style.background_or(Color::TRANSPARENT);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, radius);
+        }
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
+
         let button_size = 32.0;
         let y_center = rect.y() + rect.height() / 2.0;
         let button_y = y_center - button_size / 2.0;
@@ -127,17 +142,17 @@ impl Widget for NavigationBar {
                 painter.fill_rounded_rect(
                     Rect::new(x + 2.0, button_y, button_size, button_size),
                     theme.colors.surface_hover,
-                    BorderRadius::all(6.0),
+                    radius,
                 );
             }
 
             let color = if enabled {
-                if is_hovered { theme.colors.foreground } else { theme.colors.muted_foreground }
+                if is_hovered { active_color } else { theme.colors.muted_foreground }
             } else {
                 theme.colors.muted_foreground.with_alpha(0.4)
             };
 
-            painter.draw_text(button.icon(), Point::new(x + 10.0, y_center - 9.0), color, 18.0);
+            painter.draw_text(button.icon(), Point::new(x + 10.0, y_center - 9.0), color, icon_size);
             x += 36.0;
         }
 
@@ -147,11 +162,11 @@ impl Widget for NavigationBar {
                 painter.fill_rounded_rect(
                     Rect::new(x + 2.0, button_y, button_size, button_size),
                     theme.colors.surface_hover,
-                    BorderRadius::all(6.0),
+                    radius,
                 );
             }
-            let color = if is_hovered { theme.colors.foreground } else { theme.colors.muted_foreground };
-            painter.draw_text(NavButton::Home.icon(), Point::new(x + 10.0, y_center - 9.0), color, 18.0);
+            let color = if is_hovered { active_color } else { theme.colors.muted_foreground };
+            painter.draw_text(NavButton::Home.icon(), Point::new(x + 10.0, y_center - 9.0), color, icon_size);
         }
     }
 
@@ -176,30 +191,28 @@ impl Widget for NavigationBar {
                         ctx.request_redraw();
                     }
                 }
-                MouseEventKind::Down => {
-                    if mouse.button == Some(MouseButton::Left) {
-                        if let Some(button) = self.hovered_button {
-                            match button {
-                                NavButton::Back if self.can_go_back => {
-                                    if let Some(ref h) = self.on_back { h(); }
-                                }
-                                NavButton::Forward if self.can_go_forward => {
-                                    if let Some(ref h) = self.on_forward { h(); }
-                                }
-                                NavButton::Reload => {
-                                    if let Some(ref h) = self.on_reload { h(); }
-                                }
-                                NavButton::Stop => {
-                                    if let Some(ref h) = self.on_stop { h(); }
-                                }
-                                NavButton::Home => {
-                                    if let Some(ref h) = self.on_home { h(); }
-                                }
-                                _ => {}
+                MouseEventKind::Down if mouse.button == Some(MouseButton::Left) => {
+                    if let Some(button) = self.hovered_button {
+                        match button {
+                            NavButton::Back if self.can_go_back => {
+                                if let Some(ref h) = self.on_back { h(); }
+                            }
+                            NavButton::Forward if self.can_go_forward => {
+                                if let Some(ref h) = self.on_forward { h(); }
+                            }
+                            NavButton::Reload => {
+                                if let Some(ref h) = self.on_reload { h(); }
                             }
-                            ctx.request_redraw();
-                            return EventResult::Handled;
+                            NavButton::Stop => {
+                                if let Some(ref h) = self.on_stop { h(); }
+                            }
+                            NavButton::Home => {
+                                if let Some(ref h) = self.on_home { h(); }
+                            }
+                            _ => {}
                         }
+                        ctx.request_redraw();
+                        return EventResult::Handled;
                     }
                 }
                 _ => {}
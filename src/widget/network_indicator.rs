@@ -5,9 +5,9 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, ICON_VIEWBOX};
 
 /// Network connection type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -148,13 +148,14 @@ impl NetworkIndicator {
         self.is_open
     }
 
+    /// SVG path data of the icon for the connection type, in `ICON_VIEWBOX`.
     pub fn get_icon(&self) -> &'static str {
         match self.status.connection_type {
-            NetworkType::Wifi => "📶",
-            NetworkType::Ethernet => "🔌",
-            NetworkType::Cellular => "📱",
-            NetworkType::Vpn => "🔒",
-            NetworkType::Disconnected => "❌",
+            NetworkType::Wifi => "M12.01 21.49L23.64 7c-.45-.34-4.93-4-11.64-4C5.28 3 .81 6.66.36 7l11.63 14.49.01.01.01-.01z",
+            NetworkType::Ethernet => "M8 2h2v4h4V2h2v4h2v6a6 6 0 0 1-5 5.9V22h-2v-4.1A6 6 0 0 1 6 12V6h2z",
+            NetworkType::Cellular => "M2 22h20V2z",
+            NetworkType::Vpn => "M18 8h-1V6c0-2.76-2.24-5-5-5S7 3.24 7 6v2H6c-1.1 0-2 .9-2 2v10c0 1.1.9 2 2 2h12c1.1 0 2-.9 2-2V10c0-1.1-.9-2-2-2zm-6 9c-1.1 0-2-.9-2-2s.9-2 2-2 2 .9 2 2-.9 2-2 2zm3.1-9H8.9V6c0-1.71 1.39-3.1 3.1-3.1 1.71 0 3.1 1.39 3.1 3.1v2z",
+            NetworkType::Disconnected => "M19 6.41L17.59 5 12 10.59 6.41 5 5 6.41 10.59 12 5 17.59 6.41 19 12 13.41 17.59 19 19 17.59 13.41 12z",
         }
     }
 
@@ -206,18 +207,25 @@ impl Widget for NetworkIndicator {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
-        // Background on hover
-        if self.is_open {
-            painter.fill_rounded_rect(rect, Color::rgba(1.0, 1.0, 1.0, 0.1), BorderRadius::all(4.0));
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let padding = style.padding_or(EdgeInsets::all(2.0));
+
+        // Background, or a highlight while open
+        let background = if self.is_open { theme.colors.surface_hover } else { Color::TRANSPARENT };
+        let background = style.background_or(background);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, style.border_radius_or(BorderRadius::all(4.0)));
         }
 
         // Icon
-        painter.draw_text(
+        painter.draw_icon(
             self.get_icon(),
-            Point::new(rect.x() + 2.0, rect.y() + self.icon_size - 4.0),
-            Color::WHITE,
-            self.icon_size - 4.0,
+            Point::new(rect.x() + padding.left, rect.y() + padding.top),
+            self.icon_size - padding.horizontal(),
+            style.color_or(theme.colors.foreground),
+            ICON_VIEWBOX,
         );
     }
 
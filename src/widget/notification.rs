@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -207,22 +207,29 @@ impl Widget for Notification {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(theme.radii.lg * theme.typography.base_size);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        let padding = style.padding_or(EdgeInsets::all(16.0));
+        let font_size = style.font_size_or(14.0);
 
         // Urgency-based accent color
-        let accent = match self.urgency {
+        let accent = style.background_or(match self.urgency {
             NotificationUrgency::Low => theme.colors.muted,
             NotificationUrgency::Normal => theme.colors.card,
             NotificationUrgency::Critical => theme.colors.destructive.with_alpha(0.1),
-        };
+        });
 
         // Shadow
-        let shadow_rect = Rect::new(rect.x() + 2.0, rect.y() + 4.0, rect.width(), rect.height());
-        painter.fill_rounded_rect(shadow_rect, Color::BLACK.with_alpha(0.2), radius);
+        let shadows = style.box_shadow_or(std::slice::from_ref(&theme.shadows.lg));
+        painter.draw_shadows(rect, radius, &shadows);
 
         // Background
         painter.fill_rounded_rect(rect, accent, radius);
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        painter.draw_inset_shadows(rect, radius, &shadows);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Urgency indicator bar
         if self.urgency == NotificationUrgency::Critical {
@@ -230,7 +237,7 @@ impl Widget for Notification {
             painter.fill_rect(bar_rect, theme.colors.destructive);
         }
 
-        let mut content_x = rect.x() + 16.0;
+        let mut content_x = rect.x() + padding.left;
 
         // Icon
         if let Some(ref icon) = self.icon {
@@ -258,8 +265,8 @@ impl Widget for Notification {
         painter.draw_text(
             &self.title,
             Point::new(content_x, rect.y() + title_y),
-            theme.colors.foreground,
-            14.0,
+            style.color_or(theme.colors.foreground),
+            font_size,
         );
 
         // Body
@@ -268,7 +275,7 @@ impl Widget for Notification {
                 &self.body,
                 Point::new(content_x, rect.y() + title_y + 20.0),
                 theme.colors.muted_foreground,
-                13.0,
+                font_size - 1.0,
             );
         }
 
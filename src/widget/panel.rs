@@ -6,7 +6,8 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{Color, Rect, Size};
+use crate::geometry::{BorderRadius, Color, Rect, Size};
+use crate::theme::BoxShadow;
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -35,7 +36,8 @@ pub struct Panel {
     base: WidgetBase,
     position: PanelPosition,
     thickness: f32,
-    background: Color,
+    /// Custom background; the theme surface by default
+    background: Option<Color>,
     blur: bool,
     border_color: Option<Color>,
     autohide: PanelAutohide,
@@ -52,7 +54,7 @@ impl Panel {
             base: WidgetBase::new().with_class("panel"),
             position: PanelPosition::Bottom,
             thickness: 48.0,
-            background: Color::rgba(0.1, 0.1, 0.1, 0.9),
+            background: None,
             blur: true,
             border_color: None,
             autohide: PanelAutohide::Never,
@@ -75,7 +77,7 @@ impl Panel {
     }
 
     pub fn background(mut self, color: Color) -> Self {
-        self.background = color;
+        self.background = Some(color);
         self
     }
 
@@ -186,37 +188,43 @@ impl Widget for Panel {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         if self.is_hidden {
             return;
         }
 
-        // Draw shadow
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::ZERO);
+
+        // Draw shadow, cast away from the screen edge
+        let mut shadows = Vec::new();
         if self.shadow {
-            let shadow_offset = match self.position {
-                PanelPosition::Top => 3.0,
-                PanelPosition::Bottom => -3.0,
-                PanelPosition::Left => 3.0,
-                PanelPosition::Right => -3.0,
-            };
-            let shadow_rect = if self.is_horizontal() {
-                Rect::new(rect.x(), rect.y() + shadow_offset, rect.width(), rect.height())
-            } else {
-                Rect::new(rect.x() + shadow_offset, rect.y(), rect.width(), rect.height())
+            let (dx, dy) = match self.position {
+                PanelPosition::Top => (0.0, 3.0),
+                PanelPosition::Bottom => (0.0, -3.0),
+                PanelPosition::Left => (3.0, 0.0),
+                PanelPosition::Right => (-3.0, 0.0),
             };
-            painter.fill_rect(shadow_rect, Color::rgba(0.0, 0.0, 0.0, 0.3));
+            shadows.push(BoxShadow::new(dx, dy, 6.0, 0.0, Color::rgba(0.0, 0.0, 0.0, 0.3)));
         }
+        let shadows = style.box_shadow_or(&shadows);
+        painter.draw_shadows(rect, radius, &shadows);
 
         // Draw background
-        painter.fill_rect(rect, self.background);
-
-        // Draw border
-        if let Some(border) = self.border_color {
+        let background = style.background_or(self.background.unwrap_or(theme.colors.surface));
+        painter.fill_rounded_rect(rect, background, radius);
+        painter.draw_inset_shadows(rect, radius, &shadows);
+
+        // Draw border on the inner edge
+        let border_width = style.border_width_or(if self.border_color.is_some() { 1.0 } else { 0.0 });
+        if border_width > 0.0 {
+            let border = style.border_color_or(self.border_color.unwrap_or(theme.colors.border));
             let border_rect = match self.position {
-                PanelPosition::Top => Rect::new(rect.x(), rect.max_y() - 1.0, rect.width(), 1.0),
-                PanelPosition::Bottom => Rect::new(rect.x(), rect.y(), rect.width(), 1.0),
-                PanelPosition::Left => Rect::new(rect.max_x() - 1.0, rect.y(), 1.0, rect.height()),
-                PanelPosition::Right => Rect::new(rect.x(), rect.y(), 1.0, rect.height()),
+                PanelPosition::Top => Rect::new(rect.x(), rect.max_y() - border_width, rect.width(), border_width),
+                PanelPosition::Bottom => Rect::new(rect.x(), rect.y(), rect.width(), border_width),
+                PanelPosition::Left => Rect::new(rect.max_x() - border_width, rect.y(), border_width, rect.height()),
+                PanelPosition::Right => Rect::new(rect.x(), rect.y(), border_width, rect.height()),
             };
             painter.fill_rect(border_rect, border);
         }
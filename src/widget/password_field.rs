@@ -1,16 +1,22 @@
 //! Password field widget with masked input.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
+use crate::clipboard::{single_line, EditCommand};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, KeyEventKind, Key};
-use crate::geometry::{BorderRadius, Point, Rect, Size};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, SpanStyle, TextLayout};
+
+use super::text_edit::{selection, Editor};
+
+use std::ops::Range;
 
 /// A password input field with masked characters.
 ///
 /// The password field displays dots or asterisks instead of the actual
-/// characters for security.
+/// characters for security. Text can be pasted in, but copying or cutting
+/// it to the clipboard is refused.
 ///
 /// # Example
 ///
@@ -34,10 +40,21 @@ pub struct PasswordField {
     on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
     on_submit: Option<Box<dyn Fn(&str) + Send + Sync>>,
     cursor_position: usize,
+    /// Other end of the selection, the cursor being one end
+    selection_anchor: Option<usize>,
     cursor_visible: bool,
+    /// Left padding and font resolved at layout, for mapping clicks to the cursor
+    padding: f32,
+    font_size: f32,
+    font: SpanStyle,
 }
 
 impl PasswordField {
+    /// Padding used when CSS doesn't set one.
+    const PADDING: EdgeInsets = EdgeInsets::symmetric(0.0, 12.0);
+    /// Font size used when CSS doesn't set one.
+    const FONT_SIZE: f32 = 14.0;
+
     /// Create a new password field.
     pub fn new() -> Self {
         Self {
@@ -50,7 +67,11 @@ impl PasswordField {
             on_change: None,
             on_submit: None,
             cursor_position: 0,
+            selection_anchor: None,
             cursor_visible: true,
+            padding: Self::PADDING.left,
+            font_size: Self::FONT_SIZE,
+            font: SpanStyle::default(),
         }
     }
 
@@ -96,6 +117,12 @@ impl PasswordField {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Set the element ID.
     pub fn id(mut self, id: &str) -> Self {
         self.base.element_id = Some(id.to_string());
@@ -111,12 +138,14 @@ impl PasswordField {
     pub fn set_value(&mut self, value: impl Into<String>) {
         self.value = value.into();
         self.cursor_position = self.value.len();
+        self.selection_anchor = None;
     }
 
     /// Clear the password field.
     pub fn clear(&mut self) {
         self.value.clear();
         self.cursor_position = 0;
+        self.selection_anchor = None;
     }
 
     /// Toggle password visibility.
@@ -129,12 +158,66 @@ impl PasswordField {
         if self.is_revealed {
             self.value.clone()
         } else {
-            self.mask_char.to_string().repeat(self.value.len())
+            self.mask_char.to_string().repeat(self.value.chars().count())
+        }
+    }
+
+    /// Byte offset in the displayed text of byte offset `offset` in the value.
+    fn display_offset(&self, offset: usize) -> usize {
+        if self.is_revealed {
+            offset
+        } else {
+            self.value[..offset].chars().count() * self.mask_char.len_utf8()
+        }
+    }
+
+    /// Byte offset in the value of byte offset `offset` in the displayed text.
+    fn value_offset(&self, offset: usize) -> usize {
+        if self.is_revealed {
+            offset
+        } else {
+            let index = offset / self.mask_char.len_utf8();
+            self.value.char_indices().nth(index).map_or(self.value.len(), |(i, _)| i)
+        }
+    }
+
+    /// Byte range of the selected text, if any.
+    fn selection(&self) -> Option<Range<usize>> {
+        selection(self.cursor_position, self.selection_anchor)
+    }
+
+    /// The value, cursor and selection, for editing.
+    fn editor(&mut self) -> Editor<'_> {
+        Editor {
+            text: &mut self.value,
+            cursor: &mut self.cursor_position,
+            anchor: &mut self.selection_anchor,
+        }
+    }
+
+    /// Run a clipboard shortcut. Returns whether anything changed.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) -> bool {
+        // Never put the password on the clipboard
+        if matches!(command, EditCommand::Copy | EditCommand::Cut) {
+            return false;
+        }
+        let changed = self.editor().run(command, ctx, single_line);
+        if changed {
+            if let Some(handler) = &self.on_change {
+                handler(&self.value);
+            }
         }
+        changed || command == EditCommand::SelectAll
     }
 
     fn handle_key_input(&mut self, key: &Key, text: Option<&str>) -> bool {
         match key {
+            Key::Backspace | Key::Delete if self.editor().delete_selection() => {
+                if let Some(handler) = &self.on_change {
+                    handler(&self.value);
+                }
+                return true;
+            }
             Key::Backspace => {
                 if self.cursor_position > 0 {
                     self.cursor_position -= 1;
@@ -155,22 +238,26 @@ impl PasswordField {
                 }
             }
             Key::Left => {
+                self.selection_anchor = None;
                 if self.cursor_position > 0 {
                     self.cursor_position -= 1;
                     return true;
                 }
             }
             Key::Right => {
+                self.selection_anchor = None;
                 if self.cursor_position < self.value.len() {
                     self.cursor_position += 1;
                     return true;
                 }
             }
             Key::Home => {
+                self.selection_anchor = None;
                 self.cursor_position = 0;
                 return true;
             }
             Key::End => {
+                self.selection_anchor = None;
                 self.cursor_position = self.value.len();
                 return true;
             }
@@ -180,11 +267,14 @@ impl PasswordField {
                 }
                 return true;
             }
+            // Tab moves focus
+            Key::Tab => return false,
             _ => {}
         }
 
         // Handle text input
         if let Some(text) = text {
+            self.editor().delete_selection();
             for c in text.chars() {
                 if !c.is_control() {
                     self.value.insert(self.cursor_position, c);
@@ -233,6 +323,11 @@ impl Widget for PasswordField {
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.padding = style.padding_or(Self::PADDING).left;
+        self.font_size = style.font_size_or(Self::FONT_SIZE);
+        self.font = style.font();
+
         let intrinsic = self.intrinsic_size(ctx);
         let size = Size::new(
             constraints.max_width.min(intrinsic.width.max(constraints.min_width)),
@@ -244,29 +339,33 @@ impl Widget for PasswordField {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        let bg_color = if self.base.state.disabled {
+        let bg_color = style.background_or(if self.base.state.disabled {
             theme.colors.muted
         } else {
             theme.colors.background
-        };
-        let radius = BorderRadius::all(theme.radii.md * theme.typography.base_size);
+        });
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.md * theme.typography.base_size));
         painter.fill_rounded_rect(rect, bg_color, radius);
 
         // Border
-        let border_color = if self.base.state.focused {
+        let border_color = style.border_color_or(if self.base.state.focused {
             theme.colors.ring
         } else if self.base.state.hovered {
             theme.colors.ring.with_alpha(0.5)
         } else {
             theme.colors.border
-        };
-        painter.stroke_rect(rect, border_color, 1.0);
+        });
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, border_color, border_width);
+        }
 
         // Text or placeholder
-        let font_size = 14.0;
-        let padding = 12.0;
+        let font_size = style.font_size_or(Self::FONT_SIZE);
+        let padding = style.padding_or(Self::PADDING).left;
         let text_x = rect.x() + padding;
         let text_y = rect.y() + (rect.height() + font_size * 0.8) / 2.0;
 
@@ -282,23 +381,36 @@ impl Widget for PasswordField {
                 font_size,
             );
         } else {
-            // Draw masked/revealed text
             let display = self.display_text();
+            let layout = TextLayout::styled(&display, font_size, &style.font());
+
+            // Selection highlight
+            if let Some(range) = self.selection().filter(|_| self.base.state.focused) {
+                let range = self.display_offset(range.start)..self.display_offset(range.end);
+                for selection in layout.selection_rects(range) {
+                    painter.fill_rect(
+                        Rect::new(
+                            text_x + selection.x(),
+                            rect.y() + (rect.height() - font_size) / 2.0,
+                            selection.width(),
+                            font_size,
+                        ),
+                        theme.colors.primary.with_alpha(0.3),
+                    );
+                }
+            }
+
+            // Draw masked/revealed text
             painter.draw_text(
                 &display,
                 Point::new(text_x, text_y),
-                theme.colors.foreground,
+                style.color_or(theme.colors.foreground),
                 font_size,
             );
 
             // Draw cursor if focused
             if self.base.state.focused && self.cursor_visible {
-                let cursor_text = if self.is_revealed {
-                    &self.value[..self.cursor_position]
-                } else {
-                    &self.mask_char.to_string().repeat(self.cursor_position)
-                };
-                let cursor_x = text_x + cursor_text.len() as f32 * font_size * 0.6;
+                let cursor_x = text_x + layout.caret_x(self.display_offset(self.cursor_position));
                 painter.fill_rect(
                     Rect::new(cursor_x, rect.y() + 8.0, 2.0, rect.height() - 16.0),
                     theme.colors.foreground,
@@ -338,6 +450,11 @@ impl Widget for PasswordField {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 let in_bounds = self.bounds().contains(mouse.position);
@@ -359,16 +476,20 @@ impl Widget for PasswordField {
                         }
                     }
                     MouseEventKind::Down if in_bounds => {
-                        self.base.state.focused = true;
                         ctx.request_focus(self.base.id);
+                        self.selection_anchor = None;
                         ctx.request_redraw();
 
                         // Check if toggle button was clicked
-                        if self.show_toggle {
-                            let toggle_x = self.base.bounds.x() + self.base.bounds.width() - 36.0;
-                            if mouse.position.x >= toggle_x {
-                                self.toggle_visibility();
-                            }
+                        let toggle_x = self.base.bounds.x() + self.base.bounds.width() - 36.0;
+                        if self.show_toggle && mouse.position.x >= toggle_x {
+                            self.toggle_visibility();
+                        } else {
+                            // Place the cursor at the click
+                            let layout = TextLayout::styled(&self.display_text(), self.font_size, &self.font);
+                            let relative_x = mouse.position.x - self.base.bounds.x() - self.padding;
+                            let offset = layout.hit_test(Point::new(relative_x, self.font_size / 2.0));
+                            self.cursor_position = self.value_offset(offset);
                         }
 
                         return EventResult::Handled;
@@ -377,6 +498,12 @@ impl Widget for PasswordField {
                 }
             }
             Event::Key(key) if self.base.state.focused => {
+                if let Some(command) = EditCommand::from_key(key) {
+                    if self.edit(command, ctx) {
+                        ctx.request_redraw();
+                    }
+                    return EventResult::Handled;
+                }
                 if key.kind == KeyEventKind::Down
                     && self.handle_key_input(&key.key, key.text.as_deref()) {
                         ctx.request_redraw();
@@ -395,4 +522,8 @@ impl Widget for PasswordField {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
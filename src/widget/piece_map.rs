@@ -304,9 +304,11 @@ impl Widget for PieceMap {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        painter.fill_rounded_rect(rect, theme.colors.surface, BorderRadius::all(4.0));
+        let radius = style.border_radius_or(BorderRadius::all(4.0));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.surface), radius);
 
         if self.pieces.is_empty() {
             // Empty state
@@ -332,7 +334,10 @@ impl Widget for PieceMap {
         }
 
         // Border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
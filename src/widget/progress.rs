@@ -2,11 +2,14 @@
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult};
+use crate::event::{Event, EventResult, TimerEvent};
 use crate::geometry::{BorderRadius, Color, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
+/// Duration of one animation cycle in seconds.
+const ANIMATION_CYCLE: f32 = 1.5;
+
 /// Progress bar variant.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProgressVariant {
@@ -69,6 +72,8 @@ pub struct Progress {
     size: ProgressSize,
     color: Option<Color>,
     show_label: bool,
+    /// Animation phase (0.0 to 1.0) for the striped and indeterminate variants
+    phase: f32,
 }
 
 impl Progress {
@@ -81,6 +86,7 @@ impl Progress {
             size: ProgressSize::default(),
             color: None,
             show_label: false,
+            phase: 0.0,
         }
     }
 
@@ -179,8 +185,9 @@ impl Widget for Progress {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let bar_height = self.size.height();
-        let radius = BorderRadius::all(bar_height / 2.0);
+        let radius = style.border_radius_or(BorderRadius::all(bar_height / 2.0));
 
         let bar_rect = if self.show_label {
             Rect::new(rect.x(), rect.y(), rect.width(), bar_height)
@@ -189,10 +196,10 @@ impl Widget for Progress {
         };
 
         // Track background
-        painter.fill_rounded_rect(bar_rect, theme.colors.muted, radius);
+        painter.fill_rounded_rect(bar_rect, style.background_or(theme.colors.muted), radius);
 
-        // Fill
-        let fill_color = self.color.unwrap_or(theme.colors.primary);
+        // Fill (CSS `color` sets the fill, like an accent color)
+        let fill_color = style.color_or(self.color.unwrap_or(theme.colors.primary));
 
         match self.variant {
             ProgressVariant::Linear | ProgressVariant::Striped => {
@@ -200,27 +207,31 @@ impl Widget for Progress {
                 let fill_rect = Rect::new(bar_rect.x(), bar_rect.y(), fill_width, bar_rect.height());
                 painter.fill_rounded_rect(fill_rect, fill_color, radius);
 
-                // Striped pattern (simplified - would need animation in real implementation)
+                // Striped pattern
                 if self.variant == ProgressVariant::Striped && fill_width > 0.0 {
                     let stripe_color = Color::WHITE.with_alpha(0.2);
                     let stripe_width: f32 = 10.0;
-                    let mut x = bar_rect.x();
-                    while x < bar_rect.x() + fill_width {
-                        let stripe_rect = Rect::new(
-                            x,
-                            bar_rect.y(),
-                            (stripe_width / 2.0).min(bar_rect.x() + fill_width - x),
-                            bar_rect.height(),
-                        );
-                        painter.fill_rect(stripe_rect, stripe_color);
+                    let end = bar_rect.x() + fill_width;
+                    // Stripes scroll one stripe width per cycle
+                    let mut x = bar_rect.x() - stripe_width + stripe_width * self.phase;
+                    while x < end {
+                        let left = x.max(bar_rect.x());
+                        let right = (x + stripe_width / 2.0).min(end);
+                        if right > left {
+                            painter.fill_rect(
+                                Rect::new(left, bar_rect.y(), right - left, bar_rect.height()),
+                                stripe_color,
+                            );
+                        }
                         x += stripe_width;
                     }
                 }
             }
             ProgressVariant::Indeterminate => {
-                // Animated indeterminate bar (simplified - static position)
+                // Segment sweeps back and forth across the track
                 let segment_width = bar_rect.width() * 0.3;
-                let segment_x = bar_rect.x() + (bar_rect.width() - segment_width) * 0.3; // Would animate
+                let position = 1.0 - (2.0 * self.phase - 1.0).abs();
+                let segment_x = bar_rect.x() + (bar_rect.width() - segment_width) * position;
                 let segment_rect = Rect::new(segment_x, bar_rect.y(), segment_width, bar_rect.height());
                 painter.fill_rounded_rect(segment_rect, fill_color, radius);
             }
@@ -234,12 +245,24 @@ impl Widget for Progress {
                 &label,
                 crate::geometry::Point::new(rect.x() + rect.width() - 30.0, label_y),
                 theme.colors.foreground,
-                12.0,
+                style.font_size_or(12.0),
             );
         }
     }
 
-    fn handle_event(&mut self, _event: &Event, _ctx: &mut EventContext) -> EventResult {
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        let animated = match self.variant {
+            ProgressVariant::Indeterminate => true,
+            ProgressVariant::Striped => self.value > 0.0,
+            ProgressVariant::Linear => false,
+        };
+        if let Event::Timer(TimerEvent::Frame { elapsed }) = event {
+            if animated {
+                self.phase = (self.phase + elapsed.as_secs_f32() / ANIMATION_CYCLE).fract();
+                ctx.request_animation_frame();
+                ctx.request_redraw();
+            }
+        }
         EventResult::Ignored
     }
 
@@ -249,13 +249,15 @@ impl Widget for ScrollView {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::ZERO);
 
         // Background
-        painter.fill_rect(rect, theme.colors.background);
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.background), radius);
 
-        // Paint content (with clipping conceptually - real clipping needs render layer support)
+        // Paint content, clipped to the viewport
         if let Some(content) = &self.content {
-            let _viewport = Rect::new(
+            let viewport = Rect::new(
                 rect.x(),
                 rect.y(),
                 self.viewport_width(),
@@ -270,7 +272,9 @@ impl Widget for ScrollView {
                 self.content_size.height,
             );
 
-            content.paint(painter, content_rect, ctx);
+            painter.push_rounded_clip(viewport, radius);
+            ctx.paint_child(content.as_ref(), painter, content_rect);
+            painter.pop_clip();
         }
 
         // Vertical scrollbar
@@ -289,7 +293,10 @@ impl Widget for ScrollView {
         }
 
         // Border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rounded_rect(rect, style.border_color_or(theme.colors.border), border_width, radius);
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
@@ -320,6 +327,7 @@ impl Widget for ScrollView {
                             let thumb = self.vertical_thumb_rect();
                             if thumb.contains(mouse.position) {
                                 self.dragging_scrollbar = true;
+                                ctx.capture_pointer(self.base.id);
                                 self.drag_start_y = mouse.position.y;
                                 self.drag_start_scroll = self.scroll_y;
                             } else {
@@ -343,11 +351,6 @@ impl Widget for ScrollView {
                     // Wheel scroll would go here
                     _ => {}
                 }
-
-                // Forward to content
-                if let Some(content) = &mut self.content {
-                    return content.handle_event(event, ctx);
-                }
             }
             _ => {
                 if let Some(content) = &mut self.content {
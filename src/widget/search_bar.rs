@@ -3,11 +3,14 @@
 //! A search input with icon, clear button, and suggestion support.
 
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
+use crate::clipboard::{single_line, EditCommand};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::event::{Event, EventResult, Key, KeyEventKind, MouseButton, MouseEventKind};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, TextLayout};
+
+use super::text_edit::Editor;
 
 /// Search suggestion item
 #[derive(Debug, Clone)]
@@ -53,11 +56,13 @@ pub struct SearchBar {
     show_clear: bool,
     width: f32,
     height: f32,
-    background: Color,
-    text_color: Color,
-    placeholder_color: Color,
+    /// Custom colors; from the theme by default
+    background: Option<Color>,
+    text_color: Option<Color>,
     border_radius: f32,
     is_focused: bool,
+    /// Start of the selection, which always runs to the end of the query
+    selection_anchor: Option<usize>,
     #[allow(clippy::type_complexity)]
     on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
     #[allow(clippy::type_complexity)]
@@ -79,11 +84,11 @@ impl SearchBar {
             show_clear: true,
             width: 300.0,
             height: 40.0,
-            background: Color::rgba(1.0, 1.0, 1.0, 0.1),
-            text_color: Color::WHITE,
-            placeholder_color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+            background: None,
+            text_color: None,
             border_radius: 8.0,
             is_focused: false,
+            selection_anchor: None,
             on_change: None,
             on_submit: None,
             on_suggestion_select: None,
@@ -111,12 +116,12 @@ impl SearchBar {
     }
 
     pub fn background(mut self, color: Color) -> Self {
-        self.background = color;
+        self.background = Some(color);
         self
     }
 
     pub fn text_color(mut self, color: Color) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
@@ -165,6 +170,7 @@ impl SearchBar {
 
     pub fn set_query(&mut self, query: &str) {
         self.query = query.to_string();
+        self.selection_anchor = None;
         if let Some(ref cb) = self.on_change {
             cb(&self.query);
         }
@@ -172,6 +178,7 @@ impl SearchBar {
 
     pub fn clear(&mut self) {
         self.query.clear();
+        self.selection_anchor = None;
         if let Some(ref cb) = self.on_change {
             cb("");
         }
@@ -185,6 +192,7 @@ impl SearchBar {
     pub fn blur(&mut self) {
         self.is_focused = false;
         self.suggestions_visible = false;
+        self.selection_anchor = None;
     }
 
     pub fn set_suggestions(&mut self, suggestions: Vec<SearchSuggestion>) {
@@ -202,6 +210,31 @@ impl SearchBar {
         self.base.element_id = Some(id.to_string());
         self
     }
+
+    /// Run `f` on the query and selection. The cursor stays at the end of
+    /// the query, so the selection always runs to the end.
+    fn with_editor<R>(&mut self, f: impl FnOnce(&mut Editor) -> R) -> R {
+        let mut cursor = self.query.len();
+        f(&mut Editor {
+            text: &mut self.query,
+            cursor: &mut cursor,
+            anchor: &mut self.selection_anchor,
+        })
+    }
+
+    /// Notify the change handler after an edit.
+    fn edited(&mut self) {
+        if let Some(ref cb) = self.on_change {
+            cb(&self.query);
+        }
+    }
+
+    /// Run a clipboard shortcut.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) {
+        if self.with_editor(|editor| editor.run(command, ctx, single_line)) {
+            self.edited();
+        }
+    }
 }
 
 impl Default for SearchBar {
@@ -245,18 +278,32 @@ impl Widget for SearchBar {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let text_color = style.color_or(self.text_color.unwrap_or(theme.colors.foreground));
+        let font_size = style.font_size_or(14.0);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 12.0));
+        let radius = style.border_radius_or(BorderRadius::all(self.border_radius));
+
         // Search box background
         let search_rect = Rect::new(rect.x(), rect.y(), rect.width(), self.height);
-        painter.fill_rounded_rect(search_rect, self.background, BorderRadius::all(self.border_radius));
+        let background = style.background_or(self.background.unwrap_or(theme.colors.muted));
+        painter.fill_rounded_rect(search_rect, background, radius);
 
-        // Focus ring
+        // Border, or the focus ring
         if self.is_focused {
-            painter.stroke_rect(search_rect, Color::rgba(0.3, 0.5, 1.0, 0.5), 2.0);
+            painter.stroke_rect(search_rect, theme.colors.ring, 2.0);
+        } else {
+            let border_width = style.border_width_or(0.0);
+            if border_width > 0.0 {
+                painter.stroke_rect(search_rect, style.border_color_or(theme.colors.input), border_width);
+            }
         }
 
         // Icon
-        painter.draw_text(&self.icon, Point::new(rect.x() + 12.0, rect.y() + self.height / 2.0 + 4.0), self.text_color, 16.0);
+        let icon_point = Point::new(rect.x() + padding.left, rect.y() + self.height / 2.0 + 4.0);
+        painter.draw_text(&self.icon, icon_point, text_color, font_size + 2.0);
 
         // Text or placeholder
         let text = if self.query.is_empty() {
@@ -264,12 +311,28 @@ impl Widget for SearchBar {
         } else {
             &self.query
         };
-        let text_color = if self.query.is_empty() {
-            self.placeholder_color
+        let query_color = if self.query.is_empty() {
+            theme.colors.muted_foreground
         } else {
-            self.text_color
+            text_color
         };
-        painter.draw_text(text, Point::new(rect.x() + 40.0, rect.y() + self.height / 2.0 + 5.0), text_color, 14.0);
+        let text_x = rect.x() + padding.left + 28.0;
+        if self.is_focused {
+            let layout = TextLayout::styled(&self.query, font_size, &style.font());
+            if let Some(anchor) = self.selection_anchor {
+                for selection in layout.selection_rects(anchor..self.query.len()) {
+                    painter.fill_rect(
+                        Rect::new(text_x + selection.x(), rect.y() + self.height / 2.0 - 9.0, selection.width(), 18.0),
+                        theme.colors.primary.with_alpha(0.35),
+                    );
+                }
+            }
+
+            // Cursor, always after the query
+            let cursor_x = text_x + layout.caret_x(self.query.len());
+            painter.fill_rect(Rect::new(cursor_x, rect.y() + self.height / 2.0 - 9.0, 1.5, 18.0), text_color);
+        }
+        painter.draw_text(text, Point::new(text_x, rect.y() + self.height / 2.0 + 5.0), query_color, font_size);
 
         // Suggestion dropdown
         if self.suggestions_visible && !self.suggestions.is_empty() {
@@ -279,7 +342,8 @@ impl Widget for SearchBar {
                 rect.width(),
                 self.suggestions.len().min(5) as f32 * 40.0,
             );
-            painter.fill_rounded_rect(dropdown_rect, Color::rgba(0.15, 0.15, 0.15, 0.95), BorderRadius::all(self.border_radius));
+            painter.fill_rounded_rect(dropdown_rect, theme.colors.popover, radius);
+            painter.stroke_rect(dropdown_rect, theme.colors.border, 1.0);
 
             for (i, suggestion) in self.suggestions.iter().take(5).enumerate() {
                 let item_rect = Rect::new(
@@ -289,19 +353,103 @@ impl Widget for SearchBar {
                     40.0,
                 );
                 if self.selected_index == Some(i) {
-                    painter.fill_rect(item_rect, Color::rgba(1.0, 1.0, 1.0, 0.1));
+                    painter.fill_rect(item_rect, theme.colors.accent);
                 }
                 painter.draw_text(
                     &suggestion.text,
                     Point::new(item_rect.x() + 16.0, item_rect.y() + 25.0),
-                    Color::WHITE,
-                    14.0,
+                    theme.colors.popover_foreground,
+                    font_size,
                 );
             }
         }
     }
 
-    fn handle_event(&mut self, _event: &Event, _ctx: &mut EventContext) -> EventResult {
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            if self.base.state.focused {
+                self.focus();
+            } else {
+                self.blur();
+            }
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
+        match event {
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down && mouse.button == Some(MouseButton::Left) => {
+                let bounds = self.base.bounds;
+                let search_rect = Rect::new(bounds.x(), bounds.y(), bounds.width(), self.height);
+                if search_rect.contains(mouse.position) {
+                    self.selection_anchor = None;
+                    ctx.request_focus(self.base.id);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
+                }
+
+                // Suggestions are listed below the search box
+                if self.suggestions_visible && bounds.contains(mouse.position) {
+                    let index = ((mouse.position.y - search_rect.max_y() - 4.0) / 40.0) as usize;
+                    if let Some(suggestion) = self.suggestions.get(index).cloned() {
+                        self.set_query(&suggestion.text);
+                        self.suggestions_visible = false;
+                        if let Some(ref cb) = self.on_suggestion_select {
+                            cb(&suggestion);
+                        }
+                        ctx.request_redraw();
+                        return EventResult::Handled;
+                    }
+                }
+            }
+            Event::Key(key) if self.is_focused => {
+                if let Some(command) = EditCommand::from_key(key) {
+                    self.edit(command, ctx);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
+                }
+                if key.kind != KeyEventKind::Down {
+                    return EventResult::Ignored;
+                }
+                match key.key {
+                    Key::Enter => {
+                        if let Some(suggestion) = self.selected_index.and_then(|i| self.suggestions.get(i)).cloned() {
+                            self.set_query(&suggestion.text);
+                            if let Some(ref cb) = self.on_suggestion_select {
+                                cb(&suggestion);
+                            }
+                        } else if let Some(ref cb) = self.on_submit {
+                            cb(&self.query);
+                        }
+                        self.suggestions_visible = false;
+                    }
+                    Key::Escape => ctx.release_focus(),
+                    Key::Backspace => {
+                        if self.with_editor(|editor| editor.delete_selection()) || self.query.pop().is_some() {
+                            self.edited();
+                        }
+                    }
+                    Key::Down if self.suggestions_visible => {
+                        let last = self.suggestions.len().min(5) - 1;
+                        self.selected_index = Some(self.selected_index.map_or(0, |i| (i + 1).min(last)));
+                    }
+                    Key::Up if self.suggestions_visible => {
+                        self.selected_index = self.selected_index.and_then(|i| i.checked_sub(1));
+                    }
+                    // Tab moves focus
+                    Key::Tab => return EventResult::Ignored,
+                    _ => {
+                        let Some(text) = key.text.as_deref().filter(|text| !text.chars().any(char::is_control)) else {
+                            return EventResult::Ignored;
+                        };
+                        self.with_editor(|editor| editor.insert(text));
+                        self.edited();
+                    }
+                }
+                ctx.request_redraw();
+                return EventResult::Handled;
+            }
+            _ => {}
+        }
         EventResult::Ignored
     }
 
@@ -312,4 +460,8 @@ impl Widget for SearchBar {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
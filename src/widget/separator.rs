@@ -132,7 +132,7 @@ impl Widget for Separator {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let color = theme.colors.border;
+        let color = ctx.style(self).background_or(theme.colors.border);
 
         let line_rect = match self.orientation {
             SeparatorOrientation::Horizontal => {
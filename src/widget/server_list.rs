@@ -3,9 +3,9 @@
 //! Displays a list of VPN servers for selection, similar to OpenVPN Connect.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
-use crate::css::{ClassList, WidgetState};
+use crate::css::{ClassList, ComputedStyle, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -162,7 +162,9 @@ pub struct ServerList {
     show_latency: bool,
     show_protocol: bool,
     item_states: Vec<ServerItemState>,
+    #[allow(clippy::type_complexity)]
     on_select: Option<Box<dyn Fn(&VpnServer) + Send + Sync>>,
+    #[allow(clippy::type_complexity)]
     on_favorite: Option<Box<dyn Fn(&VpnServer) + Send + Sync>>,
 }
 
@@ -275,12 +277,17 @@ impl ServerList {
     fn paint_server_item(
         &self,
         painter: &mut Painter,
-        server: &VpnServer,
+        index: usize,
         rect: Rect,
-        is_selected: bool,
-        is_hovered: bool,
         theme: &crate::theme::ThemeData,
+        style: &ComputedStyle,
     ) {
+        let server = &self.servers[index];
+        let is_selected = self.selected_id.as_ref() == Some(&server.id);
+        let is_hovered = self.item_states.get(index).map(|s| s.hovered).unwrap_or(false);
+        let font_size = style.font_size_or(16.0);
+        let padding = style.padding_or(EdgeInsets::symmetric(0.0, 16.0));
+
         // Background
         let bg_color = if is_selected {
             theme.colors.primary.with_alpha(0.15)
@@ -298,18 +305,18 @@ impl ServerList {
             painter.fill_rect(indicator_rect, theme.colors.primary);
         }
 
-        let content_x = rect.x() + 16.0;
+        let content_x = rect.x() + padding.left;
 
         // Favorite star
         if server.favorite {
-            let star_x = rect.x() + rect.width() - 32.0;
+            let star_x = rect.x() + rect.width() - 16.0 - padding.right;
             let star_y = rect.y() + rect.height() / 2.0 - 6.0;
             painter.draw_text("★", Point::new(star_x, star_y + 12.0), Color::rgb(1.0, 0.757, 0.027), 14.0);
         }
 
         // SSO indicator
         if server.sso_enabled {
-            let sso_x = rect.x() + rect.width() - (if server.favorite { 56.0 } else { 32.0 });
+            let sso_x = rect.x() + rect.width() - padding.right - (if server.favorite { 40.0 } else { 16.0 });
             let sso_y = rect.y() + rect.height() / 2.0 - 6.0;
             painter.draw_text("🔐", Point::new(sso_x, sso_y + 12.0), theme.colors.muted_foreground, 12.0);
         }
@@ -319,8 +326,8 @@ impl ServerList {
         painter.draw_text(
             &server.name,
             Point::new(content_x, name_y),
-            theme.colors.foreground,
-            16.0,
+            style.color_or(theme.colors.card_foreground),
+            font_size,
         );
 
         // Location
@@ -329,13 +336,13 @@ impl ServerList {
             &server.location(),
             Point::new(content_x, location_y),
             theme.colors.muted_foreground,
-            12.0,
+            font_size - 4.0,
         );
 
         // Load indicator
         if self.show_load {
             if let Some(load) = server.load {
-                let load_x = rect.x() + rect.width() - 100.0;
+                let load_x = rect.x() + rect.width() - 84.0 - padding.right;
                 let load_y = rect.y() + rect.height() / 2.0;
                 let load_text = format!("{}%", load);
 
@@ -400,12 +407,12 @@ impl ServerList {
 
         // Bottom separator
         let separator_rect = Rect::new(
-            rect.x() + 16.0,
+            rect.x() + padding.left,
             rect.y() + rect.height() - 1.0,
-            rect.width() - 32.0,
+            rect.width() - padding.horizontal(),
             1.0,
         );
-        painter.fill_rect(separator_rect, theme.colors.border.with_alpha(0.3));
+        painter.fill_rect(separator_rect, style.border_color_or(theme.colors.border).with_alpha(0.3));
     }
 }
 
@@ -453,12 +460,14 @@ impl Widget for ServerList {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        painter.fill_rect(rect, theme.colors.card);
+        let radius = style.border_radius_or(BorderRadius::ZERO);
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.card), radius);
 
         // Paint each server item
-        for (i, server) in self.servers.iter().enumerate() {
+        for i in 0..self.servers.len() {
             let item_rect = Rect::new(
                 rect.x(),
                 rect.y() + (i as f32 * self.item_height),
@@ -474,10 +483,13 @@ impl Widget for ServerList {
                 continue;
             }
 
-            let is_selected = self.selected_id.as_ref() == Some(&server.id);
-            let is_hovered = self.item_states.get(i).map(|s| s.hovered).unwrap_or(false);
+            self.paint_server_item(painter, i, item_rect, theme, &style);
+        }
 
-            self.paint_server_item(painter, server, item_rect, is_selected, is_hovered, theme);
+        // Border
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
         }
     }
 
@@ -124,6 +124,12 @@ impl Slider {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Get the current value.
     pub fn get_value(&self) -> f32 {
         self.value
@@ -268,11 +274,14 @@ impl Widget for Slider {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        // CSS `color` sets the fill and thumb, like an accent color
+        let accent = style.color_or(theme.colors.primary);
 
         // Track background
         let track = self.track_rect(rect);
-        let track_radius = BorderRadius::all(2.0);
-        painter.fill_rounded_rect(track, theme.colors.muted, track_radius);
+        let track_radius = style.border_radius_or(BorderRadius::all(2.0));
+        painter.fill_rounded_rect(track, style.background_or(theme.colors.muted), track_radius);
 
         // Filled portion
         let filled_rect = match self.orientation {
@@ -288,7 +297,7 @@ impl Widget for Slider {
         let fill_color = if self.disabled {
             theme.colors.muted_foreground
         } else {
-            theme.colors.primary
+            accent
         };
         painter.fill_rounded_rect(filled_rect, fill_color, track_radius);
 
@@ -298,11 +307,11 @@ impl Widget for Slider {
         let thumb_color = if self.disabled {
             theme.colors.muted_foreground
         } else if self.dragging {
-            theme.colors.primary.darken(10.0)
+            accent.darken(10.0)
         } else if self.base.state.hovered {
-            theme.colors.primary.lighten(10.0)
+            accent.lighten(10.0)
         } else {
-            theme.colors.primary
+            accent
         };
 
         // Thumb shadow
@@ -328,7 +337,7 @@ impl Widget for Slider {
                 &value_text,
                 Point::new(text_x, text_y),
                 theme.colors.foreground,
-                12.0,
+                style.font_size_or(12.0),
             );
         }
 
@@ -345,6 +354,11 @@ impl Widget for Slider {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         if self.disabled {
             return EventResult::Ignored;
         }
@@ -374,6 +388,7 @@ impl Widget for Slider {
                 MouseEventKind::Down if mouse.button == Some(MouseButton::Left) => {
                     if in_bounds || in_thumb {
                         self.dragging = true;
+                        ctx.capture_pointer(self.base.id);
                         ctx.request_focus(self.base.id);
                         let new_value = self.position_to_value(mouse.position, self.bounds());
                         self.set_value(new_value);
@@ -401,4 +416,8 @@ impl Widget for Slider {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
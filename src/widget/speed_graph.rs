@@ -324,9 +324,11 @@ impl Widget for SpeedGraph {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        painter.fill_rounded_rect(rect, theme.colors.card, BorderRadius::all(8.0));
+        let radius = style.border_radius_or(BorderRadius::all(8.0));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.card), radius);
 
         let graph = self.graph_rect();
         let max_value = self.calculate_max();
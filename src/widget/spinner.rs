@@ -2,11 +2,14 @@
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult};
+use crate::event::{Event, EventResult, TimerEvent};
 use crate::geometry::{Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
+/// Rotation speed in radians per second (one turn per second).
+const ROTATION_SPEED: f32 = std::f32::consts::PI * 2.0;
+
 /// Spinner size presets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SpinnerSize {
@@ -62,7 +65,7 @@ pub struct Spinner {
     base: WidgetBase,
     size: SpinnerSize,
     color: Option<Color>,
-    #[allow(dead_code)]
+    /// Current rotation in radians
     rotation: f32,
 }
 
@@ -136,7 +139,7 @@ impl Widget for Spinner {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let color = self.color.unwrap_or(theme.colors.primary);
+        let color = ctx.style(self).color_or(self.color.unwrap_or(theme.colors.primary));
 
         let size = self.size.pixels();
         let stroke = self.size.stroke_width();
@@ -153,7 +156,7 @@ impl Widget for Spinner {
 
         let segments = 12;
         for i in 0..segments {
-            let angle = (i as f32) * (std::f32::consts::PI * 2.0 / segments as f32);
+            let angle = self.rotation + (i as f32) * (std::f32::consts::PI * 2.0 / segments as f32);
             let x = center_x + angle.cos() * radius;
             let y = center_y + angle.sin() * radius;
 
@@ -180,8 +183,14 @@ impl Widget for Spinner {
         );
     }
 
-    fn handle_event(&mut self, _event: &Event, _ctx: &mut EventContext) -> EventResult {
-        // Spinners don't handle events
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        // Spin continuously; frames are left unhandled for other widgets
+        if let Event::Timer(TimerEvent::Frame { elapsed }) = event {
+            self.rotation = (self.rotation + elapsed.as_secs_f32() * ROTATION_SPEED)
+                % (std::f32::consts::PI * 2.0);
+            ctx.request_animation_frame();
+            ctx.request_redraw();
+        }
         EventResult::Ignored
     }
 
@@ -2,7 +2,7 @@
 //!
 //! Provides horizontal and vertical split views with draggable dividers.
 
-use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
+use super::{Slots, Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
 use crate::geometry::{Point, Rect, Size};
@@ -19,6 +19,11 @@ pub enum SplitOrientation {
     Vertical,
 }
 
+/// Slot of the first panel in `SplitPane::panes`.
+const FIRST: usize = 0;
+/// Slot of the second panel.
+const SECOND: usize = 1;
+
 /// Split pane widget.
 ///
 /// # Example
@@ -36,8 +41,8 @@ pub enum SplitOrientation {
 pub struct SplitPane {
     base: WidgetBase,
     orientation: SplitOrientation,
-    first: Option<Box<dyn Widget>>,
-    second: Option<Box<dyn Widget>>,
+    /// The first and second panels
+    panes: Slots<2>,
     ratio: f32,
     min_first_size: f32,
     min_second_size: f32,
@@ -67,8 +72,7 @@ impl SplitPane {
         Self {
             base: WidgetBase::new().with_class("split-pane"),
             orientation,
-            first: None,
-            second: None,
+            panes: Slots::new(),
             ratio: 0.5,
             min_first_size: 100.0,
             min_second_size: 100.0,
@@ -85,13 +89,13 @@ impl SplitPane {
 
     /// Set the first panel widget.
     pub fn first<W: Widget + 'static>(mut self, widget: W) -> Self {
-        self.first = Some(Box::new(widget));
+        self.panes.set(FIRST, Box::new(widget));
         self
     }
 
     /// Set the second panel widget.
     pub fn second<W: Widget + 'static>(mut self, widget: W) -> Self {
-        self.second = Some(Box::new(widget));
+        self.panes.set(SECOND, Box::new(widget));
         self
     }
 
@@ -238,6 +242,15 @@ impl SplitPane {
         hit_rect.contains(point)
     }
 
+    /// Slots of the panels that aren't collapsed, as a range.
+    fn shown_panes(&self) -> (usize, usize) {
+        match (self.first_collapsed, self.second_collapsed) {
+            (true, _) => (SECOND, SECOND + 1),
+            (_, true) => (FIRST, SECOND),
+            _ => (FIRST, SECOND + 1),
+        }
+    }
+
     fn update_ratio_from_position(&mut self, pos: Point) {
         let bounds = self.base.bounds;
         let (total_size, current_pos) = match self.orientation {
@@ -288,11 +301,13 @@ impl Widget for SplitPane {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
-        Size::new(400.0, 300.0)
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        let sizes = Constraints::unbounded().with_style(&ctx.style(self));
+        sizes.constrain(Size::new(400.0, 300.0))
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let constraints = constraints.with_style(&ctx.style(self));
         let size = constraints.constrain(self.intrinsic_size(ctx));
         self.base.bounds.size = size;
 
@@ -300,15 +315,15 @@ impl Widget for SplitPane {
         let first_rect = self.first_rect();
         let second_rect = self.second_rect();
 
-        if let Some(ref mut first) = self.first {
+        if let Some(first) = self.panes.get_mut(FIRST) {
             let child_constraints = Constraints::tight(first_rect.size);
-            first.layout(child_constraints, ctx);
+            ctx.in_containing_block(first_rect.size, |ctx| first.layout(child_constraints, ctx));
             first.set_bounds(first_rect);
         }
 
-        if let Some(ref mut second) = self.second {
+        if let Some(second) = self.panes.get_mut(SECOND) {
             let child_constraints = Constraints::tight(second_rect.size);
-            second.layout(child_constraints, ctx);
+            ctx.in_containing_block(second_rect.size, |ctx| second.layout(child_constraints, ctx));
             second.set_bounds(second_rect);
         }
 
@@ -317,23 +332,24 @@ impl Widget for SplitPane {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        painter.fill_rect(rect, theme.colors.background);
+        painter.fill_rect(rect, style.background_or(theme.colors.background));
 
         // Paint first panel
-        if let Some(ref first) = self.first {
+        if let Some(first) = self.panes.get(FIRST) {
             if !self.first_collapsed {
                 let first_rect = self.first_rect();
-                first.paint(painter, first_rect, ctx);
+                ctx.in_containing_block(first_rect.size, |ctx| ctx.paint_child(first, painter, first_rect));
             }
         }
 
         // Paint second panel
-        if let Some(ref second) = self.second {
+        if let Some(second) = self.panes.get(SECOND) {
             if !self.second_collapsed {
                 let second_rect = self.second_rect();
-                second.paint(painter, second_rect, ctx);
+                ctx.in_containing_block(second_rect.size, |ctx| ctx.paint_child(second, painter, second_rect));
             }
         }
 
@@ -344,7 +360,7 @@ impl Widget for SplitPane {
         } else if self.divider_hovered {
             theme.colors.accent.with_alpha(0.5)
         } else {
-            theme.colors.border
+            style.border_color_or(theme.colors.border)
         };
 
         painter.fill_rect(divider, divider_color);
@@ -409,79 +425,70 @@ impl Widget for SplitPane {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
-        // First, try to handle events in children
-        if let Some(ref mut first) = self.first {
-            if !self.first_collapsed {
-                let result = first.handle_event(event, ctx);
-                if result == EventResult::Handled {
-                    return result;
+        // Pointer events are routed to the panes by the event dispatcher;
+        // forward the rest to the panes shown
+        let Event::Mouse(mouse) = event else {
+            for pane in self.children_mut() {
+                if pane.handle_event(event, ctx) == EventResult::Handled {
+                    return EventResult::Handled;
                 }
             }
-        }
-
-        if let Some(ref mut second) = self.second {
-            if !self.second_collapsed {
-                let result = second.handle_event(event, ctx);
-                if result == EventResult::Handled {
-                    return result;
-                }
-            }
-        }
+            return EventResult::Ignored;
+        };
 
         // Handle divider interaction
-        if let Event::Mouse(mouse) = event {
-            match mouse.kind {
-                MouseEventKind::Move => {
-                    if self.dragging {
-                        self.update_ratio_from_position(mouse.position);
-                        // Re-layout children - compute rects before borrowing
-                        let first_rect = self.first_rect();
-                        let second_rect = self.second_rect();
-                        if let Some(ref mut first) = self.first {
-                            first.set_bounds(first_rect);
-                        }
-                        if let Some(ref mut second) = self.second {
-                            second.set_bounds(second_rect);
-                        }
-                        ctx.request_redraw();
-                        return EventResult::Handled;
+        match mouse.kind {
+            MouseEventKind::Move => {
+                if self.dragging {
+                    self.update_ratio_from_position(mouse.position);
+                    // Re-layout children - compute rects before borrowing
+                    let first_rect = self.first_rect();
+                    let second_rect = self.second_rect();
+                    if let Some(first) = self.panes.get_mut(FIRST) {
+                        first.set_bounds(first_rect);
                     }
-
-                    let on_divider = self.point_on_divider(mouse.position);
-                    if on_divider != self.divider_hovered {
-                        self.divider_hovered = on_divider;
-                        ctx.request_redraw();
+                    if let Some(second) = self.panes.get_mut(SECOND) {
+                        second.set_bounds(second_rect);
                     }
+                    ctx.request_redraw();
+                    return EventResult::Handled;
                 }
-                MouseEventKind::Down if mouse.button == Some(MouseButton::Left) => {
-                    if self.point_on_divider(mouse.position) {
-                        self.dragging = true;
-                        // Calculate drag offset
-                        let divider = self.divider_rect();
-                        self.drag_offset = match self.orientation {
-                            SplitOrientation::Horizontal => mouse.position.x - divider.x(),
-                            SplitOrientation::Vertical => mouse.position.y - divider.y(),
-                        };
-                        ctx.request_focus(self.base.id);
-                        ctx.request_redraw();
-                        return EventResult::Handled;
-                    }
+
+                let on_divider = self.point_on_divider(mouse.position);
+                if on_divider != self.divider_hovered {
+                    self.divider_hovered = on_divider;
+                    ctx.request_redraw();
                 }
-                MouseEventKind::Up if mouse.button == Some(MouseButton::Left) => {
-                    if self.dragging {
-                        self.dragging = false;
-                        ctx.request_redraw();
-                        return EventResult::Handled;
-                    }
+            }
+            MouseEventKind::Down if mouse.button == Some(MouseButton::Left) => {
+                if self.point_on_divider(mouse.position) {
+                    self.dragging = true;
+                    ctx.capture_pointer(self.base.id);
+                    // Calculate drag offset
+                    let divider = self.divider_rect();
+                    self.drag_offset = match self.orientation {
+                        SplitOrientation::Horizontal => mouse.position.x - divider.x(),
+                        SplitOrientation::Vertical => mouse.position.y - divider.y(),
+                    };
+                    ctx.request_focus(self.base.id);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
                 }
-                MouseEventKind::Leave => {
-                    if self.divider_hovered {
-                        self.divider_hovered = false;
-                        ctx.request_redraw();
-                    }
+            }
+            MouseEventKind::Up if mouse.button == Some(MouseButton::Left) => {
+                if self.dragging {
+                    self.dragging = false;
+                    ctx.request_redraw();
+                    return EventResult::Handled;
+                }
+            }
+            MouseEventKind::Leave => {
+                if self.divider_hovered {
+                    self.divider_hovered = false;
+                    ctx.request_redraw();
                 }
-                _ => {}
             }
+            _ => {}
         }
 
         EventResult::Ignored
@@ -495,11 +502,14 @@ impl Widget for SplitPane {
         self.base.bounds = bounds;
     }
 
+    /// The panels shown; a collapsed one is left out.
     fn children(&self) -> &[Box<dyn Widget>] {
-        &[]
+        let (from, to) = self.shown_panes();
+        self.panes.range(from, to)
     }
 
     fn children_mut(&mut self) -> &mut [Box<dyn Widget>] {
-        &mut []
+        let (from, to) = self.shown_panes();
+        self.panes.range_mut(from, to)
     }
 }
@@ -6,7 +6,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseButton, MouseEvent, MouseEventKind};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -49,11 +49,11 @@ pub struct StartButton {
     size: f32,
     padding: f32,
 
-    // Colors
-    icon_color: Color,
-    hover_color: Color,
-    active_color: Color,
-    background_color: Color,
+    // Colors, from the theme unless set
+    icon_color: Option<Color>,
+    hover_color: Option<Color>,
+    active_color: Option<Color>,
+    background_color: Option<Color>,
 
     // Custom content
     icon_path: Option<String>,
@@ -82,10 +82,10 @@ impl StartButton {
             is_menu_open: false,
             size: 48.0,
             padding: 12.0,
-            icon_color: Color::WHITE,
-            hover_color: Color::rgba(1.0, 1.0, 1.0, 0.1),
-            active_color: Color::rgba(1.0, 1.0, 1.0, 0.2),
-            background_color: Color::TRANSPARENT,
+            icon_color: None,
+            hover_color: None,
+            active_color: None,
+            background_color: None,
             icon_path: None,
             label: "Start".to_string(),
             hover_scale: 1.05,
@@ -117,25 +117,25 @@ impl StartButton {
 
     /// Set the icon color
     pub fn icon_color(mut self, color: Color) -> Self {
-        self.icon_color = color;
+        self.icon_color = Some(color);
         self
     }
 
     /// Set the hover background color
     pub fn hover_color(mut self, color: Color) -> Self {
-        self.hover_color = color;
+        self.hover_color = Some(color);
         self
     }
 
     /// Set the active/pressed background color
     pub fn active_color(mut self, color: Color) -> Self {
-        self.active_color = color;
+        self.active_color = Some(color);
         self
     }
 
     /// Set the background color
     pub fn background_color(mut self, color: Color) -> Self {
-        self.background_color = color;
+        self.background_color = Some(color);
         self
     }
 
@@ -229,7 +229,7 @@ impl StartButton {
     }
 
     /// Draw the Windows-style four-square logo
-    fn draw_windows_logo(&self, painter: &mut Painter, center: Point, size: f32) {
+    fn draw_windows_logo(&self, painter: &mut Painter, center: Point, size: f32, color: Color) {
         let square_size = size * 0.4;
         let gap = size * 0.1;
         let half_gap = gap / 2.0;
@@ -241,7 +241,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(tl, self.icon_color);
+        painter.fill_rect(tl, color);
 
         // Top-right square
         let tr = Rect::new(
@@ -250,7 +250,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(tr, self.icon_color);
+        painter.fill_rect(tr, color);
 
         // Bottom-left square
         let bl = Rect::new(
@@ -259,7 +259,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(bl, self.icon_color);
+        painter.fill_rect(bl, color);
 
         // Bottom-right square
         let br = Rect::new(
@@ -268,11 +268,11 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(br, self.icon_color);
+        painter.fill_rect(br, color);
     }
 
     /// Draw hamburger menu icon
-    fn draw_hamburger(&self, painter: &mut Painter, center: Point, size: f32) {
+    fn draw_hamburger(&self, painter: &mut Painter, center: Point, size: f32, color: Color) {
         let bar_height = size * 0.12;
         let bar_width = size * 0.7;
         let spacing = size * 0.25;
@@ -285,12 +285,12 @@ impl StartButton {
                 bar_width,
                 bar_height,
             );
-            painter.fill_rounded_rect(bar, self.icon_color, BorderRadius::all(bar_height / 2.0));
+            painter.fill_rounded_rect(bar, color, BorderRadius::all(bar_height / 2.0));
         }
     }
 
     /// Draw app grid icon (3x3 dots)
-    fn draw_app_grid(&self, painter: &mut Painter, center: Point, size: f32) {
+    fn draw_app_grid(&self, painter: &mut Painter, center: Point, size: f32, color: Color) {
         let dot_size = size * 0.15;
         let spacing = size * 0.3;
 
@@ -299,13 +299,13 @@ impl StartButton {
                 let x = center.x + (col as f32) * spacing - dot_size / 2.0;
                 let y = center.y + (row as f32) * spacing - dot_size / 2.0;
                 let dot = Rect::new(x, y, dot_size, dot_size);
-                painter.fill_rounded_rect(dot, self.icon_color, BorderRadius::all(dot_size / 2.0));
+                painter.fill_rounded_rect(dot, color, BorderRadius::all(dot_size / 2.0));
             }
         }
     }
 
-    /// Draw circle logo
-    fn draw_circle_logo(&self, painter: &mut Painter, center: Point, size: f32) {
+    /// Draw circle logo, with the inner logo cut out in `inner_color`
+    fn draw_circle_logo(&self, painter: &mut Painter, center: Point, size: f32, color: Color, inner_color: Color) {
         // Outer circle
         let outer = Rect::new(
             center.x - size / 2.0,
@@ -313,7 +313,7 @@ impl StartButton {
             size,
             size,
         );
-        painter.fill_rounded_rect(outer, self.icon_color, BorderRadius::all(size / 2.0));
+        painter.fill_rounded_rect(outer, color, BorderRadius::all(size / 2.0));
 
         // Inner logo (scaled down)
         let inner_size = size * 0.6;
@@ -324,15 +324,13 @@ impl StartButton {
         let gap = inner_size * 0.08;
         let half_gap = gap / 2.0;
 
-        let bg_color = Color::rgba(0.1, 0.1, 0.1, 1.0);
-
         let tl = Rect::new(
             inner_center.x - square_size - half_gap,
             inner_center.y - square_size - half_gap,
             square_size,
             square_size,
         );
-        painter.fill_rect(tl, bg_color);
+        painter.fill_rect(tl, inner_color);
 
         let tr = Rect::new(
             inner_center.x + half_gap,
@@ -340,7 +338,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(tr, bg_color);
+        painter.fill_rect(tr, inner_color);
 
         let bl = Rect::new(
             inner_center.x - square_size - half_gap,
@@ -348,7 +346,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(bl, bg_color);
+        painter.fill_rect(bl, inner_color);
 
         let br = Rect::new(
             inner_center.x + half_gap,
@@ -356,7 +354,7 @@ impl StartButton {
             square_size,
             square_size,
         );
-        painter.fill_rect(br, bg_color);
+        painter.fill_rect(br, inner_color);
     }
 }
 
@@ -397,7 +395,13 @@ impl Widget for StartButton {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let icon_color = style.color_or(self.icon_color.unwrap_or(theme.colors.foreground));
+        let padding = style.padding_or(EdgeInsets::all(self.padding));
+        let radius = style.border_radius_or(BorderRadius::all(6.0));
+
         // Calculate scale based on state
         let scale = match self.state {
             StartButtonState::Normal => 1.0,
@@ -419,12 +423,7 @@ impl Widget for StartButton {
 
         // Glow effect
         if self.glow_enabled && (self.state == StartButtonState::Hovered || self.state == StartButtonState::Active) {
-            let glow_color = Color::rgba(
-                self.icon_color.r,
-                self.icon_color.g,
-                self.icon_color.b,
-                0.3,
-            );
+            let glow_color = icon_color.with_alpha(0.3);
             let glow_rect = Rect::new(
                 scaled_rect.x() - 4.0,
                 scaled_rect.y() - 4.0,
@@ -436,38 +435,42 @@ impl Widget for StartButton {
 
         // Background
         let bg_color = match self.state {
-            StartButtonState::Normal => self.background_color,
-            StartButtonState::Hovered => self.hover_color,
-            StartButtonState::Pressed | StartButtonState::Active => self.active_color,
+            StartButtonState::Normal => style.background_or(self.background_color.unwrap_or(Color::TRANSPARENT)),
+            StartButtonState::Hovered => self.hover_color.unwrap_or(theme.colors.surface_hover),
+            StartButtonState::Pressed | StartButtonState::Active => self.active_color.unwrap_or(theme.colors.accent),
         };
-        painter.fill_rounded_rect(scaled_rect, bg_color, BorderRadius::all(6.0));
+        painter.fill_rounded_rect(scaled_rect, bg_color, radius);
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(scaled_rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Icon/content
         let center = Point::new(
             scaled_rect.x() + scaled_rect.width() / 2.0,
             scaled_rect.y() + scaled_rect.height() / 2.0,
         );
-        let icon_size = scaled_rect.width() - self.padding * 2.0;
+        let icon_size = scaled_rect.width() - padding.horizontal();
 
         match self.style {
             StartButtonStyle::WindowsLogo => {
-                self.draw_windows_logo(painter, center, icon_size);
+                self.draw_windows_logo(painter, center, icon_size, icon_color);
             }
             StartButtonStyle::HamburgerMenu => {
-                self.draw_hamburger(painter, center, icon_size);
+                self.draw_hamburger(painter, center, icon_size, icon_color);
             }
             StartButtonStyle::AppGrid => {
-                self.draw_app_grid(painter, center, icon_size);
+                self.draw_app_grid(painter, center, icon_size, icon_color);
             }
             StartButtonStyle::CircleLogo => {
-                self.draw_circle_logo(painter, center, icon_size);
+                self.draw_circle_logo(painter, center, icon_size, icon_color, theme.colors.background);
             }
             StartButtonStyle::TextLabel => {
                 painter.draw_text(
                     &self.label,
                     Point::new(scaled_rect.x() + 8.0, center.y + 5.0),
-                    self.icon_color,
-                    14.0,
+                    icon_color,
+                    style.font_size_or(14.0),
                 );
             }
             StartButtonStyle::CustomIcon => {
@@ -479,7 +482,7 @@ impl Widget for StartButton {
                         icon_size,
                         icon_size,
                     );
-                    painter.fill_rounded_rect(icon_rect, self.icon_color, BorderRadius::all(4.0));
+                    painter.fill_rounded_rect(icon_rect, icon_color, BorderRadius::all(4.0));
                 }
             }
         }
@@ -492,50 +495,47 @@ impl Widget for StartButton {
                 scaled_rect.width(),
                 3.0,
             );
-            painter.fill_rect(indicator, self.icon_color);
+            painter.fill_rect(indicator, icon_color);
         }
     }
 
     fn handle_event(&mut self, event: &Event, _ctx: &mut EventContext) -> EventResult {
-        match event {
-            Event::Mouse(MouseEvent { kind, position, button, .. }) => {
-                let in_bounds = self.base.bounds.contains(*position);
-                let is_left_button = *button == Some(MouseButton::Left);
-
-                match kind {
-                    MouseEventKind::Move => {
-                        if in_bounds && self.state != StartButtonState::Active {
-                            self.state = StartButtonState::Hovered;
-                            return EventResult::Handled;
-                        } else if !in_bounds && self.state == StartButtonState::Hovered {
-                            self.state = StartButtonState::Normal;
-                            return EventResult::Handled;
-                        }
-                    }
-                    MouseEventKind::Down if in_bounds && is_left_button => {
-                        self.state = StartButtonState::Pressed;
+        if let Event::Mouse(MouseEvent { kind, position, button, .. }) = event {
+            let in_bounds = self.base.bounds.contains(*position);
+            let is_left_button = *button == Some(MouseButton::Left);
+
+            match kind {
+                MouseEventKind::Move => {
+                    if in_bounds && self.state != StartButtonState::Active {
+                        self.state = StartButtonState::Hovered;
+                        return EventResult::Handled;
+                    } else if !in_bounds && self.state == StartButtonState::Hovered {
+                        self.state = StartButtonState::Normal;
                         return EventResult::Handled;
                     }
-                    MouseEventKind::Up if is_left_button => {
-                        if in_bounds && self.state == StartButtonState::Pressed {
-                            self.toggle_menu();
-                            if let Some(ref cb) = self.on_click {
-                                cb();
-                            }
-                            return EventResult::Handled;
-                        }
-                        if !self.is_menu_open {
-                            self.state = if in_bounds {
-                                StartButtonState::Hovered
-                            } else {
-                                StartButtonState::Normal
-                            };
+                }
+                MouseEventKind::Down if in_bounds && is_left_button => {
+                    self.state = StartButtonState::Pressed;
+                    return EventResult::Handled;
+                }
+                MouseEventKind::Up if is_left_button => {
+                    if in_bounds && self.state == StartButtonState::Pressed {
+                        self.toggle_menu();
+                        if let Some(ref cb) = self.on_click {
+                            cb();
                         }
+                        return EventResult::Handled;
+                    }
+                    if !self.is_menu_open {
+                        self.state = if in_bounds {
+                            StartButtonState::Hovered
+                        } else {
+                            StartButtonState::Normal
+                        };
                     }
-                    _ => {}
                 }
+                _ => {}
             }
-            _ => {}
         }
         EventResult::Ignored
     }
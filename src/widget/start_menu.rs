@@ -6,7 +6,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -107,7 +107,8 @@ pub struct StartMenu {
     search_active: bool,
     width: f32,
     height: f32,
-    accent_color: Color,
+    /// Custom accent; the theme's primary color by default
+    accent_color: Option<Color>,
     blur_enabled: bool,
     #[allow(clippy::type_complexity)]
     on_item_click: Option<Box<dyn Fn(&StartMenuItem) + Send + Sync>>,
@@ -132,7 +133,7 @@ impl StartMenu {
             search_active: false,
             width: 600.0,
             height: 720.0,
-            accent_color: Color::rgb(0.0, 0.47, 0.84),
+            accent_color: None,
             blur_enabled: true,
             on_item_click: None,
             on_power_action: None,
@@ -177,7 +178,7 @@ impl StartMenu {
     }
 
     pub fn accent_color(mut self, color: Color) -> Self {
-        self.accent_color = color;
+        self.accent_color = Some(color);
         self
     }
 
@@ -308,37 +309,51 @@ impl Widget for StartMenu {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         if !self.is_open {
             return;
         }
 
-        // Background
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let padding = style.padding_or(EdgeInsets::all(16.0));
+
+        // Background, translucent when blurred
         let bg_color = if self.blur_enabled {
-            Color::rgba(0.1, 0.1, 0.1, 0.85)
+            theme.colors.popover.with_alpha(0.85)
         } else {
-            Color::rgb(0.12, 0.12, 0.12)
+            theme.colors.popover
         };
-        painter.fill_rounded_rect(rect, bg_color, BorderRadius::all(8.0));
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        painter.fill_rounded_rect(rect, style.background_or(bg_color), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Accent bar at left
         let accent_bar = Rect::new(rect.x(), rect.y(), 3.0, rect.height());
-        painter.fill_rect(accent_bar, self.accent_color);
+        painter.fill_rect(accent_bar, self.accent_color.unwrap_or(theme.colors.primary));
 
         // Search box area
-        let search_rect = Rect::new(rect.x() + 16.0, rect.y() + 16.0, rect.width() - 32.0, 40.0);
-        painter.fill_rounded_rect(search_rect, Color::rgba(1.0, 1.0, 1.0, 0.1), BorderRadius::all(4.0));
+        let search_rect = Rect::new(
+            rect.x() + padding.left,
+            rect.y() + padding.top,
+            rect.width() - padding.horizontal(),
+            40.0,
+        );
+        painter.fill_rounded_rect(search_rect, theme.colors.muted, BorderRadius::all(4.0));
 
         // User section at bottom
         let user_rect = Rect::new(rect.x(), rect.max_y() - 56.0, rect.width(), 56.0);
-        painter.fill_rect(user_rect, Color::rgba(0.0, 0.0, 0.0, 0.2));
+        painter.fill_rect(user_rect, theme.colors.muted.with_alpha(0.5));
 
         // Draw user name
         painter.draw_text(
             &self.user_name,
             Point::new(rect.x() + 64.0, rect.max_y() - 24.0),
-            Color::WHITE,
-            14.0,
+            style.color_or(theme.colors.popover_foreground),
+            style.font_size_or(14.0),
         );
     }
 
@@ -130,6 +130,12 @@ impl ToggleSwitch {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Get the checked state.
     pub fn is_checked(&self) -> bool {
         self.checked
@@ -215,22 +221,24 @@ impl Widget for ToggleSwitch {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let track = self.track_rect(rect);
-        let track_radius = BorderRadius::all(track.height() / 2.0);
+        let track_radius = style.border_radius_or(BorderRadius::all(track.height() / 2.0));
 
         // Track
-        let track_color = if self.disabled {
+        let track_color = style.background_or(if self.disabled {
             theme.colors.muted
         } else if self.checked {
             theme.colors.primary
         } else {
             theme.colors.muted
-        };
+        });
         painter.fill_rounded_rect(track, track_color, track_radius);
 
         // Track border
-        if !self.checked {
-            painter.stroke_rect(track, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(if self.checked { 0.0 } else { 1.0 });
+        if border_width > 0.0 {
+            painter.stroke_rect(track, style.border_color_or(theme.colors.border), border_width);
         }
 
         // Thumb
@@ -259,10 +267,10 @@ impl Widget for ToggleSwitch {
             let label_color = if self.disabled {
                 theme.colors.muted_foreground
             } else {
-                theme.colors.foreground
+                style.color_or(theme.colors.foreground)
             };
 
-            painter.draw_text(label, Point::new(label_x, label_y), label_color, 14.0);
+            painter.draw_text(label, Point::new(label_x, label_y), label_color, style.font_size_or(14.0));
         }
 
         // Focus ring
@@ -278,6 +286,11 @@ impl Widget for ToggleSwitch {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         if self.disabled {
             return EventResult::Ignored;
         }
@@ -301,6 +314,10 @@ impl Widget for ToggleSwitch {
                         ctx.request_redraw();
                     }
                 }
+                MouseEventKind::Down if mouse.button == Some(MouseButton::Left) && in_bounds => {
+                    ctx.request_focus(self.base.id);
+                    return EventResult::Handled;
+                }
                 MouseEventKind::Up if mouse.button == Some(MouseButton::Left) && in_bounds => {
                     self.toggle();
                     
@@ -325,4 +342,8 @@ impl Widget for ToggleSwitch {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
@@ -5,14 +5,14 @@ use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
 use crate::geometry::{Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{is_path_data, Painter, ICON_VIEWBOX};
 
 /// A system tray icon.
 #[derive(Debug, Clone)]
 pub struct TrayIcon {
     /// Unique identifier
     pub id: String,
-    /// Icon (emoji or icon name)
+    /// Icon, as an emoji or SVG path data in `ICON_VIEWBOX`
     pub icon: String,
     /// Tooltip text
     pub tooltip: Option<String>,
@@ -211,6 +211,8 @@ impl Widget for SystemTray {
 
     fn paint(&self, painter: &mut Painter, _rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let icon_color = style.color_or(theme.colors.foreground);
 
         for (i, icon) in self.icons.iter().enumerate() {
             let icon_rect = self.get_icon_rect(i);
@@ -227,13 +229,17 @@ impl Widget for SystemTray {
                 painter.fill_rect(hover_rect, theme.colors.accent.with_alpha(0.2));
             }
 
-            // Icon
-            painter.draw_text(
-                &icon.icon,
-                Point::new(icon_rect.x(), icon_rect.y() + self.icon_size * 0.85),
-                theme.colors.foreground,
-                self.icon_size,
-            );
+            // Icon, as SVG path data or text
+            if is_path_data(&icon.icon) {
+                painter.draw_icon(&icon.icon, icon_rect.origin, self.icon_size, icon_color, ICON_VIEWBOX);
+            } else {
+                painter.draw_text(
+                    &icon.icon,
+                    Point::new(icon_rect.x(), icon_rect.y() + self.icon_size * 0.85),
+                    icon_color,
+                    self.icon_size,
+                );
+            }
 
             // Badge
             if icon.has_badge {
@@ -294,7 +294,8 @@ impl Widget for Tabs {
                 max_width: size.width,
                 max_height: size.height - self.tab_height,
             };
-            let result = content.layout(content_constraints, ctx);
+            let content_box = Size::new(content_constraints.max_width, content_constraints.max_height);
+            let result = ctx.in_containing_block(content_box, |ctx| content.layout(content_constraints, ctx));
             content.set_bounds(Rect::new(
                 self.base.bounds.x(),
                 self.base.bounds.y() + self.tab_height,
@@ -308,10 +309,12 @@ impl Widget for Tabs {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(14.0);
 
         // Tab bar background
         let tab_bar_rect = Rect::new(rect.x(), rect.y(), rect.width(), self.tab_height);
-        painter.fill_rect(tab_bar_rect, theme.colors.muted.with_alpha(0.3));
+        painter.fill_rect(tab_bar_rect, style.background_or(theme.colors.muted.with_alpha(0.3)));
 
         // Draw tabs
         for (i, tab) in self.tabs.iter().enumerate() {
@@ -377,7 +380,7 @@ impl Widget for Tabs {
             } else if is_selected && self.variant == TabVariant::Pills {
                 theme.colors.accent_foreground
             } else {
-                theme.colors.foreground
+                style.color_or(theme.colors.foreground)
             };
 
             let mut content_x = tab_rect.x() + 12.0;
@@ -398,7 +401,7 @@ impl Widget for Tabs {
                 &tab.label,
                 Point::new(content_x, tab_rect.y() + self.tab_height * 0.65),
                 text_color,
-                14.0,
+                font_size,
             );
 
             // Close button
@@ -423,7 +426,7 @@ impl Widget for Tabs {
         if self.variant == TabVariant::Default {
             painter.fill_rect(
                 Rect::new(rect.x(), rect.y() + self.tab_height - 1.0, rect.width(), 1.0),
-                theme.colors.border,
+                style.border_color_or(theme.colors.border),
             );
         }
 
@@ -435,7 +438,7 @@ impl Widget for Tabs {
                 rect.width(),
                 rect.height() - self.tab_height,
             );
-            content.paint(painter, content_rect, ctx);
+            ctx.in_containing_block(content_rect.size, |ctx| ctx.paint_child(content.as_ref(), painter, content_rect));
         }
     }
 
@@ -489,13 +492,6 @@ impl Widget for Tabs {
                     }
                     _ => {}
                 }
-
-                // Forward to content
-                if let Some(content) = &mut self.content {
-                    if mouse.position.y > self.base.bounds.y() + self.tab_height {
-                        return content.handle_event(event, ctx);
-                    }
-                }
             }
             _ => {
                 if let Some(content) = &mut self.content {
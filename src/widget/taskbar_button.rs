@@ -5,7 +5,7 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -52,7 +52,8 @@ pub struct TaskbarButton {
     badge: Option<u32>,
     icon_size: f32,
     button_size: f32,
-    accent_color: Color,
+    /// Custom accent; the theme's primary color by default
+    accent_color: Option<Color>,
     #[allow(clippy::type_complexity)]
     on_click: Option<Box<dyn Fn() + Send + Sync>>,
     #[allow(clippy::type_complexity)]
@@ -77,7 +78,7 @@ impl TaskbarButton {
             badge: None,
             icon_size: 24.0,
             button_size: 48.0,
-            accent_color: Color::rgb(0.0, 0.47, 0.84),
+            accent_color: None,
             on_click: None,
             on_right_click: None,
             on_window_select: None,
@@ -122,7 +123,7 @@ impl TaskbarButton {
     }
 
     pub fn accent_color(mut self, color: Color) -> Self {
-        self.accent_color = color;
+        self.accent_color = Some(color);
         self
     }
 
@@ -234,15 +235,24 @@ impl Widget for TaskbarButton {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let accent = self.accent_color.unwrap_or(theme.colors.primary);
+        let padding = style.padding_or(EdgeInsets::all(4.0));
+
         // Background based on state
         let bg_color = match self.state {
-            TaskbarButtonState::Normal => Color::TRANSPARENT,
-            TaskbarButtonState::Hovered => Color::rgba(1.0, 1.0, 1.0, 0.1),
-            TaskbarButtonState::Active => Color::rgba(1.0, 1.0, 1.0, 0.15),
-            TaskbarButtonState::Attention => Color::rgba(1.0, 0.5, 0.0, 0.3),
+            TaskbarButtonState::Normal => style.background_or(Color::TRANSPARENT),
+            TaskbarButtonState::Hovered => theme.colors.surface_hover,
+            TaskbarButtonState::Active => theme.colors.accent,
+            TaskbarButtonState::Attention => theme.colors.warning.with_alpha(0.3),
         };
-        painter.fill_rounded_rect(rect, bg_color, BorderRadius::all(4.0));
+        painter.fill_rounded_rect(rect, bg_color, style.border_radius_or(BorderRadius::all(4.0)));
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Icon placeholder
         let icon_rect = Rect::new(
@@ -251,15 +261,15 @@ impl Widget for TaskbarButton {
             self.icon_size,
             self.icon_size,
         );
-        painter.fill_rounded_rect(icon_rect, Color::rgba(1.0, 1.0, 1.0, 0.3), BorderRadius::all(4.0));
+        painter.fill_rounded_rect(icon_rect, theme.colors.muted, BorderRadius::all(4.0));
 
         // App name (first char as placeholder)
         if let Some(c) = self.app_name.chars().next() {
             painter.draw_text(
                 &c.to_string(),
                 Point::new(icon_rect.x() + 6.0, icon_rect.y() + self.icon_size - 4.0),
-                Color::WHITE,
-                self.icon_size - 8.0,
+                style.color_or(theme.colors.foreground),
+                style.font_size_or(self.icon_size - 8.0),
             );
         }
 
@@ -272,27 +282,29 @@ impl Widget for TaskbarButton {
                 indicator_width,
                 3.0,
             );
-            painter.fill_rounded_rect(indicator_rect, self.accent_color, BorderRadius::all(1.5));
+            painter.fill_rounded_rect(indicator_rect, accent, BorderRadius::all(1.5));
         }
 
         // Progress bar
+        let track_x = rect.x() + padding.left;
+        let track_width = rect.width() - padding.horizontal();
         match self.progress {
             TaskbarProgress::None => {}
             TaskbarProgress::Indeterminate => {
-                let progress_rect = Rect::new(rect.x() + 4.0, rect.max_y() - 6.0, rect.width() - 8.0, 3.0);
-                painter.fill_rounded_rect(progress_rect, Color::rgba(0.0, 0.47, 0.84, 0.5), BorderRadius::all(1.5));
+                let progress_rect = Rect::new(track_x, rect.max_y() - 6.0, track_width, 3.0);
+                painter.fill_rounded_rect(progress_rect, accent.with_alpha(0.5), BorderRadius::all(1.5));
             }
             TaskbarProgress::Progress(p) | TaskbarProgress::Paused(p) => {
-                let progress_rect = Rect::new(rect.x() + 4.0, rect.max_y() - 6.0, (rect.width() - 8.0) * p, 3.0);
+                let progress_rect = Rect::new(track_x, rect.max_y() - 6.0, track_width * p, 3.0);
                 let color = match self.progress {
-                    TaskbarProgress::Paused(_) => Color::rgb(0.8, 0.6, 0.0),
-                    _ => Color::rgb(0.0, 0.6, 0.2),
+                    TaskbarProgress::Paused(_) => theme.colors.warning,
+                    _ => theme.colors.success,
                 };
                 painter.fill_rounded_rect(progress_rect, color, BorderRadius::all(1.5));
             }
             TaskbarProgress::Error(p) => {
-                let progress_rect = Rect::new(rect.x() + 4.0, rect.max_y() - 6.0, (rect.width() - 8.0) * p, 3.0);
-                painter.fill_rounded_rect(progress_rect, Color::rgb(0.8, 0.2, 0.2), BorderRadius::all(1.5));
+                let progress_rect = Rect::new(track_x, rect.max_y() - 6.0, track_width * p, 3.0);
+                painter.fill_rounded_rect(progress_rect, theme.colors.destructive, BorderRadius::all(1.5));
             }
         }
 
@@ -300,13 +312,13 @@ impl Widget for TaskbarButton {
         if let Some(count) = self.badge {
             let badge_size = 16.0;
             let badge_rect = Rect::new(rect.max_x() - badge_size - 2.0, rect.y() + 2.0, badge_size, badge_size);
-            painter.fill_rounded_rect(badge_rect, Color::rgb(0.8, 0.2, 0.2), BorderRadius::all(badge_size / 2.0));
+            painter.fill_rounded_rect(badge_rect, theme.colors.destructive, BorderRadius::all(badge_size / 2.0));
             
             let badge_text = if count > 99 { "99+".to_string() } else { count.to_string() };
             painter.draw_text(
                 &badge_text,
                 Point::new(badge_rect.x() + 3.0, badge_rect.y() + 12.0),
-                Color::WHITE,
+                theme.colors.destructive_foreground,
                 9.0,
             );
         }
@@ -0,0 +1,329 @@
+//! Cursor movement, selection editing and undo history shared by the
+//! text-editing widgets.
+//!
+//! Offsets are byte offsets into the edited text and always fall on
+//! grapheme boundaries.
+
+use super::EventContext;
+use crate::clipboard::EditCommand;
+
+use std::ops::Range;
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Longest gap between the clicks of a double click.
+pub(super) const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// Most edits kept for undo.
+const UNDO_LIMIT: usize = 100;
+
+/// Text, cursor and selection to return to on undo or redo.
+#[derive(Debug, Clone)]
+pub(super) struct Snapshot {
+    pub value: String,
+    pub cursor_position: usize,
+    pub selection_anchor: Option<usize>,
+}
+
+/// Kind of edit, so runs of typing or deleting undo as one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EditKind {
+    Typing,
+    Deleting,
+    Other,
+}
+
+/// Undo and redo stacks of a text widget.
+#[derive(Debug, Default)]
+pub(super) struct History {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+    /// Kind of the last edit, cleared when the cursor moves
+    last_edit: Option<EditKind>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save `current` for undo before an edit of `kind`.
+    ///
+    /// Consecutive typing or deleting is saved once, so it undoes in one
+    /// step.
+    pub fn record(&mut self, current: Snapshot, kind: EditKind) {
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            if self.undo_stack.len() == UNDO_LIMIT {
+                self.undo_stack.remove(0);
+            }
+            self.undo_stack.push(current);
+        }
+        self.redo_stack.clear();
+        self.last_edit = Some(kind);
+    }
+
+    /// End the current run of typing or deleting.
+    pub fn break_run(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// The state before the last edit, if any.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+
+    /// The state before the last undo, if any.
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+}
+
+/// A text widget's value, cursor and selection, borrowed for editing.
+pub(super) struct Editor<'a> {
+    pub text: &'a mut String,
+    /// Byte offset of the cursor
+    pub cursor: &'a mut usize,
+    /// Other end of the selection, the cursor being one end
+    pub anchor: &'a mut Option<usize>,
+}
+
+impl Editor<'_> {
+    /// Byte range of the selected text, if any.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        selection(*self.cursor, *self.anchor)
+    }
+
+    /// Remove the selected text, returning whether there was any.
+    pub fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        *self.anchor = None;
+        let Some(range) = selection else {
+            return false;
+        };
+        self.text.replace_range(range.clone(), "");
+        *self.cursor = range.start;
+        true
+    }
+
+    /// Insert `text` at the cursor, replacing the selection.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(*self.cursor, text);
+        *self.cursor += text.len();
+    }
+
+    /// Run a clipboard shortcut, passing pasted text through `clean` first.
+    ///
+    /// Returns whether the text changed.
+    pub fn run(&mut self, command: EditCommand, ctx: &mut EventContext, clean: impl FnOnce(&str) -> String) -> bool {
+        match command {
+            EditCommand::SelectAll => {
+                *self.anchor = Some(0);
+                *self.cursor = self.text.len();
+                false
+            }
+            EditCommand::Copy => {
+                if let Some(range) = self.selection() {
+                    ctx.set_clipboard_text(&self.text[range]);
+                }
+                false
+            }
+            EditCommand::Cut => match self.selection() {
+                Some(range) => {
+                    ctx.set_clipboard_text(&self.text[range]);
+                    self.delete_selection()
+                }
+                None => false,
+            },
+            EditCommand::Paste => match ctx.clipboard_text() {
+                Some(text) => {
+                    self.insert(&clean(&text));
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Byte range between `cursor` and the selection `anchor`, if it isn't
+/// empty.
+pub(super) fn selection(cursor: usize, anchor: Option<usize>) -> Option<Range<usize>> {
+    let anchor = anchor?;
+    let range = anchor.min(cursor)..anchor.max(cursor);
+    (!range.is_empty()).then_some(range)
+}
+
+/// Byte offset of the grapheme boundary before `offset`.
+pub(super) fn prev_grapheme(text: &str, offset: usize) -> usize {
+    text[..offset].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+}
+
+/// Byte offset of the grapheme boundary after `offset`.
+pub(super) fn next_grapheme(text: &str, offset: usize) -> usize {
+    text[offset..].graphemes(true).next().map_or(offset, |g| offset + g.len())
+}
+
+/// Whether a word-bounded segment is a word rather than spaces or punctuation.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+/// Start of the word before `offset`.
+pub(super) fn prev_word(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .split_word_bound_indices()
+        .rev()
+        .find(|(_, segment)| is_word(segment))
+        .map_or(0, |(i, _)| i)
+}
+
+/// End of the word after `offset`.
+pub(super) fn next_word(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .split_word_bound_indices()
+        .find(|(_, segment)| is_word(segment))
+        .map_or(text.len(), |(i, segment)| offset + i + segment.len())
+}
+
+/// Byte range of the word, or run of spaces or punctuation, at `offset`.
+pub(super) fn word_at(text: &str, offset: usize) -> Range<usize> {
+    text.split_word_bound_indices()
+        .find(|(i, segment)| i + segment.len() > offset)
+        .or_else(|| text.split_word_bound_indices().next_back())
+        .map_or(offset..offset, |(i, segment)| i..i + segment.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A family emoji: four people joined by zero-width joiners.
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+
+    fn snapshot(value: &str) -> Snapshot {
+        Snapshot {
+            value: value.to_string(),
+            cursor_position: value.len(),
+            selection_anchor: None,
+        }
+    }
+
+    #[test]
+    fn test_grapheme_boundaries() {
+        let text = format!("aé{FAMILY}b");
+        let family_start = "aé".len();
+        let family_end = family_start + FAMILY.len();
+
+        assert_eq!(next_grapheme(&text, 1), family_start);
+        assert_eq!(prev_grapheme(&text, family_start), 1);
+        // The joined emoji is stepped over whole
+        assert_eq!(next_grapheme(&text, family_start), family_end);
+        assert_eq!(prev_grapheme(&text, family_end), family_start);
+        // Ends of the text stay put
+        assert_eq!(prev_grapheme(&text, 0), 0);
+        assert_eq!(next_grapheme(&text, text.len()), text.len());
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let text = "hello, wörld  again";
+        assert_eq!(next_word(text, 0), 5);
+        // Punctuation and spaces are skipped to the end of the next word
+        assert_eq!(next_word(text, 5), "hello, wörld".len());
+        assert_eq!(next_word(text, "hello, wörld  again".len()), text.len());
+
+        assert_eq!(prev_word(text, text.len()), "hello, wörld  ".len());
+        assert_eq!(prev_word(text, "hello, wörld  ".len()), "hello, ".len());
+        assert_eq!(prev_word(text, 3), 0);
+        assert_eq!(prev_word("  ", 2), 0);
+    }
+
+    #[test]
+    fn test_word_at() {
+        let text = "hello, wörld";
+        assert_eq!(word_at(text, 2), 0..5);
+        assert_eq!(word_at(text, 5), 5..6);
+        assert_eq!(word_at(text, "hello, w".len()), "hello, ".len()..text.len());
+        // Past the end selects the last word
+        assert_eq!(word_at(text, text.len()), "hello, ".len()..text.len());
+        assert_eq!(word_at("", 0), 0..0);
+    }
+
+    #[test]
+    fn test_editor_clipboard_commands() {
+        let mut ctx = EventContext::new();
+        let mut text = String::from("hello world");
+        let mut cursor = 5;
+        let mut anchor = Some(0);
+        let mut editor = Editor {
+            text: &mut text,
+            cursor: &mut cursor,
+            anchor: &mut anchor,
+        };
+        assert_eq!(editor.selection(), Some(0..5));
+
+        assert!(editor.run(EditCommand::Cut, &mut ctx, str::to_string));
+        assert_eq!(editor.text.as_str(), " world");
+        assert_eq!((*editor.cursor, *editor.anchor), (0, None));
+        assert!(!editor.run(EditCommand::Cut, &mut ctx, str::to_string));
+
+        ctx.set_clipboard_text(" two\nlines ");
+        assert!(editor.run(EditCommand::Paste, &mut ctx, |text| text.trim().replace('\n', " ")));
+        assert_eq!(editor.text.as_str(), "two lines world");
+        assert_eq!(*editor.cursor, "two lines".len());
+
+        assert!(!editor.run(EditCommand::SelectAll, &mut ctx, str::to_string));
+        assert!(!editor.run(EditCommand::Copy, &mut ctx, str::to_string));
+        assert_eq!(ctx.clipboard_text().as_deref(), Some("two lines world"));
+        editor.insert("new");
+        assert_eq!(editor.text.as_str(), "new");
+        assert_eq!(editor.selection(), None);
+    }
+
+    #[test]
+    fn test_typing_run_undoes_as_one_step() {
+        let mut history = History::new();
+        history.record(snapshot(""), EditKind::Typing);
+        history.record(snapshot("a"), EditKind::Typing);
+        history.record(snapshot("ab"), EditKind::Typing);
+
+        // Deleting starts a new run
+        history.record(snapshot("abc"), EditKind::Deleting);
+        history.record(snapshot("ab"), EditKind::Deleting);
+
+        let restored = history.undo(snapshot("a")).unwrap();
+        assert_eq!(restored.value, "abc");
+        let restored = history.undo(restored).unwrap();
+        assert_eq!(restored.value, "");
+        assert!(history.undo(restored.clone()).is_none());
+
+        let redone = history.redo(restored).unwrap();
+        assert_eq!(redone.value, "abc");
+        let redone = history.redo(redone).unwrap();
+        assert_eq!(redone.value, "a");
+        assert!(history.redo(redone.clone()).is_none());
+
+        // A new edit after undo drops the redo stack
+        let restored = history.undo(redone).unwrap();
+        history.record(restored, EditKind::Typing);
+        assert!(history.redo(snapshot("abcx")).is_none());
+    }
+
+    #[test]
+    fn test_break_run_starts_new_step() {
+        let mut history = History::new();
+        history.record(snapshot(""), EditKind::Typing);
+        history.break_run();
+        history.record(snapshot("a"), EditKind::Typing);
+
+        assert_eq!(history.undo(snapshot("ab")).unwrap().value, "a");
+        assert_eq!(history.undo(snapshot("a")).unwrap().value, "");
+    }
+}
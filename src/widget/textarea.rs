@@ -0,0 +1,878 @@
+//! Multi-line text area widget.
+
+use super::text_edit::{
+    next_grapheme, next_word, prev_grapheme, prev_word, selection, word_at, EditKind, Editor, History,
+    Snapshot, DOUBLE_CLICK_TIME,
+};
+use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
+use crate::clipboard::EditCommand;
+use crate::css::{ClassList, WidgetState};
+use crate::event::{Event, EventResult, ImeEvent, Key, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
+use crate::layout::{Constraints, LayoutResult};
+use crate::render::{Painter, SpanStyle, TextLayout};
+
+use std::ops::Range;
+use std::time::Instant;
+
+/// A multi-line text input widget.
+///
+/// Long lines wrap at word boundaries. The area grows with its text between
+/// a minimum and maximum number of rows, then scrolls. Enter inserts a line
+/// break and Ctrl+Enter (Cmd+Enter on macOS) submits.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use openkit::prelude::*;
+///
+/// let message = TextArea::new()
+///     .placeholder("Commit message")
+///     .rows(3, 12)
+///     .on_submit(|text| println!("{}", text));
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct TextArea {
+    base: WidgetBase,
+    value: String,
+    placeholder: String,
+    on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    on_submit: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    /// Byte offset of the cursor, always on a grapheme boundary
+    cursor_position: usize,
+    /// Other end of the selection, the cursor being one end
+    selection_anchor: Option<usize>,
+    /// Caret x kept while moving up and down through shorter lines
+    preferred_x: Option<f32>,
+    history: History,
+    /// Time and position of the last click, for double clicks
+    last_click: Option<(Instant, Point)>,
+    /// Text being composed by the input method, shown at the cursor
+    preedit: String,
+    min_rows: usize,
+    max_rows: usize,
+    /// How far the text is scrolled up, in pixels
+    scroll_offset: f32,
+    /// Padding, font and wrapping width resolved at layout
+    padding: EdgeInsets,
+    font_size: f32,
+    font: SpanStyle,
+    text_width: f32,
+}
+
+impl TextArea {
+    /// Padding used when CSS doesn't set one.
+    const PADDING: EdgeInsets = EdgeInsets::symmetric(8.0, 12.0);
+    /// Font size used when CSS doesn't set one.
+    const FONT_SIZE: f32 = 14.0;
+    /// Width used when the parent doesn't set one.
+    const WIDTH: f32 = 300.0;
+    /// Lines scrolled per mouse wheel step.
+    const SCROLL_LINES: f32 = 3.0;
+
+    pub fn new() -> Self {
+        Self {
+            base: WidgetBase::new().with_class("textarea"),
+            value: String::new(),
+            placeholder: String::new(),
+            on_change: None,
+            on_submit: None,
+            cursor_position: 0,
+            selection_anchor: None,
+            preferred_x: None,
+            history: History::new(),
+            last_click: None,
+            preedit: String::new(),
+            min_rows: 2,
+            max_rows: 8,
+            scroll_offset: 0.0,
+            padding: Self::PADDING,
+            font_size: Self::FONT_SIZE,
+            font: SpanStyle::default(),
+            text_width: Self::WIDTH - Self::PADDING.horizontal(),
+        }
+    }
+
+    /// Set the initial value.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self.cursor_position = self.value.len();
+        self
+    }
+
+    /// Set the placeholder text.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Grow with the text from `min` to `max` rows, then scroll.
+    pub fn rows(mut self, min: usize, max: usize) -> Self {
+        self.min_rows = min.max(1);
+        self.max_rows = max.max(self.min_rows);
+        self
+    }
+
+    /// Set the change handler.
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Set the submit handler (called on Ctrl+Enter).
+    pub fn on_submit<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_submit = Some(Box::new(handler));
+        self
+    }
+
+    /// Add a CSS class.
+    pub fn class(mut self, class: &str) -> Self {
+        self.base.classes.add(class);
+        self
+    }
+
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
+    /// Set the element ID.
+    pub fn id(mut self, id: &str) -> Self {
+        self.base.element_id = Some(id.to_string());
+        self
+    }
+
+    /// Get the current value.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    /// Set the value.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor_position = self.value.len();
+        self.selection_anchor = None;
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
+        }
+    }
+
+    /// Revert the last edit.
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    /// Reapply the last undone edit.
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    fn line_height(&self) -> f32 {
+        self.font_size * 1.2
+    }
+
+    /// The value laid out at the current width.
+    fn text_layout(&self) -> TextLayout {
+        TextLayout::styled_wrapped(&self.value, self.font_size, self.text_width, &self.font)
+    }
+
+    /// Rows to show for a layout of the text.
+    fn rows_for(&self, layout: &TextLayout) -> usize {
+        layout.line_count().clamp(self.min_rows, self.max_rows)
+    }
+
+    /// Height of the visible part of the text.
+    fn visible_height(&self) -> f32 {
+        (self.base.bounds.height() - self.padding.vertical()).max(0.0)
+    }
+
+    /// Keep the scroll offset within the text.
+    fn clamp_scroll(&mut self, layout: &TextLayout) {
+        let max_scroll = (layout.size().height - self.visible_height()).max(0.0);
+        self.scroll_offset = self.scroll_offset.clamp(0.0, max_scroll);
+    }
+
+    /// Scroll so the cursor's line is visible.
+    fn scroll_to_cursor(&mut self) {
+        let layout = self.text_layout();
+        let caret_y = layout.caret_position(self.cursor_position).y;
+        if caret_y < self.scroll_offset {
+            self.scroll_offset = caret_y;
+        } else if caret_y + self.line_height() > self.scroll_offset + self.visible_height() {
+            self.scroll_offset = caret_y + self.line_height() - self.visible_height();
+        }
+        self.clamp_scroll(&layout);
+    }
+
+    /// Byte offset of the caret position nearest to window point `point`.
+    fn offset_at(&self, point: Point) -> usize {
+        let bounds = self.bounds();
+        self.text_layout().hit_test(Point::new(
+            point.x - bounds.x() - self.padding.left,
+            point.y - bounds.y() - self.padding.top + self.scroll_offset,
+        ))
+    }
+
+    /// Byte range of the selected text, if any.
+    fn selection(&self) -> Option<Range<usize>> {
+        selection(self.cursor_position, self.selection_anchor)
+    }
+
+    /// The value, cursor and selection, for editing.
+    fn editor(&mut self) -> Editor<'_> {
+        Editor {
+            text: &mut self.value,
+            cursor: &mut self.cursor_position,
+            anchor: &mut self.selection_anchor,
+        }
+    }
+
+    /// Move the cursor to `position`, extending the selection if `extend`.
+    fn move_cursor(&mut self, position: usize, extend: bool) {
+        if extend {
+            self.selection_anchor.get_or_insert(self.cursor_position);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor_position = position;
+        self.preferred_x = None;
+        self.history.break_run();
+    }
+
+    /// Move the cursor `lines` visual lines up (negative) or down, keeping
+    /// its horizontal position.
+    fn move_vertically(&mut self, lines: f32, extend: bool) {
+        let layout = self.text_layout();
+        let caret = layout.caret_position(self.cursor_position);
+        let x = self.preferred_x.unwrap_or(caret.x);
+        let y = caret.y + lines * self.line_height() + self.line_height() / 2.0;
+        let position = if y < 0.0 {
+            0
+        } else if y > layout.size().height {
+            self.value.len()
+        } else {
+            layout.hit_test(Point::new(x, y))
+        };
+        self.move_cursor(position, extend);
+        self.preferred_x = Some(x);
+    }
+
+    /// Byte range of the visual line holding the cursor.
+    fn cursor_line(&self) -> Range<usize> {
+        self.text_layout()
+            .lines()
+            .into_iter()
+            .rev()
+            .map(|(range, _)| range)
+            .find(|range| range.start <= self.cursor_position)
+            .unwrap_or(0..self.value.len())
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            value: self.value.clone(),
+            cursor_position: self.cursor_position,
+            selection_anchor: self.selection_anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.value = snapshot.value;
+        self.cursor_position = snapshot.cursor_position;
+        self.selection_anchor = snapshot.selection_anchor;
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
+        }
+    }
+
+    /// Save the current state for undo before an edit of `kind`.
+    fn record(&mut self, kind: EditKind) {
+        let snapshot = self.snapshot();
+        self.history.record(snapshot, kind);
+    }
+
+    /// Run a clipboard shortcut.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) {
+        let before = self.snapshot();
+        let changed = self
+            .editor()
+            .run(command, ctx, |text| text.replace("\r\n", "\n").replace('\r', "\n"));
+        if changed {
+            self.preferred_x = None;
+            self.history.record(before, EditKind::Other);
+            if let Some(handler) = &self.on_change {
+                handler(&self.value);
+            }
+        } else {
+            self.history.break_run();
+        }
+    }
+
+    /// Insert text at the cursor position, replacing the selection.
+    fn insert_text(&mut self, text: &str, kind: EditKind) {
+        self.record(kind);
+        self.editor().insert(text);
+        self.preferred_x = None;
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
+        }
+    }
+
+    /// Delete the selection if there is one, otherwise `range`.
+    fn delete_range(&mut self, range: Range<usize>) {
+        if self.selection().is_some() {
+            self.record(EditKind::Other);
+            self.editor().delete_selection();
+        } else if !range.is_empty() {
+            self.record(EditKind::Deleting);
+            self.value.replace_range(range.clone(), "");
+            self.cursor_position = range.start;
+        } else {
+            return;
+        }
+        self.preferred_x = None;
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
+        }
+    }
+
+    /// Handle a key press, returning whether it was used.
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        // Word-wise movement uses Alt on macOS and Ctrl elsewhere
+        let word = if cfg!(target_os = "macos") {
+            key.modifiers.alt
+        } else {
+            key.modifiers.control
+        };
+        let shift = key.modifiers.shift;
+        match &key.key {
+            Key::Z if key.modifiers.command() => {
+                if shift {
+                    self.redo();
+                } else {
+                    self.undo();
+                }
+            }
+            Key::Y if key.modifiers.command() => self.redo(),
+            Key::Enter | Key::NumpadEnter if key.modifiers.command() => {
+                if let Some(handler) = &self.on_submit {
+                    handler(&self.value);
+                }
+            }
+            Key::Enter | Key::NumpadEnter => self.insert_text("\n", EditKind::Other),
+            Key::Backspace => {
+                let start = if word {
+                    prev_word(&self.value, self.cursor_position)
+                } else {
+                    prev_grapheme(&self.value, self.cursor_position)
+                };
+                self.delete_range(start..self.cursor_position);
+            }
+            Key::Delete => {
+                let end = if word {
+                    next_word(&self.value, self.cursor_position)
+                } else {
+                    next_grapheme(&self.value, self.cursor_position)
+                };
+                self.delete_range(self.cursor_position..end);
+            }
+            Key::Left => {
+                let position = match self.selection() {
+                    Some(range) if !shift => range.start,
+                    _ if word => prev_word(&self.value, self.cursor_position),
+                    _ => prev_grapheme(&self.value, self.cursor_position),
+                };
+                self.move_cursor(position, shift);
+            }
+            Key::Right => {
+                let position = match self.selection() {
+                    Some(range) if !shift => range.end,
+                    _ if word => next_word(&self.value, self.cursor_position),
+                    _ => next_grapheme(&self.value, self.cursor_position),
+                };
+                self.move_cursor(position, shift);
+            }
+            Key::Up => self.move_vertically(-1.0, shift),
+            Key::Down => self.move_vertically(1.0, shift),
+            Key::PageUp => {
+                let rows = (self.visible_height() / self.line_height()).floor().max(1.0);
+                self.move_vertically(-rows, shift);
+            }
+            Key::PageDown => {
+                let rows = (self.visible_height() / self.line_height()).floor().max(1.0);
+                self.move_vertically(rows, shift);
+            }
+            Key::Home if key.modifiers.control => self.move_cursor(0, shift),
+            Key::End if key.modifiers.control => self.move_cursor(self.value.len(), shift),
+            Key::Home => {
+                let line = self.cursor_line();
+                self.move_cursor(line.start, shift);
+            }
+            Key::End => {
+                let line = self.cursor_line();
+                self.move_cursor(line.end, shift);
+            }
+            // Tab moves focus
+            Key::Tab => return false,
+            _ => {
+                // Handle text input
+                match &key.text {
+                    Some(text) if !text.is_empty() && !key.modifiers.control && !key.modifiers.alt => {
+                        self.insert_text(text, EditKind::Typing);
+                    }
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Follow an edit or cursor movement: keep the cursor in view and grow or
+    /// shrink to fit the text.
+    fn update_view(&mut self, ctx: &mut EventContext) {
+        let layout = self.text_layout();
+        let rows = (self.visible_height() / self.line_height()).round() as usize;
+        if self.rows_for(&layout) != rows {
+            ctx.request_layout();
+        }
+        self.scroll_to_cursor();
+        ctx.request_redraw();
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for TextArea {
+    fn id(&self) -> WidgetId {
+        self.base.id
+    }
+
+    fn type_name(&self) -> &'static str {
+        "textarea"
+    }
+
+    fn element_id(&self) -> Option<&str> {
+        self.base.element_id.as_deref()
+    }
+
+    fn classes(&self) -> &ClassList {
+        &self.base.classes
+    }
+
+    fn state(&self) -> WidgetState {
+        self.base.state
+    }
+
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        let style = ctx.style(self);
+        let padding = style.padding_or(Self::PADDING);
+        let font_size = style.font_size_or(Self::FONT_SIZE);
+        let layout = TextLayout::styled_wrapped(&self.value, font_size, Self::WIDTH - padding.horizontal(), &style.font());
+        let rows = self.rows_for(&layout);
+        Size::new(Self::WIDTH, rows as f32 * font_size * 1.2 + padding.vertical())
+    }
+
+    fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.padding = style.padding_or(Self::PADDING);
+        self.font_size = style.font_size_or(Self::FONT_SIZE);
+        self.font = style.font();
+
+        // Wrap at the width the parent allows, then fit the rows to the text
+        let width = constraints.constrain(Size::new(Self::WIDTH, 0.0)).width;
+        self.text_width = (width - self.padding.horizontal()).max(0.0);
+        let layout = self.text_layout();
+        let height = self.rows_for(&layout) as f32 * self.line_height() + self.padding.vertical();
+
+        let size = constraints.constrain(Size::new(width, height));
+        self.base.bounds.size = size;
+        self.clamp_scroll(&layout);
+        LayoutResult::new(size)
+    }
+
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(6.0));
+        let padding = style.padding_or(Self::PADDING);
+        let font_size = style.font_size_or(Self::FONT_SIZE);
+        let line_height = font_size * 1.2;
+
+        // Background
+        let bg_color = style.background_or(if self.base.state.focused {
+            theme.colors.background
+        } else {
+            theme.colors.input
+        });
+        painter.fill_rounded_rect(rect, bg_color, radius);
+
+        // Border
+        let border_color = style.border_color_or(if self.base.state.focused {
+            theme.colors.ring
+        } else if self.base.state.hovered {
+            theme.colors.border.darken(10.0)
+        } else {
+            theme.colors.border
+        });
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rounded_rect(rect, border_color, border_width, radius);
+        }
+
+        let text_rect = Rect::new(
+            rect.x() + padding.left,
+            rect.y() + padding.top,
+            (rect.width() - padding.horizontal()).max(0.0),
+            (rect.height() - padding.vertical()).max(0.0),
+        );
+        let origin = Point::new(text_rect.x(), text_rect.y() - self.scroll_offset);
+        painter.push_clip(text_rect);
+
+        // Lay out the value, with the composed text at the cursor
+        let composing = self.base.state.focused && !self.preedit.is_empty();
+        let mut text = self.value.clone();
+        if composing {
+            text.insert_str(self.cursor_position, &self.preedit);
+        }
+        let layout = TextLayout::styled_wrapped(&text, font_size, text_rect.width(), &style.font());
+
+        // Selection highlight
+        if let Some(range) = self.selection().filter(|_| self.base.state.focused && !composing) {
+            for selection in layout.selection_rects(range) {
+                painter.fill_rect(
+                    Rect::new(origin.x + selection.x(), origin.y + selection.y(), selection.width(), selection.height()),
+                    theme.colors.primary.with_alpha(0.3),
+                );
+            }
+        }
+
+        if text.is_empty() {
+            // Show placeholder
+            painter.draw_text(
+                &self.placeholder,
+                Point::new(text_rect.x(), text_rect.y() + font_size * 0.8),
+                theme.colors.muted_foreground,
+                font_size,
+            );
+        } else {
+            // Show the visible lines
+            let color = style.color_or(theme.colors.foreground);
+            for (range, top) in layout.lines() {
+                let y = origin.y + top;
+                if y + line_height < text_rect.y() || y > text_rect.max_y() || range.is_empty() {
+                    continue;
+                }
+                painter.draw_text(&text[range], Point::new(origin.x, y + font_size * 0.8), color, font_size);
+            }
+        }
+
+        // Underline the composed text
+        if composing {
+            for underline in layout.selection_rects(self.cursor_position..self.cursor_position + self.preedit.len()) {
+                let y = origin.y + underline.max_y() - 1.0;
+                painter.draw_line(
+                    Point::new(origin.x + underline.x(), y),
+                    Point::new(origin.x + underline.max_x(), y),
+                    style.color_or(theme.colors.foreground),
+                    1.0,
+                );
+            }
+        }
+
+        // Cursor (when focused), after the composed text while composing
+        if self.base.state.focused {
+            let offset = self.cursor_position + if composing { self.preedit.len() } else { 0 };
+            let caret = layout.caret_position(offset);
+            let cursor_x = origin.x + caret.x;
+            let cursor_y = origin.y + caret.y + (line_height - font_size) / 2.0;
+            painter.draw_line(
+                Point::new(cursor_x, cursor_y),
+                Point::new(cursor_x, cursor_y + font_size),
+                theme.colors.foreground,
+                1.0,
+            );
+            ctx.set_ime_cursor_area(painter, Rect::new(cursor_x, cursor_y, 1.0, font_size));
+        }
+
+        painter.pop_clip();
+
+        // Scroll indicator when the text overflows
+        let content_height = layout.size().height;
+        if content_height > text_rect.height() {
+            let thumb_height = (text_rect.height() * text_rect.height() / content_height).max(12.0);
+            let travel = text_rect.height() - thumb_height;
+            let max_scroll = content_height - text_rect.height();
+            let thumb_y = text_rect.y() + travel * (self.scroll_offset / max_scroll).min(1.0);
+            painter.fill_rounded_rect(
+                Rect::new(rect.max_x() - 6.0, thumb_y, 3.0, thumb_height),
+                theme.colors.muted_foreground.with_alpha(0.5),
+                BorderRadius::all(1.5),
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            // Composition does not carry over to another widget
+            self.preedit.clear();
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
+        match event {
+            Event::Mouse(mouse) => {
+                let in_bounds = self.bounds().contains(mouse.position);
+
+                match mouse.kind {
+                    // Dragging selects
+                    MouseEventKind::Move if ctx.has_pointer_capture(self.base.id) => {
+                        let position = self.offset_at(mouse.position);
+                        if position != self.cursor_position {
+                            self.cursor_position = position;
+                            self.scroll_to_cursor();
+                            ctx.request_redraw();
+                        }
+                        return EventResult::Handled;
+                    }
+                    MouseEventKind::Enter | MouseEventKind::Move if in_bounds != self.base.state.hovered => {
+                        self.base.state.hovered = in_bounds;
+                        ctx.request_redraw();
+                    }
+                    MouseEventKind::Leave if self.base.state.hovered => {
+                        self.base.state.hovered = false;
+                        ctx.request_redraw();
+                    }
+                    MouseEventKind::Down if in_bounds && mouse.button == Some(MouseButton::Left) => {
+                        ctx.request_focus(self.base.id);
+
+                        let position = self.offset_at(mouse.position);
+                        let now = Instant::now();
+                        let double_click = self.last_click.is_some_and(|(time, point)| {
+                            now.duration_since(time) < DOUBLE_CLICK_TIME
+                                && (point.x - mouse.position.x).abs() < 4.0
+                                && (point.y - mouse.position.y).abs() < 4.0
+                        });
+
+                        if double_click {
+                            // Select the word under the pointer
+                            let word = word_at(&self.value, position);
+                            self.selection_anchor = Some(word.start);
+                            self.cursor_position = word.end;
+                            self.last_click = None;
+                        } else {
+                            // Shift-click extends the selection, a drag starts one
+                            let anchor = if mouse.modifiers.shift {
+                                self.selection_anchor.unwrap_or(self.cursor_position)
+                            } else {
+                                position
+                            };
+                            self.selection_anchor = Some(anchor);
+                            self.cursor_position = position;
+                            self.last_click = Some((now, mouse.position));
+                            ctx.capture_pointer(self.base.id);
+                        }
+                        self.preferred_x = None;
+                        self.history.break_run();
+
+                        ctx.request_redraw();
+                        return EventResult::Handled;
+                    }
+                    MouseEventKind::Scroll { delta_y, .. } if in_bounds => {
+                        // Wheel steps arrive as multiples of 120
+                        let layout = self.text_layout();
+                        let before = self.scroll_offset;
+                        self.scroll_offset -= delta_y as f32 / 120.0 * Self::SCROLL_LINES * self.line_height();
+                        self.clamp_scroll(&layout);
+                        if self.scroll_offset != before {
+                            ctx.request_redraw();
+                            return EventResult::Handled;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Ime(ime) if self.base.state.focused => {
+                match ime {
+                    ImeEvent::Preedit { text, .. } => self.preedit = text.clone(),
+                    ImeEvent::Commit(text) => {
+                        self.preedit.clear();
+                        self.insert_text(text, EditKind::Typing);
+                    }
+                    ImeEvent::Enabled | ImeEvent::Disabled => self.preedit.clear(),
+                }
+                self.update_view(ctx);
+                return EventResult::Handled;
+            }
+            Event::Key(key) if self.base.state.focused => {
+                if let Some(command) = EditCommand::from_key(key) {
+                    self.edit(command, ctx);
+                    self.update_view(ctx);
+                    return EventResult::Handled;
+                }
+                if key.kind == KeyEventKind::Down && self.handle_key(key) {
+                    self.update_view(ctx);
+                    return EventResult::Handled;
+                }
+            }
+            _ => {}
+        }
+        EventResult::Ignored
+    }
+
+    fn bounds(&self) -> Rect {
+        self.base.bounds
+    }
+
+    fn set_bounds(&mut self, bounds: Rect) {
+        self.base.bounds = bounds;
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::StyleContext;
+    use crate::event::{FocusEvent, Modifiers};
+    use crate::theme::ThemeData;
+    use std::sync::{Arc, Mutex};
+
+    fn key(key: Key, modifiers: Modifiers) -> Event {
+        Event::Key(KeyEvent {
+            kind: KeyEventKind::Down,
+            key,
+            physical_key: None,
+            text: None,
+            modifiers,
+            is_repeat: false,
+        })
+    }
+
+    fn press(area: &mut TextArea, ctx: &mut EventContext, k: Key) {
+        area.handle_event(&key(k, Modifiers::empty()), ctx);
+    }
+
+    fn command() -> Modifiers {
+        if cfg!(target_os = "macos") {
+            Modifiers { super_key: true, ..Modifiers::empty() }
+        } else {
+            Modifiers { control: true, ..Modifiers::empty() }
+        }
+    }
+
+    /// Lay out `area` with the default styles and return its height.
+    fn layout(area: &mut TextArea) -> f32 {
+        let theme = ThemeData::light();
+        let style_ctx = StyleContext::new(&theme);
+        let ctx = LayoutContext::new(&style_ctx);
+        area.layout(Constraints::loose(Size::new(300.0, 1000.0)), &ctx).size.height
+    }
+
+    fn focused(mut area: TextArea) -> (TextArea, EventContext) {
+        let mut ctx = EventContext::new();
+        layout(&mut area);
+        let focus = Event::Focus(FocusEvent::FocusIn { widget_id: Widget::id(&area) });
+        area.handle_event(&focus, &mut ctx);
+        (area, ctx)
+    }
+
+    #[test]
+    fn test_vertical_movement_keeps_column() {
+        let (mut area, mut ctx) = focused(TextArea::new().value("abcdef\nx\nabcdef"));
+        assert_eq!(area.cursor_position, 15);
+
+        // The short middle line puts the caret at its end, but the column is
+        // remembered for the long line above
+        press(&mut area, &mut ctx, Key::Up);
+        assert_eq!(area.cursor_position, 8);
+        press(&mut area, &mut ctx, Key::Up);
+        assert_eq!(area.cursor_position, 6);
+        press(&mut area, &mut ctx, Key::Down);
+        press(&mut area, &mut ctx, Key::Down);
+        assert_eq!(area.cursor_position, 15);
+
+        // Horizontal movement picks a new column
+        press(&mut area, &mut ctx, Key::Left);
+        press(&mut area, &mut ctx, Key::Left);
+        press(&mut area, &mut ctx, Key::Up);
+        press(&mut area, &mut ctx, Key::Up);
+        assert_eq!(area.cursor_position, 4);
+    }
+
+    #[test]
+    fn test_enter_inserts_newline_and_command_enter_submits() {
+        let submitted = Arc::new(Mutex::new(None));
+        let sink = submitted.clone();
+        let area = TextArea::new()
+            .value("one")
+            .on_submit(move |text| *sink.lock().unwrap() = Some(text.to_string()));
+        let (mut area, mut ctx) = focused(area);
+
+        press(&mut area, &mut ctx, Key::Enter);
+        assert_eq!(area.get_value(), "one\n");
+        assert!(submitted.lock().unwrap().is_none());
+
+        area.handle_event(&key(Key::Enter, command()), &mut ctx);
+        assert_eq!(area.get_value(), "one\n");
+        assert_eq!(submitted.lock().unwrap().as_deref(), Some("one\n"));
+    }
+
+    #[test]
+    fn test_rows_grow_with_text_within_limits() {
+        let two = layout(&mut TextArea::new().rows(2, 4));
+        let one_line = layout(&mut TextArea::new().rows(2, 4).value("a"));
+        let three = layout(&mut TextArea::new().rows(2, 4).value("a\nb\nc"));
+        let four = layout(&mut TextArea::new().rows(2, 4).value("a\nb\nc\nd"));
+        let six_lines = layout(&mut TextArea::new().rows(2, 4).value("a\nb\nc\nd\ne\nf"));
+
+        assert_eq!(one_line, two);
+        assert!(three > two);
+        assert!((four - three - (three - two)).abs() < 0.01);
+        assert_eq!(six_lines, four);
+
+        // Growing past a row asks the parent for a new layout
+        let (mut area, mut ctx) = focused(TextArea::new().rows(2, 4).value("a\nb"));
+        press(&mut area, &mut ctx, Key::Enter);
+        assert!(ctx.should_layout);
+    }
+
+    #[test]
+    fn test_cursor_scrolls_into_view() {
+        let (mut area, mut ctx) = focused(TextArea::new().rows(2, 2).value("1\n2\n3\n4\n5\n6"));
+        let line_height = area.line_height();
+
+        // Rows beyond the limit scroll, keeping the cursor's line visible
+        area.handle_event(&key(Key::Home, Modifiers { control: true, ..Modifiers::empty() }), &mut ctx);
+        assert_eq!(area.scroll_offset, 0.0);
+        area.handle_event(&key(Key::End, Modifiers { control: true, ..Modifiers::empty() }), &mut ctx);
+        let caret_y = area.text_layout().caret_position(area.cursor_position).y;
+        assert!(area.scroll_offset > 0.0);
+        assert!(caret_y >= area.scroll_offset);
+        assert!(caret_y + line_height <= area.scroll_offset + area.visible_height() + 0.01);
+
+        // Moving up to a hidden line scrolls back just enough to show it
+        for _ in 0..3 {
+            press(&mut area, &mut ctx, Key::Up);
+        }
+        let caret_y = area.text_layout().caret_position(area.cursor_position).y;
+        assert_eq!(area.scroll_offset, caret_y);
+    }
+}
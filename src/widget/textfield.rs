@@ -1,11 +1,20 @@
 //! Text field widget.
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
+use crate::clipboard::{single_line, EditCommand};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult, MouseEventKind, MouseButton, KeyEventKind, Key};
-use crate::geometry::{BorderRadius, Point, Rect, Size};
+use crate::event::{Event, EventResult, ImeEvent, MouseEventKind, MouseButton, KeyEventKind, Key};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, SpanStyle, TextLayout};
+
+use super::text_edit::{
+    next_grapheme, next_word, prev_grapheme, prev_word, selection, word_at, EditKind, Editor, History,
+    Snapshot, DOUBLE_CLICK_TIME,
+};
+
+use std::ops::Range;
+use std::time::Instant;
 
 /// A single-line text input widget.
 #[allow(clippy::type_complexity)]
@@ -15,10 +24,29 @@ pub struct TextField {
     placeholder: String,
     on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
     on_submit: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    /// Byte offset of the cursor, always on a grapheme boundary
     cursor_position: usize,
+    /// Other end of the selection, the cursor being one end
+    selection_anchor: Option<usize>,
+    history: History,
+    /// Time and position of the last click, for double clicks
+    last_click: Option<(Instant, Point)>,
+    /// Text being composed by the input method, shown at the cursor
+    preedit: String,
+    /// Byte offset of the input method's cursor within `preedit`
+    preedit_cursor: Option<usize>,
+    /// Padding and font resolved at layout, for mapping clicks to the cursor
+    padding: EdgeInsets,
+    font_size: f32,
+    font: SpanStyle,
 }
 
 impl TextField {
+    /// Padding used when CSS doesn't set one.
+    const PADDING: EdgeInsets = EdgeInsets::symmetric(8.0, 12.0);
+    /// Font size used when CSS doesn't set one.
+    const FONT_SIZE: f32 = 14.0;
+
     pub fn new() -> Self {
         Self {
             base: WidgetBase::new().with_class("textfield"),
@@ -27,6 +55,14 @@ impl TextField {
             on_change: None,
             on_submit: None,
             cursor_position: 0,
+            selection_anchor: None,
+            history: History::new(),
+            last_click: None,
+            preedit: String::new(),
+            preedit_cursor: None,
+            padding: Self::PADDING,
+            font_size: Self::FONT_SIZE,
+            font: SpanStyle::default(),
         }
     }
 
@@ -67,6 +103,12 @@ impl TextField {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Set the element ID.
     pub fn id(mut self, id: &str) -> Self {
         self.base.element_id = Some(id.to_string());
@@ -82,38 +124,136 @@ impl TextField {
     pub fn set_value(&mut self, value: impl Into<String>) {
         self.value = value.into();
         self.cursor_position = self.value.len();
+        self.selection_anchor = None;
         if let Some(handler) = &self.on_change {
             handler(&self.value);
         }
     }
 
-    /// Insert text at the cursor position.
-    fn insert_text(&mut self, text: &str) {
-        self.value.insert_str(self.cursor_position, text);
-        self.cursor_position += text.len();
+    /// Byte range of the selected text, if any.
+    fn selection(&self) -> Option<Range<usize>> {
+        selection(self.cursor_position, self.selection_anchor)
+    }
+
+    /// The value, cursor and selection, for editing.
+    fn editor(&mut self) -> Editor<'_> {
+        Editor {
+            text: &mut self.value,
+            cursor: &mut self.cursor_position,
+            anchor: &mut self.selection_anchor,
+        }
+    }
+
+    /// Move the cursor to `position`, extending the selection if `extend`.
+    fn move_cursor(&mut self, position: usize, extend: bool) {
+        if extend {
+            self.selection_anchor.get_or_insert(self.cursor_position);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor_position = position;
+        self.history.break_run();
+    }
+
+    /// Byte offset of the caret position nearest to window x coordinate `x`.
+    fn offset_at(&self, x: f32) -> usize {
+        let relative_x = x - self.bounds().x() - self.padding.left;
+        TextLayout::styled(&self.value, self.font_size, &self.font).hit_test(Point::new(relative_x, self.font_size / 2.0))
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            value: self.value.clone(),
+            cursor_position: self.cursor_position,
+            selection_anchor: self.selection_anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.value = snapshot.value;
+        self.cursor_position = snapshot.cursor_position;
+        self.selection_anchor = snapshot.selection_anchor;
         if let Some(handler) = &self.on_change {
             handler(&self.value);
         }
     }
 
-    /// Delete character before cursor.
-    fn backspace(&mut self) {
-        if self.cursor_position > 0 {
-            self.cursor_position -= 1;
-            self.value.remove(self.cursor_position);
-            if let Some(handler) = &self.on_change {
-                handler(&self.value);
-            }
+    /// Save the current state for undo before an edit of `kind`.
+    fn record(&mut self, kind: EditKind) {
+        let snapshot = self.snapshot();
+        self.history.record(snapshot, kind);
+    }
+
+    /// Revert the last edit.
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot);
         }
     }
 
-    /// Delete character after cursor.
-    fn delete(&mut self) {
-        if self.cursor_position < self.value.len() {
-            self.value.remove(self.cursor_position);
+    /// Reapply the last undone edit.
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    /// Run a clipboard shortcut.
+    fn edit(&mut self, command: EditCommand, ctx: &mut EventContext) {
+        let before = self.snapshot();
+        if self.editor().run(command, ctx, single_line) {
+            self.history.record(before, EditKind::Other);
             if let Some(handler) = &self.on_change {
                 handler(&self.value);
             }
+        } else {
+            self.history.break_run();
+        }
+    }
+
+    /// Insert text at the cursor position, replacing the selection.
+    fn insert_text(&mut self, text: &str, kind: EditKind) {
+        self.record(kind);
+        self.editor().insert(text);
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
+        }
+    }
+
+    /// Delete the selection, or the grapheme or word before the cursor.
+    fn backspace(&mut self, word: bool) {
+        let start = if word {
+            prev_word(&self.value, self.cursor_position)
+        } else {
+            prev_grapheme(&self.value, self.cursor_position)
+        };
+        self.delete_range(start..self.cursor_position);
+    }
+
+    /// Delete the selection, or the grapheme or word after the cursor.
+    fn delete(&mut self, word: bool) {
+        let end = if word {
+            next_word(&self.value, self.cursor_position)
+        } else {
+            next_grapheme(&self.value, self.cursor_position)
+        };
+        self.delete_range(self.cursor_position..end);
+    }
+
+    /// Delete the selection if there is one, otherwise `range`.
+    fn delete_range(&mut self, range: Range<usize>) {
+        if self.selection().is_some() {
+            self.record(EditKind::Other);
+            self.editor().delete_selection();
+        } else if !range.is_empty() {
+            self.record(EditKind::Deleting);
+            self.value.replace_range(range.clone(), "");
+            self.cursor_position = range.start;
+        } else {
+            return;
+        }
+        if let Some(handler) = &self.on_change {
+            handler(&self.value);
         }
     }
 }
@@ -145,12 +285,20 @@ impl Widget for TextField {
         self.base.state
     }
 
-    fn intrinsic_size(&self, _ctx: &LayoutContext) -> Size {
-        // Default text field size
-        Size::new(200.0, 36.0)
+    fn intrinsic_size(&self, ctx: &LayoutContext) -> Size {
+        // Default text field size, grown to fit CSS padding and font size
+        let style = ctx.style(self);
+        let padding = style.padding_or(Self::PADDING);
+        let font_size = style.font_size_or(Self::FONT_SIZE);
+        Size::new(200.0, (font_size + padding.vertical() + 2.0).max(36.0))
     }
 
     fn layout(&mut self, constraints: Constraints, ctx: &LayoutContext) -> LayoutResult {
+        let style = ctx.style(self);
+        self.padding = style.padding_or(Self::PADDING);
+        self.font_size = style.font_size_or(Self::FONT_SIZE);
+        self.font = style.font();
+
         let intrinsic = self.intrinsic_size(ctx);
         let size = constraints.constrain(intrinsic);
         self.base.bounds.size = size;
@@ -159,68 +307,124 @@ impl Widget for TextField {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(6.0);
-        let padding = 12.0;
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(6.0));
+        let padding = style.padding_or(Self::PADDING).left;
 
         // Background
-        let bg_color = if self.base.state.focused {
+        let bg_color = style.background_or(if self.base.state.focused {
             theme.colors.background
         } else {
             theme.colors.input
-        };
+        });
         painter.fill_rounded_rect(rect, bg_color, radius);
 
         // Border
-        let border_color = if self.base.state.focused {
+        let border_color = style.border_color_or(if self.base.state.focused {
             theme.colors.ring
         } else if self.base.state.hovered {
             theme.colors.border.darken(10.0)
         } else {
             theme.colors.border
-        };
-        painter.stroke_rect(rect, border_color, 1.0);
+        });
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rounded_rect(rect, border_color, border_width, radius);
+        }
 
         // Text or placeholder
-        let font_size = 14.0;
+        let font_size = style.font_size_or(Self::FONT_SIZE);
         let text_y = rect.y() + (rect.height() + font_size * 0.8) / 2.0;
 
-        if self.value.is_empty() {
+        let text_x = rect.x() + padding;
+        let text_top = rect.y() + (rect.height() - font_size) / 2.0;
+
+        // Lay out the value, with the composed text at the cursor
+        let composing = self.base.state.focused && !self.preedit.is_empty();
+        let mut text = self.value.clone();
+        if composing {
+            text.insert_str(self.cursor_position, &self.preedit);
+        }
+        let layout = TextLayout::styled(&text, font_size, &style.font());
+
+        // Selection highlight
+        if let Some(range) = self.selection().filter(|_| self.base.state.focused && !composing) {
+            for selection in layout.selection_rects(range) {
+                painter.fill_rect(
+                    Rect::new(text_x + selection.x(), text_top, selection.width(), font_size),
+                    theme.colors.primary.with_alpha(0.3),
+                );
+            }
+        }
+
+        if text.is_empty() {
             // Show placeholder
             painter.draw_text(
                 &self.placeholder,
-                Point::new(rect.x() + padding, text_y),
+                Point::new(text_x, text_y),
                 theme.colors.muted_foreground,
                 font_size,
             );
         } else {
             // Show value
             painter.draw_text(
-                &self.value,
-                Point::new(rect.x() + padding, text_y),
-                theme.colors.foreground,
+                &text,
+                Point::new(text_x, text_y),
+                style.color_or(theme.colors.foreground),
                 font_size,
             );
         }
 
-        // Cursor (when focused)
-        if self.base.state.focused {
-            let cursor_x = rect.x() + padding + (self.cursor_position as f32 * font_size * 0.6);
-            let cursor_y = rect.y() + (rect.height() - font_size) / 2.0;
+        // Underline the composed text
+        if composing {
+            let underline_y = text_top + font_size + 1.0;
             painter.draw_line(
-                Point::new(cursor_x, cursor_y),
-                Point::new(cursor_x, cursor_y + font_size),
-                theme.colors.foreground,
+                Point::new(text_x + layout.caret_x(self.cursor_position), underline_y),
+                Point::new(text_x + layout.caret_x(self.cursor_position + self.preedit.len()), underline_y),
+                style.color_or(theme.colors.foreground),
                 1.0,
             );
         }
+
+        // Cursor (when focused), inside the composed text while composing
+        if self.base.state.focused {
+            let preedit_offset = self.preedit_cursor.filter(|_| composing).unwrap_or(0);
+            let cursor_x = text_x + layout.caret_x(self.cursor_position + preedit_offset);
+            if !composing || self.preedit_cursor.is_some() {
+                painter.draw_line(
+                    Point::new(cursor_x, text_top),
+                    Point::new(cursor_x, text_top + font_size),
+                    theme.colors.foreground,
+                    1.0,
+                );
+            }
+            ctx.set_ime_cursor_area(painter, Rect::new(cursor_x, text_top, 1.0, font_size));
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            // Composition does not carry over to another widget
+            self.preedit.clear();
+            self.preedit_cursor = None;
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 let in_bounds = self.bounds().contains(mouse.position);
 
                 match mouse.kind {
+                    // Dragging selects
+                    MouseEventKind::Move if ctx.has_pointer_capture(self.base.id) => {
+                        let position = self.offset_at(mouse.position.x);
+                        if position != self.cursor_position {
+                            self.cursor_position = position;
+                            ctx.request_redraw();
+                        }
+                        return EventResult::Handled;
+                    }
                     MouseEventKind::Enter | MouseEventKind::Move => {
                         if in_bounds && !self.base.state.hovered {
                             self.base.state.hovered = true;
@@ -236,63 +440,129 @@ impl Widget for TextField {
                             ctx.request_redraw();
                         }
                     }
-                    MouseEventKind::Down => {
-                        if in_bounds && mouse.button == Some(MouseButton::Left) {
-                            self.base.state.focused = true;
-                            ctx.request_focus(self.base.id);
-
-                            // Calculate cursor position from click
-                            let padding = 12.0;
-                            let font_size = 14.0;
-                            let relative_x = mouse.position.x - self.bounds().x() - padding;
-                            let char_index = (relative_x / (font_size * 0.6)).round() as usize;
-                            self.cursor_position = char_index.min(self.value.len());
-
-                            ctx.request_redraw();
-                            return EventResult::Handled;
-                        } else if !in_bounds && self.base.state.focused {
-                            self.base.state.focused = false;
-                            ctx.release_focus();
-                            ctx.request_redraw();
+                    MouseEventKind::Down if in_bounds && mouse.button == Some(MouseButton::Left) => {
+                        ctx.request_focus(self.base.id);
+
+                        // Calculate cursor position from click
+                        let position = self.offset_at(mouse.position.x);
+                        let now = Instant::now();
+                        let double_click = self.last_click.is_some_and(|(time, point)| {
+                            now.duration_since(time) < DOUBLE_CLICK_TIME
+                                && (point.x - mouse.position.x).abs() < 4.0
+                                && (point.y - mouse.position.y).abs() < 4.0
+                        });
+
+                        if double_click {
+                            // Select the word under the pointer
+                            let word = word_at(&self.value, position);
+                            self.selection_anchor = Some(word.start);
+                            self.cursor_position = word.end;
+                            self.last_click = None;
+                        } else {
+                            // Shift-click extends the selection, a drag starts one
+                            let anchor = if mouse.modifiers.shift {
+                                self.selection_anchor.unwrap_or(self.cursor_position)
+                            } else {
+                                position
+                            };
+                            self.selection_anchor = Some(anchor);
+                            self.cursor_position = position;
+                            self.last_click = Some((now, mouse.position));
+                            ctx.capture_pointer(self.base.id);
                         }
+                        self.history.break_run();
+
+                        ctx.request_redraw();
+                        return EventResult::Handled;
                     }
                     _ => {}
                 }
             }
+            Event::Ime(ime) if self.base.state.focused => {
+                match ime {
+                    ImeEvent::Preedit { text, cursor } => {
+                        self.preedit = text.clone();
+                        self.preedit_cursor = cursor.map(|(start, _)| start);
+                    }
+                    ImeEvent::Commit(text) => {
+                        self.preedit.clear();
+                        self.preedit_cursor = None;
+                        self.insert_text(text, EditKind::Typing);
+                    }
+                    ImeEvent::Enabled | ImeEvent::Disabled => {
+                        self.preedit.clear();
+                        self.preedit_cursor = None;
+                    }
+                }
+                ctx.request_redraw();
+                return EventResult::Handled;
+            }
             Event::Key(key) if self.base.state.focused => {
+                if let Some(command) = EditCommand::from_key(key) {
+                    self.edit(command, ctx);
+                    ctx.request_redraw();
+                    return EventResult::Handled;
+                }
                 if key.kind == KeyEventKind::Down {
+                    // Word-wise movement uses Alt on macOS and Ctrl elsewhere
+                    let word = if cfg!(target_os = "macos") {
+                        key.modifiers.alt
+                    } else {
+                        key.modifiers.control
+                    };
+                    let shift = key.modifiers.shift;
                     match &key.key {
+                        Key::Z if key.modifiers.command() => {
+                            if shift {
+                                self.redo();
+                            } else {
+                                self.undo();
+                            }
+                            ctx.request_redraw();
+                            return EventResult::Handled;
+                        }
+                        Key::Y if key.modifiers.command() => {
+                            self.redo();
+                            ctx.request_redraw();
+                            return EventResult::Handled;
+                        }
                         Key::Backspace => {
-                            self.backspace();
+                            self.backspace(word);
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Delete => {
-                            self.delete();
+                            self.delete(word);
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Left => {
-                            if self.cursor_position > 0 {
-                                self.cursor_position -= 1;
-                                ctx.request_redraw();
-                            }
+                            let position = match self.selection() {
+                                Some(range) if !shift => range.start,
+                                _ if word => prev_word(&self.value, self.cursor_position),
+                                _ => prev_grapheme(&self.value, self.cursor_position),
+                            };
+                            self.move_cursor(position, shift);
+                            ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Right => {
-                            if self.cursor_position < self.value.len() {
-                                self.cursor_position += 1;
-                                ctx.request_redraw();
-                            }
+                            let position = match self.selection() {
+                                Some(range) if !shift => range.end,
+                                _ if word => next_word(&self.value, self.cursor_position),
+                                _ => next_grapheme(&self.value, self.cursor_position),
+                            };
+                            self.move_cursor(position, shift);
+                            ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::Home => {
-                            self.cursor_position = 0;
+                            self.move_cursor(0, shift);
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
                         Key::End => {
-                            self.cursor_position = self.value.len();
+                            self.move_cursor(self.value.len(), shift);
                             ctx.request_redraw();
                             return EventResult::Handled;
                         }
@@ -302,11 +572,13 @@ impl Widget for TextField {
                             }
                             return EventResult::Handled;
                         }
+                        // Tab moves focus
+                        Key::Tab => {}
                         _ => {
                             // Handle text input
                             if let Some(text) = &key.text {
                                 if !text.is_empty() && !key.modifiers.control && !key.modifiers.alt {
-                                    self.insert_text(text);
+                                    self.insert_text(text, EditKind::Typing);
                                     ctx.request_redraw();
                                     return EventResult::Handled;
                                 }
@@ -327,4 +599,153 @@ impl Widget for TextField {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css::{StyleContext, StyleManager};
+    use crate::event::{FocusEvent, KeyEvent, Modifiers, MouseEvent};
+    use crate::render::DrawCommand;
+    use crate::theme::ThemeData;
+    use std::sync::Arc;
+
+    /// A family emoji: four people joined by zero-width joiners.
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+
+    fn key(key: Key, text: Option<&str>, modifiers: Modifiers) -> Event {
+        Event::Key(KeyEvent {
+            kind: KeyEventKind::Down,
+            key,
+            physical_key: None,
+            text: text.map(str::to_string),
+            modifiers,
+            is_repeat: false,
+        })
+    }
+
+    fn press(field: &mut TextField, ctx: &mut EventContext, k: Key) {
+        field.handle_event(&key(k, None, Modifiers::empty()), ctx);
+    }
+
+    fn type_text(field: &mut TextField, ctx: &mut EventContext, text: &str) {
+        for c in text.chars() {
+            field.handle_event(&key(Key::Unknown, Some(&c.to_string()), Modifiers::empty()), ctx);
+        }
+    }
+
+    fn focused(value: &str) -> (TextField, EventContext) {
+        let mut ctx = EventContext::new();
+        let mut field = TextField::new().value(value);
+        let focus = Event::Focus(FocusEvent::FocusIn { widget_id: Widget::id(&field) });
+        field.handle_event(&focus, &mut ctx);
+        (field, ctx)
+    }
+
+    #[test]
+    fn test_backspace_and_delete_whole_graphemes() {
+        let (mut field, mut ctx) = focused(&format!("aé{FAMILY}b"));
+
+        press(&mut field, &mut ctx, Key::Left);
+        press(&mut field, &mut ctx, Key::Backspace);
+        assert_eq!(field.get_value(), "aéb");
+
+        press(&mut field, &mut ctx, Key::Backspace);
+        assert_eq!(field.get_value(), "ab");
+
+        press(&mut field, &mut ctx, Key::Home);
+        press(&mut field, &mut ctx, Key::Delete);
+        assert_eq!(field.get_value(), "b");
+
+        let (mut field, mut ctx) = focused(&format!("{FAMILY}é"));
+        press(&mut field, &mut ctx, Key::Home);
+        press(&mut field, &mut ctx, Key::Delete);
+        assert_eq!(field.get_value(), "é");
+        press(&mut field, &mut ctx, Key::Delete);
+        assert_eq!(field.get_value(), "");
+    }
+
+    #[test]
+    fn test_word_deletion() {
+        let word = if cfg!(target_os = "macos") {
+            Modifiers { alt: true, ..Modifiers::empty() }
+        } else {
+            Modifiers { control: true, ..Modifiers::empty() }
+        };
+        let (mut field, mut ctx) = focused("hello, wörld");
+        field.handle_event(&key(Key::Backspace, None, word), &mut ctx);
+        assert_eq!(field.get_value(), "hello, ");
+
+        field.handle_event(&key(Key::Home, None, Modifiers::empty()), &mut ctx);
+        field.handle_event(&key(Key::Delete, None, word), &mut ctx);
+        assert_eq!(field.get_value(), ", ");
+    }
+
+    #[test]
+    fn test_typing_run_undo_redo() {
+        let command = if cfg!(target_os = "macos") {
+            Modifiers { super_key: true, ..Modifiers::empty() }
+        } else {
+            Modifiers { control: true, ..Modifiers::empty() }
+        };
+        let (mut field, mut ctx) = focused("");
+        type_text(&mut field, &mut ctx, "hello");
+        // Moving the cursor ends the run
+        press(&mut field, &mut ctx, Key::Left);
+        press(&mut field, &mut ctx, Key::Right);
+        type_text(&mut field, &mut ctx, " you");
+        press(&mut field, &mut ctx, Key::Backspace);
+        press(&mut field, &mut ctx, Key::Backspace);
+        assert_eq!(field.get_value(), "hello y");
+
+        let undo = key(Key::Z, None, command);
+        field.handle_event(&undo, &mut ctx);
+        assert_eq!(field.get_value(), "hello you");
+        field.handle_event(&undo, &mut ctx);
+        assert_eq!(field.get_value(), "hello");
+        field.handle_event(&undo, &mut ctx);
+        assert_eq!(field.get_value(), "");
+
+        let redo = key(Key::Z, None, Modifiers { shift: true, ..command });
+        field.handle_event(&redo, &mut ctx);
+        assert_eq!(field.get_value(), "hello");
+        field.handle_event(&key(Key::Y, None, command), &mut ctx);
+        assert_eq!(field.get_value(), "hello you");
+        assert_eq!(field.cursor_position, "hello you".len());
+    }
+
+    #[test]
+    fn test_css_font_family_and_weight() {
+        let theme = ThemeData::light();
+        let mut styles = StyleManager::empty();
+        styles
+            .load_css(".textfield { font-family: monospace; font-weight: bold; }")
+            .unwrap();
+        let style_ctx = StyleContext::with_styles(&theme, Arc::new(styles));
+
+        let mut field = TextField::new().value("iiiiiiiiii");
+        field.layout(Constraints::tight(Size::new(300.0, 36.0)), &LayoutContext::new(&style_ctx));
+
+        // The value is drawn in the CSS font
+        let mut painter = Painter::new();
+        PaintContext::new(&style_ctx).paint_child(&field, &mut painter, field.bounds());
+        let font = painter.finish().into_iter().find_map(|command| match command {
+            DrawCommand::RichText { text, .. } if text.text() == "iiiiiiiiii" => Some(text.spans()[0].style.clone()),
+            _ => None,
+        });
+        let font = font.expect("value drawn as styled text");
+        assert_eq!(font.font_family.as_deref(), Some("monospace"));
+        assert_eq!(font.weight, Some(700));
+
+        // Clicks map to carets in the same font
+        let x = field.bounds().x() + 12.0 + TextLayout::styled(&field.value, 14.0, &font).caret_x(7);
+        let click = MouseEvent::new(MouseEventKind::Down, Point::new(x, field.bounds().center().y))
+            .with_button(MouseButton::Left);
+        field.handle_event(&Event::Mouse(click), &mut EventContext::new());
+        assert_eq!(field.cursor_position, 7);
+    }
 }
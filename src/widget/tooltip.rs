@@ -3,7 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -172,25 +172,32 @@ impl Widget for Tooltip {
         }
 
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
         let rect = self.calculate_tooltip_rect();
-        let radius = BorderRadius::all(theme.radii.sm * theme.typography.base_size);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.sm * theme.typography.base_size));
+        let background = style.background_or(theme.colors.popover);
 
         // Shadow
-        let shadow_rect = Rect::new(rect.x() + 1.0, rect.y() + 2.0, rect.width(), rect.height());
-        painter.fill_rounded_rect(shadow_rect, Color::BLACK.with_alpha(0.15), radius);
+        let shadows = style.box_shadow_or(std::slice::from_ref(&theme.shadows.md));
+        painter.draw_shadows(rect, radius, &shadows);
 
         // Background
-        painter.fill_rounded_rect(rect, theme.colors.popover, radius);
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        painter.fill_rounded_rect(rect, background, radius);
+        painter.draw_inset_shadows(rect, radius, &shadows);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Text
-        let text_x = rect.x() + 8.0;
+        let text_x = rect.x() + style.padding_or(EdgeInsets::all(8.0)).left;
         let text_y = rect.y() + rect.height() * 0.7;
-        painter.draw_text(&self.text, Point::new(text_x, text_y), theme.colors.popover_foreground, 12.0);
+        let text_color = style.color_or(theme.colors.popover_foreground);
+        painter.draw_text(&self.text, Point::new(text_x, text_y), text_color, style.font_size_or(12.0));
 
         // Arrow/pointer (simplified - just a small triangle indicator)
         let arrow_size = 6.0;
-        let arrow_color = theme.colors.popover;
+        let arrow_color = background;
 
         match self.position {
             TooltipPosition::Top => {
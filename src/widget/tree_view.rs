@@ -5,7 +5,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton, Key, KeyEventKind};
-use crate::geometry::{Point, Rect, Size};
+use crate::geometry::{Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
@@ -240,6 +240,12 @@ impl TreeView {
         self
     }
 
+    /// Set the position in the keyboard tab order.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.base.tab_index = index;
+        self
+    }
+
     /// Select a node by ID.
     pub fn select(&mut self, id: &str) {
         if self.selection_mode == TreeSelectionMode::None {
@@ -438,9 +444,10 @@ impl Widget for TreeView {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
 
         // Background
-        painter.fill_rect(rect, theme.colors.background);
+        painter.fill_rect(rect, style.background_or(theme.colors.background));
 
         // Draw nodes recursively
         fn draw_nodes(
@@ -480,9 +487,11 @@ impl Widget for TreeView {
                 } else if hovered.as_ref() == Some(&node.id) {
                     theme.colors.surface_hover
                 } else {
-                    theme.colors.background
+                    Color::TRANSPARENT
                 };
-                painter.fill_rect(item_rect, bg_color);
+                if bg_color.a > 0.0 {
+                    painter.fill_rect(item_rect, bg_color);
+                }
 
                 // Connecting lines
                 if show_lines && depth > 0 {
@@ -585,10 +594,18 @@ impl Widget for TreeView {
         );
 
         // Border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        if self.base.handle_focus_event(event) {
+            ctx.request_redraw();
+            return EventResult::Handled;
+        }
+
         match event {
             Event::Mouse(mouse) => {
                 match mouse.kind {
@@ -683,4 +700,8 @@ impl Widget for TreeView {
     fn set_bounds(&mut self, bounds: Rect) {
         self.base.bounds = bounds;
     }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.base.focusable_tab_index()
+    }
 }
@@ -5,9 +5,9 @@
 use super::{EventContext, LayoutContext, PaintContext, Widget, WidgetBase, WidgetId};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult};
-use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
+use crate::geometry::{BorderRadius, Color, EdgeInsets, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
-use crate::render::Painter;
+use crate::render::{Painter, ICON_VIEWBOX};
 
 /// Audio output device
 #[derive(Debug, Clone)]
@@ -40,7 +40,8 @@ pub struct VolumeControl {
     show_devices: bool,
     width: f32,
     height: f32,
-    accent_color: Color,
+    /// Custom accent; the theme's primary color by default
+    accent_color: Option<Color>,
     #[allow(clippy::type_complexity)]
     on_volume_change: Option<Box<dyn Fn(f32) + Send + Sync>>,
     #[allow(clippy::type_complexity)]
@@ -61,7 +62,7 @@ impl VolumeControl {
             show_devices: true,
             width: 300.0,
             height: 48.0,
-            accent_color: Color::rgb(0.0, 0.47, 0.84),
+            accent_color: None,
             on_volume_change: None,
             on_mute_toggle: None,
             on_device_change: None,
@@ -95,7 +96,7 @@ impl VolumeControl {
     }
 
     pub fn accent_color(mut self, color: Color) -> Self {
-        self.accent_color = color;
+        self.accent_color = Some(color);
         self
     }
 
@@ -166,15 +167,16 @@ impl VolumeControl {
         self.is_open
     }
 
+    /// SVG path data of the speaker icon for the volume, in `ICON_VIEWBOX`.
     pub fn get_icon(&self) -> &'static str {
         if self.is_muted {
-            "🔇"
+            "M16.5 12c0-1.77-1.02-3.29-2.5-4.03v2.21l2.45 2.45c.03-.2.05-.41.05-.63zm2.5 0c0 .94-.2 1.82-.54 2.64l1.51 1.51C20.63 14.91 21 13.5 21 12c0-4.28-2.99-7.86-7-8.77v2.06c2.89.86 5 3.54 5 6.71zM4.27 3L3 4.27 7.73 9H3v6h4l5 5v-6.73l4.25 4.25c-.67.52-1.42.93-2.25 1.18v2.06c1.38-.31 2.63-.95 3.69-1.81L19.73 21 21 19.73l-9-9L4.27 3zM12 4L9.91 6.09 12 8.18V4z"
         } else if self.volume == 0.0 {
-            "🔈"
+            "M7 9v6h4l5 5V4l-5 5H7z"
         } else if self.volume < 0.5 {
-            "🔉"
+            "M18.5 12c0-1.77-1.02-3.29-2.5-4.03v8.05c1.48-.73 2.5-2.25 2.5-4.02zM5 9v6h4l5 5V4L9 9H5z"
         } else {
-            "🔊"
+            "M3 9v6h4l5 5V4L7 9H3zm13.5 3c0-1.77-1.02-3.29-2.5-4.03v8.05c1.48-.73 2.5-2.25 2.5-4.02zM14 3.23v2.06c2.89.86 5 3.54 5 6.71s-2.11 5.85-5 6.71v2.06c4.01-.91 7-4.49 7-8.77s-2.99-7.86-7-8.77z"
         }
     }
 
@@ -230,40 +232,50 @@ impl Widget for VolumeControl {
         LayoutResult::new(size)
     }
 
-    fn paint(&self, painter: &mut Painter, rect: Rect, _ctx: &PaintContext) {
+    fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
+        let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let color = style.color_or(theme.colors.popover_foreground);
+        let padding = style.padding_or(EdgeInsets::symmetric(14.0, 16.0));
+        let accent = self.accent_color.unwrap_or(theme.colors.primary);
+
         // Background
-        painter.fill_rounded_rect(rect, Color::rgba(0.15, 0.15, 0.15, 0.95), BorderRadius::all(8.0));
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.popover), radius);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Volume icon
-        painter.draw_text(
-            self.get_icon(),
-            Point::new(rect.x() + 16.0, rect.y() + 30.0),
-            Color::WHITE,
-            20.0,
-        );
+        let icon_point = Point::new(rect.x() + padding.left, rect.y() + padding.top);
+        painter.draw_icon(self.get_icon(), icon_point, 20.0, color, ICON_VIEWBOX);
 
         // Volume slider track
-        let track_y = rect.y() + 24.0;
-        let track_rect = Rect::new(rect.x() + 48.0, track_y - 2.0, rect.width() - 64.0, 4.0);
-        painter.fill_rounded_rect(track_rect, Color::rgba(1.0, 1.0, 1.0, 0.2), BorderRadius::all(2.0));
+        let track_x = rect.x() + padding.left + 32.0;
+        let track_width = rect.width() - padding.horizontal() - 32.0;
+        let track_y = rect.y() + padding.top + 10.0;
+        let track_rect = Rect::new(track_x, track_y - 2.0, track_width, 4.0);
+        painter.fill_rounded_rect(track_rect, theme.colors.muted, BorderRadius::all(2.0));
 
         // Volume slider fill
-        let fill_width = (rect.width() - 64.0) * self.volume;
-        let fill_rect = Rect::new(rect.x() + 48.0, track_y - 2.0, fill_width, 4.0);
-        painter.fill_rounded_rect(fill_rect, self.accent_color, BorderRadius::all(2.0));
+        let fill_width = track_width * self.volume;
+        let fill_rect = Rect::new(track_x, track_y - 2.0, fill_width, 4.0);
+        painter.fill_rounded_rect(fill_rect, accent, BorderRadius::all(2.0));
 
         // Slider thumb
-        let thumb_x = rect.x() + 48.0 + fill_width - 6.0;
+        let thumb_x = track_x + fill_width - 6.0;
         let thumb_rect = Rect::new(thumb_x, track_y - 6.0, 12.0, 12.0);
-        painter.fill_rounded_rect(thumb_rect, Color::WHITE, BorderRadius::all(6.0));
+        painter.fill_rounded_rect(thumb_rect, theme.colors.background, BorderRadius::all(6.0));
+        painter.stroke_rounded_rect(thumb_rect, accent, 1.0, BorderRadius::all(6.0));
 
         // Volume percentage
         let percent = format!("{}%", (self.volume * 100.0) as i32);
         painter.draw_text(
             &percent,
-            Point::new(rect.max_x() - 48.0, rect.y() + 30.0),
-            Color::WHITE,
-            14.0,
+            Point::new(rect.max_x() - 48.0, rect.y() + padding.top + 16.0),
+            color,
+            style.font_size_or(14.0),
         );
     }
 
@@ -5,11 +5,14 @@
 
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
-use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
+use crate::event::{Event, EventResult, MouseEventKind, MouseButton, TimerEvent};
 use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
 
+/// Length of one pulse of the status glow, in seconds.
+const PULSE_CYCLE: f32 = 1.2;
+
 /// VPN connection status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum VpnConnectionStatus {
@@ -116,6 +119,10 @@ pub struct VpnStatus {
     show_label: bool,
     server_name: Option<String>,
     pulse: bool,
+    /// Pulse animation phase (0.0 to 1.0)
+    pulse_phase: f32,
+    /// Whether animation frames are being requested for the pulse
+    animating: bool,
     on_click: Option<Box<dyn Fn() + Send + Sync>>,
 }
 
@@ -129,6 +136,8 @@ impl VpnStatus {
             show_label: true,
             server_name: None,
             pulse: true,
+            pulse_phase: 0.0,
+            animating: false,
             on_click: None,
         }
     }
@@ -184,6 +193,9 @@ impl VpnStatus {
     }
 
     /// Set the status programmatically.
+    ///
+    /// A transitional status starts pulsing with the next event the widget
+    /// receives.
     pub fn set_status(&mut self, status: VpnConnectionStatus) {
         self.status = status;
     }
@@ -261,12 +273,27 @@ impl Widget for VpnStatus {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
+        let style = ctx.style(self);
+        let text_color = style.color_or(theme.colors.foreground);
         let indicator_size = self.size.indicator_size();
         let status_color = self.status.color();
 
-        // Draw pulse glow for transitional states
+        // Background
+        let background = style.background_or(Color::TRANSPARENT);
+        let radius = style.border_radius_or(BorderRadius::ZERO);
+        if background.a > 0.0 {
+            painter.fill_rounded_rect(rect, background, radius);
+        }
+        let border_width = style.border_width_or(0.0);
+        if border_width > 0.0 {
+            painter.stroke_rounded_rect(rect, style.border_color_or(theme.colors.border), border_width, radius);
+        }
+
+        // Draw pulse glow for transitional states, swelling and fading once
+        // per cycle
         if self.pulse && self.is_transitional() {
-            let glow_size = indicator_size * 1.6;
+            let swell = 0.5 - 0.5 * (self.pulse_phase * std::f32::consts::TAU).cos();
+            let glow_size = indicator_size * (1.2 + 0.6 * swell);
             let glow_rect = Rect::new(
                 rect.x() + (indicator_size - glow_size) / 2.0,
                 rect.y() + (rect.height() - glow_size) / 2.0,
@@ -275,7 +302,7 @@ impl Widget for VpnStatus {
             );
             painter.fill_rounded_rect(
                 glow_rect,
-                status_color.with_alpha(0.3),
+                status_color.with_alpha(0.4 - 0.3 * swell),
                 BorderRadius::all(glow_size / 2.0),
             );
         }
@@ -309,7 +336,7 @@ impl Widget for VpnStatus {
 
         // Draw labels
         if self.show_label && self.size != VpnStatusSize::Compact {
-            let font_size = self.size.font_size();
+            let font_size = style.font_size_or(self.size.font_size());
             let text_x = rect.x() + indicator_size + 12.0;
 
             if self.size == VpnStatusSize::Large && self.server_name.is_some() {
@@ -318,7 +345,7 @@ impl Widget for VpnStatus {
                 painter.draw_text(
                     self.status.label(),
                     Point::new(text_x, status_y),
-                    theme.colors.foreground,
+                    text_color,
                     font_size,
                 );
 
@@ -337,7 +364,7 @@ impl Widget for VpnStatus {
                 painter.draw_text(
                     self.status.label(),
                     Point::new(text_x, text_y),
-                    theme.colors.foreground,
+                    text_color,
                     font_size,
                 );
             }
@@ -345,6 +372,26 @@ impl Widget for VpnStatus {
     }
 
     fn handle_event(&mut self, event: &Event, ctx: &mut EventContext) -> EventResult {
+        // Pulse while the status is transitional; frames are left unhandled
+        // for other widgets
+        let pulsing = self.pulse && self.is_transitional();
+        if let Event::Timer(TimerEvent::Frame { elapsed }) = event {
+            self.animating = pulsing;
+            if pulsing {
+                self.pulse_phase = (self.pulse_phase + elapsed.as_secs_f32() / PULSE_CYCLE).fract();
+                ctx.request_animation_frame();
+                ctx.request_redraw();
+            } else if self.pulse_phase != 0.0 {
+                self.pulse_phase = 0.0;
+                ctx.request_redraw();
+            }
+            return EventResult::Ignored;
+        }
+        if pulsing && !self.animating {
+            self.animating = true;
+            ctx.request_animation_frame();
+        }
+
         if let Event::Mouse(mouse) = event {
             let in_bounds = self.bounds().contains(mouse.position);
 
@@ -358,11 +405,9 @@ impl Widget for VpnStatus {
                         ctx.request_redraw();
                     }
                 }
-                MouseEventKind::Leave => {
-                    if self.base.state.hovered {
-                        self.base.state.hovered = false;
-                        ctx.request_redraw();
-                    }
+                MouseEventKind::Leave if self.base.state.hovered => {
+                    self.base.state.hovered = false;
+                    ctx.request_redraw();
                 }
                 MouseEventKind::Up if mouse.button == Some(MouseButton::Left) && in_bounds => {
                     if let Some(handler) = &self.on_click {
@@ -385,3 +430,36 @@ impl Widget for VpnStatus {
         self.base.bounds = bounds;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn frame(status: &mut VpnStatus, ms: u64) -> EventContext {
+        let mut ctx = EventContext::new();
+        let event = Event::Timer(TimerEvent::Frame { elapsed: Duration::from_millis(ms) });
+        status.handle_event(&event, &mut ctx);
+        ctx
+    }
+
+    #[test]
+    fn test_pulse_animates_while_transitional() {
+        let mut status = VpnStatus::new().status(VpnConnectionStatus::Connecting);
+        let ctx = frame(&mut status, 300);
+        assert!(ctx.timers.frame_requested());
+        assert!((status.pulse_phase - 0.25).abs() < 1e-6);
+
+        // Settled states stop requesting frames and reset the glow
+        status.set_status(VpnConnectionStatus::Connected);
+        let ctx = frame(&mut status, 300);
+        assert!(!ctx.timers.frame_requested());
+        assert_eq!(status.pulse_phase, 0.0);
+
+        // Becoming transitional again restarts the animation
+        status.set_status(VpnConnectionStatus::Disconnecting);
+        let mut ctx = EventContext::new();
+        status.handle_event(&Event::Focus(crate::event::FocusEvent::FocusIn { widget_id: 0 }), &mut ctx);
+        assert!(ctx.timers.frame_requested());
+    }
+}
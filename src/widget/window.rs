@@ -3,6 +3,7 @@
 use super::{Widget, WidgetBase, WidgetId, LayoutContext, PaintContext, EventContext};
 use crate::css::{ClassList, WidgetState};
 use crate::event::{Event, EventResult, MouseEventKind, MouseButton};
+use crate::focus::FocusScope;
 use crate::geometry::{BorderRadius, Color, Point, Rect, Size};
 use crate::layout::{Constraints, LayoutResult};
 use crate::render::Painter;
@@ -375,7 +376,8 @@ impl Window {
         };
 
         let r = theme.radii.lg * theme.typography.base_size;
-        let radius = BorderRadius::new(r, r, 0.0, 0.0);
+        let window_radius = ctx.style(self).border_radius_or(BorderRadius::all(r));
+        let radius = BorderRadius::new(window_radius.top_left, window_radius.top_right, 0.0, 0.0);
         painter.fill_rounded_rect(title_bar_rect, bg_color, radius);
 
         // Title bar border
@@ -571,10 +573,11 @@ impl Window {
 
     fn paint_title(&self, painter: &mut Painter, title_bar_rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let font_size = 13.0;
+        let style = ctx.style(self);
+        let font_size = style.font_size_or(13.0);
 
         let text_color = if self.is_active {
-            theme.colors.foreground
+            style.color_or(theme.colors.foreground)
         } else {
             theme.colors.muted_foreground
         };
@@ -681,7 +684,8 @@ impl Widget for Window {
 
     fn paint(&self, painter: &mut Painter, rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(theme.radii.lg * theme.typography.base_size);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(theme.radii.lg * theme.typography.base_size));
 
         // Window shadow
         let shadow_rect = Rect::new(
@@ -693,10 +697,13 @@ impl Widget for Window {
         painter.fill_rounded_rect(shadow_rect, Color::BLACK.with_alpha(0.2), radius);
 
         // Window background
-        painter.fill_rounded_rect(rect, theme.colors.background, radius);
+        painter.fill_rounded_rect(rect, style.background_or(theme.colors.background), radius);
 
         // Window border
-        painter.stroke_rect(rect, theme.colors.border, 1.0);
+        let border_width = style.border_width_or(1.0);
+        if border_width > 0.0 {
+            painter.stroke_rect(rect, style.border_color_or(theme.colors.border), border_width);
+        }
 
         // Paint title bar
         if self.has_title_bar() {
@@ -714,7 +721,7 @@ impl Widget for Window {
             );
 
             // Clip to content area and paint
-            content.paint(painter, content_rect, ctx);
+            ctx.paint_child(content.as_ref(), painter, content_rect);
         }
     }
 
@@ -786,13 +793,6 @@ impl Widget for Window {
                     }
                     _ => {}
                 }
-
-                // Forward to content if not in title bar
-                if let Some(content) = &mut self.content {
-                    if mouse.position.y > self.base.bounds.y() + title_bar_height {
-                        return content.handle_event(event, ctx);
-                    }
-                }
             }
             _ => {
                 // Forward other events to content
@@ -839,4 +839,11 @@ impl Widget for Window {
             &mut []
         }
     }
+    /// Dialogs keep keyboard focus inside while open.
+    fn focus_scope(&self) -> FocusScope {
+        match self.variant {
+            WindowVariant::Dialog => FocusScope::Trap,
+            _ => FocusScope::Scope,
+        }
+    }
 }
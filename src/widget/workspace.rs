@@ -97,7 +97,8 @@ impl Widget for WorkspaceSwitcher {
 
     fn paint(&self, painter: &mut Painter, _rect: Rect, ctx: &PaintContext) {
         let theme = ctx.style_ctx.theme;
-        let radius = BorderRadius::all(4.0);
+        let style = ctx.style(self);
+        let radius = style.border_radius_or(BorderRadius::all(4.0));
 
         for (i, ws) in self.workspaces.iter().enumerate() {
             let r = self.workspace_rect(i);
@@ -106,14 +107,15 @@ impl Widget for WorkspaceSwitcher {
 
             let bg = if active { theme.colors.accent }
                 else if hovered { theme.colors.accent.with_alpha(0.3) }
-                else { theme.colors.muted };
+                else { style.background_or(theme.colors.muted) };
             painter.fill_rounded_rect(r, bg, radius);
 
             if active { painter.stroke_rect(r, theme.colors.ring, 2.0); }
 
             let num = format!("{}", i + 1);
-            let fg = if active { theme.colors.accent_foreground } else { theme.colors.foreground };
-            painter.draw_text(&num, Point::new(r.x() + r.width()/2.0 - 4.0, r.y() + r.height()/2.0 + 5.0), fg, 14.0);
+            let fg = if active { theme.colors.accent_foreground } else { style.color_or(theme.colors.foreground) };
+            let font_size = style.font_size_or(14.0);
+            painter.draw_text(&num, Point::new(r.x() + r.width()/2.0 - 4.0, r.y() + r.height()/2.0 + 5.0), fg, font_size);
         }
     }
 